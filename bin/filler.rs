@@ -1,10 +1,15 @@
 #![recursion_limit = "256"]
 
+use eyre::WrapErr;
 use init4_bin_base::deps::tracing::debug;
 use signet_filler::{
-    AllowanceRefreshTask, FillerContext, FillerTask, config_from_env, env_var_info,
-    serve_healthcheck,
+    AdminService, AllowanceRefreshTask, ArchiveTask, BridgeTask, FeeOracleTask, FillerContext,
+    FillerTask, LeaderElectionTask, LowBalanceTask, OrderEventTask, ReorgWatchTask,
+    SpotPriceRefreshTask, TokenDiscoveryTask, TwapRefreshTask, config_from_env,
+    decode_config_from_env, decode_order, env_var_info, init_telemetry, offline_config_from_env,
+    replay_journal, run_backtest, serve_healthcheck,
 };
+use std::io::Read;
 use tokio::join;
 
 fn should_print_help() -> bool {
@@ -22,6 +27,24 @@ fn print_help() {
 
 Run with no args. The process will run until it receives a SIGTERM or SIGINT signal.
 
+Subcommands:
+  decode [ORDER]  Decode a SignedOrder (JSON, or hex-encoded ABI `(Permit2Batch, Output[])`),
+                  validate its signature and deadline, and show its on-chain fill status. Reads
+                  the order from stdin when ORDER is omitted or `-`. Uses only
+                  SIGNET_FILLER_CHAIN_NAME and SIGNET_FILLER_ROLLUP_RPC_URL.
+  replay [JOURNAL]
+                  Replay the orders in archived decision records through the current fixed-rate
+                  pricing configuration and report how the pricing decisions would differ. Reads
+                  the records from stdin when JOURNAL is omitted or `-`. Uses only
+                  SIGNET_FILLER_CHAIN_NAME, SIGNET_FILLER_MAX_LOSS_PERCENT,
+                  SIGNET_FILLER_TOKEN_TABLE, and SIGNET_FILLER_VOLATILITY_HAIRCUTS.
+  backtest DATASET [PRICES]
+                  Run the orders in a captured order-flow dataset (archived records) through
+                  fixed-rate pricing and selection offline, optionally with historical prices, and
+                  report hypothetical fills, profit, and missed opportunities. Makes no RPC calls
+                  and submits nothing. Uses the same variables as `replay`, plus
+                  SIGNET_FILLER_MIN_MARGIN_BPS.
+
 Configuration is via the following environment variables:
 {env_vars}
 "#
@@ -35,9 +58,16 @@ async fn main() -> eyre::Result<()> {
         return Ok(());
     }
 
-    #[expect(deprecated, reason = "ticketed to be fixed in ENG-1990")]
-    let _guard = init4_bin_base::init4();
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("decode") => return decode(args.next()).await,
+        Some("replay") => return replay(args.next()),
+        Some("backtest") => return backtest(args.next(), args.next()),
+        _ => {}
+    }
+
     let config = config_from_env()?;
+    let _guard = init_telemetry(&config)?;
     debug!(chain = %config.constants().environment().rollup_name(), "starting filler");
 
     let cancellation_token = signet_filler::handle_signals()?;
@@ -47,15 +77,91 @@ async fn main() -> eyre::Result<()> {
         Err(error) => return Err(error),
     };
 
-    let filler_task = FillerTask::new(&context);
+    let file_order_source = context.file_order_source();
+    let filler_task = async {
+        match file_order_source {
+            Some(order_source) => FillerTask::with_order_source(&context, order_source).run().await,
+            None => FillerTask::new(&context).run().await,
+        }
+    };
     let allowance_task = AllowanceRefreshTask::initialize(&context).await;
-    let healthcheck_port = context.healthcheck_port();
+    let archive_task = ArchiveTask::initialize(&context).await;
+    let twap_task = TwapRefreshTask::initialize(&context).await;
+    let spot_price_task = SpotPriceRefreshTask::initialize(&context).await;
+    let fee_oracle_task = FeeOracleTask::initialize(&context).await;
+    let discovery_task = TokenDiscoveryTask::new(&context);
+    let bridge_task = BridgeTask::new(&context);
+    let low_balance_task = LowBalanceTask::new(&context);
+    let leader_election_task = LeaderElectionTask::new(&context);
+    let order_event_task = OrderEventTask::new(&context);
+    let reorg_watch_task = ReorgWatchTask::new(&context);
+    let admin_service = AdminService::new(&context);
 
-    let (filler_result, _, server_result) = join!(
-        filler_task.run(),
+    let (filler_result, _, _, _, _, _, _, _, _, _, _, _, server_result, admin_result) = join!(
+        filler_task,
         allowance_task.run(),
-        serve_healthcheck(healthcheck_port, cancellation_token),
+        archive_task.run(),
+        twap_task.run(),
+        spot_price_task.run(),
+        fee_oracle_task.run(),
+        discovery_task.run(),
+        bridge_task.run(),
+        low_balance_task.run(),
+        leader_election_task.run(),
+        order_event_task.run(),
+        reorg_watch_task.run(),
+        serve_healthcheck(&context),
+        admin_service.run(),
     );
     filler_result?;
-    server_result
+    server_result?;
+    admin_result
+}
+
+/// Run the `decode` subcommand, reading the order from stdin if no argument (or `-`) is given.
+async fn decode(order: Option<String>) -> eyre::Result<()> {
+    let order = match order {
+        Some(order) if order != "-" => order,
+        _ => {
+            let mut order = String::new();
+            std::io::stdin()
+                .read_to_string(&mut order)
+                .wrap_err("failed to read order from stdin")?;
+            order
+        }
+    };
+    let config = decode_config_from_env()?;
+    decode_order(&config, &order).await
+}
+
+/// Run the `replay` subcommand, reading the journal from stdin if no path (or `-`) is given.
+fn replay(journal: Option<String>) -> eyre::Result<()> {
+    let journal = match journal {
+        Some(path) if path != "-" => std::fs::read_to_string(&path)
+            .wrap_err_with(|| format!("failed to read journal from {path}"))?,
+        _ => {
+            let mut journal = String::new();
+            std::io::stdin()
+                .read_to_string(&mut journal)
+                .wrap_err("failed to read journal from stdin")?;
+            journal
+        }
+    };
+    let config = offline_config_from_env()?;
+    replay_journal(&config, &journal)
+}
+
+/// Run the `backtest` subcommand on the dataset at `dataset`, with historical prices from
+/// `prices` if given.
+fn backtest(dataset: Option<String>, prices: Option<String>) -> eyre::Result<()> {
+    let read = |path: &str, what: &str| {
+        std::fs::read_to_string(path).wrap_err_with(|| format!("failed to read {what} from {path}"))
+    };
+    let Some(dataset) = dataset else {
+        eyre::bail!("backtest requires a dataset path (run with '--help' for usage)");
+    };
+    let dataset = read(&dataset, "dataset")?;
+    let prices = prices.map(|path| read(&path, "prices")).transpose()?;
+    let config = offline_config_from_env()?;
+    run_backtest(&config, &dataset, prices.as_deref())
 }
@@ -0,0 +1,502 @@
+use crate::{
+    AdminConfig, DegradationLadder, DeniedOwners, FillHistory, FillThrottle, FillerContext,
+    LossLimit, OwnerReputation, PauseSwitch, PnlLedger, QuietSchedule, RiskEngine, SpendPolicy,
+    SpendPolicyConfig, TokenDiscovery, ToxicFlow,
+    capacity::{Capacity, CapacityReporter},
+    degradation::DegradedDependency,
+    discovery::DiscoveredToken,
+    ledger::LedgerSnapshot,
+    loss_limit::DailyLoss,
+    pause::PauseState,
+    reputation::OwnerPenalty,
+    risk::TokenPosition,
+    service,
+    spend_policy::PendingFill,
+    throttle::ThrottleSnapshot,
+    toxic_flow::OwnerMarkout,
+};
+use alloy::{
+    primitives::{Address, B256},
+    signers::Signer,
+};
+use axum::{
+    Json, Router,
+    extract::{Path, Query, Request, State},
+    http::{
+        StatusCode,
+        header::{AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
+};
+use eyre::Result;
+use init4_bin_base::deps::tracing::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// Authenticated admin HTTP API, served on its own address so it can stay internal-only while the
+/// healthcheck endpoint is exposed broadly. Every request must carry the configured bearer token,
+/// except approving or rejecting queued fills, which requires the separate approver token.
+#[derive(Debug)]
+pub struct AdminService {
+    config: Option<AdminConfig>,
+    state: AdminState,
+    cancellation_token: CancellationToken,
+}
+
+/// State shared with the admin route handlers.
+#[derive(Debug, Clone)]
+struct AdminState {
+    token: Arc<str>,
+    chain_name: Arc<str>,
+    filler_address: Address,
+    app_start_instant: Instant,
+    fill_throttle: Option<FillThrottle>,
+    reputation: OwnerReputation,
+    toxic_flow: Option<ToxicFlow>,
+    denied_owners: DeniedOwners,
+    degradation: DegradationLadder,
+    spend_policy: Option<SpendPolicy>,
+    approver_token: Arc<str>,
+    quiet_schedule: QuietSchedule,
+    pause: PauseSwitch,
+    loss_limit: Option<LossLimit>,
+    risk: RiskEngine,
+    ledger: PnlLedger,
+    fill_history: FillHistory,
+    capacity: Arc<CapacityReporter>,
+    token_discovery: Option<TokenDiscovery>,
+}
+
+/// Response body for `GET /status`.
+#[derive(Debug, Serialize)]
+struct StatusResponse {
+    version: &'static str,
+    chain_name: String,
+    filler_address: Address,
+    uptime_seconds: u64,
+    /// The quiet period currently in effect, if any.
+    quiet_period: Option<String>,
+    /// Why and since when bundle submission is paused, if it is.
+    paused: Option<PauseState>,
+    /// The day's loss against the daily loss limit, if one is set.
+    daily_loss: Option<DailyLoss>,
+    /// Dependencies the filler is currently running on a fallback for.
+    degraded: Vec<DegradedDependency>,
+}
+
+/// Request body for `POST /pause`.
+#[derive(Debug, Deserialize)]
+struct PauseRequest {
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Format of the fill history download.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ExportFormat {
+    #[default]
+    Jsonl,
+    Csv,
+}
+
+/// Query parameters for `GET /fills`.
+#[derive(Debug, Deserialize)]
+struct FillsQuery {
+    #[serde(default)]
+    format: ExportFormat,
+}
+
+/// Request body for `POST /throttle/override`.
+#[derive(Debug, Deserialize)]
+struct ThrottleOverrideRequest {
+    active: bool,
+}
+
+impl AdminService {
+    /// Create the admin service from the shared context. The service is a no-op when the admin
+    /// API is not configured.
+    pub fn new(context: &FillerContext) -> Self {
+        let config = context.admin_config().cloned();
+        let state = AdminState {
+            token: config.as_ref().map(AdminConfig::token).unwrap_or_default().into(),
+            chain_name: context.chain_name().into(),
+            filler_address: context.signer().address(),
+            app_start_instant: context.app_start_instant(),
+            fill_throttle: context.fill_throttle().cloned(),
+            reputation: context.reputation().clone(),
+            toxic_flow: context.toxic_flow().cloned(),
+            denied_owners: context.denied_owners().clone(),
+            degradation: context.degradation().clone(),
+            spend_policy: context.spend_policy().cloned(),
+            approver_token: context
+                .spend_policy_config()
+                .map(SpendPolicyConfig::approver_token)
+                .unwrap_or_default()
+                .into(),
+            quiet_schedule: context.quiet_schedule().clone(),
+            pause: context.pause().clone(),
+            loss_limit: context.loss_limit().cloned(),
+            risk: context.risk().clone(),
+            ledger: context.ledger().clone(),
+            fill_history: context.fill_history().clone(),
+            capacity: Arc::new(CapacityReporter::new(context)),
+            token_discovery: context.token_discovery().cloned(),
+        };
+        Self { config, state, cancellation_token: context.cancellation_token().clone() }
+    }
+
+    /// Serve the admin API until cancelled or failure. Returns `Ok(())` immediately if the admin
+    /// API is disabled.
+    pub async fn run(self) -> Result<()> {
+        let Some(config) = self.config else {
+            debug!("admin API disabled");
+            return Ok(());
+        };
+        info!(address = %config.address(), "starting admin API");
+        let router = router(self.state);
+        service::serve_router("admin", router, config.address(), self.cancellation_token).await
+    }
+}
+
+fn router(state: AdminState) -> Router {
+    let router = Router::new()
+        .route("/status", get(status))
+        .route("/pause", post(pause))
+        .route("/resume", post(resume))
+        .route("/throttle", get(throttle_status))
+        .route("/throttle/override", post(throttle_override))
+        .route("/positions", get(positions))
+        .route("/ledger", get(ledger))
+        .route("/fills", get(fills))
+        .route("/reputation", get(reputation_list))
+        .route("/reputation/{owner}", delete(reputation_reset))
+        .route("/toxic-flow", get(toxic_flow_list))
+        .route("/toxic-flow/{owner}", delete(toxic_flow_reset))
+        .route("/owners/denied", get(denied_owner_list))
+        .route("/owners/denied/{owner}", put(denied_owner_add).delete(denied_owner_remove))
+        .route("/approvals", get(approval_list))
+        .route("/capacity", get(capacity))
+        .route("/tokens/discovered", get(discovered_tokens))
+        .route("/tokens/discovered/{token}/enable", post(discovered_token_enable))
+        .route("/tokens/discovered/{token}/reject", post(discovered_token_reject))
+        .fallback(service::return_404)
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_bearer_token));
+    // Without a spend policy there is no approver token, so the approval routes are left out
+    // entirely rather than guarded by an empty token.
+    let router = if state.spend_policy.is_some() {
+        router.merge(
+            Router::new()
+                .route("/approvals/{order_hash}/approve", post(approval_approve))
+                .route("/approvals/{order_hash}/reject", post(approval_reject))
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_approver_token)),
+        )
+    } else {
+        router
+    };
+    router.with_state(state)
+}
+
+async fn status(State(state): State<AdminState>) -> Json<StatusResponse> {
+    // Read first, as it lifts the daily loss limit's pause if the daily reset has passed.
+    let daily_loss = state.loss_limit.as_ref().map(LossLimit::daily_loss);
+    Json(StatusResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        chain_name: state.chain_name.to_string(),
+        filler_address: state.filler_address,
+        uptime_seconds: state.app_start_instant.elapsed().as_secs(),
+        quiet_period: state
+            .quiet_schedule
+            .active_at(Instant::now())
+            .map(|period| period.to_string()),
+        paused: state.pause.state(),
+        daily_loss,
+        degraded: state.degradation.snapshot(),
+    })
+}
+
+/// Pauses bundle submission, returning the pause in effect. Orders keep being evaluated. Pausing
+/// while already paused keeps the original reason.
+async fn pause(
+    State(state): State<AdminState>,
+    Json(request): Json<PauseRequest>,
+) -> Json<Option<PauseState>> {
+    let reason = request.reason.unwrap_or_else(|| "paused via admin API".to_owned());
+    if state.pause.pause(reason.clone()) {
+        warn!(%reason, "bundle submission paused via admin API");
+    }
+    Json(state.pause.state())
+}
+
+/// Resumes bundle submission (204, or 409 if it wasn't paused).
+async fn resume(State(state): State<AdminState>) -> StatusCode {
+    if state.pause.resume() {
+        warn!("bundle submission resumed via admin API");
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::CONFLICT
+    }
+}
+
+/// Advertises the token pairs and fill sizes the filler currently serves, querying live balances.
+async fn capacity(State(state): State<AdminState>) -> Json<Capacity> {
+    Json(state.capacity.capacity().await)
+}
+
+async fn throttle_status(
+    State(state): State<AdminState>,
+) -> Result<Json<ThrottleSnapshot>, Response> {
+    let throttle = state.fill_throttle.as_ref().ok_or_else(throttle_not_configured)?;
+    Ok(Json(throttle.snapshot()))
+}
+
+/// Enables or disables the fill-rate throttle override. While active, every fill is let through
+/// (and still counted), so the override should be cleared once the suspected incident is resolved.
+async fn throttle_override(
+    State(state): State<AdminState>,
+    Json(request): Json<ThrottleOverrideRequest>,
+) -> Result<Json<ThrottleSnapshot>, Response> {
+    let throttle = state.fill_throttle.as_ref().ok_or_else(throttle_not_configured)?;
+    throttle.set_override(request.active);
+    warn!(active = request.active, "fill-rate throttle override changed via admin API");
+    Ok(Json(throttle.snapshot()))
+}
+
+fn throttle_not_configured() -> Response {
+    (StatusCode::NOT_FOUND, "fill-rate throttle not configured").into_response()
+}
+
+async fn positions(State(state): State<AdminState>) -> Json<Vec<TokenPosition>> {
+    Json(state.risk.positions())
+}
+
+async fn ledger(State(state): State<AdminState>) -> Json<LedgerSnapshot> {
+    Json(state.ledger.snapshot())
+}
+
+async fn fills(State(state): State<AdminState>, Query(query): Query<FillsQuery>) -> Response {
+    let (content_type, filename, body) = match query.format {
+        ExportFormat::Jsonl => ("application/x-ndjson", "fills.jsonl", state.fill_history.jsonl()),
+        ExportFormat::Csv => ("text/csv", "fills.csv", state.fill_history.csv()),
+    };
+    let disposition = format!("attachment; filename=\"{filename}\"");
+    ([(CONTENT_TYPE, content_type.to_string()), (CONTENT_DISPOSITION, disposition)], body)
+        .into_response()
+}
+
+async fn reputation_list(State(state): State<AdminState>) -> Json<Vec<OwnerPenalty>> {
+    Json(state.reputation.penalties())
+}
+
+/// Clears an owner's reputation penalty, so their orders are considered normally again.
+async fn reputation_reset(
+    State(state): State<AdminState>,
+    Path(owner): Path<Address>,
+) -> StatusCode {
+    if state.reputation.reset(owner) {
+        info!(%owner, "owner reputation reset via admin API");
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+async fn toxic_flow_list(
+    State(state): State<AdminState>,
+) -> Result<Json<Vec<OwnerMarkout>>, Response> {
+    let toxic_flow = state.toxic_flow.as_ref().ok_or_else(toxic_flow_not_configured)?;
+    Ok(Json(toxic_flow.markouts()))
+}
+
+/// Clears an owner's accumulated markout, so their orders are considered normally again.
+async fn toxic_flow_reset(
+    State(state): State<AdminState>,
+    Path(owner): Path<Address>,
+) -> Result<StatusCode, Response> {
+    let toxic_flow = state.toxic_flow.as_ref().ok_or_else(toxic_flow_not_configured)?;
+    if toxic_flow.reset(owner) {
+        info!(%owner, "owner markout reset via admin API");
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Ok(StatusCode::NOT_FOUND)
+    }
+}
+
+fn toxic_flow_not_configured() -> Response {
+    (StatusCode::NOT_FOUND, "toxic-flow detection not configured").into_response()
+}
+
+async fn denied_owner_list(State(state): State<AdminState>) -> Json<Vec<Address>> {
+    Json(state.denied_owners.list())
+}
+
+/// Denies an owner, so their orders are skipped before pricing from the next evaluation on.
+async fn denied_owner_add(
+    State(state): State<AdminState>,
+    Path(owner): Path<Address>,
+) -> StatusCode {
+    if state.denied_owners.deny(owner) {
+        warn!(%owner, "owner denied via admin API");
+    }
+    StatusCode::NO_CONTENT
+}
+
+/// Allows a denied owner's orders again.
+async fn denied_owner_remove(
+    State(state): State<AdminState>,
+    Path(owner): Path<Address>,
+) -> StatusCode {
+    if state.denied_owners.allow(&owner) {
+        info!(%owner, "owner allowed via admin API");
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+async fn approval_list(
+    State(state): State<AdminState>,
+) -> Result<Json<Vec<PendingFill>>, Response> {
+    let spend_policy = state.spend_policy.as_ref().ok_or_else(spend_policy_not_configured)?;
+    Ok(Json(spend_policy.pending()))
+}
+
+/// Approves a queued fill, letting it through the next time the order is selected.
+async fn approval_approve(
+    State(state): State<AdminState>,
+    Path(order_hash): Path<B256>,
+) -> Result<StatusCode, Response> {
+    let spend_policy = state.spend_policy.as_ref().ok_or_else(spend_policy_not_configured)?;
+    if !spend_policy.approve(order_hash) {
+        return Ok(StatusCode::NOT_FOUND);
+    }
+    warn!(%order_hash, "fill approved via admin API");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Rejects a queued fill, so the order is skipped rather than queued again.
+async fn approval_reject(
+    State(state): State<AdminState>,
+    Path(order_hash): Path<B256>,
+) -> Result<StatusCode, Response> {
+    let spend_policy = state.spend_policy.as_ref().ok_or_else(spend_policy_not_configured)?;
+    if !spend_policy.reject(order_hash) {
+        return Ok(StatusCode::NOT_FOUND);
+    }
+    info!(%order_hash, "fill rejected via admin API");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn spend_policy_not_configured() -> Response {
+    (StatusCode::NOT_FOUND, "spend policy not configured").into_response()
+}
+
+async fn discovered_tokens(
+    State(state): State<AdminState>,
+) -> Result<Json<Vec<DiscoveredToken>>, Response> {
+    let discovery = state.token_discovery.as_ref().ok_or_else(discovery_not_configured)?;
+    Ok(Json(discovery.tokens()))
+}
+
+/// Enables a discovered token awaiting review, so it is priced from its pool's TWAP.
+async fn discovered_token_enable(
+    State(state): State<AdminState>,
+    Path(token): Path<Address>,
+) -> Result<StatusCode, Response> {
+    let discovery = state.token_discovery.as_ref().ok_or_else(discovery_not_configured)?;
+    if !discovery.approve(token) {
+        return Ok(StatusCode::NOT_FOUND);
+    }
+    warn!(%token, "discovered token enabled via admin API");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Rejects a discovered token awaiting review, so orders referencing it stay unpriced.
+async fn discovered_token_reject(
+    State(state): State<AdminState>,
+    Path(token): Path<Address>,
+) -> Result<StatusCode, Response> {
+    let discovery = state.token_discovery.as_ref().ok_or_else(discovery_not_configured)?;
+    if !discovery.reject(token) {
+        return Ok(StatusCode::NOT_FOUND);
+    }
+    info!(%token, "discovered token rejected via admin API");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn discovery_not_configured() -> Response {
+    (StatusCode::NOT_FOUND, "token discovery not configured").into_response()
+}
+
+/// Rejects requests whose `Authorization` header does not carry the configured bearer token.
+async fn require_bearer_token(
+    State(state): State<AdminState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !is_authorized(&request, &state.token) {
+        warn!(path = %request.uri().path(), "rejected unauthenticated admin request");
+        return (StatusCode::UNAUTHORIZED, "unauthorized").into_response();
+    }
+    next.run(request).await
+}
+
+/// Rejects requests whose `Authorization` header does not carry the approver token.
+async fn require_approver_token(
+    State(state): State<AdminState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !is_authorized(&request, &state.approver_token) {
+        warn!(path = %request.uri().path(), "rejected unauthenticated approval request");
+        return (StatusCode::UNAUTHORIZED, "unauthorized").into_response();
+    }
+    next.run(request).await
+}
+
+/// Whether the request's `Authorization` header carries `Bearer <expected>`.
+fn is_authorized(request: &Request, expected: &str) -> bool {
+    request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| bearer_token_matches(value, expected))
+}
+
+/// Whether an `Authorization` header value is `Bearer <expected>`. The token comparison runs in
+/// time independent of where the first mismatching byte is, so the token can't be recovered by
+/// timing responses.
+fn bearer_token_matches(header_value: &str, expected: &str) -> bool {
+    let Some(token) = header_value.strip_prefix("Bearer ") else {
+        return false;
+    };
+    token.len() == expected.len()
+        && token.bytes().zip(expected.bytes()).fold(0_u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearer_token_matches_exact_token() {
+        assert!(bearer_token_matches("Bearer secret", "secret"));
+    }
+
+    #[test]
+    fn bearer_token_rejects_wrong_token() {
+        assert!(!bearer_token_matches("Bearer secreT", "secret"));
+        assert!(!bearer_token_matches("Bearer secret2", "secret"));
+        assert!(!bearer_token_matches("Bearer ", "secret"));
+    }
+
+    #[test]
+    fn bearer_token_rejects_missing_scheme() {
+        assert!(!bearer_token_matches("secret", "secret"));
+        assert!(!bearer_token_matches("Basic secret", "secret"));
+    }
+}
@@ -1,10 +1,15 @@
 use crate::FillerContext;
-use crate::{ChainTokenPair, FillProviderType, IERC20, KnownToken, metrics};
+use crate::{
+    ApprovalPolicy, ChainTokenPair, FillProviderType, IERC20, KnownToken, metrics, query_balance,
+};
 use alloy::primitives::{Address, U256};
 use alloy::signers::Signer;
 use core::fmt::{self, Display, Formatter};
+use eyre::WrapErr;
 use futures_util::future::join_all;
 use init4_bin_base::deps::tracing::{debug, info, instrument, trace, warn};
+use itertools::Itertools;
+use signet_constants::NATIVE_TOKEN_ADDRESS;
 use signet_orders::permit2::PERMIT2;
 use std::{
     collections::HashMap,
@@ -16,6 +21,9 @@ use tokio_util::sync::CancellationToken;
 /// How often the background task refreshes cached Permit2 allowances.
 const REFRESH_INTERVAL: Duration = Duration::from_secs(600);
 
+/// How long to wait for an approval transaction to be included before giving up on it.
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(120);
+
 /// Cached Permit2 allowances for known tokens, shared between the background refresh task and the
 /// per-cycle balance filter.
 #[derive(Debug, Clone)]
@@ -65,15 +73,30 @@ impl Display for DisplayAllowance {
     }
 }
 
-/// Background task that periodically refreshes Permit2 allowances for all known ERC20 tokens.
+/// The Permit2 approval to send for a token under `policy`, if its `allowance` doesn't cover the
+/// filler's `balance`.
+fn approval_amount(policy: ApprovalPolicy, allowance: U256, balance: U256) -> Option<U256> {
+    if allowance >= balance {
+        return None;
+    }
+    match policy {
+        ApprovalPolicy::Off => None,
+        ApprovalPolicy::Max => Some(U256::MAX),
+        ApprovalPolicy::Exact => Some(balance),
+    }
+}
+
+/// Background task that periodically refreshes Permit2 allowances for all known ERC20 tokens and
+/// token table tokens, and tops them up under the approval policy.
 #[derive(Debug)]
 pub struct AllowanceRefreshTask {
     cache: AllowanceCache,
     ru_provider: FillProviderType,
     host_provider: FillProviderType,
     filler_address: Address,
-    tokens: [ChainTokenPair; 6],
+    tokens: Vec<ChainTokenPair>,
     ru_chain_id: u64,
+    approval_policy: ApprovalPolicy,
     cancellation_token: CancellationToken,
 }
 
@@ -82,7 +105,18 @@ impl AllowanceRefreshTask {
     #[instrument(skip_all, name = "initialize_allowance_refresh_task")]
     pub async fn initialize(context: &FillerContext) -> Self {
         let ru_chain_id = context.constants().system().ru_chain_id();
-        let tokens = KnownToken::ERC20.map(|known| known.resolve(context.constants().system()));
+        let tokens = KnownToken::ERC20
+            .iter()
+            .map(|known| known.resolve(context.constants().system()))
+            .chain(
+                context
+                    .token_table()
+                    .iter()
+                    .filter(|token| token.address() != NATIVE_TOKEN_ADDRESS)
+                    .map(|token| ChainTokenPair::new(token.chain_id(), token.address())),
+            )
+            .unique()
+            .collect();
 
         let cache = context.allowance_cache().clone();
         let ru_provider = context.ru_provider().clone();
@@ -97,6 +131,7 @@ impl AllowanceRefreshTask {
             filler_address,
             tokens,
             ru_chain_id,
+            approval_policy: context.approval_policy(),
             cancellation_token,
         };
         select! {
@@ -134,12 +169,13 @@ impl AllowanceRefreshTask {
     #[instrument(skip_all, fields(token_count = self.tokens.len()))]
     async fn refresh(&self) {
         let results = self.query_and_cache().await;
-        for AllowanceQueryResult { chain_token, outcome } in results {
+        for AllowanceQueryResult { chain_token, outcome } in &results {
             outcome
+                .as_ref()
                 .map(|allowance| {
                     trace!(
                         %chain_token,
-                        allowance = %DisplayAllowance(allowance),
+                        allowance = %DisplayAllowance(*allowance),
                         "refreshed Permit2 allowance",
                     );
                 })
@@ -152,11 +188,13 @@ impl AllowanceRefreshTask {
                     );
                 });
         }
+        self.maintain_approvals(&results).await;
         info!("allowance cache refreshed");
     }
 
-    /// Same query + cache update as [`Self::refresh`], but logs one info line per token and a
-    /// summary warning if no known token has a non-zero allowance. Mirrors the startup balance
+    /// Same query + cache update as [`Self::refresh`], but logs one info line per token and, unless
+    /// the approval policy will approve them, a summary warning if no known token has a non-zero
+    /// allowance. Mirrors the startup balance
     /// report in `initialization.rs` so operators see both funding and Permit2 approvals surfaced
     /// the same way at deploy time.
     #[instrument(skip_all)]
@@ -165,15 +203,16 @@ impl AllowanceRefreshTask {
         let total = results.len();
         let mut non_zero_count = 0_usize;
         let mut error_count = 0_usize;
-        for AllowanceQueryResult { chain_token, outcome } in results {
+        for AllowanceQueryResult { chain_token, outcome } in &results {
             outcome
+                .as_ref()
                 .map(|allowance| {
                     if !allowance.is_zero() {
                         non_zero_count += 1;
                     }
                     info!(
                         %chain_token,
-                        allowance = %DisplayAllowance(allowance),
+                        allowance = %DisplayAllowance(*allowance),
                         "startup allowance",
                     );
                 })
@@ -191,12 +230,78 @@ impl AllowanceRefreshTask {
             warn!(
                 "all startup allowance queries failed; could not determine Permit2 approval state"
             );
-        } else if non_zero_count == 0 {
+        } else if non_zero_count == 0 && self.approval_policy == ApprovalPolicy::Off {
             warn!(
                 error_count,
                 "no non-zero allowance found among successfully-queried tokens; check Permit2 approvals"
             );
         }
+        self.maintain_approvals(&results).await;
+    }
+
+    /// Under the approval policy, approves Permit2 for every token whose freshly queried allowance
+    /// is below the filler's balance, and caches the new allowance once the approval is included.
+    /// Failures are logged and counted, and retried on the next refresh.
+    async fn maintain_approvals(&self, results: &[AllowanceQueryResult]) {
+        if self.approval_policy == ApprovalPolicy::Off {
+            return;
+        }
+        let approved = join_all(results.iter().filter_map(|result| {
+            let allowance = *result.outcome.as_ref().ok()?;
+            Some(self.maintain_approval(result.chain_token, allowance))
+        }))
+        .await;
+        self.cache.update(approved.into_iter().flatten().collect());
+    }
+
+    async fn maintain_approval(
+        &self,
+        chain_token: ChainTokenPair,
+        allowance: U256,
+    ) -> Option<(ChainTokenPair, U256)> {
+        let provider = self.provider(&chain_token);
+        let balance = query_balance(provider, self.filler_address, chain_token.token())
+            .await
+            .inspect_err(|error| {
+                warn!(
+                    %chain_token,
+                    error = format!("{error:#}"),
+                    "failed to query balance for approval"
+                );
+            })
+            .ok()?;
+        let amount = approval_amount(self.approval_policy, allowance, balance)?;
+        let approval = async {
+            IERC20::new(chain_token.token(), provider)
+                .approve(PERMIT2, amount)
+                .send()
+                .await
+                .wrap_err("failed to send approval")?
+                .with_timeout(Some(APPROVAL_TIMEOUT))
+                .watch()
+                .await
+                .wrap_err("approval not included")
+        };
+        match approval.await {
+            Ok(tx_hash) => {
+                info!(%chain_token, %tx_hash, allowance = %DisplayAllowance(amount), "approved Permit2");
+                metrics::record_approval(metrics::SubmissionResult::Success);
+                Some((chain_token, amount))
+            }
+            Err(error) => {
+                warn!(%chain_token, error = format!("{error:#}"), "failed to approve Permit2");
+                metrics::record_approval(metrics::SubmissionResult::Failure);
+                None
+            }
+        }
+    }
+
+    const fn provider(&self, chain_token: &ChainTokenPair) -> &FillProviderType {
+        if chain_token.chain_id() == self.ru_chain_id {
+            &self.ru_provider
+        } else {
+            &self.host_provider
+        }
     }
 
     /// Queries Permit2 allowances for all known tokens concurrently, merges every successful
@@ -207,12 +312,7 @@ impl AllowanceRefreshTask {
     async fn query_and_cache(&self) -> Vec<AllowanceQueryResult> {
         let results: Vec<AllowanceQueryResult> =
             join_all(self.tokens.iter().map(|chain_token| async move {
-                let provider = if chain_token.chain_id() == self.ru_chain_id {
-                    &self.ru_provider
-                } else {
-                    &self.host_provider
-                };
-                let contract = IERC20::new(chain_token.token(), provider);
+                let contract = IERC20::new(chain_token.token(), self.provider(chain_token));
                 let outcome = contract
                     .allowance(self.filler_address, PERMIT2)
                     .call()
@@ -243,6 +343,16 @@ mod tests {
     const TOKEN_A: Address = Address::repeat_byte(0xAA);
     const TOKEN_B: Address = Address::repeat_byte(0xBB);
 
+    #[test]
+    fn approval_tops_up_allowance_below_balance() {
+        let balance = U256::from(100);
+        assert_eq!(approval_amount(ApprovalPolicy::Exact, U256::from(40), balance), Some(balance));
+        assert_eq!(approval_amount(ApprovalPolicy::Max, U256::from(40), balance), Some(U256::MAX));
+        assert_eq!(approval_amount(ApprovalPolicy::Off, U256::from(40), balance), None);
+        assert_eq!(approval_amount(ApprovalPolicy::Max, balance, balance), None);
+        assert_eq!(approval_amount(ApprovalPolicy::Exact, U256::ZERO, U256::ZERO), None);
+    }
+
     #[test]
     fn get_on_empty_cache_returns_none() {
         let cache = AllowanceCache::new();
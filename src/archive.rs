@@ -0,0 +1,406 @@
+use crate::{ArchiveConfig, FillerContext, metrics};
+use alloy::primitives::{B256, keccak256};
+use aws_sdk_s3::{Client, primitives::ByteStream};
+use init4_bin_base::deps::tracing::{debug, info, instrument, warn};
+use serde::Serialize;
+use signet_types::SignedOrder;
+use std::{
+    collections::{HashSet, VecDeque},
+    mem,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::select;
+use tokio_util::sync::CancellationToken;
+
+/// Maximum number of records held in memory between uploads. Records arriving while the buffer is
+/// full are dropped (and counted) rather than blocking the filler loop.
+const MAX_BUFFERED_RECORDS: usize = 100_000;
+/// Maximum number of serialized batches retained for retry after a failed upload.
+const MAX_PENDING_BATCHES: usize = 10;
+
+/// A single line in an archived JSONL batch.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum ArchiveRecord {
+    /// A raw order as fetched from the transaction cache. Each order is archived at most once per
+    /// batch, however many cycles it was fetched in.
+    Order { observed_at_ms: u64, order: SignedOrder },
+    /// A decision the filler made about an order during a processing cycle.
+    Decision {
+        observed_at_ms: u64,
+        cycle: u64,
+        order_hash: B256,
+        #[serde(flatten)]
+        decision: Decision,
+    },
+    /// Every order fetched in a processing cycle, before any filtering, chained to the previous
+    /// snapshot by `previous_hash`. See [`intake_hash`] for how `hash` is computed.
+    Intake {
+        cycle: u64,
+        fetched_at_ms: u64,
+        fetch_duration_ms: u64,
+        order_hashes: Vec<B256>,
+        previous_hash: B256,
+        hash: B256,
+    },
+}
+
+/// The outcome of a processing cycle for a single order.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub(crate) enum Decision {
+    /// The order was dropped before submission, with the same reason label used by the
+    /// `orders_skipped` metric.
+    Skipped { reason: &'static str },
+    /// Profitability could not be computed for the order.
+    PricingFailed,
+    /// The order was priced, with its margin before gas, the estimated gas cost of filling it, and
+    /// its margin net of that gas, all in normalized 18-decimal USD.
+    Priced { gross_margin: i128, gas_cost: i128, margin: i128 },
+    /// The order passed every check and was selected for a fill bundle, with its margin in
+    /// normalized 18-decimal USD.
+    Selected { margin: i128 },
+    /// The order was placed in the `chunk`th of the cycle's `chunks` fill bundles, about to be
+    /// submitted.
+    Bundled { chunk: usize, chunks: usize },
+    /// The order was part of a fill bundle submission.
+    Submitted { success: bool },
+    /// A fill of the order submitted by this filler was seen filled at `block_number`.
+    Confirmed { block_number: u64 },
+}
+
+#[derive(Debug, Default)]
+struct BufferInner {
+    records: Vec<ArchiveRecord>,
+    archived_orders: HashSet<B256>,
+    /// Hash of the latest intake snapshot, or zero before the first. Kept across batches.
+    last_intake_hash: B256,
+}
+
+/// In-memory record buffer shared between the filler loop (which appends) and the
+/// [`ArchiveTask`] (which drains and uploads it on a schedule).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ArchiveBuffer {
+    inner: Arc<Mutex<BufferInner>>,
+}
+
+impl ArchiveBuffer {
+    /// Record a fetched order, unless it is already part of the current batch.
+    pub(crate) fn record_order(&self, order: &SignedOrder) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.archived_orders.contains(order.order_hash()) {
+            return;
+        }
+        if Self::push(
+            &mut inner,
+            ArchiveRecord::Order { observed_at_ms: now_ms(), order: order.clone() },
+        ) {
+            inner.archived_orders.insert(*order.order_hash());
+        }
+    }
+
+    /// Record a decision made about an order during the given cycle.
+    pub(crate) fn record_decision(&self, cycle: u64, order_hash: B256, decision: Decision) {
+        let record =
+            ArchiveRecord::Decision { observed_at_ms: now_ms(), cycle, order_hash, decision };
+        Self::push(&mut self.inner.lock().unwrap(), record);
+    }
+
+    /// Record the set of orders fetched during the given cycle, in the order the source returned
+    /// them, extending the intake hash chain.
+    pub(crate) fn record_intake(
+        &self,
+        cycle: u64,
+        fetched_at: SystemTime,
+        fetch_duration: Duration,
+        order_hashes: Vec<B256>,
+    ) {
+        let fetched_at_ms = fetched_at
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock set before UNIX epoch")
+            .as_millis() as u64;
+        let fetch_duration_ms = fetch_duration.as_millis() as u64;
+        let mut inner = self.inner.lock().unwrap();
+        let previous_hash = inner.last_intake_hash;
+        let hash =
+            intake_hash(previous_hash, cycle, fetched_at_ms, fetch_duration_ms, &order_hashes);
+        // The chain advances even if the record is dropped, so the gap shows up as a broken link.
+        inner.last_intake_hash = hash;
+        let record = ArchiveRecord::Intake {
+            cycle,
+            fetched_at_ms,
+            fetch_duration_ms,
+            order_hashes,
+            previous_hash,
+            hash,
+        };
+        Self::push(&mut inner, record);
+    }
+
+    /// Appends a record, returning `false` if it was dropped because the buffer is full.
+    fn push(inner: &mut BufferInner, record: ArchiveRecord) -> bool {
+        if inner.records.len() >= MAX_BUFFERED_RECORDS {
+            metrics::record_archive_record_dropped();
+            return false;
+        }
+        inner.records.push(record);
+        true
+    }
+
+    /// Take every buffered record, resetting the buffer for the next batch.
+    fn drain(&self) -> Vec<ArchiveRecord> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.archived_orders.clear();
+        mem::take(&mut inner.records)
+    }
+}
+
+/// A serialized batch awaiting upload.
+#[derive(Debug)]
+struct Batch {
+    key: String,
+    body: Vec<u8>,
+}
+
+/// Background task that periodically uploads buffered orders and decision records as JSONL
+/// objects to S3-compatible storage.
+#[derive(Debug)]
+pub struct ArchiveTask {
+    state: Option<ArchiveState>,
+    cancellation_token: CancellationToken,
+}
+
+#[derive(Debug)]
+struct ArchiveState {
+    config: ArchiveConfig,
+    buffer: ArchiveBuffer,
+    client: Client,
+    key_prefix: String,
+    pending: VecDeque<Batch>,
+}
+
+impl ArchiveTask {
+    /// Create the archive task, loading AWS credentials from the environment. The task is a no-op
+    /// when archival is not configured.
+    #[instrument(skip_all, name = "initialize_archive_task")]
+    pub async fn initialize(context: &FillerContext) -> Self {
+        let cancellation_token = context.cancellation_token().clone();
+        let (Some(config), Some(buffer)) = (context.archive_config(), context.archive_buffer())
+        else {
+            return Self { state: None, cancellation_token };
+        };
+
+        let sdk_config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+        let mut builder = aws_sdk_s3::config::Builder::from(&sdk_config);
+        if let Some(endpoint_url) = config.endpoint_url() {
+            // Most S3-compatible stores (MinIO, R2, etc.) require path-style addressing.
+            builder = builder.endpoint_url(endpoint_url).force_path_style(true);
+        }
+        let client = Client::from_conf(builder.build());
+        let key_prefix =
+            format!("{}/{}", config.prefix().trim_end_matches('/'), context.chain_name());
+        info!(bucket = config.bucket(), %key_prefix, "archival enabled");
+
+        Self {
+            state: Some(ArchiveState {
+                config: config.clone(),
+                buffer: buffer.clone(),
+                client,
+                key_prefix,
+                pending: VecDeque::new(),
+            }),
+            cancellation_token,
+        }
+    }
+
+    /// Run the periodic upload loop. Flushes any remaining records once more on cancellation.
+    pub async fn run(self) {
+        let Some(mut state) = self.state else {
+            debug!("archival disabled");
+            return;
+        };
+        let mut interval = tokio::time::interval(state.config.interval());
+        // Consume the immediate first tick; there is nothing to upload yet.
+        interval.tick().await;
+
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("archive task cancelled, flushing remaining records");
+                    state.flush().await;
+                    break;
+                }
+                _ = interval.tick() => {
+                    state.flush().await;
+                }
+            }
+        }
+    }
+}
+
+impl ArchiveState {
+    /// Serialize buffered records into a new batch and attempt to upload it along with any batches
+    /// that failed previously. Failed batches are retained (up to [`MAX_PENDING_BATCHES`]) and
+    /// retried on the next flush.
+    #[instrument(skip_all)]
+    async fn flush(&mut self) {
+        let records = self.buffer.drain();
+        if !records.is_empty() {
+            let key = format!("{}/{}-{}.jsonl", self.key_prefix, now_ms(), records.len());
+            self.pending.push_back(Batch { key, body: encode_jsonl(&records) });
+            if self.pending.len() > MAX_PENDING_BATCHES {
+                let dropped = self.pending.pop_front().expect("pending is non-empty");
+                warn!(key = %dropped.key, "too many failed archive uploads, dropping oldest batch");
+                metrics::record_archive_upload(metrics::SubmissionResult::Failure);
+            }
+        }
+
+        while let Some(batch) = self.pending.pop_front() {
+            let result = self
+                .client
+                .put_object()
+                .bucket(self.config.bucket())
+                .key(&batch.key)
+                .content_type("application/x-ndjson")
+                .body(ByteStream::from(batch.body.clone()))
+                .send()
+                .await;
+            match result {
+                Ok(_) => {
+                    debug!(key = %batch.key, bytes = batch.body.len(), "uploaded archive batch");
+                    metrics::record_archive_upload(metrics::SubmissionResult::Success);
+                }
+                Err(error) => {
+                    warn!(
+                        key = %batch.key,
+                        error = format!("{:#}", eyre::Report::from(error)),
+                        "failed to upload archive batch, will retry"
+                    );
+                    metrics::record_archive_upload(metrics::SubmissionResult::Failure);
+                    self.pending.push_front(batch);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Serialize records as newline-delimited JSON. Records that fail to serialize are skipped.
+fn encode_jsonl(records: &[ArchiveRecord]) -> Vec<u8> {
+    let mut body = Vec::new();
+    for record in records {
+        match serde_json::to_writer(&mut body, record) {
+            Ok(()) => body.push(b'\n'),
+            Err(error) => warn!(%error, "failed to serialize archive record"),
+        }
+    }
+    body
+}
+
+/// The hash of an intake snapshot: keccak256 over the previous snapshot's hash, then `cycle`,
+/// `fetched_at_ms`, and `fetch_duration_ms` as 8-byte big-endian integers, then each order hash.
+pub(crate) fn intake_hash(
+    previous_hash: B256,
+    cycle: u64,
+    fetched_at_ms: u64,
+    fetch_duration_ms: u64,
+    order_hashes: &[B256],
+) -> B256 {
+    let mut preimage = Vec::with_capacity(B256::len_bytes() * (order_hashes.len() + 1) + 24);
+    preimage.extend_from_slice(previous_hash.as_slice());
+    preimage.extend_from_slice(&cycle.to_be_bytes());
+    preimage.extend_from_slice(&fetched_at_ms.to_be_bytes());
+    preimage.extend_from_slice(&fetch_duration_ms.to_be_bytes());
+    for order_hash in order_hashes {
+        preimage.extend_from_slice(order_hash.as_slice());
+    }
+    keccak256(preimage)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock set before UNIX epoch")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use alloy::primitives::{Address, U256};
+
+    fn build_order(nonce: u64) -> SignedOrder {
+        OrderBuilder::default().with_input(Address::ZERO, U256::from(1)).with_nonce(nonce).build()
+    }
+
+    #[test]
+    fn record_order_deduplicates_within_batch() {
+        let buffer = ArchiveBuffer::default();
+        let order = build_order(1);
+        buffer.record_order(&order);
+        buffer.record_order(&order);
+        buffer.record_order(&build_order(2));
+        assert_eq!(buffer.drain().len(), 2);
+
+        // After a drain the same order is archived again in the next batch.
+        buffer.record_order(&order);
+        assert_eq!(buffer.drain().len(), 1);
+    }
+
+    #[test]
+    fn encode_jsonl_writes_one_line_per_record() {
+        let buffer = ArchiveBuffer::default();
+        let order = build_order(1);
+        buffer.record_order(&order);
+        buffer.record_decision(7, *order.order_hash(), Decision::Selected { margin: -5 });
+        buffer.record_decision(7, *order.order_hash(), Decision::Skipped { reason: "expired" });
+        let priced = Decision::Priced { gross_margin: 9, gas_cost: 4, margin: 5 };
+        buffer.record_decision(8, *order.order_hash(), priced);
+        buffer.record_decision(9, *order.order_hash(), Decision::Confirmed { block_number: 12 });
+
+        let body = String::from_utf8(encode_jsonl(&buffer.drain())).unwrap();
+        let lines: Vec<serde_json::Value> =
+            body.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0]["kind"], "order");
+        assert_eq!(lines[1]["kind"], "decision");
+        assert_eq!(lines[1]["cycle"], 7);
+        assert_eq!(lines[1]["decision"], "selected");
+        assert_eq!(lines[1]["margin"], -5);
+        assert_eq!(lines[2]["reason"], "expired");
+        assert_eq!(lines[3]["decision"], "priced");
+        assert_eq!(
+            (lines[3]["gross_margin"].as_i64(), lines[3]["gas_cost"].as_i64()),
+            (Some(9), Some(4))
+        );
+        assert_eq!(lines[4]["decision"], "confirmed");
+        assert_eq!(lines[4]["block_number"], 12);
+    }
+
+    #[test]
+    fn intake_snapshots_are_hash_chained() {
+        let buffer = ArchiveBuffer::default();
+        let hashes = vec![*build_order(1).order_hash(), *build_order(2).order_hash()];
+        let at = |ms| UNIX_EPOCH + Duration::from_millis(ms);
+        buffer.record_intake(1, at(1_000), Duration::from_millis(20), hashes.clone());
+        // The chain continues across batches.
+        let first = encode_jsonl(&buffer.drain());
+        buffer.record_intake(2, at(3_000), Duration::from_millis(25), Vec::new());
+        let second = encode_jsonl(&buffer.drain());
+
+        let first: serde_json::Value = serde_json::from_slice(&first).unwrap();
+        let second: serde_json::Value = serde_json::from_slice(&second).unwrap();
+        assert_eq!(first["kind"], "intake");
+        assert_eq!(first["previous_hash"], serde_json::to_value(B256::ZERO).unwrap());
+        let first_hash = intake_hash(B256::ZERO, 1, 1_000, 20, &hashes);
+        assert_eq!(first["hash"], serde_json::to_value(first_hash).unwrap());
+        assert_eq!(second["previous_hash"], first["hash"]);
+        assert_eq!(
+            second["hash"],
+            serde_json::to_value(intake_hash(first_hash, 2, 3_000, 25, &[])).unwrap()
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    FixedPricingClient, FixedPricingError, OfflineConfig, SpotPriceCache,
+    filler_task::meets_min_margin_bps, metrics::OrderSkippedReason,
+};
+use alloy::primitives::{Address, U256};
+use core::fmt::{self, Display, Formatter};
+use eyre::{Result, WrapErr};
+use serde::Deserialize;
+use signet_types::SignedOrder;
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
+
+/// One USD in normalized 18-decimal USD.
+const USD: f64 = 1e18;
+
+/// Skip label for an order whose profitability can't be computed.
+const PRICING_FAILED: &str = "pricing-failed";
+
+/// A line of a captured order-flow dataset, in the archive's record format. Lines of other kinds
+/// are ignored.
+#[derive(Debug, Deserialize)]
+struct DatasetLine {
+    kind: String,
+    observed_at_ms: Option<u64>,
+    order: Option<SignedOrder>,
+}
+
+/// A historical USD price per whole token, in effect from `timestamp_ms`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct PricePoint {
+    timestamp_ms: u64,
+    token: Address,
+    price_usd: f64,
+}
+
+/// Parses the orders in a dataset, with when each was observed in milliseconds since the Unix
+/// epoch, ordered by observation.
+fn parse_orders(dataset: &str) -> Result<Vec<(u64, SignedOrder)>> {
+    let mut orders = Vec::new();
+    for (index, line) in dataset.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line: DatasetLine = serde_json::from_str(line)
+            .wrap_err_with(|| format!("invalid dataset line {}", index + 1))?;
+        if let ("order", Some(observed_at_ms), Some(order)) =
+            (line.kind.as_str(), line.observed_at_ms, line.order)
+        {
+            orders.push((observed_at_ms, order));
+        }
+    }
+    orders.sort_by_key(|(observed_at_ms, _order)| *observed_at_ms);
+    Ok(orders)
+}
+
+/// Parses newline-delimited historical prices, ordered by time.
+fn parse_prices(prices: &str) -> Result<Vec<PricePoint>> {
+    let mut points = prices
+        .lines()
+        .enumerate()
+        .filter(|(_index, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).wrap_err_with(|| format!("invalid price line {}", index + 1))
+        })
+        .collect::<Result<Vec<PricePoint>>>()?;
+    points.sort_by_key(|point| point.timestamp_ms);
+    Ok(points)
+}
+
+/// The hypothetical outcome of running a dataset through the filler's selection offline.
+#[derive(Debug, Default)]
+struct BacktestReport {
+    orders: usize,
+    fills: usize,
+    /// Margin of the hypothetical fills, before gas, in normalized 18-decimal USD.
+    profit: i128,
+    /// Orders skipped, by skip reason.
+    skipped: BTreeMap<&'static str, usize>,
+    /// Skipped orders that would have been profitable.
+    missed: usize,
+    /// Margin of the missed orders, before gas, in normalized 18-decimal USD.
+    missed_profit: i128,
+}
+
+impl Display for BacktestReport {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let usd = |margin: i128| margin as f64 / USD;
+        writeln!(formatter, "orders:                {}", self.orders)?;
+        writeln!(formatter, "hypothetical fills:    {}", self.fills)?;
+        writeln!(formatter, "profit before gas:     ${:.2}", usd(self.profit))?;
+        writeln!(
+            formatter,
+            "missed opportunities:  {} (${:.2})",
+            self.missed,
+            usd(self.missed_profit)
+        )?;
+        if self.skipped.is_empty() {
+            return Ok(());
+        }
+        writeln!(formatter, "skipped:")?;
+        for (reason, count) in &self.skipped {
+            writeln!(formatter, "  {reason}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs captured orders through the filler's offline checks: expiry, Permit2 nonces already
+/// consumed by an earlier hypothetical fill, pricing, and the minimum margin.
+#[derive(Debug)]
+struct Backtest {
+    pricing_client: FixedPricingClient,
+    /// Historical prices in effect, when the dataset comes with them.
+    spot_prices: SpotPriceCache,
+    min_margin_bps: Option<u16>,
+}
+
+impl Backtest {
+    fn run(&self, orders: Vec<(u64, SignedOrder)>, prices: Vec<PricePoint>) -> BacktestReport {
+        let mut report = BacktestReport::default();
+        let mut prices = prices.into_iter().peekable();
+        let mut seen = HashSet::new();
+        let mut consumed_nonces: HashSet<(Address, U256)> = HashSet::new();
+        for (observed_at_ms, order) in orders {
+            // An order is fetched every cycle until it is filled or expires; only the first
+            // observation counts.
+            if !seen.insert(*order.order_hash()) {
+                continue;
+            }
+            while let Some(price) = prices.next_if(|price| price.timestamp_ms <= observed_at_ms) {
+                self.spot_prices.insert(price.token, price.price_usd);
+            }
+            report.orders += 1;
+
+            let nonce = (order.permit().owner, order.permit().permit.nonce);
+            let profitability = self.pricing_client.profitability(&order);
+            // The margin of a fill, or why the order is skipped.
+            let fill = if order.is_expired_at(observed_at_ms / 1000) {
+                Err(OrderSkippedReason::Expired.as_str())
+            } else if consumed_nonces.contains(&nonce) {
+                Err(OrderSkippedReason::NonceConflict.as_str())
+            } else {
+                match &profitability {
+                    Ok(Some(margin)) if self.meets_min_margin(&order, *margin) => Ok(*margin),
+                    Ok(Some(_margin)) => Err(OrderSkippedReason::BelowMinMargin.as_str()),
+                    Ok(None) => Err(OrderSkippedReason::ExceedsMaxLoss.as_str()),
+                    Err(FixedPricingError::UnknownToken(_)) => {
+                        Err(OrderSkippedReason::UnknownToken.as_str())
+                    }
+                    Err(_error) => Err(PRICING_FAILED),
+                }
+            };
+            match fill {
+                Ok(margin) => {
+                    consumed_nonces.insert(nonce);
+                    report.fills += 1;
+                    report.profit = report.profit.saturating_add(margin);
+                }
+                Err(reason) => {
+                    *report.skipped.entry(reason).or_default() += 1;
+                    if let Ok(Some(margin @ 1..)) = profitability {
+                        report.missed += 1;
+                        report.missed_profit = report.missed_profit.saturating_add(margin);
+                    }
+                }
+            }
+        }
+        report
+    }
+
+    /// Whether `margin` is at least the configured share of the order's input value.
+    fn meets_min_margin(&self, order: &SignedOrder, margin: i128) -> bool {
+        let Some(min_margin_bps) = self.min_margin_bps else {
+            return true;
+        };
+        self.pricing_client
+            .input_value(order)
+            .is_ok_and(|input| meets_min_margin_bps(margin, input, min_margin_bps))
+    }
+}
+
+/// Run a captured order-flow dataset (newline-delimited archive records) through the filler's
+/// selection offline with the configured pricing, optionally with historical prices, and print
+/// the hypothetical fills, profit, and missed opportunities to stdout. Makes no RPC calls and
+/// submits nothing.
+pub fn run_backtest(config: &OfflineConfig, dataset: &str, prices: Option<&str>) -> Result<()> {
+    let orders = parse_orders(dataset)?;
+    let prices = prices.map(parse_prices).transpose()?.unwrap_or_default();
+    // Historical prices stay in effect until replaced, however old.
+    let spot_prices = SpotPriceCache::new(Duration::MAX);
+    let backtest = Backtest {
+        pricing_client: FixedPricingClient::for_offline(config)
+            .with_spot_prices(spot_prices.clone()),
+        spot_prices,
+        min_margin_bps: config.min_margin_bps(),
+    };
+    print!("{}", backtest.run(orders, prices));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use serde_json::json;
+    use signet_constants::SignetSystemConstants;
+
+    const ONE: u128 = 10_u128.pow(18);
+
+    /// 1 rollup WETH in, `usdc` host USDC out.
+    fn order(
+        constants: &SignetSystemConstants,
+        nonce: u64,
+        deadline: u64,
+        usdc: u64,
+    ) -> SignedOrder {
+        OrderBuilder::default()
+            .with_input(constants.rollup().tokens().weth(), U256::from(ONE))
+            .with_output(
+                constants.host().tokens().usdc(),
+                U256::from(usdc * 1_000_000),
+                Address::repeat_byte(0x01),
+                constants.host_chain_id(),
+            )
+            .with_nonce(nonce)
+            .with_deadline(deadline)
+            .build()
+    }
+
+    #[test]
+    fn backtest_reports_fills_skips_and_missed_profit() {
+        let constants = SignetSystemConstants::parmigiana();
+        let spot_prices = SpotPriceCache::new(Duration::MAX);
+        let backtest = Backtest {
+            pricing_client: FixedPricingClient::new(&constants, "parmigiana", 10)
+                .with_spot_prices(spot_prices.clone()),
+            spot_prices,
+            // 1% of the $3000 input: $30.
+            min_margin_bps: Some(100),
+        };
+        let record = |observed_at_ms: u64, order: &SignedOrder| {
+            json!({ "kind": "order", "observed_at_ms": observed_at_ms, "order": order }).to_string()
+        };
+        let filled = order(&constants, 1, 100, 2_900);
+        let dataset = [
+            record(1_000, &filled),
+            // Seen again the next cycle.
+            record(2_000, &filled),
+            // Same nonce as the fill.
+            record(3_000, &order(&constants, 1, 100, 2_800)),
+            // Expired when seen, at the later WETH price.
+            record(200_000, &order(&constants, 2, 150, 2_800)),
+            // $20 margin, below the minimum.
+            record(4_000, &order(&constants, 3, 100, 2_980)),
+            // WETH at $3100 by now: $110 margin.
+            record(6_000, &order(&constants, 4, 100, 2_990)),
+        ]
+        .join("\n");
+        let prices = json!({
+            "timestamp_ms": 5_000,
+            "token": constants.rollup().tokens().weth(),
+            "price_usd": 3_100.0,
+        })
+        .to_string();
+
+        let report = backtest.run(parse_orders(&dataset).unwrap(), parse_prices(&prices).unwrap());
+        assert_eq!((report.orders, report.fills), (5, 2));
+        assert_eq!(report.profit, (210 * ONE) as i128);
+        assert_eq!(report.skipped["expired"], 1);
+        assert_eq!(report.skipped["nonce-conflict"], 1);
+        assert_eq!(report.skipped["below-min-margin"], 1);
+        // The expired, conflicting, and below-minimum orders would all have been profitable.
+        assert_eq!(report.missed, 3);
+        assert_eq!(report.missed_profit, ((300 + 200 + 20) * ONE) as i128);
+    }
+}
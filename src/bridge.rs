@@ -0,0 +1,305 @@
+use crate::{
+    BridgeConfig, ChainTokenPair, FillProviderType, FillerContext, FixedPricingClient, IERC20,
+    InventoryAsset, KnownToken, metrics, query_balance,
+};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use eyre::{Result, WrapErr, eyre};
+use futures_util::future::join_all;
+use init4_bin_base::deps::tracing::{debug, info, instrument, warn};
+use signet_constants::SignetSystemConstants;
+use signet_zenith::Passage;
+use tokio::{select, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+/// How long to wait for each bridging transaction to be included before giving up on it.
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Host tokens that enter the rollup through Passage, and the rollup token each arrives as.
+const ROUTES: [(KnownToken, KnownToken); 4] = [
+    (KnownToken::HostUsdc, KnownToken::RollupUsd),
+    (KnownToken::HostUsdt, KnownToken::RollupUsd),
+    (KnownToken::HostWeth, KnownToken::RollupWeth),
+    (KnownToken::HostWbtc, KnownToken::RollupWbtc),
+];
+
+/// A token's balance and its value in normalized 18-decimal USD.
+#[derive(Debug, Clone, Copy)]
+struct Holding {
+    chain_token: ChainTokenPair,
+    balance: U256,
+    value: U256,
+}
+
+/// The filler's holdings of one inventory asset on each chain.
+#[derive(Debug, Default)]
+struct AssetHoldings {
+    host: Vec<Holding>,
+    rollup_value: U256,
+}
+
+impl AssetHoldings {
+    fn host_value(&self) -> U256 {
+        self.host.iter().map(|holding| holding.value).fold(U256::ZERO, U256::saturating_add)
+    }
+}
+
+/// The value to move onto the rollup to even out an asset whose rollup share has fallen below
+/// `min_share_percent` of its total, or `None` if the rollup share isn't low.
+fn rollup_shortfall(host_value: U256, rollup_value: U256, min_share_percent: u8) -> Option<U256> {
+    let total = host_value.saturating_add(rollup_value);
+    let low = rollup_value.saturating_mul(U256::from(100))
+        < total.saturating_mul(U256::from(min_share_percent));
+    low.then(|| total / U256::from(2) - rollup_value)
+}
+
+/// The amount of `holding` worth `value`, capped at its balance.
+fn amount_for_value(holding: &Holding, value: U256) -> U256 {
+    if holding.value.is_zero() || value >= holding.value {
+        return holding.balance;
+    }
+    holding.balance.saturating_mul(value) / holding.value
+}
+
+#[derive(Debug)]
+struct BridgeState {
+    config: BridgeConfig,
+    filler_address: Address,
+    constants: SignetSystemConstants,
+    host_provider: FillProviderType,
+    ru_provider: FillProviderType,
+    pricing_client: FixedPricingClient,
+}
+
+/// Background task that keeps inventory usable on the rollup: every interval it values the
+/// filler's USD, ETH, and BTC holdings on each chain, and when the rollup holds less than the
+/// configured share of an asset, enters host tokens into the rollup through the host Passage
+/// contract to even it out.
+///
+/// Moving inventory the other way needs an order filled by another filler, so a low host share
+/// is only logged and counted.
+#[derive(Debug)]
+pub struct BridgeTask {
+    state: Option<BridgeState>,
+    cancellation_token: CancellationToken,
+}
+
+impl BridgeTask {
+    /// Create the task. It does nothing if bridging is disabled.
+    pub fn new(context: &FillerContext) -> Self {
+        let cancellation_token = context.cancellation_token().clone();
+        let Some(config) = context.bridge_config() else {
+            return Self { state: None, cancellation_token };
+        };
+        let constants = context.constants().system().clone();
+        let mut pricing_client =
+            FixedPricingClient::new(&constants, context.chain_name(), context.max_loss_percent())
+                .with_token_table(context.token_table());
+        if let Some(twap_prices) = context.twap_prices() {
+            pricing_client = pricing_client.with_twap_prices(twap_prices.clone());
+        }
+        if let Some(spot_prices) = context.spot_prices() {
+            pricing_client = pricing_client.with_spot_prices(spot_prices.clone());
+        }
+        let state = BridgeState {
+            config: *config,
+            filler_address: context.signer().address(),
+            constants,
+            host_provider: context.host_provider().clone(),
+            ru_provider: context.ru_provider().clone(),
+            pricing_client,
+        };
+        info!(min_share_percent = config.min_share_percent(), "inventory bridging enabled");
+        Self { state: Some(state), cancellation_token }
+    }
+
+    /// Run the periodic bridging loop.
+    pub async fn run(self) {
+        let Some(state) = self.state else {
+            debug!("inventory bridging disabled");
+            return;
+        };
+        let mut interval = tokio::time::interval(state.config.interval());
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("bridge task cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    state.rebalance().await;
+                }
+            }
+        }
+    }
+}
+
+impl BridgeState {
+    /// Checks each asset's split between the chains and bridges the first asset found low on the
+    /// rollup. At most one transfer is made per check, so its effect on balances is seen before
+    /// the next.
+    #[instrument(skip_all)]
+    async fn rebalance(&self) {
+        let holdings = match self.holdings().await {
+            Ok(holdings) => holdings,
+            Err(error) => {
+                warn!(error = format!("{error:#}"), "failed to query inventory for bridging");
+                return;
+            }
+        };
+        let min_share_percent = self.config.min_share_percent();
+        for (asset, holdings) in InventoryAsset::ALL.iter().zip(holdings) {
+            let host_value = holdings.host_value();
+            if rollup_shortfall(holdings.rollup_value, host_value, min_share_percent).is_some() {
+                warn!(
+                    asset = asset.as_str(),
+                    %host_value,
+                    rollup_value = %holdings.rollup_value,
+                    "host inventory low; moving it back from the rollup needs an order"
+                );
+            }
+            let Some(shortfall) =
+                rollup_shortfall(host_value, holdings.rollup_value, min_share_percent)
+            else {
+                continue;
+            };
+            let Some(source) = holdings.host.iter().max_by_key(|holding| holding.value) else {
+                continue;
+            };
+            let amount = amount_for_value(source, shortfall);
+            if amount.is_zero() {
+                continue;
+            }
+            match self.enter(source.chain_token.token(), amount).await {
+                Ok(()) => {
+                    info!(
+                        asset = asset.as_str(),
+                        token = %source.chain_token,
+                        %amount,
+                        "bridged inventory onto the rollup"
+                    );
+                    metrics::record_bridge_transfer(*asset, metrics::SubmissionResult::Success);
+                }
+                Err(error) => {
+                    warn!(
+                        asset = asset.as_str(),
+                        token = %source.chain_token,
+                        error = format!("{error:#}"),
+                        "failed to bridge inventory onto the rollup"
+                    );
+                    metrics::record_bridge_transfer(*asset, metrics::SubmissionResult::Failure);
+                }
+            }
+            return;
+        }
+    }
+
+    /// The filler's holdings of each inventory asset, in [`InventoryAsset::ALL`] order. Fails if
+    /// any balance can't be queried or valued, since a partial view would misstate the split.
+    async fn holdings(&self) -> Result<[AssetHoldings; InventoryAsset::ALL.len()]> {
+        let known = ROUTES.iter().map(|(host, _)| *host).chain([
+            KnownToken::RollupUsd,
+            KnownToken::RollupWeth,
+            KnownToken::RollupWbtc,
+        ]);
+        let holdings = join_all(known.map(|known| async move {
+            let chain_token = known.resolve(&self.constants);
+            let provider = if chain_token.chain_id() == self.constants.ru_chain_id() {
+                &self.ru_provider
+            } else {
+                &self.host_provider
+            };
+            let balance = query_balance(provider, self.filler_address, chain_token.token())
+                .await
+                .wrap_err_with(|| format!("failed to query {chain_token} balance"))?;
+            let value = self
+                .pricing_client
+                .value(&chain_token.token(), balance)
+                .wrap_err_with(|| format!("failed to value {chain_token} balance"))?;
+            let asset = known.inventory_asset().expect("bridged tokens are inventory assets");
+            Ok::<_, eyre::Report>((asset, Holding { chain_token, balance, value }))
+        }))
+        .await;
+
+        let mut assets: [AssetHoldings; InventoryAsset::ALL.len()] = Default::default();
+        for holding in holdings {
+            let (asset, holding) = holding?;
+            let asset = &mut assets[asset as usize];
+            if holding.chain_token.chain_id() == self.constants.ru_chain_id() {
+                asset.rollup_value = asset.rollup_value.saturating_add(holding.value);
+            } else {
+                asset.host.push(holding);
+            }
+        }
+        Ok(assets)
+    }
+
+    /// Enters `amount` of the host `token` into the rollup, to the filler's own rollup address,
+    /// first approving Passage to move it if needed.
+    async fn enter(&self, token: Address, amount: U256) -> Result<()> {
+        let passage_address = self.constants.host().passage();
+        let erc20 = IERC20::new(token, &self.host_provider);
+        let allowance = erc20
+            .allowance(self.filler_address, passage_address)
+            .call()
+            .await
+            .wrap_err("failed to query Passage allowance")?;
+        if allowance < amount {
+            let approved = erc20
+                .approve(passage_address, amount)
+                .send()
+                .await
+                .wrap_err("failed to send Passage approval")?
+                .with_timeout(Some(TRANSACTION_TIMEOUT))
+                .get_receipt()
+                .await
+                .wrap_err("Passage approval not included")?;
+            if !approved.status() {
+                return Err(eyre!("Passage approval reverted"));
+            }
+        }
+        let receipt = Passage::new(passage_address, &self.host_provider)
+            .enterToken_0(
+                U256::from(self.constants.ru_chain_id()),
+                self.filler_address,
+                token,
+                amount,
+            )
+            .send()
+            .await
+            .wrap_err("failed to send Passage enter")?
+            .with_timeout(Some(TRANSACTION_TIMEOUT))
+            .get_receipt()
+            .await
+            .wrap_err("Passage enter not included")?;
+        if !receipt.status() {
+            return Err(eyre!("Passage enter reverted"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rollup_shortfall_evens_out_low_share() {
+        let usd = |dollars: u64| U256::from(dollars);
+        // 10% on the rollup, below a 20% minimum: move enough to reach half.
+        assert_eq!(rollup_shortfall(usd(900), usd(100), 20), Some(usd(400)));
+        assert_eq!(rollup_shortfall(usd(800), usd(200), 20), None);
+        assert_eq!(rollup_shortfall(U256::ZERO, U256::ZERO, 20), None);
+
+        let holding = Holding {
+            chain_token: ChainTokenPair::new(1, Address::ZERO),
+            balance: usd(2_000),
+            value: usd(900),
+        };
+        assert_eq!(amount_for_value(&holding, usd(450)), usd(1_000));
+        assert_eq!(amount_for_value(&holding, usd(5_000)), usd(2_000));
+    }
+}
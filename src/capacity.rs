@@ -0,0 +1,225 @@
+use crate::{
+    AllowanceCache, ChainTokenPair, FillProviderType, FillerContext, FixedPricingClient,
+    KnownToken, query_balance,
+};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use futures_util::future::join_all;
+use init4_bin_base::deps::tracing::warn;
+use serde::Serialize;
+use signet_constants::{NATIVE_TOKEN_ADDRESS, SignetSystemConstants};
+
+/// Rollup tokens the filler accepts as order inputs: those the pricing client can value.
+const INPUT_TOKENS: [KnownToken; 3] =
+    [KnownToken::RollupWeth, KnownToken::RollupWbtc, KnownToken::RollupUsd];
+
+/// A token on a specific chain, as advertised.
+#[derive(Debug, Clone, Copy, Serialize)]
+struct AdvertisedToken {
+    chain_id: u64,
+    token: Address,
+    name: &'static str,
+}
+
+impl AdvertisedToken {
+    fn new(known: KnownToken, constants: &SignetSystemConstants) -> Self {
+        let chain_token = known.resolve(constants);
+        Self { chain_id: chain_token.chain_id(), token: chain_token.token(), name: known.name() }
+    }
+}
+
+/// How much of an output token the filler can currently supply.
+#[derive(Debug, Serialize)]
+struct OutputCapacity {
+    #[serde(flatten)]
+    token: AdvertisedToken,
+    /// The filler's balance, capped by its Permit2 allowance for ERC20 tokens, in raw units.
+    available: U256,
+    /// The largest fill the filler will make in this token, in normalized 18-decimal USD: the
+    /// value of `available`, capped by the hot-key per-fill limit if one is configured.
+    max_fill_notional: U256,
+}
+
+/// An input token the filler accepts in exchange for an output token it can supply.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct TokenPair {
+    input_token: Address,
+    output_chain_id: u64,
+    output_token: Address,
+}
+
+/// Response body for `GET /capacity`.
+#[derive(Debug, Serialize)]
+pub(crate) struct Capacity {
+    filler_address: Address,
+    /// Maximum loss the filler accepts on an order, as a percentage of its input value.
+    max_loss_percent: u8,
+    input_tokens: Vec<AdvertisedToken>,
+    outputs: Vec<OutputCapacity>,
+    /// Every accepted input paired with every output the filler can currently supply.
+    pairs: Vec<TokenPair>,
+}
+
+/// Builds the filler's capacity advertisement from its configuration and live balances, for
+/// upstream order routing. Served by the admin API; the transaction cache has no endpoint to push
+/// it to.
+#[derive(Debug)]
+pub(crate) struct CapacityReporter {
+    filler_address: Address,
+    constants: SignetSystemConstants,
+    host_provider: FillProviderType,
+    ru_provider: FillProviderType,
+    allowance_cache: AllowanceCache,
+    pricing_client: FixedPricingClient,
+    max_loss_percent: u8,
+    max_fill_notional: Option<U256>,
+}
+
+impl CapacityReporter {
+    pub(crate) fn new(context: &FillerContext) -> Self {
+        let constants = context.constants().system().clone();
+        let mut pricing_client =
+            FixedPricingClient::new(&constants, context.chain_name(), context.max_loss_percent())
+                .with_token_table(context.token_table());
+        if let Some(twap_prices) = context.twap_prices() {
+            pricing_client = pricing_client.with_twap_prices(twap_prices.clone());
+        }
+        if let Some(spot_prices) = context.spot_prices() {
+            pricing_client = pricing_client.with_spot_prices(spot_prices.clone());
+        }
+        Self {
+            filler_address: context.signer().address(),
+            constants,
+            host_provider: context.host_provider().clone(),
+            ru_provider: context.ru_provider().clone(),
+            allowance_cache: context.allowance_cache().clone(),
+            pricing_client,
+            max_loss_percent: context.max_loss_percent(),
+            max_fill_notional: context
+                .spend_policy_config()
+                .and_then(|config| config.max_fill_notional()),
+        }
+    }
+
+    /// Queries the filler's balance of every output token. Tokens whose balance can't be queried
+    /// or valued are left out, so the advertisement never overstates capacity.
+    pub(crate) async fn capacity(&self) -> Capacity {
+        // Host ETH is reserved for gas, and can't be told apart from the rollup USD token by
+        // address when pricing.
+        let output_tokens =
+            KnownToken::ALL.iter().filter(|known| known.inventory_asset().is_some());
+        let outputs = join_all(output_tokens.map(|known| self.output_capacity(*known))).await;
+        let input_tokens: Vec<AdvertisedToken> = INPUT_TOKENS
+            .iter()
+            .map(|known| AdvertisedToken::new(*known, &self.constants))
+            .collect();
+        let outputs: Vec<OutputCapacity> = outputs.into_iter().flatten().collect();
+        let pairs = pairs(&input_tokens, &outputs);
+        Capacity {
+            filler_address: self.filler_address,
+            max_loss_percent: self.max_loss_percent,
+            input_tokens,
+            outputs,
+            pairs,
+        }
+    }
+
+    async fn output_capacity(&self, known: KnownToken) -> Option<OutputCapacity> {
+        let chain_token = known.resolve(&self.constants);
+        let provider = if chain_token.chain_id() == self.constants.ru_chain_id() {
+            &self.ru_provider
+        } else {
+            &self.host_provider
+        };
+        let balance = query_balance(provider, self.filler_address, chain_token.token())
+            .await
+            .inspect_err(|error| {
+                warn!(
+                    %chain_token,
+                    error = format!("{error:#}"),
+                    "failed to query balance for capacity"
+                );
+            })
+            .ok()?;
+        let available = balance.min(self.allowance(&chain_token));
+        let value = self
+            .pricing_client
+            .value(&chain_token.token(), available)
+            .inspect_err(|error| warn!(%chain_token, %error, "failed to value capacity"))
+            .ok()?;
+        Some(OutputCapacity {
+            token: AdvertisedToken::new(known, &self.constants),
+            available,
+            max_fill_notional: max_fill_notional(value, self.max_fill_notional),
+        })
+    }
+
+    /// Native tokens need no allowance; a missing cached allowance is assumed to be zero, as in
+    /// the filler's preflight check.
+    fn allowance(&self, chain_token: &ChainTokenPair) -> U256 {
+        if chain_token.token() == NATIVE_TOKEN_ADDRESS {
+            U256::MAX
+        } else {
+            self.allowance_cache.get(chain_token).unwrap_or_default()
+        }
+    }
+}
+
+fn max_fill_notional(available_value: U256, per_fill_limit: Option<U256>) -> U256 {
+    per_fill_limit.map_or(available_value, |limit| available_value.min(limit))
+}
+
+/// Pairs every input token with every output the filler has something of to supply.
+fn pairs(input_tokens: &[AdvertisedToken], outputs: &[OutputCapacity]) -> Vec<TokenPair> {
+    outputs
+        .iter()
+        .filter(|output| !output.available.is_zero())
+        .flat_map(|output| {
+            input_tokens.iter().map(|input| TokenPair {
+                input_token: input.token,
+                output_chain_id: output.token.chain_id,
+                output_token: output.token.token,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(chain_id: u64, byte: u8) -> AdvertisedToken {
+        AdvertisedToken { chain_id, token: Address::repeat_byte(byte), name: "test" }
+    }
+
+    fn output(chain_id: u64, byte: u8, available: u64) -> OutputCapacity {
+        OutputCapacity {
+            token: token(chain_id, byte),
+            available: U256::from(available),
+            max_fill_notional: U256::ZERO,
+        }
+    }
+
+    #[test]
+    fn pairs_cover_only_supplied_outputs() {
+        let inputs = [token(2, 0x01), token(2, 0x02)];
+        let outputs = [output(1, 0x11, 5), output(1, 0x12, 0), output(2, 0x13, 1)];
+        let pairs = pairs(&inputs, &outputs);
+        assert_eq!(pairs.len(), 4);
+        assert!(pairs.iter().all(|pair| pair.output_token != Address::repeat_byte(0x12)));
+        assert!(pairs.contains(&TokenPair {
+            input_token: Address::repeat_byte(0x02),
+            output_chain_id: 2,
+            output_token: Address::repeat_byte(0x13),
+        }));
+    }
+
+    #[test]
+    fn max_fill_is_capped_by_per_fill_limit() {
+        assert_eq!(max_fill_notional(U256::from(500), None), U256::from(500));
+        assert_eq!(max_fill_notional(U256::from(500), Some(U256::from(100))), U256::from(100));
+        assert_eq!(max_fill_notional(U256::from(50), Some(U256::from(100))), U256::from(50));
+    }
+}
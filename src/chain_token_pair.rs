@@ -1,3 +1,4 @@
+use crate::InventoryAsset;
 use alloy::primitives::Address;
 use init4_bin_base::deps::tracing::warn;
 use signet_constants::SignetSystemConstants;
@@ -72,6 +73,26 @@ impl KnownToken {
         }
     }
 
+    /// Number of decimals in the token's base unit.
+    pub(crate) const fn decimals(&self) -> u8 {
+        match self {
+            Self::HostUsdc | Self::HostUsdt => 6,
+            Self::HostWbtc | Self::RollupWbtc => 8,
+            Self::HostEth | Self::HostWeth | Self::RollupWeth | Self::RollupUsd => 18,
+        }
+    }
+
+    /// The asset class this token counts towards for inventory targets. Host ETH is excluded
+    /// because it is reserved for gas rather than fill inventory.
+    pub(crate) const fn inventory_asset(&self) -> Option<InventoryAsset> {
+        match self {
+            Self::HostEth => None,
+            Self::HostUsdc | Self::HostUsdt | Self::RollupUsd => Some(InventoryAsset::Usd),
+            Self::HostWeth | Self::RollupWeth => Some(InventoryAsset::Eth),
+            Self::HostWbtc | Self::RollupWbtc => Some(InventoryAsset::Btc),
+        }
+    }
+
     /// Resolve to the concrete [`ChainTokenPair`] using chain constants.
     pub(crate) fn resolve(&self, constants: &SignetSystemConstants) -> ChainTokenPair {
         let host_chain_id = constants.host_chain_id();
@@ -1,28 +1,165 @@
-use alloy::rpc::client::BuiltInConnectionString;
+use alloy::{
+    primitives::{Address, U256},
+    rpc::client::BuiltInConnectionString,
+};
+use core::fmt::{self, Debug, Formatter};
 use eyre::{Result, WrapErr, bail};
 use init4_bin_base::utils::{
     from_env::FromEnv,
+    metrics::MetricsConfig,
     provider::{ProviderConfig, PubSubConfig},
     signer::LocalOrAwsConfig,
 };
 use itertools::Itertools;
+use serde::Deserialize;
 use signet_constants::SignetConstants;
-use std::{num::NonZeroUsize, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    num::{NonZeroU32, NonZeroUsize},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
 const CHAIN_NAME_VAR: &str = "SIGNET_FILLER_CHAIN_NAME";
 const HOST_RPC_VAR: &str = "SIGNET_FILLER_HOST_RPC_URL";
 const RU_RPC_VAR: &str = "SIGNET_FILLER_ROLLUP_RPC_URL";
 const MAX_LOSS_PERCENT_VAR: &str = "SIGNET_FILLER_MAX_LOSS_PERCENT";
+const MIN_MARGIN_BPS_VAR: &str = "SIGNET_FILLER_MIN_MARGIN_BPS";
+const MIN_SUBMISSION_LEAD_MS_VAR: &str = "SIGNET_FILLER_MIN_SUBMISSION_LEAD_MS";
+const FOLLOW_UP_LEADS_MS_VAR: &str = "SIGNET_FILLER_FOLLOW_UP_LEADS_MS";
+const ADAPTIVE_LEAD_MIN_MS_VAR: &str = "SIGNET_FILLER_ADAPTIVE_LEAD_MIN_MS";
+const ADAPTIVE_LEAD_MAX_MS_VAR: &str = "SIGNET_FILLER_ADAPTIVE_LEAD_MAX_MS";
+const EVALUATION_INTERVAL_MS_VAR: &str = "SIGNET_FILLER_EVALUATION_INTERVAL_MS";
+const CYCLE_MAX_QUOTES_VAR: &str = "SIGNET_FILLER_CYCLE_MAX_QUOTES";
+const CYCLE_MAX_RPC_CALLS_VAR: &str = "SIGNET_FILLER_CYCLE_MAX_RPC_CALLS";
+const CYCLE_MAX_WALL_MS_VAR: &str = "SIGNET_FILLER_CYCLE_MAX_WALL_MS";
+const REPUTATION_HALF_LIFE_SECS_VAR: &str = "SIGNET_FILLER_REPUTATION_HALF_LIFE_SECS";
+const TOXIC_FLOW_DEPRIORITIZE_USD_VAR: &str = "SIGNET_FILLER_TOXIC_FLOW_DEPRIORITIZE_USD";
+const TOXIC_FLOW_PAUSE_USD_VAR: &str = "SIGNET_FILLER_TOXIC_FLOW_PAUSE_USD";
+const TOXIC_FLOW_MARKOUT_SECS_VAR: &str = "SIGNET_FILLER_TOXIC_FLOW_MARKOUT_SECS";
+const TOXIC_FLOW_HALF_LIFE_SECS_VAR: &str = "SIGNET_FILLER_TOXIC_FLOW_HALF_LIFE_SECS";
+const MAX_DAILY_LOSS_USD_VAR: &str = "SIGNET_FILLER_MAX_DAILY_LOSS_USD";
+const DAILY_LOSS_RESET_UTC_VAR: &str = "SIGNET_FILLER_DAILY_LOSS_RESET_UTC";
+const REPUTATION_SKIP_THRESHOLD_VAR: &str = "SIGNET_FILLER_REPUTATION_SKIP_THRESHOLD";
+const TRUSTED_OWNERS_VAR: &str = "SIGNET_FILLER_TRUSTED_OWNERS";
+const DENIED_OWNERS_VAR: &str = "SIGNET_FILLER_DENIED_OWNERS";
+const SUBMIT_RETRY_BACKOFF_MS_VAR: &str = "SIGNET_FILLER_SUBMIT_RETRY_BACKOFF_MS";
+const INVENTORY_TARGETS_VAR: &str = "SIGNET_FILLER_INVENTORY_TARGETS";
+const INVENTORY_BONUS_BPS_VAR: &str = "SIGNET_FILLER_INVENTORY_BONUS_BPS";
+const INVENTORY_PENALTY_BPS_VAR: &str = "SIGNET_FILLER_INVENTORY_PENALTY_BPS";
+const TWAP_POOLS_VAR: &str = "SIGNET_FILLER_TWAP_POOLS";
+const TOKEN_TABLE_VAR: &str = "SIGNET_FILLER_TOKEN_TABLE";
+const APPROVAL_POLICY_VAR: &str = "SIGNET_FILLER_APPROVAL_POLICY";
+const TWAP_WINDOW_SECS_VAR: &str = "SIGNET_FILLER_TWAP_WINDOW_SECS";
+const PRICE_API_VAR: &str = "SIGNET_FILLER_PRICE_API";
+const PRICE_API_KEY_VAR: &str = "SIGNET_FILLER_PRICE_API_KEY";
+const PRICE_API_URL_VAR: &str = "SIGNET_FILLER_PRICE_API_URL";
+const STALE_PRICE_POLICY_VAR: &str = "SIGNET_FILLER_STALE_PRICE_POLICY";
+const PRICE_API_REFRESH_SECS_VAR: &str = "SIGNET_FILLER_PRICE_API_REFRESH_SECS";
+const PRICE_CROSS_CHECK_BPS_VAR: &str = "SIGNET_FILLER_PRICE_CROSS_CHECK_BPS";
+const VOLATILITY_HAIRCUTS_VAR: &str = "SIGNET_FILLER_VOLATILITY_HAIRCUTS";
+const INPUT_TOKEN_ALLOWLIST_VAR: &str = "SIGNET_FILLER_INPUT_TOKEN_ALLOWLIST";
+const INPUT_TOKEN_DENYLIST_VAR: &str = "SIGNET_FILLER_INPUT_TOKEN_DENYLIST";
+const OUTPUT_TOKEN_ALLOWLIST_VAR: &str = "SIGNET_FILLER_OUTPUT_TOKEN_ALLOWLIST";
+const OUTPUT_TOKEN_DENYLIST_VAR: &str = "SIGNET_FILLER_OUTPUT_TOKEN_DENYLIST";
+const QUOTE_CACHE_TTL_MS_VAR: &str = "SIGNET_FILLER_QUOTE_CACHE_TTL_MS";
+const UNPROFITABLE_MAX_BACKOFF_SLOTS_VAR: &str = "SIGNET_FILLER_UNPROFITABLE_MAX_BACKOFF_SLOTS";
+const TOKEN_DISCOVERY_VAR: &str = "SIGNET_FILLER_TOKEN_DISCOVERY";
+const UNISWAP_V3_FACTORY_VAR: &str = "SIGNET_FILLER_UNISWAP_V3_FACTORY";
+const DISCOVERY_MIN_LIQUIDITY_USD_VAR: &str = "SIGNET_FILLER_DISCOVERY_MIN_LIQUIDITY_USD";
+const GAS_PER_ORDER_VAR: &str = "SIGNET_FILLER_GAS_PER_ORDER";
+const HOST_GAS_PER_ORDER_VAR: &str = "SIGNET_FILLER_HOST_GAS_PER_ORDER";
+const GAS_PER_BUNDLE_VAR: &str = "SIGNET_FILLER_GAS_PER_BUNDLE";
+const BUNDLE_GAS_BUDGET_VAR: &str = "SIGNET_FILLER_BUNDLE_GAS_BUDGET";
+const FEE_ORACLE_REFRESH_SECS_VAR: &str = "SIGNET_FILLER_FEE_ORACLE_REFRESH_SECS";
+const BRIDGE_MIN_SHARE_PERCENT_VAR: &str = "SIGNET_FILLER_BRIDGE_MIN_SHARE_PERCENT";
+const REDIS_URL_VAR: &str = "SIGNET_FILLER_REDIS_URL";
+const LEADER_LEASE_SECS_VAR: &str = "SIGNET_FILLER_LEADER_LEASE_SECS";
+const FILLED_ORDERS_CACHE_SIZE_VAR: &str = "SIGNET_FILLER_FILLED_ORDERS_CACHE_SIZE";
+const BRIDGE_INTERVAL_SECS_VAR: &str = "SIGNET_FILLER_BRIDGE_INTERVAL_SECS";
+const SIMULATION_MIN_NOTIONAL_USD_VAR: &str = "SIGNET_FILLER_SIMULATION_MIN_NOTIONAL_USD";
+const MIN_ORDER_NOTIONAL_USD_VAR: &str = "SIGNET_FILLER_MIN_ORDER_NOTIONAL_USD";
+const MAX_ORDER_NOTIONAL_USD_VAR: &str = "SIGNET_FILLER_MAX_ORDER_NOTIONAL_USD";
+const FILL_WEBHOOK_URL_VAR: &str = "SIGNET_FILLER_FILL_WEBHOOK_URL";
+const SCREENING_LIST_VAR: &str = "SIGNET_FILLER_SCREENING_LIST";
+const SCREENING_API_URL_VAR: &str = "SIGNET_FILLER_SCREENING_API_URL";
+const SCREENING_API_KEY_VAR: &str = "SIGNET_FILLER_SCREENING_API_KEY";
+const SCREENING_CACHE_SECS_VAR: &str = "SIGNET_FILLER_SCREENING_CACHE_SECS";
+const SCORING_HOOK_URL_VAR: &str = "SIGNET_FILLER_SCORING_HOOK_URL";
+const SCORING_HOOK_TIMEOUT_MS_VAR: &str = "SIGNET_FILLER_SCORING_HOOK_TIMEOUT_MS";
+const LOW_BALANCE_USD_VAR: &str = "SIGNET_FILLER_LOW_BALANCE_USD";
+const MAX_POSITIONS_USD_VAR: &str = "SIGNET_FILLER_MAX_POSITIONS_USD";
+const TREASURY_ADDRESS_VAR: &str = "SIGNET_FILLER_TREASURY_ADDRESS";
+const ORDER_FILE_VAR: &str = "SIGNET_FILLER_ORDER_FILE";
+const BUNDLE_FILE_VAR: &str = "SIGNET_FILLER_BUNDLE_FILE";
+const BOOTSTRAP_MODE_VAR: &str = "SIGNET_FILLER_BOOTSTRAP_MODE";
+const SCHEDULING_MODE_VAR: &str = "SIGNET_FILLER_SCHEDULING_MODE";
+const ORDER_SELECTION_POLICY_VAR: &str = "SIGNET_FILLER_ORDER_SELECTION_POLICY";
+const AGING_BOOST_PERCENT_VAR: &str = "SIGNET_FILLER_AGING_BOOST_PERCENT";
+const QUIET_PERIODS_VAR: &str = "SIGNET_FILLER_QUIET_PERIODS";
 const TARGET_BLOCKS_VAR: &str = "SIGNET_FILLER_TARGET_BLOCKS";
+const FILL_DEADLINE_OFFSET_SECS_VAR: &str = "SIGNET_FILLER_FILL_DEADLINE_OFFSET_SECS";
 const MAX_ORDERS_PER_BUNDLE_VAR: &str = "SIGNET_FILLER_MAX_ORDERS_PER_BUNDLE";
+const HEALTHCHECK_HOST_VAR: &str = "SIGNET_FILLER_HEALTHCHECK_HOST";
+const METRICS_HOST_VAR: &str = "SIGNET_FILLER_METRICS_HOST";
+const ADMIN_PORT_VAR: &str = "SIGNET_FILLER_ADMIN_PORT";
+const ADMIN_HOST_VAR: &str = "SIGNET_FILLER_ADMIN_HOST";
+const ADMIN_TOKEN_VAR: &str = "SIGNET_FILLER_ADMIN_TOKEN";
+const ARCHIVE_INTERVAL_SECS_VAR: &str = "SIGNET_FILLER_ARCHIVE_INTERVAL_SECS";
+const MAX_FILLS_PER_MINUTE_VAR: &str = "SIGNET_FILLER_MAX_FILLS_PER_MINUTE";
+const MAX_FILLS_PER_HOUR_VAR: &str = "SIGNET_FILLER_MAX_FILLS_PER_HOUR";
+const MAX_FILLS_PER_OWNER_PER_HOUR_VAR: &str = "SIGNET_FILLER_MAX_FILLS_PER_OWNER_PER_HOUR";
+const MAX_NOTIONAL_USD_PER_OWNER_PER_HOUR_VAR: &str =
+    "SIGNET_FILLER_MAX_NOTIONAL_USD_PER_OWNER_PER_HOUR";
+const MAX_NOTIONAL_USD_PER_MINUTE_VAR: &str = "SIGNET_FILLER_MAX_NOTIONAL_USD_PER_MINUTE";
+const MAX_NOTIONAL_USD_PER_HOUR_VAR: &str = "SIGNET_FILLER_MAX_NOTIONAL_USD_PER_HOUR";
+const HOT_MAX_FILL_USD_VAR: &str = "SIGNET_FILLER_HOT_MAX_FILL_USD";
+const HOT_MAX_DAILY_USD_VAR: &str = "SIGNET_FILLER_HOT_MAX_DAILY_USD";
+const APPROVER_TOKEN_VAR: &str = "SIGNET_FILLER_APPROVER_TOKEN";
 
 const DEFAULT_CHAIN_NAME: &str = "parmigiana";
 const DEFAULT_HOST_RPC: &str = "https://host-rpc.parmigiana.signet.sh";
 const DEFAULT_RU_RPC: &str = "wss://rpc.parmigiana.signet.sh";
 const DEFAULT_BLOCK_LEAD_DURATION: Duration = Duration::from_secs(2);
+const DEFAULT_MIN_SUBMISSION_LEAD: Duration = Duration::from_millis(250);
 const DEFAULT_MAX_LOSS_PERCENT: u8 = 10;
 const DEFAULT_HEALTHCHECK_PORT: u16 = 8080;
+/// Health and metrics listen on all interfaces by default, matching the previous behaviour.
+const DEFAULT_PUBLIC_HOST: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+/// The admin API is internal-only by default.
+const DEFAULT_ADMIN_HOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+const DEFAULT_ARCHIVE_PREFIX: &str = "signet-filler";
+const DEFAULT_ARCHIVE_INTERVAL: Duration = Duration::from_secs(300);
 const DEFAULT_TARGET_BLOCKS: u8 = 5;
+const DEFAULT_BOOTSTRAP_MODE: BootstrapMode = BootstrapMode::DryRun;
+const DEFAULT_SCHEDULING_MODE: SchedulingMode = SchedulingMode::Clock;
+const DEFAULT_ORDER_SELECTION_POLICY: OrderSelectionPolicy = OrderSelectionPolicy::ProfitMax;
+const DEFAULT_REPUTATION_HALF_LIFE: Duration = Duration::from_secs(3600);
+const DEFAULT_REPUTATION_SKIP_THRESHOLD: u32 = 3;
+const DEFAULT_TOXIC_FLOW_MARKOUT: Duration = Duration::from_secs(60);
+const DEFAULT_TOXIC_FLOW_HALF_LIFE: Duration = Duration::from_secs(24 * 3600);
+const DEFAULT_SUBMIT_MAX_RETRIES: u32 = 2;
+const DEFAULT_SUBMIT_RETRY_BACKOFF: Duration = Duration::from_millis(50);
+const DEFAULT_INVENTORY_BONUS_BPS: u16 = 10;
+const DEFAULT_INVENTORY_PENALTY_BPS: u16 = 10;
+const MAX_BPS: u16 = 10_000;
+const DEFAULT_TWAP_WINDOW: Duration = Duration::from_secs(1800);
+/// Keeps within CoinGecko's public rate limit with plenty of headroom.
+const DEFAULT_PRICE_API_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_PRIORITY_FEE_MARKUP_PERCENT: u16 = 20;
+/// About a week of 12-second host blocks.
+const DEFAULT_DISCOVERY_MIN_AGE_BLOCKS: u64 = 50_400;
+const DEFAULT_DISCOVERY_MIN_LIQUIDITY_USD: u64 = 100_000;
+/// One host slot, so the estimate tracks every block's base fee change.
+const DEFAULT_FEE_ORACLE_REFRESH_INTERVAL: Duration = Duration::from_secs(12);
+const DEFAULT_BRIDGE_INTERVAL: Duration = Duration::from_secs(600);
+const DEFAULT_REDIS_KEY_PREFIX: &str = "signet-filler";
+const DEFAULT_ORDER_BACKFILL_BLOCKS: u64 = 256;
+const DEFAULT_FILLED_ORDERS_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+const DEFAULT_SCREENING_CACHE_TTL: Duration = Duration::from_secs(3600);
+const DEFAULT_SCORING_HOOK_TIMEOUT: Duration = Duration::from_millis(500);
 /// Caps `target_blocks` to avoid wasting resources on redundant inclusion attempts once a bundle
 /// has either landed or become clearly stale.
 const MAX_TARGET_BLOCKS: u8 = 10;
@@ -61,6 +198,52 @@ struct ConfigInner {
     )]
     block_lead_duration_ms: Option<u64>,
 
+    #[from_env(
+        var = "SIGNET_FILLER_MIN_SUBMISSION_LEAD_MS",
+        desc = "Minimum time that must remain before the block boundary when a fill bundle is \
+            about to be submitted, in milliseconds. Submission is skipped for the slot if order \
+            processing leaves less than this. Must be less than the block lead duration \
+            [default: 250]",
+        optional
+    )]
+    min_submission_lead_ms: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_FOLLOW_UP_LEADS_MS",
+        desc = "Comma-separated further submission attempts per slot, each as milliseconds before \
+            the block boundary, picking up orders that arrived after the first attempt. Each must \
+            be less than the block lead duration and more than SIGNET_FILLER_MIN_SUBMISSION_LEAD_MS \
+            [default: unset, one attempt per slot]",
+        optional
+    )]
+    follow_up_leads_ms: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ADAPTIVE_LEAD_MIN_MS",
+        desc = "Lower bound, in milliseconds, of the block lead when tuned to observed inclusion. \
+            Requires SIGNET_FILLER_ADAPTIVE_LEAD_MAX_MS; must be more than \
+            SIGNET_FILLER_MIN_SUBMISSION_LEAD_MS [default: unset, fixed block lead]",
+        optional
+    )]
+    adaptive_lead_min_ms: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ADAPTIVE_LEAD_MAX_MS",
+        desc = "Upper bound, in milliseconds, of the block lead when tuned to observed inclusion. \
+            Requires SIGNET_FILLER_ADAPTIVE_LEAD_MIN_MS [default: unset, fixed block lead]",
+        optional
+    )]
+    adaptive_lead_max_ms: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_EVALUATION_INTERVAL_MS",
+        desc = "Evaluate orders continuously, every this many milliseconds, and submit the \
+            precomputed ready-to-fill set at the block lead. Must be greater than 0 when set \
+            [default: unset, evaluate at the block lead]",
+        optional
+    )]
+    evaluation_interval_ms: Option<u64>,
+
     #[from_env(
         var = "SIGNET_FILLER_MAX_LOSS_PERCENT",
         desc = "Maximum acceptable loss percent for order pricing, 0-100 [default: 10]",
@@ -68,6 +251,22 @@ struct ConfigInner {
     )]
     max_loss_percent: Option<u8>,
 
+    #[from_env(
+        var = "SIGNET_FILLER_MIN_EXPECTED_PROFIT_CENTS",
+        desc = "Minimum expected profit in US cents, after discounting by the estimated inclusion \
+                probability, for an order to be filled [default: unset, no minimum]",
+        optional
+    )]
+    min_expected_profit_cents: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MIN_MARGIN_BPS",
+        desc = "Minimum margin, after gas, in basis points of the order's input value, 0-10000, \
+                for an order to be filled [default: unset, no minimum]",
+        optional
+    )]
+    min_margin_bps: Option<u16>,
+
     #[from_env(
         var = "SIGNET_FILLER_HEALTHCHECK_PORT",
         desc = "Port for the healthcheck HTTP server [default: 8080]",
@@ -75,22 +274,2141 @@ struct ConfigInner {
     )]
     healthcheck_port: Option<u16>,
 
-    #[from_env(
-        var = "SIGNET_FILLER_TARGET_BLOCKS",
-        desc = "Number of consecutive blocks to target per fill bundle, 1-10 [default: 5]",
-        optional
-    )]
-    target_blocks: Option<u8>,
+    #[from_env(
+        var = "SIGNET_FILLER_HEALTHCHECK_HOST",
+        desc = "IP address the healthcheck HTTP server binds to [default: 0.0.0.0]",
+        optional
+    )]
+    healthcheck_host: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_METRICS_HOST",
+        desc = "IP address the Prometheus metrics exporter binds to. The port is set via \
+            METRICS_PORT [default: 0.0.0.0]",
+        optional
+    )]
+    metrics_host: Option<String>,
+
+    metrics: MetricsConfig,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ADMIN_PORT",
+        desc = "Port for the authenticated admin HTTP API. The admin API is disabled when unset \
+            [default: unset]",
+        optional
+    )]
+    admin_port: Option<u16>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ADMIN_HOST",
+        desc = "IP address the admin HTTP API binds to [default: 127.0.0.1]",
+        optional
+    )]
+    admin_host: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ADMIN_TOKEN",
+        desc = "Bearer token required on every admin API request; must not be empty. Required \
+            when SIGNET_FILLER_ADMIN_PORT is set",
+        optional
+    )]
+    admin_token: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ARCHIVE_BUCKET",
+        desc = "S3 bucket to archive fetched orders and decision records to. Archival is disabled \
+            when unset. Credentials are loaded from the standard AWS environment [default: unset]",
+        optional
+    )]
+    archive_bucket: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ARCHIVE_PREFIX",
+        desc = "Object key prefix for archived batches [default: signet-filler]",
+        optional
+    )]
+    archive_prefix: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ARCHIVE_ENDPOINT_URL",
+        desc = "Endpoint URL for S3-compatible storage (e.g. MinIO). Uses AWS S3 when unset \
+            [default: unset]",
+        optional
+    )]
+    archive_endpoint_url: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ARCHIVE_INTERVAL_SECS",
+        desc = "How often buffered archive records are uploaded, in seconds [default: 300]",
+        optional
+    )]
+    archive_interval_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TARGET_BLOCKS",
+        desc = "Number of consecutive blocks to target per fill bundle, 1-10 [default: 5]",
+        optional
+    )]
+    target_blocks: Option<u8>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_FILL_DEADLINE_OFFSET_SECS",
+        desc = "How long fill signatures stay valid after signing, in seconds. Must cover the \
+            block lead and SIGNET_FILLER_TARGET_BLOCKS slots [default: the block lead plus \
+            SIGNET_FILLER_TARGET_BLOCKS slots plus 5 seconds]",
+        optional
+    )]
+    fill_deadline_offset_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_ORDERS_PER_BUNDLE",
+        desc = "Maximum number of orders to include in a single fill bundle. Must be greater \
+            than 0 when set [default: unset, no cap]",
+        optional
+    )]
+    max_orders_per_bundle: Option<usize>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_BOOTSTRAP_MODE",
+        desc = "How to run the immediate bootstrap cycle after startup, before the first slot \
+            boundary: off, dry-run (fetch, score, and check orders without submitting), or live \
+            [default: dry-run]",
+        optional
+    )]
+    bootstrap_mode: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SCHEDULING_MODE",
+        desc = "How each slot's cycle is timed: clock (from the host chain's start timestamp and \
+            slot duration) or headers (one slot after the latest rollup block header's timestamp) \
+            [default: clock]",
+        optional
+    )]
+    scheduling_mode: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ORDER_SELECTION_POLICY",
+        desc = "The order in which candidate orders are selected for fill bundles: profit-max \
+            (most profitable first) or fifo (first seen first) [default: profit-max]",
+        optional
+    )]
+    order_selection_policy: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_AGING_BOOST_PERCENT",
+        desc = "Under profit-max selection, the percentage by which a profitable order's margin is \
+            boosted for ranking for each earlier cycle in which it was a candidate but wasn't \
+            filled. Must be greater than 0 [default: unset, no aging boost]",
+        optional
+    )]
+    aging_boost_percent: Option<u16>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_QUIET_PERIODS",
+        desc = "Comma-separated quiet periods during which orders are evaluated but no bundles \
+            are submitted. Each is a daily UTC time range HH:MM-HH:MM (which may wrap past \
+            midnight) or an inclusive host slot range slots:START-END [default: unset, none]",
+        optional
+    )]
+    quiet_periods: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_CYCLE_MAX_QUOTES",
+        desc = "Maximum number of orders priced per cycle. Must be greater than 0 when set \
+            [default: unset, no cap]",
+        optional
+    )]
+    cycle_max_quotes: Option<usize>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_CYCLE_MAX_RPC_CALLS",
+        desc = "Maximum number of RPC calls per cycle for balance and Permit2 nonce checks. Must \
+            be greater than 0 when set [default: unset, no cap]",
+        optional
+    )]
+    cycle_max_rpc_calls: Option<usize>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_CYCLE_MAX_WALL_MS",
+        desc = "Maximum wall time spent evaluating orders per cycle, in milliseconds. Evaluation \
+            always stops once the minimum submission lead time is reached. Must be greater than \
+            0 when set [default: unset]",
+        optional
+    )]
+    cycle_max_wall_ms: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_REPUTATION_HALF_LIFE_SECS",
+        desc = "Half-life over which an order owner's failure penalties decay, in seconds. Must \
+            be greater than 0 [default: 3600]",
+        optional
+    )]
+    reputation_half_life_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_REPUTATION_SKIP_THRESHOLD",
+        desc = "Decayed number of failed orders at which an owner's orders are skipped entirely; \
+            owners below it with any recent failure are deprioritized. Must be greater than 0 \
+            [default: 3]",
+        optional
+    )]
+    reputation_skip_threshold: Option<u32>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TOXIC_FLOW_DEPRIORITIZE_USD",
+        desc = "Decayed markout loss, in whole USD, at which an order owner's orders are ranked \
+            after everyone else's. Must be greater than 0 [default: unset, disabled]",
+        optional
+    )]
+    toxic_flow_deprioritize_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TOXIC_FLOW_PAUSE_USD",
+        desc = "Decayed markout loss, in whole USD, at which an order owner's orders are skipped. \
+            Must be greater than 0, and no less than SIGNET_FILLER_TOXIC_FLOW_DEPRIORITIZE_USD \
+            [default: unset, disabled]",
+        optional
+    )]
+    toxic_flow_pause_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TOXIC_FLOW_MARKOUT_SECS",
+        desc = "Seconds after a fill is confirmed at which the inputs received are revalued to \
+            measure its markout. Must be greater than 0 [default: 60]",
+        optional
+    )]
+    toxic_flow_markout_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TOXIC_FLOW_HALF_LIFE_SECS",
+        desc = "Half-life over which an order owner's markout losses decay, in seconds. Must be \
+            greater than 0 [default: 86400]",
+        optional
+    )]
+    toxic_flow_half_life_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_DAILY_LOSS_USD",
+        desc = "Net loss on landed fills, in whole USD, at which bundle submission is paused for \
+            the rest of the day. Must be greater than 0 [default: unset, no daily loss limit]",
+        optional
+    )]
+    max_daily_loss_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_DAILY_LOSS_RESET_UTC",
+        desc = "UTC time of day HH:MM at which the daily loss resets, and a pause set by the daily \
+            loss limit is lifted [default: 00:00]",
+        optional
+    )]
+    daily_loss_reset_utc: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TRUSTED_OWNERS",
+        desc = "Comma-separated order owner addresses whose orders are placed ahead of all others \
+            in bundles and skip fill simulation and the signature check [default: unset, no \
+            trusted owners]",
+        optional
+    )]
+    trusted_owners: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_DENIED_OWNERS",
+        desc = "Comma-separated order owner addresses whose orders are never filled. Owners can \
+            also be denied or allowed at runtime through the admin API [default: unset, no \
+            denied owners]",
+        optional
+    )]
+    denied_owners: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SKIP_SIGNATURE_CHECK",
+        desc = "Set to any non-empty value to stop recovering each order's Permit2 signer locally \
+            and skipping orders it doesn't match, e.g. to fill orders from contract-wallet owners \
+            [default: unset, signatures checked]",
+        optional
+    )]
+    skip_signature_check: Option<bool>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SUBMIT_MAX_RETRIES",
+        desc = "Maximum number of times a bundle submission that failed with a transient error \
+            (timeout, connection failure, 5xx or 429 response) is retried within the slot's \
+            submission window; 0 disables retries [default: 2]",
+        optional
+    )]
+    submit_max_retries: Option<u32>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SUBMIT_RETRY_BACKOFF_MS",
+        desc = "Delay before the first bundle submission retry, in milliseconds, doubling for each \
+            further retry. Must be greater than 0 [default: 50]",
+        optional
+    )]
+    submit_retry_backoff_ms: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_INVENTORY_TARGETS",
+        desc = "Target inventory allocation as comma-separated asset:percent pairs over usd, eth, \
+            and btc, summing to 100 (e.g. usd:50,eth:30,btc:20). Enables inventory-aware \
+            profitability adjustment [default: unset, disabled]",
+        optional
+    )]
+    inventory_targets: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_INVENTORY_BONUS_BPS",
+        desc = "Bonus margin, in basis points of the order's output value, for fills that move \
+            inventory toward the target allocation, 0-10000 [default: 10]",
+        optional
+    )]
+    inventory_bonus_bps: Option<u16>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_INVENTORY_PENALTY_BPS",
+        desc = "Extra profit, in basis points of the order's output value, required of fills that \
+            move inventory away from the target allocation, 0-10000 [default: 10]",
+        optional
+    )]
+    inventory_penalty_bps: Option<u16>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_FILLS_PER_MINUTE",
+        desc = "Maximum number of orders filled in any rolling one-minute window. Must be greater \
+            than 0 when set [default: unset, no cap]",
+        optional
+    )]
+    max_fills_per_minute: Option<u32>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_FILLS_PER_HOUR",
+        desc = "Maximum number of orders filled in any rolling one-hour window. Must be greater \
+            than 0 when set [default: unset, no cap]",
+        optional
+    )]
+    max_fills_per_hour: Option<u32>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_NOTIONAL_USD_PER_MINUTE",
+        desc = "Maximum total USD value of order outputs filled in any rolling one-minute window. \
+            Must be greater than 0 when set [default: unset, no cap]",
+        optional
+    )]
+    max_notional_usd_per_minute: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_NOTIONAL_USD_PER_HOUR",
+        desc = "Maximum total USD value of order outputs filled in any rolling one-hour window. \
+            Must be greater than 0 when set [default: unset, no cap]",
+        optional
+    )]
+    max_notional_usd_per_hour: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_FILLS_PER_OWNER_PER_HOUR",
+        desc = "Maximum number of one order owner's orders filled in any rolling one-hour window. \
+            Must be greater than 0 when set [default: unset, no cap]",
+        optional
+    )]
+    max_fills_per_owner_per_hour: Option<u32>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_NOTIONAL_USD_PER_OWNER_PER_HOUR",
+        desc = "Maximum total USD value of one order owner's order outputs filled in any rolling \
+            one-hour window. Must be greater than 0 when set [default: unset, no cap]",
+        optional
+    )]
+    max_notional_usd_per_owner_per_hour: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_HOT_MAX_FILL_USD",
+        desc = "Maximum USD value of a single order's outputs filled without operator approval. \
+            Larger orders are queued for approval via the admin API. Must be greater than 0 when \
+            set [default: unset, no limit]",
+        optional
+    )]
+    hot_max_fill_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_HOT_MAX_DAILY_USD",
+        desc = "Maximum total USD value of order outputs filled without operator approval in any \
+            rolling 24-hour window. Orders beyond it are queued for approval via the admin API. \
+            Must be greater than 0 when set [default: unset, no limit]",
+        optional
+    )]
+    hot_max_daily_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_APPROVER_TOKEN",
+        desc = "Bearer token required to approve or reject queued fills via the admin API. Must \
+            not be empty, and must differ from SIGNET_FILLER_ADMIN_TOKEN. Required when a \
+            SIGNET_FILLER_HOT_MAX_* limit is set",
+        optional
+    )]
+    approver_token: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TOKEN_TABLE",
+        desc = "Path to a JSON array of token entries with chain_id, address, decimals, and \
+            whole-USD price_usd, read at startup. Entries add tokens to the fixed-price table or \
+            override a built-in token's rate [default: unset, built-in tokens only]",
+        optional
+    )]
+    token_table: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_APPROVAL_POLICY",
+        desc = "Whether the filler approves Permit2 to spend its ERC20 output tokens itself, when \
+            an allowance is below the filler's balance: off, max (approve U256::MAX), or exact \
+            (approve the current balance) [default: off]",
+        optional
+    )]
+    approval_policy: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TWAP_POOLS",
+        desc = "Comma-separated host-chain Uniswap V3 pool addresses whose TWAPs price one of \
+            their tokens against the other, which must have a fixed USD price. Each entry may be \
+            suffixed with :<window_secs> to override the observation window [default: unset, \
+            fixed prices only]",
+        optional
+    )]
+    twap_pools: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TWAP_WINDOW_SECS",
+        desc = "Default TWAP observation window in seconds. Must be greater than 0 \
+            [default: 1800]",
+        optional
+    )]
+    twap_window_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_PRICE_API",
+        desc = "Price API to fetch spot prices from, replacing fixed USD prices: coingecko or \
+            coinmarketcap for ETH, BTC, USDC, and USDT, or custom for a token price table served \
+            at SIGNET_FILLER_PRICE_API_URL [default: unset, fixed prices only]",
+        optional
+    )]
+    price_api: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_PRICE_API_URL",
+        desc = "URL serving a JSON object of token address to USD price per whole token. Required \
+            when SIGNET_FILLER_PRICE_API is custom [default: unset]",
+        optional
+    )]
+    price_api_url: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_STALE_PRICE_POLICY",
+        desc = "What happens to a token once its price API price goes stale: fixed to fall back \
+            to its fixed USD price, or reject to refuse orders involving it until the price is \
+            refreshed. Requires SIGNET_FILLER_PRICE_API [default: fixed]",
+        optional
+    )]
+    stale_price_policy: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_PRICE_API_KEY",
+        desc = "API key for SIGNET_FILLER_PRICE_API. Optional for coingecko (sent as a demo API \
+            key) and custom (sent as a bearer token), required for coinmarketcap [default: unset]",
+        optional
+    )]
+    price_api_key: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_PRICE_API_REFRESH_SECS",
+        desc = "Interval in seconds between price API requests. Must be greater than 0 \
+            [default: 60]",
+        optional
+    )]
+    price_api_refresh_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_PRICE_CROSS_CHECK_BPS",
+        desc = "Maximum divergence in basis points between an order's TWAP and price API \
+            valuations for it to be filled, 0-10000. Requires SIGNET_FILLER_TWAP_POOLS and \
+            SIGNET_FILLER_PRICE_API [default: unset, no cross-check]",
+        optional
+    )]
+    price_cross_check_bps: Option<u16>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_VOLATILITY_HAIRCUTS",
+        desc = "Comma-separated <token address>:<bps> entries, 0-10000. When pricing an order, \
+            each listed token is valued that many basis points worse for the filler: lower when \
+            received as an input, higher when paid as an output [default: unset, no haircuts]",
+        optional
+    )]
+    volatility_haircuts: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_INPUT_TOKEN_ALLOWLIST",
+        desc = "Comma-separated token addresses; only orders whose inputs are all listed are \
+            filled [default: unset, any input token]",
+        optional
+    )]
+    input_token_allowlist: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_INPUT_TOKEN_DENYLIST",
+        desc = "Comma-separated token addresses; orders with any listed input are never filled \
+            [default: unset, no input token denied]",
+        optional
+    )]
+    input_token_denylist: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_OUTPUT_TOKEN_ALLOWLIST",
+        desc = "Comma-separated token addresses; only orders whose outputs are all listed, on any \
+            chain, are filled [default: unset, any output token]",
+        optional
+    )]
+    output_token_allowlist: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_OUTPUT_TOKEN_DENYLIST",
+        desc = "Comma-separated token addresses; orders with any listed output, on any chain, are \
+            never filled [default: unset, no output token denied]",
+        optional
+    )]
+    output_token_denylist: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_QUOTE_CACHE_TTL_MS",
+        desc = "How long an order's profitability result is reused before it is priced again, in \
+            milliseconds. Must be greater than 0 [default: unset, every order priced every cycle]",
+        optional
+    )]
+    quote_cache_ttl_ms: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_UNPROFITABLE_MAX_BACKOFF_SLOTS",
+        desc = "Maximum number of slots between re-checks of an order found repeatedly \
+            unprofitable; the interval doubles from 1 slot with each unprofitable result. Must be \
+            greater than 0 [default: unset, every order priced every slot]",
+        optional
+    )]
+    unprofitable_max_backoff_slots: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TOKEN_DISCOVERY",
+        desc = "Opt-in discovery of tokens in order flow that can't be priced: auto (enable tokens \
+            that pass the safety checks) or review (park them for approval via the admin API). \
+            Requires SIGNET_FILLER_UNISWAP_V3_FACTORY [default: unset, unknown tokens skipped]",
+        optional
+    )]
+    token_discovery: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_UNISWAP_V3_FACTORY",
+        desc = "Uniswap V3 factory on the host chain, searched for a pool to price each \
+            discovered token from [default: unset]",
+        optional
+    )]
+    uniswap_v3_factory: Option<Address>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_DISCOVERY_MIN_AGE_BLOCKS",
+        desc = "Minimum age in host blocks of a discovered token's contract. Checking it needs an \
+            RPC node that serves state that many blocks back [default: 50400]",
+        optional
+    )]
+    discovery_min_age_blocks: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_DISCOVERY_MIN_LIQUIDITY_USD",
+        desc = "Minimum USD value of the priced token held by a discovered token's deepest Uniswap \
+            V3 pool. Must be greater than 0 [default: 100000]",
+        optional
+    )]
+    discovery_min_liquidity_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_GAS_PER_ORDER",
+        desc = "Estimated rollup gas used per filled order, including its share of the bundle's \
+            fill transaction. Enables the fee oracle, which deducts each order's estimated gas cost \
+            from its margin. Must be greater than 0 [default: unset, gas cost ignored]",
+        optional
+    )]
+    gas_per_order: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_HOST_GAS_PER_ORDER",
+        desc = "Estimated host gas used per filled order with host-chain outputs. Deducted, at the \
+            host gas price valued in USD, from those orders' margins in addition to their rollup \
+            gas cost. Requires SIGNET_FILLER_GAS_PER_ORDER. Must be greater than 0 \
+            [default: unset, host gas cost ignored]",
+        optional
+    )]
+    host_gas_per_order: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_GAS_PER_BUNDLE",
+        desc = "Estimated fixed rollup gas overhead of each fill bundle, on top of its orders' gas. \
+            Enables a bundle-level check that drops the least-preferred orders from a cycle's \
+            fills if their total margin doesn't cover it. Requires SIGNET_FILLER_GAS_PER_ORDER. \
+            Must be greater than 0 [default: unset, no bundle-level check]",
+        optional
+    )]
+    gas_per_bundle: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_BUNDLE_GAS_BUDGET",
+        desc = "Maximum estimated rollup gas of a single fill bundle: its fixed overhead plus its \
+            orders' gas. Caps each bundle at the most profitable orders that fit, with the rest \
+            chunked into further bundles. Requires SIGNET_FILLER_GAS_PER_ORDER, and must fit at \
+            least one order [default: unset, no gas budget]",
+        optional
+    )]
+    bundle_gas_budget: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_PRIORITY_FEE_MARKUP_PERCENT",
+        desc = "Markup applied to the recent median rollup priority fee when estimating gas cost \
+            [default: 20]",
+        optional
+    )]
+    priority_fee_markup_percent: Option<u16>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_FEE_ORACLE_REFRESH_SECS",
+        desc = "Interval in seconds between rollup gas price estimates. Must be greater than 0 \
+            [default: 12]",
+        optional
+    )]
+    fee_oracle_refresh_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_BRIDGE_MIN_SHARE_PERCENT",
+        desc = "Enables inventory bridging: when the rollup holds less than this percentage of \
+            the filler's USD, ETH, or BTC inventory, host tokens are moved onto the rollup through \
+            Passage to even it out. Between 1 and 49 [default: unset, no bridging]",
+        optional
+    )]
+    bridge_min_share_percent: Option<u8>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_BRIDGE_INTERVAL_SECS",
+        desc = "Interval in seconds between inventory bridging checks. Must be greater than 0 \
+            [default: 600]",
+        optional
+    )]
+    bridge_interval_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SIMULATION_MIN_NOTIONAL_USD",
+        desc = "Output notional in whole USD at or above which an order's fill bundle is simulated \
+            with eth_simulateV1 before submission, and only submitted if the filler's simulated \
+            token transfers are within the maximum loss. Must be greater than 0 [default: unset, \
+            no simulation]",
+        optional
+    )]
+    simulation_min_notional_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MIN_ORDER_NOTIONAL_USD",
+        desc = "Output notional in whole USD below which an order is skipped before pricing, as too \
+            small to cover gas. Must be greater than 0 [default: unset, no minimum]",
+        optional
+    )]
+    min_order_notional_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_ORDER_NOTIONAL_USD",
+        desc = "Output notional in whole USD above which an order is skipped before pricing, as \
+            beyond the filler's risk appetite. Must be greater than 0, and no less than \
+            SIGNET_FILLER_MIN_ORDER_NOTIONAL_USD [default: unset, no limit]",
+        optional
+    )]
+    max_order_notional_usd: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_FILL_WEBHOOK_URL",
+        desc = "URL to which a JSON receipt of each of the filler's fills seen landing on-chain \
+            (order hash, landed bundle ID, and block) is POSTed, signed by the filler's key \
+            [default: unset, no receipts]",
+        optional
+    )]
+    fill_webhook_url: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SCREENING_LIST",
+        desc = "Path to a file of screened addresses, one per line, read at startup. Orders whose \
+            owner or any output recipient is listed are never filled [default: unset]",
+        optional
+    )]
+    screening_list: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SCREENING_API_URL",
+        desc = "Base URL of an address screening API; each order owner and output recipient is \
+            looked up at <url>/<address>, and an address with any identifications is treated as \
+            screened. Orders are skipped while a lookup fails [default: unset]",
+        optional
+    )]
+    screening_api_url: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SCREENING_API_KEY",
+        desc = "API key sent as X-API-Key with screening API requests. Requires \
+            SIGNET_FILLER_SCREENING_API_URL [default: unset]",
+        optional
+    )]
+    screening_api_key: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SCREENING_CACHE_SECS",
+        desc = "How long a screening API result for an address is reused, in seconds. Must be \
+            greater than 0 [default: 3600]",
+        optional
+    )]
+    screening_cache_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SCORING_HOOK_URL",
+        desc = "URL each cycle's profitable candidate orders are POSTed to for an external \
+            accept/reject/priority decision [default: unset, no scoring hook]",
+        optional
+    )]
+    scoring_hook_url: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SCORING_HOOK_TIMEOUT_MS",
+        desc = "How long to wait for the scoring hook's response, in milliseconds. Must be greater \
+            than 0 [default: 500]",
+        optional
+    )]
+    scoring_hook_timeout_ms: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_SCORING_HOOK_FAIL_OPEN",
+        desc = "Set to any non-empty value to fill candidates as unscored when the scoring hook \
+            fails, rather than skipping them [default: unset, fail closed]",
+        optional
+    )]
+    scoring_hook_fail_open: Option<bool>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_LOW_BALANCE_USD",
+        desc = "Comma-separated chain_id:token:usd entries. The filler alerts when its balance of \
+            the token on that chain is worth less than the whole-USD amount [default: unset, no \
+            alerts]",
+        optional
+    )]
+    low_balance_usd: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TREASURY_ADDRESS",
+        desc = "Treasury address to top up low ERC20 balances from, using allowances it has \
+            granted the filler. Requires SIGNET_FILLER_LOW_BALANCE_USD [default: unset, alert \
+            only]",
+        optional
+    )]
+    treasury_address: Option<Address>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_POSITIONS_USD",
+        desc = "Comma-separated chain_id:token:usd entries capping the filler's net position in \
+            the token on that chain (balance, plus inputs of pending fills, less their outputs) \
+            at the whole-USD amount. Orders that would take a position past its cap are skipped \
+            [default: unset, no position limits]",
+        optional
+    )]
+    max_positions_usd: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_FILLED_ORDER_STORE",
+        desc = "Path to a directory in which the filled-order cache is persisted, so it survives \
+            restarts. Created if it doesn't exist [default: unset, in-memory only]",
+        optional
+    )]
+    filled_order_store: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_REDIS_URL",
+        desc = "Redis URL shared by filler replicas. Enables a shared set of filled and in-flight \
+            orders, so replicas don't submit fills for the same orders [default: unset, no \
+            coordination]",
+        optional
+    )]
+    redis_url: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_REDIS_KEY_PREFIX",
+        desc = "Prefix for the Redis keys shared by filler replicas. Replicas coordinate only with \
+            others using the same prefix [default: signet-filler]",
+        optional
+    )]
+    redis_key_prefix: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_LEADER_LEASE_SECS",
+        desc = "Enables leader election among replicas sharing SIGNET_FILLER_REDIS_URL: only the \
+            replica holding this lease submits bundles, and a standby takes over once the \
+            leader's lease lapses (must be > 0) [default: unset, every replica submits]",
+        optional
+    )]
+    leader_lease_secs: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ORDER_BACKFILL_BLOCKS",
+        desc = "Number of recent rollup blocks scanned for order events at startup, so orders \
+            filled shortly before a restart aren't nonce-checked again (0 disables) \
+            [default: 256]",
+        optional
+    )]
+    order_backfill_blocks: Option<u64>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_FILLED_ORDERS_CACHE_SIZE",
+        desc = "Number of filled orders remembered so their Permit2 nonces aren't checked again, \
+            and kept in SIGNET_FILLER_FILLED_ORDER_STORE if set (must be > 0) [default: 10240]",
+        optional
+    )]
+    filled_orders_cache_size: Option<usize>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ORDER_FILE",
+        desc = "Path to a file of orders (JSON array, or one JSON order per line) to fill instead \
+            of those in the transaction cache. The file is re-read every cycle [default: unset, \
+            use the transaction cache]",
+        optional
+    )]
+    order_file: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_BUNDLE_FILE",
+        desc = "Path to which fill bundles are appended as JSON lines when orders come from \
+            SIGNET_FILLER_ORDER_FILE. Bundles are only logged when unset. Requires \
+            SIGNET_FILLER_ORDER_FILE [default: unset]",
+        optional
+    )]
+    bundle_file: Option<String>,
+
+    signer: LocalOrAwsConfig,
+}
+
+/// How the filler runs its bootstrap cycle immediately after initialization, rather than idling
+/// until the first slot anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootstrapMode {
+    /// No bootstrap cycle; the first cycle runs at the first slot anchor.
+    Off,
+    /// Fetch, score, and check orders to warm caches, but don't submit any bundles.
+    DryRun,
+    /// Run a full cycle, submitting bundles for the next block boundary.
+    Live,
+}
+
+impl BootstrapMode {
+    /// The configuration value for this mode.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::DryRun => "dry-run",
+            Self::Live => "live",
+        }
+    }
+}
+
+impl FromStr for BootstrapMode {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "off" => Ok(Self::Off),
+            "dry-run" => Ok(Self::DryRun),
+            "live" => Ok(Self::Live),
+            _ => bail!("expected one of off, dry-run, or live (got {value})"),
+        }
+    }
+}
+
+/// How the filler times each slot's cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulingMode {
+    /// Slot boundaries are computed from the host chain's start timestamp and slot duration, by
+    /// the local clock.
+    Clock,
+    /// Each cycle targets one slot after the timestamp of the latest rollup block header seen over
+    /// the rollup WebSocket subscription, so the schedule follows the chain's actual block times.
+    Headers,
+}
+
+impl SchedulingMode {
+    /// The configuration value for this mode.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Clock => "clock",
+            Self::Headers => "headers",
+        }
+    }
+}
+
+impl FromStr for SchedulingMode {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "clock" => Ok(Self::Clock),
+            "headers" => Ok(Self::Headers),
+            _ => bail!("expected one of clock or headers (got {value})"),
+        }
+    }
+}
+
+/// The order in which profitable candidates are selected for fill bundles. Either way, orders
+/// from trusted owners go first and orders from deprioritized owners go last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSelectionPolicy {
+    /// Most profitable first, maximizing the profit of each cycle's fills.
+    ProfitMax,
+    /// The order first seen by the filler first, with ties broken by profit, so that orders are
+    /// filled in the order they arrived when the filler can't fill them all.
+    Fifo,
+}
+
+impl OrderSelectionPolicy {
+    /// The configuration value for this policy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ProfitMax => "profit-max",
+            Self::Fifo => "fifo",
+        }
+    }
+}
+
+impl FromStr for OrderSelectionPolicy {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "profit-max" => Ok(Self::ProfitMax),
+            "fifo" => Ok(Self::Fifo),
+            _ => bail!("expected one of profit-max or fifo (got {value})"),
+        }
+    }
+}
+
+/// How the filler maintains the Permit2 allowances of its ERC20 output tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalPolicy {
+    /// Never send approvals; allowances are managed by the operator.
+    Off,
+    /// Approve `U256::MAX`, so the allowance never runs out.
+    Max,
+    /// Approve the filler's current balance, so Permit2 can never move more than the filler held
+    /// when it last approved.
+    Exact,
+}
+
+impl ApprovalPolicy {
+    /// The configuration value for this policy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Max => "max",
+            Self::Exact => "exact",
+        }
+    }
+}
+
+impl FromStr for ApprovalPolicy {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "off" => Ok(Self::Off),
+            "max" => Ok(Self::Max),
+            "exact" => Ok(Self::Exact),
+            _ => bail!("expected one of off, max, or exact (got {value})"),
+        }
+    }
+}
+
+/// A public REST API serving token spot prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceApi {
+    /// CoinGecko's `simple/price` endpoint.
+    CoinGecko,
+    /// CoinMarketCap's `cryptocurrency/quotes/latest` endpoint.
+    CoinMarketCap,
+    /// A configured endpoint serving USD prices by token address.
+    Custom,
+}
+
+impl PriceApi {
+    /// The configuration value for this API.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::CoinGecko => "coingecko",
+            Self::CoinMarketCap => "coinmarketcap",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+/// How tokens are priced once their price API price goes stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StalePricePolicy {
+    /// Fall back to the token's fixed USD price.
+    Fixed,
+    /// Refuse to price the token until the price API refreshes it.
+    Reject,
+}
+
+impl StalePricePolicy {
+    /// The configuration value for this policy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fixed => "fixed",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+impl FromStr for StalePricePolicy {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "fixed" => Ok(Self::Fixed),
+            "reject" => Ok(Self::Reject),
+            _ => bail!("expected one of fixed or reject (got {value})"),
+        }
+    }
+}
+
+/// What happens to a discovered token that passes the safety checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscoveryMode {
+    /// Start pricing it immediately.
+    Auto,
+    /// Park it until an operator enables it through the admin API.
+    Review,
+}
+
+impl DiscoveryMode {
+    /// The configuration value for this mode.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Review => "review",
+        }
+    }
+}
+
+impl FromStr for DiscoveryMode {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "auto" => Ok(Self::Auto),
+            "review" => Ok(Self::Review),
+            _ => bail!("expected one of auto or review (got {value})"),
+        }
+    }
+}
+
+impl FromStr for PriceApi {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "coingecko" => Ok(Self::CoinGecko),
+            "coinmarketcap" => Ok(Self::CoinMarketCap),
+            "custom" => Ok(Self::Custom),
+            _ => bail!("expected one of coingecko, coinmarketcap, or custom (got {value})"),
+        }
+    }
+}
+
+/// A scheduled window during which the filler evaluates orders but does not submit bundles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuietPeriod {
+    /// Every day between two UTC times of day, as minutes after midnight. The range is half-open
+    /// and wraps past midnight when `end` is before `start`.
+    Daily {
+        /// Minutes after midnight UTC at which the period starts.
+        start: u16,
+        /// Minutes after midnight UTC at which the period ends.
+        end: u16,
+    },
+    /// An inclusive range of host chain slot numbers.
+    Slots {
+        /// First quiet slot.
+        start: u64,
+        /// Last quiet slot.
+        end: u64,
+    },
+}
+
+impl QuietPeriod {
+    /// Whether the period covers the given UTC minute of the day or host slot.
+    pub const fn contains(&self, minute_of_day: u16, slot: u64) -> bool {
+        match *self {
+            Self::Daily { start, end } if start <= end => {
+                start <= minute_of_day && minute_of_day < end
+            }
+            Self::Daily { start, end } => minute_of_day >= start || minute_of_day < end,
+            Self::Slots { start, end } => start <= slot && slot <= end,
+        }
+    }
+}
+
+impl fmt::Display for QuietPeriod {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Daily { start, end } => write!(
+                formatter,
+                "{:02}:{:02}-{:02}:{:02}",
+                start / 60,
+                start % 60,
+                end / 60,
+                end % 60
+            ),
+            Self::Slots { start, end } => write!(formatter, "slots:{start}-{end}"),
+        }
+    }
+}
+
+impl FromStr for QuietPeriod {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        if let Some(slots) = value.strip_prefix("slots:") {
+            let Some((start, end)) = slots.split_once('-') else {
+                bail!("expected slots:START-END (got {value})");
+            };
+            let start =
+                start.trim().parse().wrap_err_with(|| format!("invalid start slot in {value}"))?;
+            let end =
+                end.trim().parse().wrap_err_with(|| format!("invalid end slot in {value}"))?;
+            if end < start {
+                bail!("end slot must not be before start slot (got {value})");
+            }
+            return Ok(Self::Slots { start, end });
+        }
+        let Some((start, end)) = value.split_once('-') else {
+            bail!("expected HH:MM-HH:MM or slots:START-END (got {value})");
+        };
+        let (start, end) = (parse_time_of_day(start.trim())?, parse_time_of_day(end.trim())?);
+        if start == end {
+            bail!("start and end times must differ (got {value})");
+        }
+        Ok(Self::Daily { start, end })
+    }
+}
+
+/// An asset class the filler holds inventory in, across both chains and all token variants (e.g.
+/// host USDC, host USDT, and rollup USD are all `Usd`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InventoryAsset {
+    /// US dollar stablecoins.
+    Usd,
+    /// Wrapped ether.
+    Eth,
+    /// Wrapped bitcoin.
+    Btc,
+}
+
+impl InventoryAsset {
+    /// All inventory assets, in index order.
+    pub const ALL: [Self; 3] = [Self::Usd, Self::Eth, Self::Btc];
+
+    /// The configuration value for this asset.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Usd => "usd",
+            Self::Eth => "eth",
+            Self::Btc => "btc",
+        }
+    }
+}
+
+impl FromStr for InventoryAsset {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "usd" => Ok(Self::Usd),
+            "eth" => Ok(Self::Eth),
+            "btc" => Ok(Self::Btc),
+            _ => bail!("expected one of usd, eth, or btc (got {value})"),
+        }
+    }
+}
+
+/// Environment loaded by the `decode` subcommand: only the chain and rollup RPC are needed, so it
+/// can be run without signer or server configuration.
+#[derive(Debug, FromEnv)]
+struct DecodeConfigInner {
+    #[from_env(
+        var = "SIGNET_FILLER_CHAIN_NAME",
+        desc = "Signet chain name [default: parmigiana]",
+        optional
+    )]
+    chain_name: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_ROLLUP_RPC_URL",
+        desc = "URL for Rollup RPC node [default: wss://rpc.parmigiana.signet.sh]",
+        optional
+    )]
+    ru_rpc: Option<String>,
+}
+
+/// Environment loaded by the offline `replay` and `backtest` subcommands: only the settings that
+/// affect how orders are priced from fixed rates and selected, so they run without RPC access.
+#[derive(Debug, FromEnv)]
+struct OfflineConfigInner {
+    #[from_env(
+        var = "SIGNET_FILLER_CHAIN_NAME",
+        desc = "Signet chain name [default: parmigiana]",
+        optional
+    )]
+    chain_name: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MAX_LOSS_PERCENT",
+        desc = "Maximum acceptable loss percent for order pricing, 0-100 [default: 10]",
+        optional
+    )]
+    max_loss_percent: Option<u8>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_TOKEN_TABLE",
+        desc = "Path to a JSON array of token entries with chain_id, address, decimals, and \
+            whole-USD price_usd [default: unset, built-in tokens only]",
+        optional
+    )]
+    token_table: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_VOLATILITY_HAIRCUTS",
+        desc = "Comma-separated <token address>:<bps> entries, 0-10000 [default: unset, no \
+            haircuts]",
+        optional
+    )]
+    volatility_haircuts: Option<String>,
+
+    #[from_env(
+        var = "SIGNET_FILLER_MIN_MARGIN_BPS",
+        desc = "Minimum margin in basis points of the order's input value, 0-10000 [default: \
+            unset, no minimum]",
+        optional
+    )]
+    min_margin_bps: Option<u16>,
+}
+
+/// Configuration for the offline `replay` and `backtest` subcommands: the pricing configuration
+/// orders are run through.
+#[derive(Debug)]
+pub struct OfflineConfig {
+    chain_name: String,
+    constants: SignetConstants,
+    max_loss_percent: u8,
+    token_table: Vec<TokenMetadata>,
+    volatility_haircuts: HashMap<Address, u16>,
+    min_margin_bps: Option<u16>,
+}
+
+impl OfflineConfig {
+    /// Signet chain name.
+    pub fn chain_name(&self) -> &str {
+        &self.chain_name
+    }
+
+    /// Chain-specific constants derived from the chain name.
+    pub const fn constants(&self) -> &SignetConstants {
+        &self.constants
+    }
+
+    /// Maximum acceptable loss percent for order pricing.
+    pub const fn max_loss_percent(&self) -> u8 {
+        self.max_loss_percent
+    }
+
+    /// Tokens added to, or overriding, the fixed-price table.
+    pub fn token_table(&self) -> &[TokenMetadata] {
+        &self.token_table
+    }
+
+    /// Basis points each listed token is valued worse for the filler when pricing.
+    pub const fn volatility_haircuts(&self) -> &HashMap<Address, u16> {
+        &self.volatility_haircuts
+    }
+
+    /// Minimum margin in basis points of the order's input value, if set.
+    pub const fn min_margin_bps(&self) -> Option<u16> {
+        self.min_margin_bps
+    }
+}
+
+/// Configuration for the `decode` subcommand.
+#[derive(Debug)]
+pub struct DecodeConfig {
+    ru_rpc: PubSubConfig,
+    constants: SignetConstants,
+}
+
+impl DecodeConfig {
+    /// URL for the Rollup RPC node, used to look up the order's fill status.
+    pub const fn ru_rpc(&self) -> &PubSubConfig {
+        &self.ru_rpc
+    }
+
+    /// Chain-specific constants derived from the chain name.
+    pub const fn constants(&self) -> &SignetConstants {
+        &self.constants
+    }
+}
+
+/// Configuration for the authenticated admin HTTP API.
+#[derive(Clone)]
+pub struct AdminConfig {
+    address: SocketAddr,
+    token: String,
+}
+
+impl AdminConfig {
+    /// Address the admin HTTP API binds to.
+    pub const fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Bearer token required on every admin API request.
+    pub const fn token(&self) -> &str {
+        self.token.as_str()
+    }
+}
+
+impl Debug for AdminConfig {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("AdminConfig")
+            .field("address", &self.address)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Configuration for coordinating filler replicas through a shared Redis.
+#[derive(Clone)]
+pub struct CoordinationConfig {
+    redis_url: String,
+    key_prefix: String,
+    leader_lease: Option<Duration>,
+}
+
+impl CoordinationConfig {
+    /// URL of the shared Redis.
+    pub const fn redis_url(&self) -> &str {
+        self.redis_url.as_str()
+    }
+
+    /// Prefix for every shared key.
+    pub const fn key_prefix(&self) -> &str {
+        self.key_prefix.as_str()
+    }
+
+    /// How long a leader's lease lasts without renewal, if leader election is enabled.
+    pub const fn leader_lease(&self) -> Option<Duration> {
+        self.leader_lease
+    }
+}
+
+impl Debug for CoordinationConfig {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("CoordinationConfig")
+            .field("redis_url", &"<redacted>")
+            .field("key_prefix", &self.key_prefix)
+            .field("leader_lease", &self.leader_lease)
+            .finish()
+    }
+}
+
+/// Configuration for archiving fetched orders and decision records to S3-compatible storage.
+#[derive(Debug, Clone)]
+pub struct ArchiveConfig {
+    bucket: String,
+    prefix: String,
+    endpoint_url: Option<String>,
+    interval: Duration,
+}
+
+impl ArchiveConfig {
+    /// Bucket that archived batches are written to.
+    pub const fn bucket(&self) -> &str {
+        self.bucket.as_str()
+    }
+
+    /// Object key prefix for archived batches.
+    pub const fn prefix(&self) -> &str {
+        self.prefix.as_str()
+    }
+
+    /// Endpoint URL override for S3-compatible storage, or `None` to use AWS S3.
+    pub fn endpoint_url(&self) -> Option<&str> {
+        self.endpoint_url.as_deref()
+    }
+
+    /// How often buffered records are uploaded.
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+/// Explicit per-cycle budgets. Every budget is optional; evaluation is additionally always bounded
+/// by the minimum submission lead time before the block boundary.
+#[derive(Debug, Clone, Copy)]
+pub struct CycleBudgetConfig {
+    max_quotes: Option<NonZeroUsize>,
+    max_rpc_calls: Option<NonZeroUsize>,
+    max_wall_time: Option<Duration>,
+}
+
+impl CycleBudgetConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(
+        max_quotes: Option<NonZeroUsize>,
+        max_rpc_calls: Option<NonZeroUsize>,
+        max_wall_time: Option<Duration>,
+    ) -> Self {
+        Self { max_quotes, max_rpc_calls, max_wall_time }
+    }
+
+    /// Maximum number of orders priced per cycle.
+    pub const fn max_quotes(&self) -> Option<NonZeroUsize> {
+        self.max_quotes
+    }
+
+    /// Maximum number of balance and Permit2 nonce RPC calls per cycle.
+    pub const fn max_rpc_calls(&self) -> Option<NonZeroUsize> {
+        self.max_rpc_calls
+    }
+
+    /// Maximum wall time spent evaluating orders per cycle.
+    pub const fn max_wall_time(&self) -> Option<Duration> {
+        self.max_wall_time
+    }
+}
+
+/// Configuration for per-owner reputation scoring.
+#[derive(Debug, Clone, Copy)]
+pub struct ReputationConfig {
+    half_life: Duration,
+    skip_threshold: f64,
+}
+
+impl ReputationConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(half_life: Duration, skip_threshold: f64) -> Self {
+        Self { half_life, skip_threshold }
+    }
+
+    /// Half-life over which failure penalties decay.
+    pub const fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    /// Decayed penalty at which an owner's orders are skipped.
+    pub const fn skip_threshold(&self) -> f64 {
+        self.skip_threshold
+    }
+}
+
+/// Configuration for toxic-flow detection: the markout of each landed fill, the value of the
+/// inputs received a delay after the fill less the value of the outputs paid, accumulates per
+/// order owner with exponential decay, and owners whose decayed loss reaches a threshold are
+/// deprioritized or paused.
+#[derive(Debug, Clone, Copy)]
+pub struct ToxicFlowConfig {
+    markout_delay: Duration,
+    half_life: Duration,
+    deprioritize_loss: Option<f64>,
+    pause_loss: Option<f64>,
+}
+
+impl ToxicFlowConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(
+        markout_delay: Duration,
+        half_life: Duration,
+        deprioritize_loss: Option<f64>,
+        pause_loss: Option<f64>,
+    ) -> Self {
+        Self { markout_delay, half_life, deprioritize_loss, pause_loss }
+    }
+
+    /// How long after a fill is confirmed its markout is measured.
+    pub const fn markout_delay(&self) -> Duration {
+        self.markout_delay
+    }
+
+    /// Half-life over which markouts decay.
+    pub const fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    /// Decayed markout loss in USD at which an owner's orders are deprioritized, if set.
+    pub const fn deprioritize_loss(&self) -> Option<f64> {
+        self.deprioritize_loss
+    }
+
+    /// Decayed markout loss in USD at which an owner's orders are skipped, if set.
+    pub const fn pause_loss(&self) -> Option<f64> {
+        self.pause_loss
+    }
+}
+
+/// Configuration for the daily loss limit: the net PnL of landed fills accumulates over a UTC
+/// day starting at a configured time, and bundle submission is paused once the loss reaches the
+/// limit, until the next reset.
+#[derive(Debug, Clone, Copy)]
+pub struct LossLimitConfig {
+    max_loss: f64,
+    reset_minute: u16,
+}
+
+impl LossLimitConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(max_loss: f64, reset_minute: u16) -> Self {
+        Self { max_loss, reset_minute }
+    }
+
+    /// Net loss in USD over a day at which submission is paused.
+    pub const fn max_loss(&self) -> f64 {
+        self.max_loss
+    }
+
+    /// Minutes after midnight UTC at which the day's loss resets.
+    pub const fn reset_minute(&self) -> u16 {
+        self.reset_minute
+    }
+}
+
+/// Retry policy for bundle submissions that fail with a transient error.
+#[derive(Debug, Clone, Copy)]
+pub struct SubmissionRetryConfig {
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+impl SubmissionRetryConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(max_retries: u32, initial_backoff: Duration) -> Self {
+        Self { max_retries, initial_backoff }
+    }
+
+    /// Maximum number of retries per submission.
+    pub const fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Delay before the first retry, doubling for each further retry.
+    pub const fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+}
+
+/// Discovery of unpriced tokens from order flow.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenDiscoveryConfig {
+    mode: DiscoveryMode,
+    uniswap_v3_factory: Address,
+    min_age_blocks: u64,
+    min_liquidity: U256,
+    twap_window: Duration,
+}
+
+impl TokenDiscoveryConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(mode: DiscoveryMode, twap_window: Duration) -> Self {
+        Self {
+            mode,
+            uniswap_v3_factory: Address::ZERO,
+            min_age_blocks: 0,
+            min_liquidity: U256::ZERO,
+            twap_window,
+        }
+    }
+
+    /// What happens to tokens that pass the safety checks.
+    pub const fn mode(&self) -> DiscoveryMode {
+        self.mode
+    }
+
+    /// The host chain Uniswap V3 factory searched for pools.
+    pub const fn uniswap_v3_factory(&self) -> Address {
+        self.uniswap_v3_factory
+    }
+
+    /// Minimum contract age in host blocks.
+    pub const fn min_age_blocks(&self) -> u64 {
+        self.min_age_blocks
+    }
+
+    /// Minimum pool liquidity, in normalized 18-decimal USD.
+    pub const fn min_liquidity(&self) -> U256 {
+        self.min_liquidity
+    }
+
+    /// TWAP observation window for enabled tokens' pools.
+    pub const fn twap_window(&self) -> Duration {
+        self.twap_window
+    }
+}
+
+/// Rollup gas cost estimation from `eth_feeHistory`.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeOracleConfig {
+    gas_per_order: u64,
+    host_gas_per_order: Option<u64>,
+    gas_per_bundle: Option<u64>,
+    priority_fee_markup_percent: u16,
+    refresh_interval: Duration,
+}
+
+impl FeeOracleConfig {
+    /// Estimated rollup gas used per filled order.
+    pub const fn gas_per_order(&self) -> u64 {
+        self.gas_per_order
+    }
+
+    /// Estimated host gas used per filled order with host-chain outputs, if host gas cost is
+    /// estimated.
+    pub const fn host_gas_per_order(&self) -> Option<u64> {
+        self.host_gas_per_order
+    }
+
+    /// Estimated fixed rollup gas overhead of each fill bundle, if bundle-level profitability is
+    /// checked.
+    pub const fn gas_per_bundle(&self) -> Option<u64> {
+        self.gas_per_bundle
+    }
+
+    /// Markup applied to the recent median priority fee.
+    pub const fn priority_fee_markup_percent(&self) -> u16 {
+        self.priority_fee_markup_percent
+    }
+
+    /// Interval between gas price estimates.
+    pub const fn refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+}
+
+/// Moving inventory from the host chain onto the rollup when the rollup side runs low.
+#[derive(Debug, Clone, Copy)]
+pub struct BridgeConfig {
+    min_share_percent: u8,
+    interval: Duration,
+}
+
+impl BridgeConfig {
+    /// The smallest share of an asset's inventory either chain may hold before it counts as low.
+    pub const fn min_share_percent(&self) -> u8 {
+        self.min_share_percent
+    }
+
+    /// Interval between bridging checks.
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+/// A token balance below which the filler alerts, and tops up from the treasury if one is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LowBalanceThreshold {
+    chain_id: u64,
+    token: Address,
+    min_value: U256,
+}
+
+impl LowBalanceThreshold {
+    /// The host or rollup chain the token is on.
+    pub const fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// The token address.
+    pub const fn token(&self) -> Address {
+        self.token
+    }
+
+    /// The balance value below which the token is low, in normalized 18-decimal USD.
+    pub const fn min_value(&self) -> U256 {
+        self.min_value
+    }
+}
+
+/// A cap on the filler's net position in a token, above which orders paying the filler in it
+/// aren't selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionLimit {
+    chain_id: u64,
+    token: Address,
+    max_value: U256,
+}
+
+impl PositionLimit {
+    #[cfg(test)]
+    pub(crate) const fn new(chain_id: u64, token: Address, max_value: U256) -> Self {
+        Self { chain_id, token, max_value }
+    }
+
+    /// The host or rollup chain the token is on.
+    pub const fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// The token address.
+    pub const fn token(&self) -> Address {
+        self.token
+    }
+
+    /// The largest net position allowed, in normalized 18-decimal USD.
+    pub const fn max_value(&self) -> U256 {
+        self.max_value
+    }
+}
+
+/// Low-balance alerting, and topping up from a treasury.
+#[derive(Debug, Clone)]
+pub struct LowBalanceConfig {
+    thresholds: Vec<LowBalanceThreshold>,
+    treasury: Option<Address>,
+}
+
+impl LowBalanceConfig {
+    /// The watched tokens and their thresholds.
+    pub fn thresholds(&self) -> &[LowBalanceThreshold] {
+        &self.thresholds
+    }
+
+    /// The treasury to top up low ERC20 balances from, if set.
+    pub const fn treasury(&self) -> Option<Address> {
+        self.treasury
+    }
+}
+
+/// Target inventory allocation and the margin adjustments applied to fills that move inventory
+/// toward or away from it.
+#[derive(Debug, Clone, Copy)]
+pub struct InventoryConfig {
+    targets: [u8; InventoryAsset::ALL.len()],
+    bonus_bps: u16,
+    penalty_bps: u16,
+}
+
+impl InventoryConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(
+        targets: [u8; InventoryAsset::ALL.len()],
+        bonus_bps: u16,
+        penalty_bps: u16,
+    ) -> Self {
+        Self { targets, bonus_bps, penalty_bps }
+    }
+
+    /// Target share of total inventory value held in `asset`, in percent.
+    pub const fn target_percent(&self, asset: InventoryAsset) -> u8 {
+        self.targets[asset as usize]
+    }
+
+    /// Bonus margin for skew-reducing fills, in basis points of the order's output value.
+    pub const fn bonus_bps(&self) -> u16 {
+        self.bonus_bps
+    }
+
+    /// Extra profit required of skew-increasing fills, in basis points of the order's output
+    /// value.
+    pub const fn penalty_bps(&self) -> u16 {
+        self.penalty_bps
+    }
+}
+
+/// Fixed-price metadata for a token, read from the token table file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenMetadata {
+    chain_id: u64,
+    address: Address,
+    decimals: u8,
+    price_usd: u64,
+}
+
+impl TokenMetadata {
+    /// The host or rollup chain the token is deployed on.
+    pub const fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// The token contract address.
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
+    /// The token's decimals, at most 18.
+    pub const fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// The fixed USD price per whole token.
+    pub const fn price_usd(&self) -> u64 {
+        self.price_usd
+    }
+}
+
+/// A Uniswap V3 pool whose TWAP is used for pricing.
+#[derive(Debug, Clone, Copy)]
+pub struct TwapPool {
+    address: Address,
+    window: Duration,
+}
+
+impl TwapPool {
+    pub(crate) const fn new(address: Address, window: Duration) -> Self {
+        Self { address, window }
+    }
+
+    /// The pool contract address on the host chain.
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
+    /// The TWAP observation window.
+    pub const fn window(&self) -> Duration {
+        self.window
+    }
+}
+
+/// Configuration for Uniswap V3 TWAP pricing.
+#[derive(Debug, Clone)]
+pub struct TwapConfig {
+    pools: Vec<TwapPool>,
+}
+
+impl TwapConfig {
+    /// The pools to read TWAPs from.
+    pub fn pools(&self) -> &[TwapPool] {
+        &self.pools
+    }
+}
+
+/// Spend limits on fills made without operator approval, and the token operators approve
+/// queued fills with.
+#[derive(Clone)]
+pub struct SpendPolicyConfig {
+    max_fill_notional: Option<U256>,
+    max_daily_notional: Option<U256>,
+    approver_token: String,
+}
+
+impl SpendPolicyConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(
+        max_fill_notional: Option<U256>,
+        max_daily_notional: Option<U256>,
+    ) -> Self {
+        Self { max_fill_notional, max_daily_notional, approver_token: String::new() }
+    }
+
+    /// Maximum output value of a single order filled without approval, in normalized 18-decimal
+    /// USD.
+    pub const fn max_fill_notional(&self) -> Option<U256> {
+        self.max_fill_notional
+    }
+
+    /// Maximum total output value filled without approval in any rolling 24-hour window, in
+    /// normalized 18-decimal USD.
+    pub const fn max_daily_notional(&self) -> Option<U256> {
+        self.max_daily_notional
+    }
+
+    /// Bearer token required to approve or reject queued fills.
+    pub const fn approver_token(&self) -> &str {
+        self.approver_token.as_str()
+    }
+}
+
+impl Debug for SpendPolicyConfig {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("SpendPolicyConfig")
+            .field("max_fill_notional", &self.max_fill_notional)
+            .field("max_daily_notional", &self.max_daily_notional)
+            .field("approver_token", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Configuration for fetching spot prices from a public price API.
+#[derive(Clone)]
+pub struct PriceApiConfig {
+    api: PriceApi,
+    api_key: Option<String>,
+    url: Option<reqwest::Url>,
+    refresh_interval: Duration,
+    stale_price_policy: StalePricePolicy,
+}
+
+impl PriceApiConfig {
+    /// The API to fetch prices from.
+    pub const fn api(&self) -> PriceApi {
+        self.api
+    }
+
+    /// The API key sent with each request, if any.
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
+    /// The endpoint to fetch prices from, set for [`PriceApi::Custom`] only.
+    pub const fn url(&self) -> Option<&reqwest::Url> {
+        self.url.as_ref()
+    }
+
+    /// Interval between price requests.
+    pub const fn refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+
+    /// How tokens are priced once their price goes stale.
+    pub const fn stale_price_policy(&self) -> StalePricePolicy {
+        self.stale_price_policy
+    }
+}
+
+impl Debug for PriceApiConfig {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("PriceApiConfig")
+            .field("api", &self.api)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("url", &self.url)
+            .field("refresh_interval", &self.refresh_interval)
+            .field("stale_price_policy", &self.stale_price_policy)
+            .finish()
+    }
+}
+
+/// Bounds within which the block lead is tuned to observed inclusion: lengthened when bundles
+/// miss their first target block, and shortened while they keep landing in it.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveLeadConfig {
+    min: Duration,
+    max: Duration,
+}
+
+impl AdaptiveLeadConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(min: Duration, max: Duration) -> Self {
+        Self { min, max }
+    }
+
+    /// Shortest block lead.
+    pub const fn min(&self) -> Duration {
+        self.min
+    }
+
+    /// Longest block lead.
+    pub const fn max(&self) -> Duration {
+        self.max
+    }
+}
+
+/// Operator allow and deny lists of the tokens an order may have as inputs and as outputs. An
+/// unset allowlist allows any token not denied. Output tokens are matched by address on any chain.
+#[derive(Debug, Clone, Default)]
+pub struct TokenFilterConfig {
+    input_allowlist: Option<HashSet<Address>>,
+    input_denylist: HashSet<Address>,
+    output_allowlist: Option<HashSet<Address>>,
+    output_denylist: HashSet<Address>,
+}
+
+impl TokenFilterConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(
+        input_allowlist: Option<HashSet<Address>>,
+        input_denylist: HashSet<Address>,
+        output_allowlist: Option<HashSet<Address>>,
+        output_denylist: HashSet<Address>,
+    ) -> Self {
+        Self { input_allowlist, input_denylist, output_allowlist, output_denylist }
+    }
+
+    /// Whether no list is configured, so every token is allowed.
+    pub fn is_empty(&self) -> bool {
+        self.input_allowlist.is_none()
+            && self.input_denylist.is_empty()
+            && self.output_allowlist.is_none()
+            && self.output_denylist.is_empty()
+    }
+
+    /// Whether orders may have `token` as an input.
+    pub fn allows_input(&self, token: &Address) -> bool {
+        allowed(token, self.input_allowlist.as_ref(), &self.input_denylist)
+    }
+
+    /// Whether orders may have `token` as an output.
+    pub fn allows_output(&self, token: &Address) -> bool {
+        allowed(token, self.output_allowlist.as_ref(), &self.output_denylist)
+    }
+}
+
+fn allowed(
+    token: &Address,
+    allowlist: Option<&HashSet<Address>>,
+    denylist: &HashSet<Address>,
+) -> bool {
+    allowlist.is_none_or(|allowlist| allowlist.contains(token)) && !denylist.contains(token)
+}
+
+/// Compliance screening of order owners and output recipients against a list of addresses read
+/// at startup and, optionally, an external screening API.
+#[derive(Clone)]
+pub struct ScreeningConfig {
+    list: HashSet<Address>,
+    api_url: Option<reqwest::Url>,
+    api_key: Option<String>,
+    cache_ttl: Duration,
+}
+
+impl ScreeningConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(list: HashSet<Address>, cache_ttl: Duration) -> Self {
+        Self { list, api_url: None, api_key: None, cache_ttl }
+    }
+
+    /// Addresses whose orders are never filled.
+    pub const fn list(&self) -> &HashSet<Address> {
+        &self.list
+    }
+
+    /// Base URL of the screening API, if any.
+    pub const fn api_url(&self) -> Option<&reqwest::Url> {
+        self.api_url.as_ref()
+    }
+
+    /// The API key sent with each screening API request, if any.
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
+    /// How long a screening API result is reused.
+    pub const fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+}
+
+impl Debug for ScreeningConfig {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ScreeningConfig")
+            .field("list", &self.list.len())
+            .field("api_url", &self.api_url)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("cache_ttl", &self.cache_ttl)
+            .finish()
+    }
+}
+
+/// External scoring hook that each cycle's profitable candidates are sent to, letting operators
+/// run their own strategy logic on top of the filler's pricing.
+#[derive(Debug, Clone)]
+pub struct ScoringHookConfig {
+    url: reqwest::Url,
+    timeout: Duration,
+    fail_open: bool,
+}
+
+impl ScoringHookConfig {
+    /// URL candidates are POSTed to.
+    pub const fn url(&self) -> &reqwest::Url {
+        &self.url
+    }
+
+    /// How long to wait for the hook's response.
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Whether candidates are filled as unscored, rather than skipped, when the hook fails.
+    pub const fn fail_open(&self) -> bool {
+        self.fail_open
+    }
+}
+
+/// Rolling-window caps on fill count and notional value, enforced across cycles. Every limit is
+/// optional; the throttle is disabled when none is set.
+#[derive(Debug, Clone, Copy)]
+pub struct FillThrottleConfig {
+    max_fills_per_minute: Option<NonZeroU32>,
+    max_fills_per_hour: Option<NonZeroU32>,
+    max_notional_per_minute: Option<U256>,
+    max_notional_per_hour: Option<U256>,
+    max_fills_per_owner_per_hour: Option<NonZeroU32>,
+    max_notional_per_owner_per_hour: Option<U256>,
+}
+
+impl FillThrottleConfig {
+    #[cfg(test)]
+    pub(crate) const fn new(
+        max_fills_per_minute: Option<NonZeroU32>,
+        max_fills_per_hour: Option<NonZeroU32>,
+        max_notional_per_minute: Option<U256>,
+        max_notional_per_hour: Option<U256>,
+        max_fills_per_owner_per_hour: Option<NonZeroU32>,
+        max_notional_per_owner_per_hour: Option<U256>,
+    ) -> Self {
+        Self {
+            max_fills_per_minute,
+            max_fills_per_hour,
+            max_notional_per_minute,
+            max_notional_per_hour,
+            max_fills_per_owner_per_hour,
+            max_notional_per_owner_per_hour,
+        }
+    }
+
+    /// Maximum number of fills in any rolling one-minute window.
+    pub const fn max_fills_per_minute(&self) -> Option<NonZeroU32> {
+        self.max_fills_per_minute
+    }
+
+    /// Maximum number of fills in any rolling one-hour window.
+    pub const fn max_fills_per_hour(&self) -> Option<NonZeroU32> {
+        self.max_fills_per_hour
+    }
 
-    #[from_env(
-        var = "SIGNET_FILLER_MAX_ORDERS_PER_BUNDLE",
-        desc = "Maximum number of orders to include in a single fill bundle. Must be greater \
-            than 0 when set [default: unset, no cap]",
-        optional
-    )]
-    max_orders_per_bundle: Option<usize>,
+    /// Maximum total output value filled in any rolling one-minute window, in normalized
+    /// 18-decimal USD.
+    pub const fn max_notional_per_minute(&self) -> Option<U256> {
+        self.max_notional_per_minute
+    }
 
-    signer: LocalOrAwsConfig,
+    /// Maximum total output value filled in any rolling one-hour window, in normalized 18-decimal
+    /// USD.
+    pub const fn max_notional_per_hour(&self) -> Option<U256> {
+        self.max_notional_per_hour
+    }
+
+    /// Maximum number of one order owner's fills in any rolling one-hour window.
+    pub const fn max_fills_per_owner_per_hour(&self) -> Option<NonZeroU32> {
+        self.max_fills_per_owner_per_hour
+    }
+
+    /// Maximum total output value of one order owner's fills in any rolling one-hour window, in
+    /// normalized 18-decimal USD.
+    pub const fn max_notional_per_owner_per_hour(&self) -> Option<U256> {
+        self.max_notional_per_owner_per_hour
+    }
 }
 
 /// Configuration for the Signet Filler service.
@@ -103,9 +2421,61 @@ pub struct Config {
     host_rpc: ProviderConfig,
     ru_rpc: PubSubConfig,
     block_lead_duration: Duration,
+    min_submission_lead: Duration,
+    follow_up_leads: Vec<Duration>,
+    adaptive_lead: Option<AdaptiveLeadConfig>,
+    evaluation_interval: Option<Duration>,
     max_loss_percent: u8,
-    healthcheck_port: u16,
+    min_expected_profit: Option<i128>,
+    min_margin_bps: Option<u16>,
+    healthcheck_address: SocketAddr,
+    metrics_address: SocketAddr,
+    admin: Option<AdminConfig>,
+    archive: Option<ArchiveConfig>,
+    fill_throttle: Option<FillThrottleConfig>,
+    spend_policy: Option<SpendPolicyConfig>,
+    bootstrap_mode: BootstrapMode,
+    scheduling_mode: SchedulingMode,
+    order_selection_policy: OrderSelectionPolicy,
+    aging_boost_percent: Option<u16>,
+    quiet_periods: Vec<QuietPeriod>,
+    cycle_budget: CycleBudgetConfig,
+    reputation: ReputationConfig,
+    toxic_flow: Option<ToxicFlowConfig>,
+    loss_limit: Option<LossLimitConfig>,
+    trusted_owners: Vec<Address>,
+    denied_owners: Vec<Address>,
+    skip_signature_check: bool,
+    submission_retry: SubmissionRetryConfig,
+    inventory: Option<InventoryConfig>,
+    token_table: Vec<TokenMetadata>,
+    approval_policy: ApprovalPolicy,
+    twap: Option<TwapConfig>,
+    price_api: Option<PriceApiConfig>,
+    price_cross_check_bps: Option<u16>,
+    volatility_haircuts: HashMap<Address, u16>,
+    token_filter: TokenFilterConfig,
+    quote_cache_ttl: Option<Duration>,
+    unprofitable_max_backoff_slots: Option<u64>,
+    fee_oracle: Option<FeeOracleConfig>,
+    bridge: Option<BridgeConfig>,
+    token_discovery: Option<TokenDiscoveryConfig>,
+    simulation_min_notional: Option<U256>,
+    min_order_notional: Option<U256>,
+    max_order_notional: Option<U256>,
+    fill_webhook_url: Option<reqwest::Url>,
+    screening: Option<ScreeningConfig>,
+    scoring_hook: Option<ScoringHookConfig>,
+    low_balance: Option<LowBalanceConfig>,
+    position_limits: Vec<PositionLimit>,
+    filled_order_store: Option<PathBuf>,
+    coordination: Option<CoordinationConfig>,
+    order_backfill_blocks: u64,
+    filled_orders_cache_size: NonZeroUsize,
+    order_file: Option<PathBuf>,
+    bundle_file: Option<PathBuf>,
     target_blocks: u8,
+    fill_deadline_offset: Option<Duration>,
     max_orders_per_bundle: Option<NonZeroUsize>,
     signer: LocalOrAwsConfig,
     constants: SignetConstants,
@@ -132,14 +2502,278 @@ impl Config {
         self.block_lead_duration
     }
 
+    /// Minimum time that must remain before the block boundary for a fill bundle to be submitted.
+    pub const fn min_submission_lead(&self) -> Duration {
+        self.min_submission_lead
+    }
+
+    /// Bounds for tuning the block lead to observed inclusion, if enabled. The block lead
+    /// duration is then the starting lead.
+    pub const fn adaptive_lead(&self) -> Option<AdaptiveLeadConfig> {
+        self.adaptive_lead
+    }
+
+    /// How often orders are evaluated when evaluation runs continuously, apart from submission.
+    pub const fn evaluation_interval(&self) -> Option<Duration> {
+        self.evaluation_interval
+    }
+
+    /// Leads before the block boundary of further submission attempts per slot, largest first, the
+    /// order in which the attempts run.
+    pub fn follow_up_leads(&self) -> &[Duration] {
+        &self.follow_up_leads
+    }
+
     /// Maximum acceptable loss percentage (0-100) for order pricing.
     pub const fn max_loss_percent(&self) -> u8 {
         self.max_loss_percent
     }
 
-    /// Port for the healthcheck HTTP server.
-    pub const fn healthcheck_port(&self) -> u16 {
-        self.healthcheck_port
+    /// Minimum expected profit (normalized 18-decimal USD) for an order to be filled, if set.
+    pub const fn min_expected_profit(&self) -> Option<i128> {
+        self.min_expected_profit
+    }
+
+    /// Minimum margin, after gas, in basis points of an order's input value, if set.
+    pub const fn min_margin_bps(&self) -> Option<u16> {
+        self.min_margin_bps
+    }
+
+    /// Address the healthcheck HTTP server binds to.
+    pub const fn healthcheck_address(&self) -> SocketAddr {
+        self.healthcheck_address
+    }
+
+    /// Address the Prometheus metrics exporter binds to.
+    pub const fn metrics_address(&self) -> SocketAddr {
+        self.metrics_address
+    }
+
+    /// Admin HTTP API configuration, or `None` if the admin API is disabled.
+    pub const fn admin(&self) -> Option<&AdminConfig> {
+        self.admin.as_ref()
+    }
+
+    /// Order and decision archival configuration, or `None` if archival is disabled.
+    pub const fn archive(&self) -> Option<&ArchiveConfig> {
+        self.archive.as_ref()
+    }
+
+    /// Fill-rate throttle configuration, or `None` if no fill-rate limit is set.
+    pub const fn fill_throttle(&self) -> Option<&FillThrottleConfig> {
+        self.fill_throttle.as_ref()
+    }
+
+    /// Hot-key spend policy configuration, or `None` if fills need no approval.
+    pub const fn spend_policy(&self) -> Option<&SpendPolicyConfig> {
+        self.spend_policy.as_ref()
+    }
+
+    /// How to run the bootstrap cycle immediately after initialization.
+    pub const fn bootstrap_mode(&self) -> BootstrapMode {
+        self.bootstrap_mode
+    }
+
+    /// How each slot's cycle is timed.
+    pub const fn scheduling_mode(&self) -> SchedulingMode {
+        self.scheduling_mode
+    }
+
+    /// The order in which candidate orders are selected for fill bundles.
+    pub const fn order_selection_policy(&self) -> OrderSelectionPolicy {
+        self.order_selection_policy
+    }
+
+    /// Percentage by which an order's ranking margin is boosted per cycle it has gone unfilled,
+    /// under profit-max selection, if set.
+    pub const fn aging_boost_percent(&self) -> Option<u16> {
+        self.aging_boost_percent
+    }
+
+    /// Scheduled periods during which no bundles are submitted.
+    pub fn quiet_periods(&self) -> &[QuietPeriod] {
+        &self.quiet_periods
+    }
+
+    /// Per-cycle evaluation budgets.
+    pub const fn cycle_budget(&self) -> &CycleBudgetConfig {
+        &self.cycle_budget
+    }
+
+    /// Per-owner reputation scoring configuration.
+    pub const fn reputation(&self) -> &ReputationConfig {
+        &self.reputation
+    }
+
+    /// Toxic-flow detection configuration, if a markout loss threshold is set.
+    pub const fn toxic_flow(&self) -> Option<&ToxicFlowConfig> {
+        self.toxic_flow.as_ref()
+    }
+
+    /// Daily loss limit configuration, if a maximum daily loss is set.
+    pub const fn loss_limit(&self) -> Option<&LossLimitConfig> {
+        self.loss_limit.as_ref()
+    }
+
+    /// Order owners whose orders take the fast path: placed first and never simulated.
+    pub fn trusted_owners(&self) -> &[Address] {
+        &self.trusted_owners
+    }
+
+    /// Order owners whose orders are never filled, at startup.
+    pub fn denied_owners(&self) -> &[Address] {
+        &self.denied_owners
+    }
+
+    /// Whether orders' Permit2 signatures are verified locally before they are priced.
+    pub const fn verify_signatures(&self) -> bool {
+        !self.skip_signature_check
+    }
+
+    /// Retry policy for bundle submissions that fail with a transient error.
+    pub const fn submission_retry(&self) -> &SubmissionRetryConfig {
+        &self.submission_retry
+    }
+
+    /// Inventory-aware profitability adjustment configuration, if target allocations are set.
+    pub const fn inventory(&self) -> Option<&InventoryConfig> {
+        self.inventory.as_ref()
+    }
+
+    /// Fixed-price metadata for tokens from the token table file, if one is set.
+    pub fn token_table(&self) -> &[TokenMetadata] {
+        &self.token_table
+    }
+
+    /// How the filler maintains the Permit2 allowances of its ERC20 output tokens.
+    pub const fn approval_policy(&self) -> ApprovalPolicy {
+        self.approval_policy
+    }
+
+    /// Uniswap V3 TWAP pricing configuration, if any pools are set.
+    pub const fn twap(&self) -> Option<&TwapConfig> {
+        self.twap.as_ref()
+    }
+
+    /// Public price API configuration, if one is selected.
+    pub const fn price_api(&self) -> Option<&PriceApiConfig> {
+        self.price_api.as_ref()
+    }
+
+    /// Maximum divergence in basis points between an order's TWAP and price API valuations, if
+    /// cross-validation is enabled.
+    pub const fn price_cross_check_bps(&self) -> Option<u16> {
+        self.price_cross_check_bps
+    }
+
+    /// Per-token volatility haircuts in basis points, applied against the filler when pricing
+    /// orders.
+    pub const fn volatility_haircuts(&self) -> &HashMap<Address, u16> {
+        &self.volatility_haircuts
+    }
+
+    /// Token allow and deny lists for order inputs and outputs.
+    pub const fn token_filter(&self) -> &TokenFilterConfig {
+        &self.token_filter
+    }
+
+    /// How long an order's profitability result is reused, if quote caching is enabled.
+    pub const fn quote_cache_ttl(&self) -> Option<Duration> {
+        self.quote_cache_ttl
+    }
+
+    /// Maximum number of slots between re-checks of a repeatedly unprofitable order, if backoff is
+    /// enabled.
+    pub const fn unprofitable_max_backoff_slots(&self) -> Option<u64> {
+        self.unprofitable_max_backoff_slots
+    }
+
+    /// Discovery of unpriced tokens from order flow, if enabled.
+    pub const fn token_discovery(&self) -> Option<&TokenDiscoveryConfig> {
+        self.token_discovery.as_ref()
+    }
+
+    /// Rollup gas cost estimation, if enabled.
+    pub const fn fee_oracle(&self) -> Option<&FeeOracleConfig> {
+        self.fee_oracle.as_ref()
+    }
+
+    /// Inventory bridging from the host chain to the rollup, if enabled.
+    pub const fn bridge(&self) -> Option<&BridgeConfig> {
+        self.bridge.as_ref()
+    }
+
+    /// Output notional, in normalized 18-decimal USD, at or above which fills are simulated before
+    /// submission, if set.
+    pub const fn simulation_min_notional(&self) -> Option<U256> {
+        self.simulation_min_notional
+    }
+
+    /// Output notional, in normalized 18-decimal USD, below which orders are skipped before
+    /// pricing, if set.
+    pub const fn min_order_notional(&self) -> Option<U256> {
+        self.min_order_notional
+    }
+
+    /// Output notional, in normalized 18-decimal USD, above which orders are skipped before
+    /// pricing, if set.
+    pub const fn max_order_notional(&self) -> Option<U256> {
+        self.max_order_notional
+    }
+
+    /// URL to send fill receipts to, if set.
+    pub const fn fill_webhook_url(&self) -> Option<&reqwest::Url> {
+        self.fill_webhook_url.as_ref()
+    }
+
+    /// Compliance screening configuration, if a screening list or API is set.
+    pub const fn screening(&self) -> Option<&ScreeningConfig> {
+        self.screening.as_ref()
+    }
+
+    /// External scoring hook configuration, if a scoring hook URL is set.
+    pub const fn scoring_hook(&self) -> Option<&ScoringHookConfig> {
+        self.scoring_hook.as_ref()
+    }
+
+    /// Low-balance alerting and treasury top-ups, if enabled.
+    pub const fn low_balance(&self) -> Option<&LowBalanceConfig> {
+        self.low_balance.as_ref()
+    }
+
+    /// Caps on the filler's net position per token.
+    pub fn position_limits(&self) -> &[PositionLimit] {
+        &self.position_limits
+    }
+
+    /// Directory the filled-order cache is persisted in, if set.
+    pub fn filled_order_store(&self) -> Option<&Path> {
+        self.filled_order_store.as_deref()
+    }
+
+    /// Number of recent rollup blocks scanned for order events at startup. Zero disables the scan.
+    pub const fn order_backfill_blocks(&self) -> u64 {
+        self.order_backfill_blocks
+    }
+
+    /// Number of filled orders remembered in the filled-order cache.
+    pub const fn filled_orders_cache_size(&self) -> NonZeroUsize {
+        self.filled_orders_cache_size
+    }
+
+    /// Coordination with other filler replicas through a shared Redis, if enabled.
+    pub const fn coordination(&self) -> Option<&CoordinationConfig> {
+        self.coordination.as_ref()
+    }
+
+    /// File to read orders from instead of the transaction cache, if set.
+    pub fn order_file(&self) -> Option<&Path> {
+        self.order_file.as_deref()
+    }
+
+    /// File to append fill bundles to when reading orders from [`Self::order_file`], if set.
+    pub fn bundle_file(&self) -> Option<&Path> {
+        self.bundle_file.as_deref()
     }
 
     /// Number of consecutive blocks to target per fill bundle.
@@ -147,7 +2781,15 @@ impl Config {
         self.target_blocks
     }
 
-    /// Maximum number of orders to include in a single fill bundle, or `None` for no cap.
+    /// How long fill signatures stay valid after signing, if set. Otherwise this is derived from
+    /// the block lead and [`Self::target_blocks`].
+    pub const fn fill_deadline_offset(&self) -> Option<Duration> {
+        self.fill_deadline_offset
+    }
+
+    /// Maximum number of orders to include in a single fill bundle, or `None` for no cap: the
+    /// configured maximum or the number of orders that fit the bundle gas budget, whichever is
+    /// smaller.
     pub const fn max_orders_per_bundle(&self) -> Option<NonZeroUsize> {
         self.max_orders_per_bundle
     }
@@ -162,21 +2804,141 @@ impl Config {
         &self.constants
     }
 
+    /// This config with the RPC URLs and order and bundle files replaced, for running the filler
+    /// against local forks of its chain.
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn with_forks(
+        mut self,
+        host_rpc: &str,
+        ru_rpc: &str,
+        order_file: PathBuf,
+        bundle_file: PathBuf,
+    ) -> Result<Self> {
+        self.host_rpc = ProviderConfig::new(
+            host_rpc.parse().wrap_err_with(|| format!("failed to parse {HOST_RPC_VAR}"))?,
+        );
+        self.ru_rpc = parse_ru_rpc(Some(ru_rpc.to_string()))?;
+        self.order_file = Some(order_file);
+        self.bundle_file = Some(bundle_file);
+        Ok(self)
+    }
+
     fn from_env() -> Result<Self> {
         let ConfigInner {
             chain_name,
             host_rpc,
             ru_rpc,
             block_lead_duration_ms,
+            min_submission_lead_ms,
+            follow_up_leads_ms,
+            adaptive_lead_min_ms,
+            adaptive_lead_max_ms,
+            evaluation_interval_ms,
             max_loss_percent,
+            min_expected_profit_cents,
+            min_margin_bps,
             healthcheck_port,
+            healthcheck_host,
+            metrics_host,
+            metrics,
+            admin_port,
+            admin_host,
+            admin_token,
+            archive_bucket,
+            archive_prefix,
+            archive_endpoint_url,
+            archive_interval_secs,
             target_blocks,
+            fill_deadline_offset_secs,
             max_orders_per_bundle,
+            bootstrap_mode,
+            scheduling_mode,
+            order_selection_policy,
+            aging_boost_percent,
+            quiet_periods,
+            cycle_max_quotes,
+            cycle_max_rpc_calls,
+            cycle_max_wall_ms,
+            reputation_half_life_secs,
+            reputation_skip_threshold,
+            toxic_flow_deprioritize_usd,
+            toxic_flow_pause_usd,
+            toxic_flow_markout_secs,
+            toxic_flow_half_life_secs,
+            max_daily_loss_usd,
+            daily_loss_reset_utc,
+            trusted_owners,
+            denied_owners,
+            skip_signature_check,
+            submit_max_retries,
+            submit_retry_backoff_ms,
+            inventory_targets,
+            inventory_bonus_bps,
+            inventory_penalty_bps,
+            max_fills_per_minute,
+            max_fills_per_hour,
+            max_notional_usd_per_minute,
+            max_notional_usd_per_hour,
+            max_fills_per_owner_per_hour,
+            max_notional_usd_per_owner_per_hour,
+            hot_max_fill_usd,
+            hot_max_daily_usd,
+            approver_token,
+            token_table,
+            approval_policy,
+            twap_pools,
+            twap_window_secs,
+            price_api,
+            price_api_url,
+            stale_price_policy,
+            price_api_key,
+            price_api_refresh_secs,
+            price_cross_check_bps,
+            volatility_haircuts,
+            input_token_allowlist,
+            input_token_denylist,
+            output_token_allowlist,
+            output_token_denylist,
+            quote_cache_ttl_ms,
+            unprofitable_max_backoff_slots,
+            token_discovery,
+            uniswap_v3_factory,
+            discovery_min_age_blocks,
+            discovery_min_liquidity_usd,
+            gas_per_order,
+            host_gas_per_order,
+            gas_per_bundle,
+            bundle_gas_budget,
+            priority_fee_markup_percent,
+            fee_oracle_refresh_secs,
+            bridge_min_share_percent,
+            bridge_interval_secs,
+            simulation_min_notional_usd,
+            min_order_notional_usd,
+            max_order_notional_usd,
+            fill_webhook_url,
+            screening_list,
+            screening_api_url,
+            screening_api_key,
+            screening_cache_secs,
+            scoring_hook_url,
+            scoring_hook_timeout_ms,
+            scoring_hook_fail_open,
+            low_balance_usd,
+            treasury_address,
+            max_positions_usd,
+            filled_order_store,
+            redis_url,
+            redis_key_prefix,
+            leader_lease_secs,
+            order_backfill_blocks,
+            filled_orders_cache_size,
+            order_file,
+            bundle_file,
             signer,
         } = ConfigInner::from_env()?;
         let chain_name = chain_name.unwrap_or(DEFAULT_CHAIN_NAME.to_string());
-        let constants =
-            chain_name.parse().wrap_err_with(|| format!("invalid value for {CHAIN_NAME_VAR}"))?;
+        let constants = parse_constants(&chain_name)?;
 
         let host_rpc = ProviderConfig::new(
             host_rpc
@@ -184,16 +2946,57 @@ impl Config {
                 .parse()
                 .wrap_err_with(|| format!("failed to parse {HOST_RPC_VAR}"))?,
         );
-        let ru_rpc = PubSubConfig::try_from(
-            ru_rpc
-                .unwrap_or(DEFAULT_RU_RPC.to_string())
-                .parse::<BuiltInConnectionString>()
-                .wrap_err_with(|| format!("failed to parse {RU_RPC_VAR}"))?,
-        )
-        .wrap_err_with(|| format!("{RU_RPC_VAR} must start with ws:// or wss://"))?;
+        let ru_rpc = parse_ru_rpc(ru_rpc)?;
         let block_lead_duration = block_lead_duration_ms
             .map(Duration::from_millis)
             .unwrap_or(DEFAULT_BLOCK_LEAD_DURATION);
+        let min_submission_lead = min_submission_lead_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_MIN_SUBMISSION_LEAD);
+        if min_submission_lead >= block_lead_duration {
+            bail!(
+                "{MIN_SUBMISSION_LEAD_MS_VAR} ({}ms) must be less than the block lead duration \
+                 ({}ms)",
+                min_submission_lead.as_millis(),
+                block_lead_duration.as_millis()
+            );
+        }
+        let follow_up_leads = follow_up_leads_ms
+            .map(|leads| {
+                parse_follow_up_leads(&leads, block_lead_duration, min_submission_lead)
+                    .wrap_err_with(|| format!("invalid value for {FOLLOW_UP_LEADS_MS_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let adaptive_lead = match (adaptive_lead_min_ms, adaptive_lead_max_ms) {
+            (None, None) => None,
+            (Some(min_ms), Some(max_ms)) => {
+                let (min, max) = (Duration::from_millis(min_ms), Duration::from_millis(max_ms));
+                if min <= min_submission_lead {
+                    bail!(
+                        "{ADAPTIVE_LEAD_MIN_MS_VAR} must be more than {MIN_SUBMISSION_LEAD_MS_VAR}"
+                    );
+                }
+                if !(min..=max).contains(&block_lead_duration) {
+                    bail!(
+                        "the block lead duration ({}ms) must be between \
+                         {ADAPTIVE_LEAD_MIN_MS_VAR} and {ADAPTIVE_LEAD_MAX_MS_VAR}",
+                        block_lead_duration.as_millis()
+                    );
+                }
+                if follow_up_leads.first().is_some_and(|lead| *lead >= min) {
+                    bail!("{FOLLOW_UP_LEADS_MS_VAR} must be less than {ADAPTIVE_LEAD_MIN_MS_VAR}");
+                }
+                Some(AdaptiveLeadConfig { min, max })
+            }
+            _ => bail!(
+                "{ADAPTIVE_LEAD_MIN_MS_VAR} and {ADAPTIVE_LEAD_MAX_MS_VAR} must be set together"
+            ),
+        };
+        if evaluation_interval_ms == Some(0) {
+            bail!("{EVALUATION_INTERVAL_MS_VAR} must be greater than 0");
+        }
+        let evaluation_interval = evaluation_interval_ms.map(Duration::from_millis);
         let max_loss_percent = max_loss_percent.unwrap_or(DEFAULT_MAX_LOSS_PERCENT);
         if max_loss_percent > 100 {
             bail!(
@@ -201,7 +3004,43 @@ impl Config {
                  (got {max_loss_percent})"
             );
         }
-        let healthcheck_port = healthcheck_port.unwrap_or(DEFAULT_HEALTHCHECK_PORT);
+        // One cent is 10^16 in normalized 18-decimal USD; any u64 fits in an i128 after scaling.
+        let min_expected_profit =
+            min_expected_profit_cents.map(|cents| i128::from(cents) * 10_i128.pow(16));
+        if let Some(bps) = min_margin_bps.filter(|bps| *bps > 10_000) {
+            bail!("{MIN_MARGIN_BPS_VAR} must be between 0 and 10000 (got {bps})");
+        }
+        let healthcheck_address = SocketAddr::new(
+            parse_host(healthcheck_host, DEFAULT_PUBLIC_HOST, HEALTHCHECK_HOST_VAR)?,
+            healthcheck_port.unwrap_or(DEFAULT_HEALTHCHECK_PORT),
+        );
+        let metrics_address = SocketAddr::new(
+            parse_host(metrics_host, DEFAULT_PUBLIC_HOST, METRICS_HOST_VAR)?,
+            metrics.port,
+        );
+        let admin_token = parse_token(admin_token, ADMIN_TOKEN_VAR)?;
+        let admin = match (admin_port, admin_token) {
+            (None, _) => None,
+            (Some(_), None) => bail!("{ADMIN_TOKEN_VAR} must be set when {ADMIN_PORT_VAR} is set"),
+            (Some(port), Some(token)) => Some(AdminConfig {
+                address: SocketAddr::new(
+                    parse_host(admin_host, DEFAULT_ADMIN_HOST, ADMIN_HOST_VAR)?,
+                    port,
+                ),
+                token,
+            }),
+        };
+        if archive_interval_secs == Some(0) {
+            bail!("{ARCHIVE_INTERVAL_SECS_VAR} must be greater than 0");
+        }
+        let archive = archive_bucket.map(|bucket| ArchiveConfig {
+            bucket,
+            prefix: archive_prefix.unwrap_or(DEFAULT_ARCHIVE_PREFIX.to_string()),
+            endpoint_url: archive_endpoint_url,
+            interval: archive_interval_secs
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_ARCHIVE_INTERVAL),
+        });
         let target_blocks = target_blocks.unwrap_or(DEFAULT_TARGET_BLOCKS);
         if !(1..=MAX_TARGET_BLOCKS).contains(&target_blocks) {
             bail!(
@@ -209,20 +3048,564 @@ impl Config {
                  (got {target_blocks})"
             );
         }
+        let min_fill_deadline_offset = block_lead_duration.as_secs()
+            + u64::from(target_blocks) * constants.system().host().slot_duration();
+        if let Some(offset) =
+            fill_deadline_offset_secs.filter(|offset| *offset < min_fill_deadline_offset)
+        {
+            bail!(
+                "{FILL_DEADLINE_OFFSET_SECS_VAR} must be at least {min_fill_deadline_offset} to \
+                 cover the block lead and {TARGET_BLOCKS_VAR} slots (got {offset})"
+            );
+        }
+        let fill_deadline_offset = fill_deadline_offset_secs.map(Duration::from_secs);
         if max_orders_per_bundle == Some(0) {
             bail!("{MAX_ORDERS_PER_BUNDLE_VAR} must be greater than 0");
         }
         let max_orders_per_bundle =
             max_orders_per_bundle.map(|v| NonZeroUsize::new(v).expect("already checked non-zero"));
+        let bootstrap_mode = bootstrap_mode
+            .map(|mode| {
+                mode.parse().wrap_err_with(|| format!("invalid value for {BOOTSTRAP_MODE_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_BOOTSTRAP_MODE);
+        let scheduling_mode = scheduling_mode
+            .map(|mode| {
+                mode.parse().wrap_err_with(|| format!("invalid value for {SCHEDULING_MODE_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_SCHEDULING_MODE);
+        let order_selection_policy = order_selection_policy
+            .map(|policy| {
+                policy
+                    .parse()
+                    .wrap_err_with(|| format!("invalid value for {ORDER_SELECTION_POLICY_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_ORDER_SELECTION_POLICY);
+        if aging_boost_percent == Some(0) {
+            bail!("{AGING_BOOST_PERCENT_VAR} must be greater than 0");
+        }
+        let quiet_periods = quiet_periods
+            .map(|periods| {
+                periods
+                    .split(',')
+                    .map(|period| period.trim().parse())
+                    .collect::<Result<Vec<QuietPeriod>>>()
+                    .wrap_err_with(|| format!("invalid value for {QUIET_PERIODS_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        if cycle_max_wall_ms == Some(0) {
+            bail!("{CYCLE_MAX_WALL_MS_VAR} must be greater than 0");
+        }
+        let cycle_budget = CycleBudgetConfig {
+            max_quotes: parse_cycle_limit(cycle_max_quotes, CYCLE_MAX_QUOTES_VAR)?,
+            max_rpc_calls: parse_cycle_limit(cycle_max_rpc_calls, CYCLE_MAX_RPC_CALLS_VAR)?,
+            max_wall_time: cycle_max_wall_ms.map(Duration::from_millis),
+        };
+        if reputation_half_life_secs == Some(0) {
+            bail!("{REPUTATION_HALF_LIFE_SECS_VAR} must be greater than 0");
+        }
+        if reputation_skip_threshold == Some(0) {
+            bail!("{REPUTATION_SKIP_THRESHOLD_VAR} must be greater than 0");
+        }
+        let reputation = ReputationConfig {
+            half_life: reputation_half_life_secs
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_REPUTATION_HALF_LIFE),
+            skip_threshold: f64::from(
+                reputation_skip_threshold.unwrap_or(DEFAULT_REPUTATION_SKIP_THRESHOLD),
+            ),
+        };
+        for (value, var) in [
+            (toxic_flow_deprioritize_usd, TOXIC_FLOW_DEPRIORITIZE_USD_VAR),
+            (toxic_flow_pause_usd, TOXIC_FLOW_PAUSE_USD_VAR),
+            (toxic_flow_markout_secs, TOXIC_FLOW_MARKOUT_SECS_VAR),
+            (toxic_flow_half_life_secs, TOXIC_FLOW_HALF_LIFE_SECS_VAR),
+        ] {
+            if value == Some(0) {
+                bail!("{var} must be greater than 0");
+            }
+        }
+        if toxic_flow_deprioritize_usd
+            .zip(toxic_flow_pause_usd)
+            .is_some_and(|(deprioritize, pause)| pause < deprioritize)
+        {
+            bail!("{TOXIC_FLOW_PAUSE_USD_VAR} must be at least {TOXIC_FLOW_DEPRIORITIZE_USD_VAR}");
+        }
+        let toxic_flow = (toxic_flow_deprioritize_usd.is_some() || toxic_flow_pause_usd.is_some())
+            .then(|| ToxicFlowConfig {
+                markout_delay: toxic_flow_markout_secs
+                    .map_or(DEFAULT_TOXIC_FLOW_MARKOUT, Duration::from_secs),
+                half_life: toxic_flow_half_life_secs
+                    .map_or(DEFAULT_TOXIC_FLOW_HALF_LIFE, Duration::from_secs),
+                deprioritize_loss: toxic_flow_deprioritize_usd.map(|usd| usd as f64),
+                pause_loss: toxic_flow_pause_usd.map(|usd| usd as f64),
+            });
+        if max_daily_loss_usd == Some(0) {
+            bail!("{MAX_DAILY_LOSS_USD_VAR} must be greater than 0");
+        }
+        let daily_loss_reset_minute = daily_loss_reset_utc
+            .map(|reset| {
+                parse_time_of_day(reset.trim())
+                    .wrap_err_with(|| format!("invalid value for {DAILY_LOSS_RESET_UTC_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let loss_limit = max_daily_loss_usd.map(|usd| LossLimitConfig {
+            max_loss: usd as f64,
+            reset_minute: daily_loss_reset_minute,
+        });
+        let skip_signature_check = skip_signature_check.unwrap_or_default();
+        let trusted_owners = trusted_owners
+            .map(|owners| {
+                parse_addresses(&owners)
+                    .wrap_err_with(|| format!("invalid value for {TRUSTED_OWNERS_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let denied_owners = denied_owners
+            .map(|owners| {
+                parse_addresses(&owners)
+                    .wrap_err_with(|| format!("invalid value for {DENIED_OWNERS_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        if submit_retry_backoff_ms == Some(0) {
+            bail!("{SUBMIT_RETRY_BACKOFF_MS_VAR} must be greater than 0");
+        }
+        let submission_retry = SubmissionRetryConfig {
+            max_retries: submit_max_retries.unwrap_or(DEFAULT_SUBMIT_MAX_RETRIES),
+            initial_backoff: submit_retry_backoff_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_SUBMIT_RETRY_BACKOFF),
+        };
+        let inventory = inventory_targets
+            .map(|targets| {
+                Ok::<_, eyre::Report>(InventoryConfig {
+                    targets: parse_inventory_targets(&targets)
+                        .wrap_err_with(|| format!("invalid value for {INVENTORY_TARGETS_VAR}"))?,
+                    bonus_bps: parse_bps(
+                        inventory_bonus_bps,
+                        DEFAULT_INVENTORY_BONUS_BPS,
+                        INVENTORY_BONUS_BPS_VAR,
+                    )?,
+                    penalty_bps: parse_bps(
+                        inventory_penalty_bps,
+                        DEFAULT_INVENTORY_PENALTY_BPS,
+                        INVENTORY_PENALTY_BPS_VAR,
+                    )?,
+                })
+            })
+            .transpose()?;
+        if twap_window_secs == Some(0) {
+            bail!("{TWAP_WINDOW_SECS_VAR} must be greater than 0");
+        }
+        let token_table = token_table
+            .map(|path| {
+                read_token_table(Path::new(&path), &constants)
+                    .wrap_err_with(|| format!("invalid value for {TOKEN_TABLE_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let approval_policy = approval_policy
+            .map(|policy| {
+                policy.parse().wrap_err_with(|| format!("invalid value for {APPROVAL_POLICY_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or(ApprovalPolicy::Off);
+        let twap_window = twap_window_secs.map(Duration::from_secs).unwrap_or(DEFAULT_TWAP_WINDOW);
+        let twap = twap_pools
+            .map(|pools| {
+                parse_twap_pools(&pools, twap_window)
+                    .wrap_err_with(|| format!("invalid value for {TWAP_POOLS_VAR}"))
+            })
+            .transpose()?
+            .map(|pools| TwapConfig { pools });
+        if price_api_refresh_secs == Some(0) {
+            bail!("{PRICE_API_REFRESH_SECS_VAR} must be greater than 0");
+        }
+        let stale_price_policy = stale_price_policy
+            .map(|policy| {
+                policy
+                    .parse()
+                    .wrap_err_with(|| format!("invalid value for {STALE_PRICE_POLICY_VAR}"))
+            })
+            .transpose()?;
+        let price_api_url = price_api_url
+            .map(|url| {
+                url.parse::<reqwest::Url>()
+                    .wrap_err_with(|| format!("invalid value for {PRICE_API_URL_VAR}"))
+            })
+            .transpose()?;
+        if price_api.is_none() && stale_price_policy.is_some() {
+            bail!("{PRICE_API_VAR} must be set when {STALE_PRICE_POLICY_VAR} is set");
+        }
+        let price_api = price_api
+            .map(|api| api.parse().wrap_err_with(|| format!("invalid value for {PRICE_API_VAR}")))
+            .transpose()?
+            .map(|api| {
+                if api == PriceApi::CoinMarketCap && price_api_key.is_none() {
+                    bail!("{PRICE_API_KEY_VAR} must be set when {PRICE_API_VAR} is coinmarketcap");
+                }
+                match (api, &price_api_url) {
+                    (PriceApi::Custom, None) => {
+                        bail!("{PRICE_API_URL_VAR} must be set when {PRICE_API_VAR} is custom")
+                    }
+                    (PriceApi::CoinGecko | PriceApi::CoinMarketCap, Some(_)) => {
+                        bail!("{PRICE_API_URL_VAR} is only used when {PRICE_API_VAR} is custom")
+                    }
+                    _ => {}
+                }
+                Ok(PriceApiConfig {
+                    api,
+                    api_key: price_api_key,
+                    url: price_api_url,
+                    refresh_interval: price_api_refresh_secs
+                        .map(Duration::from_secs)
+                        .unwrap_or(DEFAULT_PRICE_API_REFRESH_INTERVAL),
+                    stale_price_policy: stale_price_policy.unwrap_or(StalePricePolicy::Fixed),
+                })
+            })
+            .transpose()?;
+        let parse_token_list = |list: Option<String>, var: &str| {
+            list.map(|list| {
+                parse_addresses(&list)
+                    .map(|tokens| tokens.into_iter().collect::<HashSet<_>>())
+                    .wrap_err_with(|| format!("invalid value for {var}"))
+            })
+            .transpose()
+        };
+        let token_filter = TokenFilterConfig {
+            input_allowlist: parse_token_list(input_token_allowlist, INPUT_TOKEN_ALLOWLIST_VAR)?,
+            input_denylist: parse_token_list(input_token_denylist, INPUT_TOKEN_DENYLIST_VAR)?
+                .unwrap_or_default(),
+            output_allowlist: parse_token_list(output_token_allowlist, OUTPUT_TOKEN_ALLOWLIST_VAR)?,
+            output_denylist: parse_token_list(output_token_denylist, OUTPUT_TOKEN_DENYLIST_VAR)?
+                .unwrap_or_default(),
+        };
+        let volatility_haircuts = volatility_haircuts
+            .as_deref()
+            .map(parse_volatility_haircuts)
+            .transpose()
+            .wrap_err_with(|| format!("invalid value for {VOLATILITY_HAIRCUTS_VAR}"))?
+            .unwrap_or_default();
+        if let Some(bps) = price_cross_check_bps {
+            if bps > 10_000 {
+                bail!("{PRICE_CROSS_CHECK_BPS_VAR} must be between 0 and 10000 (got {bps})");
+            }
+            if twap.is_none() || price_api.is_none() {
+                bail!(
+                    "{PRICE_CROSS_CHECK_BPS_VAR} requires both {TWAP_POOLS_VAR} and \
+                     {PRICE_API_VAR} to be set"
+                );
+            }
+        }
+        if quote_cache_ttl_ms == Some(0) {
+            bail!("{QUOTE_CACHE_TTL_MS_VAR} must be greater than 0");
+        }
+        let quote_cache_ttl = quote_cache_ttl_ms.map(Duration::from_millis);
+        if unprofitable_max_backoff_slots == Some(0) {
+            bail!("{UNPROFITABLE_MAX_BACKOFF_SLOTS_VAR} must be greater than 0");
+        }
+        let token_discovery = token_discovery
+            .map(|mode| {
+                let mode = mode
+                    .parse()
+                    .wrap_err_with(|| format!("invalid value for {TOKEN_DISCOVERY_VAR}"))?;
+                let Some(uniswap_v3_factory) = uniswap_v3_factory else {
+                    bail!("{UNISWAP_V3_FACTORY_VAR} must be set when {TOKEN_DISCOVERY_VAR} is set");
+                };
+                let min_liquidity = parse_notional_limit(
+                    Some(
+                        discovery_min_liquidity_usd.unwrap_or(DEFAULT_DISCOVERY_MIN_LIQUIDITY_USD),
+                    ),
+                    DISCOVERY_MIN_LIQUIDITY_USD_VAR,
+                )?
+                .unwrap_or_default();
+                Ok(TokenDiscoveryConfig {
+                    mode,
+                    uniswap_v3_factory,
+                    min_age_blocks: discovery_min_age_blocks
+                        .unwrap_or(DEFAULT_DISCOVERY_MIN_AGE_BLOCKS),
+                    min_liquidity,
+                    twap_window,
+                })
+            })
+            .transpose()?;
+        if gas_per_order == Some(0) {
+            bail!("{GAS_PER_ORDER_VAR} must be greater than 0");
+        }
+        if host_gas_per_order == Some(0) {
+            bail!("{HOST_GAS_PER_ORDER_VAR} must be greater than 0");
+        }
+        if host_gas_per_order.is_some() && gas_per_order.is_none() {
+            bail!("{HOST_GAS_PER_ORDER_VAR} requires {GAS_PER_ORDER_VAR}");
+        }
+        if gas_per_bundle == Some(0) {
+            bail!("{GAS_PER_BUNDLE_VAR} must be greater than 0");
+        }
+        if gas_per_bundle.is_some() && gas_per_order.is_none() {
+            bail!("{GAS_PER_BUNDLE_VAR} requires {GAS_PER_ORDER_VAR}");
+        }
+        let max_orders_per_bundle = match (bundle_gas_budget, gas_per_order) {
+            (None, _) => max_orders_per_bundle,
+            (Some(_), None) => bail!("{BUNDLE_GAS_BUDGET_VAR} requires {GAS_PER_ORDER_VAR}"),
+            (Some(budget), Some(gas_per_order)) => {
+                let Some(fitting) = orders_within_gas_budget(budget, gas_per_order, gas_per_bundle)
+                else {
+                    bail!("{BUNDLE_GAS_BUDGET_VAR} must fit at least one order");
+                };
+                Some(max_orders_per_bundle.map_or(fitting, |max| max.min(fitting)))
+            }
+        };
+        if fee_oracle_refresh_secs == Some(0) {
+            bail!("{FEE_ORACLE_REFRESH_SECS_VAR} must be greater than 0");
+        }
+        let fee_oracle = gas_per_order.map(|gas_per_order| FeeOracleConfig {
+            gas_per_order,
+            host_gas_per_order,
+            gas_per_bundle,
+            priority_fee_markup_percent: priority_fee_markup_percent
+                .unwrap_or(DEFAULT_PRIORITY_FEE_MARKUP_PERCENT),
+            refresh_interval: fee_oracle_refresh_secs
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_FEE_ORACLE_REFRESH_INTERVAL),
+        });
+        if let Some(percent) =
+            bridge_min_share_percent.filter(|percent| !(1..=49).contains(percent))
+        {
+            bail!("{BRIDGE_MIN_SHARE_PERCENT_VAR} must be between 1 and 49 (got {percent})");
+        }
+        if bridge_interval_secs == Some(0) {
+            bail!("{BRIDGE_INTERVAL_SECS_VAR} must be greater than 0");
+        }
+        let bridge = bridge_min_share_percent.map(|min_share_percent| BridgeConfig {
+            min_share_percent,
+            interval: bridge_interval_secs
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_BRIDGE_INTERVAL),
+        });
+        let simulation_min_notional =
+            parse_notional_limit(simulation_min_notional_usd, SIMULATION_MIN_NOTIONAL_USD_VAR)?;
+        let min_order_notional =
+            parse_notional_limit(min_order_notional_usd, MIN_ORDER_NOTIONAL_USD_VAR)?;
+        let max_order_notional =
+            parse_notional_limit(max_order_notional_usd, MAX_ORDER_NOTIONAL_USD_VAR)?;
+        if min_order_notional.zip(max_order_notional).is_some_and(|(min, max)| max < min) {
+            bail!("{MAX_ORDER_NOTIONAL_USD_VAR} must be at least {MIN_ORDER_NOTIONAL_USD_VAR}");
+        }
+        let fill_webhook_url = fill_webhook_url
+            .map(|url| {
+                url.parse::<reqwest::Url>()
+                    .wrap_err_with(|| format!("invalid value for {FILL_WEBHOOK_URL_VAR}"))
+            })
+            .transpose()?;
+        if screening_api_key.is_some() && screening_api_url.is_none() {
+            bail!("{SCREENING_API_URL_VAR} must be set when {SCREENING_API_KEY_VAR} is set");
+        }
+        if screening_cache_secs == Some(0) {
+            bail!("{SCREENING_CACHE_SECS_VAR} must be greater than 0");
+        }
+        let screening = if screening_list.is_some() || screening_api_url.is_some() {
+            Some(ScreeningConfig {
+                list: screening_list
+                    .map(|path| {
+                        read_screening_list(Path::new(&path))
+                            .wrap_err_with(|| format!("invalid value for {SCREENING_LIST_VAR}"))
+                    })
+                    .transpose()?
+                    .unwrap_or_default(),
+                api_url: screening_api_url
+                    .map(|url| {
+                        url.parse::<reqwest::Url>()
+                            .wrap_err_with(|| format!("invalid value for {SCREENING_API_URL_VAR}"))
+                    })
+                    .transpose()?,
+                api_key: screening_api_key,
+                cache_ttl: screening_cache_secs
+                    .map_or(DEFAULT_SCREENING_CACHE_TTL, Duration::from_secs),
+            })
+        } else {
+            None
+        };
+        if scoring_hook_timeout_ms == Some(0) {
+            bail!("{SCORING_HOOK_TIMEOUT_MS_VAR} must be greater than 0");
+        }
+        let scoring_hook = scoring_hook_url
+            .map(|url| {
+                let url = url
+                    .parse::<reqwest::Url>()
+                    .wrap_err_with(|| format!("invalid value for {SCORING_HOOK_URL_VAR}"))?;
+                Ok::<_, eyre::Report>(ScoringHookConfig {
+                    url,
+                    timeout: scoring_hook_timeout_ms
+                        .map_or(DEFAULT_SCORING_HOOK_TIMEOUT, Duration::from_millis),
+                    fail_open: scoring_hook_fail_open.unwrap_or_default(),
+                })
+            })
+            .transpose()?;
+        let low_balance = low_balance_usd
+            .map(|thresholds| {
+                parse_token_usd_amounts(&thresholds, &constants)
+                    .wrap_err_with(|| format!("invalid value for {LOW_BALANCE_USD_VAR}"))
+            })
+            .transpose()?
+            .map(|thresholds| LowBalanceConfig {
+                thresholds: thresholds
+                    .into_iter()
+                    .map(|(chain_id, token, min_value)| LowBalanceThreshold {
+                        chain_id,
+                        token,
+                        min_value,
+                    })
+                    .collect(),
+                treasury: treasury_address,
+            });
+        if treasury_address.is_some() && low_balance.is_none() {
+            bail!("{TREASURY_ADDRESS_VAR} requires {LOW_BALANCE_USD_VAR}");
+        }
+        let position_limits = max_positions_usd
+            .map(|limits| {
+                parse_token_usd_amounts(&limits, &constants)
+                    .wrap_err_with(|| format!("invalid value for {MAX_POSITIONS_USD_VAR}"))
+            })
+            .transpose()?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(chain_id, token, max_value)| PositionLimit { chain_id, token, max_value })
+            .collect();
+        if bundle_file.is_some() && order_file.is_none() {
+            bail!("{ORDER_FILE_VAR} must be set when {BUNDLE_FILE_VAR} is set");
+        }
+        let filled_order_store = filled_order_store.map(PathBuf::from);
+        if leader_lease_secs == Some(0) {
+            bail!("{LEADER_LEASE_SECS_VAR} must be greater than 0");
+        }
+        if leader_lease_secs.is_some() && redis_url.is_none() {
+            bail!("{LEADER_LEASE_SECS_VAR} requires {REDIS_URL_VAR}");
+        }
+        let coordination = redis_url.map(|redis_url| CoordinationConfig {
+            redis_url,
+            key_prefix: redis_key_prefix.unwrap_or_else(|| DEFAULT_REDIS_KEY_PREFIX.to_string()),
+            leader_lease: leader_lease_secs.map(Duration::from_secs),
+        });
+        let order_backfill_blocks = order_backfill_blocks.unwrap_or(DEFAULT_ORDER_BACKFILL_BLOCKS);
+        if filled_orders_cache_size == Some(0) {
+            bail!("{FILLED_ORDERS_CACHE_SIZE_VAR} must be greater than 0");
+        }
+        let filled_orders_cache_size = filled_orders_cache_size
+            .map_or(DEFAULT_FILLED_ORDERS_CACHE_SIZE, |v| {
+                NonZeroUsize::new(v).expect("already checked non-zero")
+            });
+        let order_file = order_file.map(PathBuf::from);
+        let bundle_file = bundle_file.map(PathBuf::from);
+        let fill_throttle = FillThrottleConfig {
+            max_fills_per_minute: parse_fill_limit(max_fills_per_minute, MAX_FILLS_PER_MINUTE_VAR)?,
+            max_fills_per_hour: parse_fill_limit(max_fills_per_hour, MAX_FILLS_PER_HOUR_VAR)?,
+            max_notional_per_minute: parse_notional_limit(
+                max_notional_usd_per_minute,
+                MAX_NOTIONAL_USD_PER_MINUTE_VAR,
+            )?,
+            max_notional_per_hour: parse_notional_limit(
+                max_notional_usd_per_hour,
+                MAX_NOTIONAL_USD_PER_HOUR_VAR,
+            )?,
+            max_fills_per_owner_per_hour: parse_fill_limit(
+                max_fills_per_owner_per_hour,
+                MAX_FILLS_PER_OWNER_PER_HOUR_VAR,
+            )?,
+            max_notional_per_owner_per_hour: parse_notional_limit(
+                max_notional_usd_per_owner_per_hour,
+                MAX_NOTIONAL_USD_PER_OWNER_PER_HOUR_VAR,
+            )?,
+        };
+        let fill_throttle = (fill_throttle.max_fills_per_minute.is_some()
+            || fill_throttle.max_fills_per_hour.is_some()
+            || fill_throttle.max_notional_per_minute.is_some()
+            || fill_throttle.max_notional_per_hour.is_some()
+            || fill_throttle.max_fills_per_owner_per_hour.is_some()
+            || fill_throttle.max_notional_per_owner_per_hour.is_some())
+        .then_some(fill_throttle);
+        let max_fill_notional = parse_notional_limit(hot_max_fill_usd, HOT_MAX_FILL_USD_VAR)?;
+        let max_daily_notional = parse_notional_limit(hot_max_daily_usd, HOT_MAX_DAILY_USD_VAR)?;
+        let spend_policy = if max_fill_notional.is_none() && max_daily_notional.is_none() {
+            None
+        } else {
+            let Some(admin) = &admin else {
+                bail!("{ADMIN_PORT_VAR} must be set when a hot-key spend limit is set");
+            };
+            let Some(approver_token) = parse_token(approver_token, APPROVER_TOKEN_VAR)? else {
+                bail!("{APPROVER_TOKEN_VAR} must be set when a hot-key spend limit is set");
+            };
+            if approver_token == admin.token {
+                bail!("{APPROVER_TOKEN_VAR} must differ from {ADMIN_TOKEN_VAR}");
+            }
+            Some(SpendPolicyConfig { max_fill_notional, max_daily_notional, approver_token })
+        };
 
         Ok(Config {
             chain_name,
             host_rpc,
             ru_rpc,
             block_lead_duration,
+            min_submission_lead,
+            follow_up_leads,
+            adaptive_lead,
+            evaluation_interval,
             max_loss_percent,
-            healthcheck_port,
+            min_expected_profit,
+            min_margin_bps,
+            healthcheck_address,
+            metrics_address,
+            admin,
+            archive,
+            fill_throttle,
+            spend_policy,
+            bootstrap_mode,
+            scheduling_mode,
+            order_selection_policy,
+            aging_boost_percent,
+            quiet_periods,
+            cycle_budget,
+            reputation,
+            toxic_flow,
+            loss_limit,
+            trusted_owners,
+            denied_owners,
+            skip_signature_check,
+            submission_retry,
+            inventory,
+            token_table,
+            approval_policy,
+            twap,
+            price_api,
+            price_cross_check_bps,
+            volatility_haircuts,
+            token_filter,
+            quote_cache_ttl,
+            unprofitable_max_backoff_slots,
+            fee_oracle,
+            bridge,
+            token_discovery,
+            simulation_min_notional,
+            min_order_notional,
+            max_order_notional,
+            fill_webhook_url,
+            screening,
+            scoring_hook,
+            low_balance,
+            position_limits,
+            filled_order_store,
+            coordination,
+            order_backfill_blocks,
+            filled_orders_cache_size,
+            order_file,
+            bundle_file,
             target_blocks,
+            fill_deadline_offset,
             max_orders_per_bundle,
             signer,
             constants,
@@ -230,6 +3613,290 @@ impl Config {
     }
 }
 
+/// Parses the chain constants for `chain_name`.
+fn parse_constants(chain_name: &str) -> Result<SignetConstants> {
+    chain_name.parse().wrap_err_with(|| format!("invalid value for {CHAIN_NAME_VAR}"))
+}
+
+/// Parses the optional rollup RPC URL, which must be a websocket URL.
+fn parse_ru_rpc(ru_rpc: Option<String>) -> Result<PubSubConfig> {
+    PubSubConfig::try_from(
+        ru_rpc
+            .unwrap_or(DEFAULT_RU_RPC.to_string())
+            .parse::<BuiltInConnectionString>()
+            .wrap_err_with(|| format!("failed to parse {RU_RPC_VAR}"))?,
+    )
+    .wrap_err_with(|| format!("{RU_RPC_VAR} must start with ws:// or wss://"))
+}
+
+/// Parses an optional IP address env var, falling back to `default` when unset.
+fn parse_host(host: Option<String>, default: IpAddr, var: &str) -> Result<IpAddr> {
+    host.map(|host| host.parse().wrap_err_with(|| format!("failed to parse {var}")))
+        .transpose()
+        .map(|host| host.unwrap_or(default))
+}
+
+/// Validates an optional per-cycle count limit, which must be non-zero when set.
+fn parse_cycle_limit(limit: Option<usize>, var: &str) -> Result<Option<NonZeroUsize>> {
+    match limit {
+        Some(0) => bail!("{var} must be greater than 0"),
+        limit => Ok(limit.and_then(NonZeroUsize::new)),
+    }
+}
+
+/// Validates an optional fill-count limit, which must be non-zero when set.
+fn parse_fill_limit(limit: Option<u32>, var: &str) -> Result<Option<NonZeroU32>> {
+    match limit {
+        Some(0) => bail!("{var} must be greater than 0"),
+        limit => Ok(limit.and_then(NonZeroU32::new)),
+    }
+}
+
+/// The number of orders of `gas_per_order` that fit in a bundle with a gas `budget`, after the
+/// bundle's fixed `gas_per_bundle` overhead. `None` if not even one fits.
+///
+/// Every order is estimated at the same gas, so the profit-maximizing set of orders under the
+/// budget is simply the most profitable orders that fit.
+fn orders_within_gas_budget(
+    budget: u64,
+    gas_per_order: u64,
+    gas_per_bundle: Option<u64>,
+) -> Option<NonZeroUsize> {
+    let available = budget.checked_sub(gas_per_bundle.unwrap_or_default())?;
+    NonZeroUsize::new(usize::try_from(available / gas_per_order).unwrap_or(usize::MAX))
+}
+
+/// Validates an optional whole-USD notional limit, which must be non-zero when set, and converts
+/// it to normalized 18-decimal USD.
+fn parse_notional_limit(limit_usd: Option<u64>, var: &str) -> Result<Option<U256>> {
+    match limit_usd {
+        Some(0) => bail!("{var} must be greater than 0"),
+        limit_usd => {
+            Ok(limit_usd.map(|usd| U256::from(usd) * U256::from(10_u64).pow(U256::from(18))))
+        }
+    }
+}
+
+/// Parses comma-separated `asset:percent` target allocations. Unlisted assets target 0%, and the
+/// listed percentages must sum to 100.
+fn parse_inventory_targets(value: &str) -> Result<[u8; InventoryAsset::ALL.len()]> {
+    let mut targets = [None; InventoryAsset::ALL.len()];
+    for entry in value.split(',').map(str::trim) {
+        let Some((asset, percent)) = entry.split_once(':') else {
+            bail!("expected asset:percent (got {entry})");
+        };
+        let asset: InventoryAsset = asset.trim().parse()?;
+        let percent: u8 = percent
+            .trim()
+            .parse()
+            .wrap_err_with(|| format!("invalid percentage for {}", asset.as_str()))?;
+        if targets[asset as usize].replace(percent).is_some() {
+            bail!("duplicate target for {}", asset.as_str());
+        }
+    }
+    let targets = targets.map(|target| target.unwrap_or(0));
+    let total: u32 = targets.iter().copied().map(u32::from).sum();
+    if total != 100 {
+        bail!("target percentages must sum to 100 (got {total})");
+    }
+    Ok(targets)
+}
+
+/// Reads screened addresses from the file at `path`, one per line. Blank lines and lines starting
+/// with `#` are ignored.
+fn read_screening_list(path: &Path) -> Result<HashSet<Address>> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.parse().wrap_err_with(|| format!("invalid address {line}")))
+        .collect()
+}
+
+/// Reads the token table from the JSON file at `path`. Each token must be on the host or rollup
+/// chain, have at most 18 decimals and a non-zero price, and appear only once.
+fn read_token_table(path: &Path, constants: &SignetConstants) -> Result<Vec<TokenMetadata>> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+    let table: Vec<TokenMetadata> = serde_json::from_str(&contents)
+        .wrap_err_with(|| format!("failed to parse {}", path.display()))?;
+    let chain_ids = [constants.system().host_chain_id(), constants.system().ru_chain_id()];
+    for token in &table {
+        if !chain_ids.contains(&token.chain_id) {
+            bail!(
+                "token {} is on chain {}, expected one of {chain_ids:?}",
+                token.address,
+                token.chain_id
+            );
+        }
+        if token.decimals > 18 {
+            bail!("token {} must have at most 18 decimals (got {})", token.address, token.decimals);
+        }
+        if token.price_usd == 0 {
+            bail!("token {} must have a price greater than 0", token.address);
+        }
+    }
+    if let Some(duplicate) = table.iter().map(|token| token.address).duplicates().next() {
+        bail!("token {duplicate} is listed more than once");
+    }
+    Ok(table)
+}
+
+/// Parses comma-separated follow-up submission leads in milliseconds, each strictly between
+/// `min_submission_lead` and `block_lead`, into leads sorted largest first, the order in which the
+/// attempts run.
+fn parse_follow_up_leads(
+    value: &str,
+    block_lead: Duration,
+    min_submission_lead: Duration,
+) -> Result<Vec<Duration>> {
+    let mut leads = Vec::new();
+    for lead_ms in value.split(',').map(str::trim) {
+        let lead = lead_ms
+            .parse()
+            .map(Duration::from_millis)
+            .wrap_err_with(|| format!("invalid lead {lead_ms}"))?;
+        if lead >= block_lead || lead <= min_submission_lead {
+            bail!(
+                "lead {lead_ms}ms must be less than the block lead duration ({}ms) and more than \
+                 the minimum submission lead ({}ms)",
+                block_lead.as_millis(),
+                min_submission_lead.as_millis()
+            );
+        }
+        leads.push(lead);
+    }
+    leads.sort_unstable_by(|a, b| b.cmp(a));
+    leads.dedup();
+    Ok(leads)
+}
+
+/// Parses comma-separated addresses, ignoring duplicates.
+fn parse_addresses(value: &str) -> Result<Vec<Address>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .map(|address| address.parse().wrap_err_with(|| format!("invalid address {address}")))
+        .process_results(|addresses| addresses.unique().collect())
+}
+
+/// Parses comma-separated `token:bps` volatility haircut entries.
+fn parse_volatility_haircuts(value: &str) -> Result<HashMap<Address, u16>> {
+    let mut haircuts = HashMap::new();
+    for entry in value.split(',').map(str::trim) {
+        let Some((token, bps)) = entry.split_once(':') else {
+            bail!("expected <token>:<bps> (got {entry})");
+        };
+        let token: Address =
+            token.trim().parse().wrap_err_with(|| format!("invalid token address {token}"))?;
+        let bps: u16 =
+            bps.trim().parse().wrap_err_with(|| format!("invalid haircut for {token}"))?;
+        if bps > MAX_BPS {
+            bail!("haircut for {token} must be between 0 and {MAX_BPS} (got {bps})");
+        }
+        if haircuts.insert(token, bps).is_some() {
+            bail!("duplicate haircut for {token}");
+        }
+    }
+    Ok(haircuts)
+}
+
+/// Parses comma-separated `pool[:window_secs]` entries, using `default_window` where no window is
+/// given.
+fn parse_twap_pools(value: &str, default_window: Duration) -> Result<Vec<TwapPool>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .map(|entry| {
+            let (address, window) = match entry.split_once(':') {
+                Some((address, window_secs)) => {
+                    let window_secs: u64 = window_secs
+                        .trim()
+                        .parse()
+                        .wrap_err_with(|| format!("invalid window for pool {address}"))?;
+                    if window_secs == 0 {
+                        bail!("window for pool {address} must be greater than 0");
+                    }
+                    (address, Duration::from_secs(window_secs))
+                }
+                None => (entry, default_window),
+            };
+            let address = address
+                .trim()
+                .parse()
+                .wrap_err_with(|| format!("invalid pool address {address}"))?;
+            Ok(TwapPool { address, window })
+        })
+        .collect()
+}
+
+/// Parses comma-separated `chain_id:token:usd` entries, such as low-balance thresholds, converting
+/// each whole-USD amount to normalized 18-decimal USD.
+fn parse_token_usd_amounts(
+    value: &str,
+    constants: &SignetConstants,
+) -> Result<Vec<(u64, Address, U256)>> {
+    let chain_ids = [constants.system().host_chain_id(), constants.system().ru_chain_id()];
+    let mut amounts: Vec<(u64, Address, U256)> = Vec::new();
+    for entry in value.split(',').map(str::trim) {
+        let [chain_id, token, usd] = entry.split(':').map(str::trim).collect::<Vec<_>>()[..] else {
+            bail!("expected <chain_id>:<token>:<usd> (got {entry})");
+        };
+        let chain_id: u64 =
+            chain_id.parse().wrap_err_with(|| format!("invalid chain ID {chain_id}"))?;
+        if !chain_ids.contains(&chain_id) {
+            bail!("chain {chain_id} must be one of {chain_ids:?}");
+        }
+        let token: Address =
+            token.parse().wrap_err_with(|| format!("invalid token address {token}"))?;
+        let usd: u64 = usd.parse().wrap_err_with(|| format!("invalid USD amount for {token}"))?;
+        if usd == 0 {
+            bail!("amount for {token} must be greater than 0");
+        }
+        if amounts.iter().any(|(other_chain_id, other_token, _)| {
+            *other_chain_id == chain_id && *other_token == token
+        }) {
+            bail!("duplicate entry for {token} on chain {chain_id}");
+        }
+        amounts.push((chain_id, token, U256::from(usd) * U256::from(10_u64).pow(U256::from(18))));
+    }
+    Ok(amounts)
+}
+
+/// Parses a UTC `HH:MM` time of day into minutes after midnight.
+fn parse_time_of_day(value: &str) -> Result<u16> {
+    let Some((hours, minutes)) = value.split_once(':') else {
+        bail!("expected HH:MM (got {value})");
+    };
+    let hours: u16 = hours.parse().wrap_err_with(|| format!("invalid hour in {value}"))?;
+    let minutes: u16 = minutes.parse().wrap_err_with(|| format!("invalid minute in {value}"))?;
+    if hours > 23 || minutes > 59 {
+        bail!("time of day out of range (got {value})");
+    }
+    Ok(hours * 60 + minutes)
+}
+
+/// Validates an optional bearer token, which must not be empty or whitespace: an empty token would
+/// authenticate a request with a bare `Authorization: Bearer ` header.
+fn parse_token(token: Option<String>, var: &str) -> Result<Option<String>> {
+    if token.as_deref().is_some_and(|token| token.trim().is_empty()) {
+        bail!("{var} must not be empty");
+    }
+    Ok(token)
+}
+
+/// Validates an optional basis-point value, which must not exceed 100%.
+fn parse_bps(bps: Option<u16>, default: u16, var: &str) -> Result<u16> {
+    match bps.unwrap_or(default) {
+        bps if bps > MAX_BPS => {
+            bail!("{var} must be between 0 and {MAX_BPS} inclusive (got {bps})")
+        }
+        bps => Ok(bps),
+    }
+}
+
 /// Get a list of the env vars used to configure the app.
 pub fn env_var_info() -> String {
     let inventory = ConfigInner::inventory();
@@ -253,3 +3920,76 @@ pub fn config_from_env() -> Result<Config> {
     Config::from_env()
         .wrap_err("failed to configure filler (run with '--help' to see all required env vars)")
 }
+
+/// Load the `decode` subcommand configuration from environment variables.
+pub fn decode_config_from_env() -> Result<DecodeConfig> {
+    let DecodeConfigInner { chain_name, ru_rpc } =
+        DecodeConfigInner::from_env().wrap_err("failed to load decode configuration")?;
+    Ok(DecodeConfig {
+        constants: parse_constants(chain_name.as_deref().unwrap_or(DEFAULT_CHAIN_NAME))?,
+        ru_rpc: parse_ru_rpc(ru_rpc)?,
+    })
+}
+
+/// Load the offline `replay` and `backtest` subcommand configuration from environment variables.
+pub fn offline_config_from_env() -> Result<OfflineConfig> {
+    let OfflineConfigInner {
+        chain_name,
+        max_loss_percent,
+        token_table,
+        volatility_haircuts,
+        min_margin_bps,
+    } = OfflineConfigInner::from_env().wrap_err("failed to load offline configuration")?;
+    let chain_name = chain_name.unwrap_or(DEFAULT_CHAIN_NAME.to_string());
+    let constants = parse_constants(&chain_name)?;
+    let max_loss_percent = max_loss_percent.unwrap_or(DEFAULT_MAX_LOSS_PERCENT);
+    if max_loss_percent > 100 {
+        bail!(
+            "{MAX_LOSS_PERCENT_VAR} must be between 0 and 100 inclusive (got {max_loss_percent})"
+        );
+    }
+    let token_table = token_table
+        .map(|path| {
+            read_token_table(Path::new(&path), &constants)
+                .wrap_err_with(|| format!("invalid value for {TOKEN_TABLE_VAR}"))
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let volatility_haircuts = volatility_haircuts
+        .as_deref()
+        .map(parse_volatility_haircuts)
+        .transpose()
+        .wrap_err_with(|| format!("invalid value for {VOLATILITY_HAIRCUTS_VAR}"))?
+        .unwrap_or_default();
+    if let Some(bps) = min_margin_bps.filter(|bps| *bps > 10_000) {
+        bail!("{MIN_MARGIN_BPS_VAR} must be between 0 and 10000 (got {bps})");
+    }
+    Ok(OfflineConfig {
+        chain_name,
+        constants,
+        max_loss_percent,
+        token_table,
+        volatility_haircuts,
+        min_margin_bps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_tokens_are_rejected() {
+        assert_eq!(parse_token(None, ADMIN_TOKEN_VAR).unwrap(), None);
+        assert_eq!(
+            parse_token(Some("secret".to_string()), ADMIN_TOKEN_VAR).unwrap().as_deref(),
+            Some("secret")
+        );
+        for token in ["", "  \t"] {
+            let error = parse_token(Some(token.to_string()), ADMIN_TOKEN_VAR).unwrap_err();
+            assert_eq!(error.to_string(), format!("{ADMIN_TOKEN_VAR} must not be empty"));
+        }
+        let error = parse_token(Some(" ".to_string()), APPROVER_TOKEN_VAR).unwrap_err();
+        assert_eq!(error.to_string(), format!("{APPROVER_TOKEN_VAR} must not be empty"));
+    }
+}
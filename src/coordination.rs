@@ -0,0 +1,218 @@
+use crate::CoordinationConfig;
+use alloy::primitives::{Address, B256};
+use core::fmt::{self, Debug, Formatter};
+use eyre::{Result, WrapErr, bail};
+use redis::{Script, aio::ConnectionManager};
+use std::{
+    sync::{Arc, LazyLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// How long an order stays in the shared filled set. Long past any order's deadline, so a filled
+/// order is never attempted again while it can still appear in the transaction cache.
+const FILLED_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Claims an in-flight order for this replica, unless it is already filled or another replica
+/// holds the claim. A replica re-claiming its own order extends the claim.
+///
+/// KEYS[1] is the in-flight key and KEYS[2] the filled key; ARGV[1] is the replica's instance ID
+/// and ARGV[2] the claim's lifetime in milliseconds.
+static CLAIM_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('EXISTS', KEYS[2]) == 1 then return 2 end
+        local owner = redis.call('GET', KEYS[1])
+        if owner and owner ~= ARGV[1] then return 0 end
+        redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+        return 1
+        ",
+    )
+});
+
+/// Takes or renews the leader lease for this replica, unless another replica holds it. Returns 1
+/// if this replica now holds the lease.
+///
+/// KEYS[1] is the leader key; ARGV[1] is the replica's instance ID and ARGV[2] the lease's
+/// lifetime in milliseconds.
+static ACQUIRE_LEASE_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local owner = redis.call('GET', KEYS[1])
+        if owner and owner ~= ARGV[1] then return 0 end
+        redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+        return 1
+        ",
+    )
+});
+
+/// Releases the leader lease or an order claim if this replica holds it.
+///
+/// KEYS[1] is the leader or in-flight key; ARGV[1] is the replica's instance ID.
+static RELEASE_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('DEL', KEYS[1]) end
+        return 0
+        ",
+    )
+});
+
+/// The outcome of claiming an order in the shared in-flight set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Claim {
+    /// This replica holds the claim and may submit a fill.
+    Claimed,
+    /// Another replica holds the claim.
+    HeldByPeer,
+    /// A replica has already seen the order filled.
+    Filled,
+}
+
+impl Claim {
+    fn from_code(code: i64) -> Result<Self> {
+        match code {
+            0 => Ok(Self::HeldByPeer),
+            1 => Ok(Self::Claimed),
+            2 => Ok(Self::Filled),
+            _ => bail!("unexpected claim result {code}"),
+        }
+    }
+}
+
+/// A shared handle to the Redis through which filler replicas coordinate, identifying this
+/// replica by an instance ID unique to the process.
+///
+/// Replicas share a set of filled orders, and a set of in-flight orders each claimed by the
+/// replica about to submit a fill for it. Claims expire when the fill's target window has passed,
+/// so a replica that stops submitting frees its orders for the others, and are released early when
+/// the replica drops the fill before submitting it.
+#[derive(Clone)]
+pub(crate) struct Coordination {
+    redis: ConnectionManager,
+    key_prefix: Arc<str>,
+    instance_id: Arc<str>,
+}
+
+impl Debug for Coordination {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Coordination")
+            .field("key_prefix", &self.key_prefix)
+            .field("instance_id", &self.instance_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Coordination {
+    /// Connects to the shared Redis.
+    pub(crate) async fn connect(
+        config: &CoordinationConfig,
+        filler_address: Address,
+    ) -> Result<Self> {
+        let client = redis::Client::open(config.redis_url()).wrap_err("invalid Redis URL")?;
+        let redis = client.get_connection_manager().await.wrap_err("failed to connect to Redis")?;
+        let started_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock set before UNIX epoch")
+            .as_millis();
+        let instance_id = format!("{filler_address}:{}:{started_at_ms}", std::process::id());
+        Ok(Self { redis, key_prefix: config.key_prefix().into(), instance_id: instance_id.into() })
+    }
+
+    /// This replica's instance ID.
+    pub(crate) fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn leader_key(&self) -> String {
+        format!("{}:leader", self.key_prefix)
+    }
+
+    fn key(&self, kind: &str, order_hash: B256) -> String {
+        format!("{}:{kind}:{order_hash}", self.key_prefix)
+    }
+
+    /// Claims `order_hash` for this replica for `ttl`.
+    pub(crate) async fn claim_order(&self, order_hash: B256, ttl: Duration) -> Result<Claim> {
+        let code: i64 = CLAIM_SCRIPT
+            .key(self.key("inflight", order_hash))
+            .key(self.key("filled", order_hash))
+            .arg(&*self.instance_id)
+            .arg(ttl.as_millis().max(1) as u64)
+            .invoke_async(&mut self.redis.clone())
+            .await
+            .wrap_err("failed to claim order")?;
+        Claim::from_code(code)
+    }
+
+    /// Releases this replica's claim on `order_hash`, if it holds one, so other replicas can fill
+    /// the order without waiting for the claim to expire.
+    pub(crate) async fn release_order(&self, order_hash: B256) -> Result<()> {
+        RELEASE_SCRIPT
+            .key(self.key("inflight", order_hash))
+            .arg(&*self.instance_id)
+            .invoke_async::<i64>(&mut self.redis.clone())
+            .await
+            .wrap_err("failed to release order claim")?;
+        Ok(())
+    }
+
+    /// Takes the leader lease for `lease`, or renews it if this replica already holds it. Returns
+    /// whether this replica holds the lease.
+    pub(crate) async fn acquire_lease(&self, lease: Duration) -> Result<bool> {
+        let acquired: i64 = ACQUIRE_LEASE_SCRIPT
+            .key(self.leader_key())
+            .arg(&*self.instance_id)
+            .arg(lease.as_millis() as u64)
+            .invoke_async(&mut self.redis.clone())
+            .await
+            .wrap_err("failed to acquire leader lease")?;
+        Ok(acquired == 1)
+    }
+
+    /// Releases the leader lease if this replica holds it, so a standby can take over without
+    /// waiting for it to lapse.
+    pub(crate) async fn release_lease(&self) -> Result<()> {
+        RELEASE_SCRIPT
+            .key(self.leader_key())
+            .arg(&*self.instance_id)
+            .invoke_async::<i64>(&mut self.redis.clone())
+            .await
+            .wrap_err("failed to release leader lease")?;
+        Ok(())
+    }
+
+    /// Adds `order_hash` to the shared filled set.
+    pub(crate) async fn mark_filled(&self, order_hash: B256) -> Result<()> {
+        redis::cmd("SET")
+            .arg(self.key("filled", order_hash))
+            .arg(1)
+            .arg("EX")
+            .arg(FILLED_TTL.as_secs())
+            .query_async::<()>(&mut self.redis.clone())
+            .await
+            .wrap_err("failed to mark order filled")
+    }
+
+    /// Removes `order_hash` from the shared filled set, after a reorg undid its fill.
+    pub(crate) async fn forget_filled(&self, order_hash: B256) -> Result<()> {
+        redis::cmd("DEL")
+            .arg(self.key("filled", order_hash))
+            .query_async::<()>(&mut self.redis.clone())
+            .await
+            .wrap_err("failed to forget filled order")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_claim_results() {
+        assert_eq!(Claim::from_code(0).unwrap(), Claim::HeldByPeer);
+        assert_eq!(Claim::from_code(1).unwrap(), Claim::Claimed);
+        assert_eq!(Claim::from_code(2).unwrap(), Claim::Filled);
+        assert!(Claim::from_code(3).is_err());
+    }
+}
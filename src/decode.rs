@@ -0,0 +1,279 @@
+use crate::{ChainTokenPair, DecodeConfig, KnownToken};
+use alloy::{
+    hex,
+    primitives::{Address, Signature, U256, utils::format_units},
+    sol_types::{Eip712Domain, SolStruct, SolValue},
+};
+use core::fmt::{self, Display, Formatter};
+use eyre::{Result, WrapErr, bail};
+use signet_constants::SignetSystemConstants;
+use signet_orders::permit2::{PERMIT2, is_order_nonce_consumed};
+use signet_types::SignedOrder;
+use signet_zenith::RollupOrders::{Output, Permit2Batch, PermitBatchWitnessTransferFrom};
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const PERMIT2_CONTRACT_NAME: &str = "Permit2";
+
+/// On-chain fill status of an order's Permit2 nonce.
+#[derive(Debug)]
+enum FillStatus {
+    Filled,
+    Unfilled,
+    Unknown(String),
+}
+
+/// Result of checking the order's Permit2 signature against its owner.
+#[derive(Debug)]
+pub(crate) enum SignatureCheck {
+    Valid,
+    Mismatch(Address),
+    Invalid(String),
+}
+
+/// Decode a `SignedOrder` from `input` (JSON, or hex-encoded ABI `(Permit2Batch, Output[])`),
+/// validate it, look up its fill status, and print a human-readable report to stdout.
+pub async fn decode_order(config: &DecodeConfig, input: &str) -> Result<()> {
+    let order = parse_order(input)?;
+    let constants = config.constants().system();
+    ChainTokenPair::init_token_names(constants);
+
+    let fill_status = match config.ru_rpc().connect().await {
+        Ok(provider) => match is_order_nonce_consumed(&provider, &order).await {
+            Ok(true) => FillStatus::Filled,
+            Ok(false) => FillStatus::Unfilled,
+            Err(error) => FillStatus::Unknown(format!("{:#}", eyre::Report::from(error))),
+        },
+        Err(error) => FillStatus::Unknown(format!("failed to connect to rollup RPC: {error}")),
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock set before UNIX epoch")
+        .as_secs();
+
+    println!("{}", OrderReport::new(&order, constants, now, fill_status));
+    Ok(())
+}
+
+/// Parses a `SignedOrder` from its JSON representation (as served by the transaction cache), or
+/// from hex-encoded ABI `(Permit2Batch, Output[])` with an optional `0x` prefix.
+fn parse_order(input: &str) -> Result<SignedOrder> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("no order provided");
+    }
+    if input.starts_with('{') {
+        return serde_json::from_str(input).wrap_err("failed to parse order JSON");
+    }
+    let bytes = hex::decode(input).wrap_err("order is neither JSON nor valid hex")?;
+    let (permit, outputs) = <(Permit2Batch, Vec<Output>)>::abi_decode(&bytes)
+        .wrap_err("failed to ABI-decode order as (Permit2Batch, Output[])")?;
+    Ok(SignedOrder::new(permit, outputs))
+}
+
+/// Recovers the address that signed the order's Permit2 batch witness transfer, reconstructing
+/// the EIP-712 hash the same way the order was signed.
+pub(crate) fn check_signature(
+    order: &SignedOrder,
+    constants: &SignetSystemConstants,
+) -> SignatureCheck {
+    let permit = &order.permit().permit;
+    let witness = PermitBatchWitnessTransferFrom {
+        permitted: permit.permitted.clone(),
+        spender: constants.ru_orders(),
+        nonce: permit.nonce,
+        deadline: permit.deadline,
+        outputs: order.outputs().to_vec(),
+    };
+    let domain = Eip712Domain {
+        name: Some(PERMIT2_CONTRACT_NAME.into()),
+        version: None,
+        chain_id: Some(constants.ru_chain_id_u256()),
+        verifying_contract: Some(PERMIT2),
+        salt: None,
+    };
+    let signing_hash = witness.eip712_signing_hash(&domain);
+    let recovered = Signature::try_from(order.permit().signature.as_ref())
+        .and_then(|signature| signature.recover_address_from_prehash(&signing_hash));
+    match recovered {
+        Ok(signer) if signer == order.permit().owner => SignatureCheck::Valid,
+        Ok(signer) => SignatureCheck::Mismatch(signer),
+        Err(error) => SignatureCheck::Invalid(error.to_string()),
+    }
+}
+
+/// Human-readable report for a decoded order.
+struct OrderReport<'a> {
+    order: &'a SignedOrder,
+    known_tokens: HashMap<ChainTokenPair, KnownToken>,
+    ru_chain_id: u64,
+    signature: SignatureCheck,
+    now: u64,
+    fill_status: FillStatus,
+}
+
+impl<'a> OrderReport<'a> {
+    fn new(
+        order: &'a SignedOrder,
+        constants: &SignetSystemConstants,
+        now: u64,
+        fill_status: FillStatus,
+    ) -> Self {
+        let known_tokens =
+            KnownToken::ALL.iter().map(|known| (known.resolve(constants), *known)).collect();
+        Self {
+            order,
+            known_tokens,
+            ru_chain_id: constants.ru_chain_id(),
+            signature: check_signature(order, constants),
+            now,
+            fill_status,
+        }
+    }
+
+    /// Formats `amount` of `token` on `chain_id` using known decimals, falling back to the raw
+    /// amount for unknown tokens.
+    fn format_amount(&self, chain_id: u64, token: Address, amount: U256) -> String {
+        let pair = ChainTokenPair::new(chain_id, token);
+        match self.known_tokens.get(&pair) {
+            Some(known) => {
+                let decimals = known.decimals();
+                let formatted =
+                    format_units(amount, decimals).unwrap_or_else(|_| amount.to_string());
+                format!("{formatted} {pair} ({decimals} decimals, raw {amount})")
+            }
+            None => format!("{amount} {pair} (unknown token, raw amount)"),
+        }
+    }
+}
+
+impl Display for OrderReport<'_> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let permit = self.order.permit();
+        writeln!(formatter, "order hash:   {}", self.order.order_hash())?;
+        writeln!(formatter, "owner:        {}", permit.owner)?;
+        match &self.signature {
+            SignatureCheck::Valid => writeln!(formatter, "signature:    valid")?,
+            SignatureCheck::Mismatch(signer) => {
+                writeln!(formatter, "signature:    INVALID (recovered signer {signer})")?
+            }
+            SignatureCheck::Invalid(error) => {
+                writeln!(formatter, "signature:    INVALID ({error})")?
+            }
+        }
+
+        let nonce = permit.permit.nonce;
+        writeln!(
+            formatter,
+            "nonce:        {nonce} (word {}, bit {})",
+            nonce >> 8,
+            nonce & U256::from(0xFF)
+        )?;
+
+        let deadline = permit.permit.deadline;
+        match self.order.validate(self.now) {
+            Ok(()) => writeln!(
+                formatter,
+                "deadline:     {deadline} (valid for {}s)",
+                deadline.saturating_to::<u64>().saturating_sub(self.now)
+            )?,
+            Err(_) => writeln!(
+                formatter,
+                "deadline:     {deadline} (EXPIRED {}s ago)",
+                self.now.saturating_sub(deadline.saturating_to())
+            )?,
+        }
+
+        writeln!(formatter, "inputs:")?;
+        for input in &permit.permit.permitted {
+            writeln!(
+                formatter,
+                "  {}",
+                self.format_amount(self.ru_chain_id, input.token, input.amount)
+            )?;
+        }
+        writeln!(formatter, "outputs:")?;
+        for output in self.order.outputs() {
+            writeln!(
+                formatter,
+                "  {} -> {}",
+                self.format_amount(u64::from(output.chainId), output.token, output.amount),
+                output.recipient
+            )?;
+        }
+
+        match &self.fill_status {
+            FillStatus::Filled => write!(formatter, "fill status:  filled"),
+            FillStatus::Unfilled => write!(formatter, "fill status:  unfilled"),
+            FillStatus::Unknown(error) => write!(formatter, "fill status:  unknown ({error})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use signet_constants::SignetConstants;
+    use signet_types::UnsignedOrder;
+
+    fn constants() -> SignetSystemConstants {
+        "parmigiana".parse::<SignetConstants>().unwrap().system().clone()
+    }
+
+    async fn signed_order(constants: &SignetSystemConstants) -> SignedOrder {
+        UnsignedOrder::new()
+            .with_input(constants.rollup().tokens().weth(), U256::from(10).pow(U256::from(18)))
+            .with_output(
+                constants.host().tokens().usdc(),
+                U256::from(3_000_000_000_u64),
+                Address::repeat_byte(0x11),
+                constants.host_chain_id() as u32,
+            )
+            .with_deadline(1_000)
+            .with_nonce(0x12A)
+            .with_chain(constants)
+            .sign(&PrivateKeySigner::random())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn parses_json_and_hex() {
+        let order = signed_order(&constants()).await;
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(parse_order(&json).unwrap(), order);
+
+        let (permit, outputs) = order.clone().into_parts();
+        let encoded = hex::encode_prefixed((permit, outputs).abi_encode());
+        assert_eq!(parse_order(&encoded).unwrap(), order);
+
+        assert!(parse_order("not an order").is_err());
+    }
+
+    #[tokio::test]
+    async fn detects_valid_and_tampered_signatures() {
+        let constants = constants();
+        let order = signed_order(&constants).await;
+        assert!(matches!(check_signature(&order, &constants), SignatureCheck::Valid));
+
+        let (mut permit, outputs) = order.into_parts();
+        permit.permit.nonce += U256::from(1);
+        let tampered = SignedOrder::new(permit, outputs);
+        assert!(matches!(check_signature(&tampered, &constants), SignatureCheck::Mismatch(_)));
+    }
+
+    #[tokio::test]
+    async fn report_shows_nonce_position_amounts_and_expiry() {
+        let constants = constants();
+        let order = signed_order(&constants).await;
+        let report = OrderReport::new(&order, &constants, 1_030, FillStatus::Unfilled).to_string();
+        assert!(report.contains("nonce:        298 (word 1, bit 42)"));
+        assert!(report.contains("EXPIRED 30s ago"));
+        assert!(report.contains("1.000000000000000000"));
+        assert!(report.contains("3000.000000"));
+        assert!(report.contains("fill status:  unfilled"));
+    }
+}
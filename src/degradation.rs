@@ -0,0 +1,123 @@
+use crate::metrics;
+use init4_bin_base::deps::tracing::{info, warn};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+use tokio::time::Instant;
+
+/// A dependency whose outage the filler works around with a fallback instead of failing cycles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Dependency {
+    /// The spot price API. While its prices are stale, tokens are priced from their TWAP or fixed
+    /// rates, and only orders at break-even or better are filled.
+    PriceApi,
+    /// The transaction cache (or other order source). While it can't be fetched from, the last
+    /// fetched orders are evaluated in a dry run, without submitting.
+    OrderSource,
+}
+
+impl Dependency {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            Self::PriceApi => "price-api",
+            Self::OrderSource => "order-source",
+        }
+    }
+
+    /// The fallback in effect while the dependency is degraded.
+    const fn fallback(&self) -> &'static str {
+        match self {
+            Self::PriceApi => "local-prices-break-even",
+            Self::OrderSource => "snapshot-dry-run",
+        }
+    }
+}
+
+/// A degraded dependency, as served by the admin API.
+#[derive(Debug, Serialize)]
+pub(crate) struct DegradedDependency {
+    dependency: Dependency,
+    fallback: &'static str,
+    degraded_for_seconds: u64,
+}
+
+/// Which dependencies the filler is currently degraded around, and since when. Each dependency
+/// is reported healthy or degraded by whichever task uses it; transitions are logged and
+/// exported as the `signet.filler.degraded` gauge, and the current state is served by the admin
+/// API's `/status`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DegradationLadder {
+    degraded_since: Arc<Mutex<BTreeMap<Dependency, Instant>>>,
+}
+
+impl DegradationLadder {
+    /// Record whether `dependency` is currently degraded.
+    pub(crate) fn set(&self, dependency: Dependency, degraded: bool) {
+        let mut degraded_since = self.degraded_since.lock().unwrap();
+        match (degraded, degraded_since.get(&dependency).copied()) {
+            (true, None) => {
+                degraded_since.insert(dependency, Instant::now());
+                warn!(
+                    dependency = dependency.as_str(),
+                    fallback = dependency.fallback(),
+                    "dependency degraded, falling back"
+                );
+            }
+            (false, Some(since)) => {
+                degraded_since.remove(&dependency);
+                info!(
+                    dependency = dependency.as_str(),
+                    degraded_for_secs = since.elapsed().as_secs(),
+                    "dependency recovered"
+                );
+            }
+            _ => {}
+        }
+        metrics::record_degraded(dependency, degraded);
+    }
+
+    /// Whether `dependency` is currently degraded.
+    pub(crate) fn is_degraded(&self, dependency: Dependency) -> bool {
+        self.degraded_since.lock().unwrap().contains_key(&dependency)
+    }
+
+    /// Every currently degraded dependency.
+    pub(crate) fn snapshot(&self) -> Vec<DegradedDependency> {
+        self.degraded_since
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(dependency, since)| DegradedDependency {
+                dependency: *dependency,
+                fallback: dependency.fallback(),
+                degraded_for_seconds: since.elapsed().as_secs(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_degraded_dependencies() {
+        let ladder = DegradationLadder::default();
+        ladder.set(Dependency::PriceApi, false);
+        assert!(ladder.snapshot().is_empty());
+
+        ladder.set(Dependency::OrderSource, true);
+        ladder.set(Dependency::PriceApi, true);
+        assert!(ladder.is_degraded(Dependency::OrderSource));
+        let degraded: Vec<_> =
+            ladder.snapshot().into_iter().map(|degraded| degraded.dependency).collect();
+        assert_eq!(degraded, [Dependency::PriceApi, Dependency::OrderSource]);
+
+        ladder.set(Dependency::OrderSource, false);
+        assert!(!ladder.is_degraded(Dependency::OrderSource));
+        assert!(ladder.is_degraded(Dependency::PriceApi));
+    }
+}
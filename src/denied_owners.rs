@@ -0,0 +1,63 @@
+use alloy::primitives::Address;
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+/// Order owners whose orders are never filled, seeded from configuration and shared between the
+/// filler loop, which skips their orders before pricing, and the admin API, which can deny or
+/// allow an owner at runtime so a misbehaving counterparty can be blocked without a restart.
+/// Changes made at runtime are held in memory and don't survive a restart.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeniedOwners {
+    owners: Arc<Mutex<BTreeSet<Address>>>,
+}
+
+impl DeniedOwners {
+    pub(crate) fn new(owners: impl IntoIterator<Item = Address>) -> Self {
+        Self { owners: Arc::new(Mutex::new(owners.into_iter().collect())) }
+    }
+
+    /// Whether the owner's orders are denied.
+    pub(crate) fn contains(&self, owner: &Address) -> bool {
+        self.owners.lock().unwrap().contains(owner)
+    }
+
+    /// Denies the owner's orders, returning whether they weren't already denied.
+    pub(crate) fn deny(&self, owner: Address) -> bool {
+        self.owners.lock().unwrap().insert(owner)
+    }
+
+    /// Allows the owner's orders again, returning whether they were denied.
+    pub(crate) fn allow(&self, owner: &Address) -> bool {
+        self.owners.lock().unwrap().remove(owner)
+    }
+
+    /// Every denied owner, in address order.
+    pub(crate) fn list(&self) -> Vec<Address> {
+        self.owners.lock().unwrap().iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deny_and_allow_at_runtime() {
+        let (first, second) = (Address::repeat_byte(0x01), Address::repeat_byte(0x02));
+        let denied_owners = DeniedOwners::new([second]);
+        assert!(!denied_owners.contains(&first));
+        assert!(denied_owners.clone().contains(&second));
+
+        assert!(denied_owners.deny(first));
+        assert!(!denied_owners.deny(first));
+        assert!(denied_owners.contains(&first));
+        assert_eq!(denied_owners.list(), vec![first, second]);
+
+        assert!(denied_owners.allow(&second));
+        assert!(!denied_owners.allow(&second));
+        assert!(!denied_owners.contains(&second));
+        assert_eq!(denied_owners.list(), vec![first]);
+    }
+}
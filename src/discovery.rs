@@ -0,0 +1,442 @@
+use crate::{
+    DiscoveryMode, FillProviderType, FillerContext, FixedPricingClient, IERC20, KnownToken,
+    TokenDiscoveryConfig, TwapPool, metrics,
+};
+use alloy::{
+    primitives::{Address, U256, aliases::U24},
+    providers::Provider,
+    sol,
+};
+use eyre::Result;
+use init4_bin_base::deps::tracing::{debug, info, instrument, warn};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    select,
+    time::{Duration, Instant},
+};
+use tokio_util::sync::CancellationToken;
+
+/// How often queued tokens are evaluated.
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Upper bound on tracked tokens, so a flood of junk tokens can't grow memory without bound.
+/// Tokens seen once the limit is reached are ignored.
+const MAX_TRACKED_TOKENS: usize = 1024;
+
+/// Uniswap V3 fee tiers searched for a pool, in hundredths of a basis point.
+const FEE_TIERS: [u32; 3] = [500, 3_000, 10_000];
+
+/// Host tokens with fixed USD prices that a discovered token's pool may be quoted in.
+const QUOTE_TOKENS: [KnownToken; 3] =
+    [KnownToken::HostUsdc, KnownToken::HostUsdt, KnownToken::HostWeth];
+
+sol! {
+    /// The part of the Uniswap V3 factory interface needed to find a token's pools.
+    #[sol(rpc)]
+    interface IUniswapV3Factory {
+        function getPool(address tokenA, address tokenB, uint24 fee)
+            external
+            view
+            returns (address pool);
+    }
+}
+
+/// Where a discovered token is in the discovery flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum DiscoveryStatus {
+    /// Seen in an order, not yet evaluated.
+    Queued,
+    /// Passed the safety checks, waiting for an operator to enable it.
+    Pending,
+    /// Priced from its pool's TWAP.
+    Enabled,
+    /// Failed a safety check, or rejected by an operator.
+    Rejected,
+}
+
+/// A token seen in order flow that no pricing source covers, as served by the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct DiscoveredToken {
+    token: Address,
+    status: DiscoveryStatus,
+    symbol: Option<String>,
+    decimals: Option<u8>,
+    /// The deepest Uniswap V3 pool pairing the token with a priced token.
+    pool: Option<Address>,
+    /// Value of the priced token held by `pool`, in normalized 18-decimal USD.
+    pool_liquidity: Option<U256>,
+    rejection: Option<String>,
+    first_seen_seconds_ago: u64,
+    #[serde(skip)]
+    first_seen: Instant,
+}
+
+impl DiscoveredToken {
+    const fn queued(token: Address, now: Instant) -> Self {
+        Self {
+            token,
+            status: DiscoveryStatus::Queued,
+            symbol: None,
+            decimals: None,
+            pool: None,
+            pool_liquidity: None,
+            rejection: None,
+            first_seen_seconds_ago: 0,
+            first_seen: now,
+        }
+    }
+}
+
+/// What was learned about a token while evaluating it.
+#[derive(Debug, Default)]
+struct Findings {
+    symbol: Option<String>,
+    decimals: Option<u8>,
+    pool: Option<(Address, U256)>,
+}
+
+#[derive(Debug)]
+struct DiscoveryInner {
+    config: TokenDiscoveryConfig,
+    tokens: HashMap<Address, DiscoveredToken>,
+    /// Pools of newly enabled tokens, not yet picked up by the TWAP refresh task.
+    new_pools: Vec<TwapPool>,
+}
+
+impl DiscoveryInner {
+    /// Marks a token enabled and hands its pool to the TWAP refresh task.
+    fn enable(&mut self, token: Address) {
+        let window = self.config.twap_window();
+        let Some(discovered) = self.tokens.get_mut(&token) else { return };
+        let Some(pool) = discovered.pool else { return };
+        discovered.status = DiscoveryStatus::Enabled;
+        self.new_pools.push(TwapPool::new(pool, window));
+    }
+}
+
+/// Tokens seen in order flow that no pricing source covers, shared between the filler loop
+/// (which reports them), the discovery task (which evaluates them), the TWAP refresh task (which
+/// prices enabled ones), and the admin API (which lists them and enables or rejects pending ones).
+#[derive(Debug, Clone)]
+pub(crate) struct TokenDiscovery {
+    inner: Arc<Mutex<DiscoveryInner>>,
+}
+
+impl TokenDiscovery {
+    pub(crate) fn new(config: TokenDiscoveryConfig) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(DiscoveryInner {
+                config,
+                tokens: HashMap::new(),
+                new_pools: Vec::new(),
+            })),
+        }
+    }
+
+    /// Report a token that an order referenced but couldn't be priced. Queues it for evaluation
+    /// unless it has already been seen.
+    pub(crate) fn observe(&self, token: Address) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.tokens.len() < MAX_TRACKED_TOKENS && !inner.tokens.contains_key(&token) {
+            debug!(%token, "queued unpriced token for discovery");
+            inner.tokens.insert(token, DiscoveredToken::queued(token, Instant::now()));
+        }
+    }
+
+    fn queued(&self) -> Vec<Address> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .tokens
+            .values()
+            .filter(|discovered| discovered.status == DiscoveryStatus::Queued)
+            .map(|discovered| discovered.token)
+            .collect()
+    }
+
+    /// Record the outcome of evaluating a queued token: rejected if `rejection` is set, otherwise
+    /// enabled or pending approval depending on the mode.
+    fn record(&self, token: Address, findings: Findings, rejection: Option<String>) {
+        let mut inner = self.inner.lock().unwrap();
+        let mode = inner.config.mode();
+        let Some(discovered) = inner.tokens.get_mut(&token) else { return };
+        discovered.symbol = findings.symbol;
+        discovered.decimals = findings.decimals;
+        discovered.pool = findings.pool.map(|(pool, _)| pool);
+        discovered.pool_liquidity = findings.pool.map(|(_, liquidity)| liquidity);
+        match rejection {
+            Some(reason) => {
+                info!(%token, reason, "discovered token rejected");
+                discovered.status = DiscoveryStatus::Rejected;
+                discovered.rejection = Some(reason);
+            }
+            None if mode == DiscoveryMode::Auto => {
+                info!(%token, symbol = ?discovered.symbol, "discovered token enabled");
+                inner.enable(token);
+            }
+            None => {
+                info!(%token, symbol = ?discovered.symbol, "discovered token awaiting approval");
+                discovered.status = DiscoveryStatus::Pending;
+            }
+        }
+    }
+
+    /// Enable a pending token, returning whether it was pending.
+    pub(crate) fn approve(&self, token: Address) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let pending = inner
+            .tokens
+            .get(&token)
+            .is_some_and(|discovered| discovered.status == DiscoveryStatus::Pending);
+        if pending {
+            inner.enable(token);
+        }
+        pending
+    }
+
+    /// Reject a pending token, returning whether it was pending.
+    pub(crate) fn reject(&self, token: Address) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(discovered) = inner
+            .tokens
+            .get_mut(&token)
+            .filter(|discovered| discovered.status == DiscoveryStatus::Pending)
+        else {
+            return false;
+        };
+        discovered.status = DiscoveryStatus::Rejected;
+        discovered.rejection = Some("rejected by operator".to_string());
+        true
+    }
+
+    /// Every tracked token, oldest first.
+    pub(crate) fn tokens(&self) -> Vec<DiscoveredToken> {
+        let inner = self.inner.lock().unwrap();
+        let mut tokens: Vec<DiscoveredToken> = inner
+            .tokens
+            .values()
+            .map(|discovered| DiscoveredToken {
+                first_seen_seconds_ago: discovered.first_seen.elapsed().as_secs(),
+                ..discovered.clone()
+            })
+            .collect();
+        tokens.sort_by_key(|discovered| discovered.first_seen);
+        tokens
+    }
+
+    /// Pools of tokens enabled since the last call.
+    pub(crate) fn take_new_pools(&self) -> Vec<TwapPool> {
+        std::mem::take(&mut self.inner.lock().unwrap().new_pools)
+    }
+}
+
+#[derive(Debug)]
+struct DiscoveryState {
+    config: TokenDiscoveryConfig,
+    discovery: TokenDiscovery,
+    host_provider: FillProviderType,
+    fixed_prices: FixedPricingClient,
+    quote_tokens: Vec<Address>,
+}
+
+/// Background task that evaluates tokens queued by the [`TokenDiscovery`] against the safety
+/// checks: the token must be an ERC20 contract on the host chain, at least the configured number
+/// of blocks old, with a Uniswap V3 pool against USDC, USDT, or WETH holding at least the
+/// configured USD liquidity. Tokens that pass are priced from that pool's TWAP, so only host
+/// tokens can be discovered.
+#[derive(Debug)]
+pub struct TokenDiscoveryTask {
+    state: Option<DiscoveryState>,
+    cancellation_token: CancellationToken,
+}
+
+impl TokenDiscoveryTask {
+    /// Create the discovery task. The task does nothing if token discovery is disabled.
+    pub fn new(context: &FillerContext) -> Self {
+        let cancellation_token = context.cancellation_token().clone();
+        let (Some(config), Some(discovery)) =
+            (context.token_discovery_config(), context.token_discovery())
+        else {
+            return Self { state: None, cancellation_token };
+        };
+        let constants = context.constants().system();
+        let state = DiscoveryState {
+            config: *config,
+            discovery: discovery.clone(),
+            host_provider: context.host_provider().clone(),
+            fixed_prices: FixedPricingClient::new(constants, context.chain_name(), 0)
+                .with_token_table(context.token_table()),
+            quote_tokens: QUOTE_TOKENS
+                .iter()
+                .map(|known| known.resolve(constants).token())
+                .collect(),
+        };
+        info!(mode = config.mode().as_str(), "token discovery enabled");
+        Self { state: Some(state), cancellation_token }
+    }
+
+    /// Run the periodic evaluation loop.
+    pub async fn run(self) {
+        let Some(state) = self.state else {
+            debug!("token discovery disabled");
+            return;
+        };
+        let mut interval = tokio::time::interval(EVALUATION_INTERVAL);
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("token discovery task cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    state.evaluate_queued().await;
+                }
+            }
+        }
+    }
+}
+
+impl DiscoveryState {
+    /// Evaluates each queued token in turn. Tokens whose evaluation fails on an RPC error stay
+    /// queued and are retried next interval.
+    #[instrument(skip_all)]
+    async fn evaluate_queued(&self) {
+        for token in self.discovery.queued() {
+            let mut findings = Findings::default();
+            match self.evaluate(token, &mut findings).await {
+                Ok(rejection) => self.discovery.record(token, findings, rejection),
+                Err(error) => {
+                    metrics::record_token_discovery_error();
+                    warn!(%token, error = format!("{error:#}"), "failed to evaluate discovered token");
+                }
+            }
+        }
+    }
+
+    /// Runs the safety checks, returning the reason the token was rejected, if it was.
+    async fn evaluate(&self, token: Address, findings: &mut Findings) -> Result<Option<String>> {
+        if self.host_provider.get_code_at(token).await?.is_empty() {
+            return Ok(Some("no contract on the host chain".to_string()));
+        }
+        let min_age_blocks = self.config.min_age_blocks();
+        let latest = self.host_provider.get_block_number().await?;
+        let aged_block = latest.saturating_sub(min_age_blocks);
+        if self.host_provider.get_code_at(token).number(aged_block).await?.is_empty() {
+            return Ok(Some(format!("contract is less than {min_age_blocks} blocks old")));
+        }
+
+        let erc20 = IERC20::new(token, &self.host_provider);
+        let Ok(decimals) = erc20.decimals().call().await else {
+            return Ok(Some("decimals() call failed".to_string()));
+        };
+        findings.decimals = Some(decimals);
+        findings.symbol = erc20.symbol().call().await.ok();
+
+        findings.pool = self.deepest_pool(token).await?;
+        let min_liquidity = self.config.min_liquidity();
+        Ok(match findings.pool {
+            None => Some("no Uniswap V3 pool against USDC, USDT, or WETH".to_string()),
+            Some((_, liquidity)) if liquidity < min_liquidity => {
+                Some("pool liquidity below the configured minimum".to_string())
+            }
+            Some(_) => None,
+        })
+    }
+
+    /// The pool pairing `token` with a quote token that holds the most quote-token value, with
+    /// that value in normalized 18-decimal USD.
+    async fn deepest_pool(&self, token: Address) -> Result<Option<(Address, U256)>> {
+        let factory = IUniswapV3Factory::new(self.config.uniswap_v3_factory(), &self.host_provider);
+        let mut deepest: Option<(Address, U256)> = None;
+        for quote in &self.quote_tokens {
+            for fee in FEE_TIERS {
+                let pool = factory.getPool(token, *quote, U24::from(fee)).call().await?;
+                if pool.is_zero() {
+                    continue;
+                }
+                let reserve =
+                    IERC20::new(*quote, &self.host_provider).balanceOf(pool).call().await?;
+                let liquidity = self.fixed_prices.value(quote, reserve)?;
+                if deepest.is_none_or(|(_, deepest)| liquidity > deepest) {
+                    deepest = Some((pool, liquidity));
+                }
+            }
+        }
+        Ok(deepest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: Address = Address::repeat_byte(0xaa);
+    const POOL: Address = Address::repeat_byte(0xbb);
+
+    fn discovery(mode: DiscoveryMode) -> TokenDiscovery {
+        TokenDiscovery::new(TokenDiscoveryConfig::new(mode, Duration::from_secs(600)))
+    }
+
+    fn passing() -> Findings {
+        Findings {
+            symbol: Some("TKN".to_string()),
+            decimals: Some(18),
+            pool: Some((POOL, U256::MAX)),
+        }
+    }
+
+    #[test]
+    fn auto_mode_enables_passing_tokens() {
+        let discovery = discovery(DiscoveryMode::Auto);
+        discovery.observe(TOKEN);
+        discovery.observe(TOKEN);
+        assert_eq!(discovery.queued(), vec![TOKEN]);
+
+        discovery.record(TOKEN, passing(), None);
+        assert!(discovery.queued().is_empty());
+        assert_eq!(discovery.tokens()[0].status, DiscoveryStatus::Enabled);
+        let pools = discovery.take_new_pools();
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].address(), POOL);
+        assert_eq!(pools[0].window(), Duration::from_secs(600));
+        assert!(discovery.take_new_pools().is_empty());
+    }
+
+    #[test]
+    fn review_mode_parks_passing_tokens() {
+        let discovery = discovery(DiscoveryMode::Review);
+        discovery.observe(TOKEN);
+        discovery.record(TOKEN, passing(), None);
+        assert_eq!(discovery.tokens()[0].status, DiscoveryStatus::Pending);
+        assert!(discovery.take_new_pools().is_empty());
+
+        assert!(discovery.approve(TOKEN));
+        assert!(!discovery.approve(TOKEN));
+        assert_eq!(discovery.tokens()[0].status, DiscoveryStatus::Enabled);
+        assert_eq!(discovery.take_new_pools().len(), 1);
+    }
+
+    #[test]
+    fn failing_and_operator_rejected_tokens_are_not_enabled() {
+        let discovery = discovery(DiscoveryMode::Review);
+        let other = Address::repeat_byte(0xcc);
+        discovery.observe(TOKEN);
+        discovery.observe(other);
+        discovery.record(TOKEN, Findings::default(), Some("too young".to_string()));
+        discovery.record(other, passing(), None);
+        assert!(!discovery.approve(TOKEN));
+        assert!(discovery.reject(other));
+
+        let tokens = discovery.tokens();
+        assert!(tokens.iter().all(|discovered| discovered.status == DiscoveryStatus::Rejected));
+        assert!(discovery.take_new_pools().is_empty());
+        // Rejected tokens aren't re-queued when seen again.
+        discovery.observe(TOKEN);
+        assert!(discovery.queued().is_empty());
+    }
+}
@@ -7,13 +7,21 @@ use alloy::{
 use signet_constants::NATIVE_TOKEN_ADDRESS;
 
 sol! {
-    /// Minimal ERC20 interface covering the read-only calls used across the filler:
-    /// balance queries on startup and in the per-cycle budget check, and allowance queries for the
-    /// Permit2 allowance cache.
+    /// Minimal ERC20 interface covering the calls used across the filler: balance queries on
+    /// startup and in the per-cycle budget check, allowance queries for the Permit2 allowance
+    /// cache, Permit2 approvals under the approval policy, treasury top-ups, decimals for TWAP pool
+    /// tokens, and symbols for discovered tokens. The `Transfer` event is decoded from simulated
+    /// fills.
     #[sol(rpc)]
     interface IERC20 {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+
         function balanceOf(address account) external view returns (uint256);
         function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 amount) external returns (bool);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
+        function decimals() external view returns (uint8);
+        function symbol() external view returns (string);
     }
 }
 
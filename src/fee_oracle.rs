@@ -0,0 +1,272 @@
+use crate::{FeeOracleConfig, FillProviderType, FillerContext, metrics};
+use alloy::{
+    eips::BlockNumberOrTag, primitives::U256, providers::Provider, rpc::types::FeeHistory,
+};
+use eyre::{Result, WrapErr};
+use init4_bin_base::deps::tracing::{debug, info, instrument, trace, warn};
+use std::sync::{Arc, RwLock};
+use tokio::select;
+use tokio_util::sync::CancellationToken;
+
+/// Number of recent blocks whose priority fees are sampled.
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Reward percentile requested for each sampled block.
+const PRIORITY_FEE_PERCENTILE: f64 = 50.0;
+
+/// Latest rollup and host gas price estimates, shared between the background fee oracle and the
+/// filler task.
+///
+/// Rollup gas is paid in the rollup's native USD token, which has 18 decimals, so a gas cost in
+/// wei is already in the normalized 18-decimal USD units that order margins are expressed in.
+/// Host gas is paid in ETH, so its cost is left in wei for the filler task to value.
+#[derive(Debug, Clone)]
+pub(crate) struct GasPriceCache {
+    gas_price: Arc<RwLock<Option<u128>>>,
+    gas_per_order: u64,
+    host_gas_price: Arc<RwLock<Option<u128>>>,
+    host_gas_per_order: Option<u64>,
+    gas_per_bundle: Option<u64>,
+}
+
+impl GasPriceCache {
+    pub(crate) fn new(
+        gas_per_order: u64,
+        host_gas_per_order: Option<u64>,
+        gas_per_bundle: Option<u64>,
+    ) -> Self {
+        Self {
+            gas_price: Default::default(),
+            gas_per_order,
+            host_gas_price: Default::default(),
+            host_gas_per_order,
+            gas_per_bundle,
+        }
+    }
+
+    /// Estimated rollup gas cost of filling one order, in normalized 18-decimal USD. `None` until
+    /// the oracle's first successful refresh.
+    pub(crate) fn order_gas_cost(&self) -> Option<i128> {
+        let gas_price = (*self.gas_price.read().unwrap())?;
+        i128::try_from(gas_price.saturating_mul(u128::from(self.gas_per_order))).ok()
+    }
+
+    /// Estimated fixed rollup gas cost of one fill bundle, in normalized 18-decimal USD. `None`
+    /// if bundle-level checks are disabled or until the oracle's first successful refresh.
+    pub(crate) fn bundle_gas_cost(&self) -> Option<i128> {
+        let gas_per_bundle = self.gas_per_bundle?;
+        let gas_price = (*self.gas_price.read().unwrap())?;
+        i128::try_from(gas_price.saturating_mul(u128::from(gas_per_bundle))).ok()
+    }
+
+    /// Estimated host gas cost of an order's host-chain fill, in wei of host ETH. `None` if host
+    /// gas estimation is disabled or until its first successful refresh.
+    pub(crate) fn host_order_gas_cost(&self) -> Option<U256> {
+        let host_gas_per_order = self.host_gas_per_order?;
+        let gas_price = (*self.host_gas_price.read().unwrap())?;
+        Some(U256::from(gas_price).saturating_mul(U256::from(host_gas_per_order)))
+    }
+
+    fn set(&self, chain: metrics::GasChain, gas_price: u128) {
+        let slot = match chain {
+            metrics::GasChain::Rollup => &self.gas_price,
+            metrics::GasChain::Host => &self.host_gas_price,
+        };
+        *slot.write().unwrap() = Some(gas_price);
+    }
+}
+
+#[derive(Debug)]
+struct FeeOracleState {
+    config: FeeOracleConfig,
+    cache: GasPriceCache,
+    ru_provider: FillProviderType,
+    host_provider: FillProviderType,
+}
+
+/// Background task that periodically estimates the rollup gas price, and the host gas price if
+/// host gas estimation is enabled, from `eth_feeHistory`, marking up the recent median priority
+/// fee, and publishes them to the [`GasPriceCache`]. Falls back to `eth_gasPrice` if the fee
+/// history is unavailable.
+#[derive(Debug)]
+pub struct FeeOracleTask {
+    state: Option<FeeOracleState>,
+    cancellation_token: CancellationToken,
+}
+
+impl FeeOracleTask {
+    /// Create the oracle and estimate the gas price once so it is available for the first cycle.
+    /// The task does nothing if gas cost estimation is disabled.
+    #[instrument(skip_all, name = "initialize_fee_oracle_task")]
+    pub async fn initialize(context: &FillerContext) -> Self {
+        let cancellation_token = context.cancellation_token().clone();
+        let (Some(config), Some(cache)) = (context.fee_oracle_config(), context.gas_prices())
+        else {
+            return Self { state: None, cancellation_token };
+        };
+        let state = FeeOracleState {
+            config: *config,
+            cache: cache.clone(),
+            ru_provider: context.ru_provider().clone(),
+            host_provider: context.host_provider().clone(),
+        };
+        info!(
+            gas_per_order = config.gas_per_order(),
+            host_gas_per_order = config.host_gas_per_order(),
+            priority_fee_markup_percent = config.priority_fee_markup_percent(),
+            "fee oracle enabled"
+        );
+        select! {
+            biased;
+            _ = cancellation_token.cancelled() => {
+                debug!("fee oracle task initialization cancelled");
+            }
+            _ = state.refresh() => {}
+        }
+        Self { state: Some(state), cancellation_token }
+    }
+
+    /// Run the periodic refresh loop.
+    pub async fn run(self) {
+        let Some(state) = self.state else {
+            debug!("fee oracle disabled");
+            return;
+        };
+        let mut interval = tokio::time::interval(state.config.refresh_interval());
+        // Consume the immediate first tick; `initialize` already refreshed.
+        interval.tick().await;
+
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("fee oracle task cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    state.refresh().await;
+                }
+            }
+        }
+    }
+}
+
+impl FeeOracleState {
+    /// Estimates each enabled chain's gas price and updates the cache.
+    #[instrument(skip_all)]
+    async fn refresh(&self) {
+        if self.config.host_gas_per_order().is_some() {
+            tokio::join!(
+                self.refresh_chain(metrics::GasChain::Rollup),
+                self.refresh_chain(metrics::GasChain::Host)
+            );
+        } else {
+            self.refresh_chain(metrics::GasChain::Rollup).await;
+        }
+    }
+
+    /// Estimates one chain's gas price and updates the cache. On failure the previous estimate is
+    /// kept: a slightly stale gas price is a better cost estimate than none.
+    async fn refresh_chain(&self, chain: metrics::GasChain) {
+        let provider = match chain {
+            metrics::GasChain::Rollup => &self.ru_provider,
+            metrics::GasChain::Host => &self.host_provider,
+        };
+        match self.estimate(provider).await {
+            Ok(gas_price) => {
+                trace!(chain = chain.as_str(), gas_price, "refreshed gas price");
+                metrics::record_gas_price(chain, gas_price);
+                self.cache.set(chain, gas_price);
+            }
+            Err(error) => {
+                metrics::record_fee_oracle_error(chain);
+                warn!(
+                    chain = chain.as_str(),
+                    error = format!("{error:#}"),
+                    "failed to estimate gas price"
+                );
+            }
+        }
+    }
+
+    async fn estimate(&self, provider: &FillProviderType) -> Result<u128> {
+        let history = provider
+            .get_fee_history(
+                FEE_HISTORY_BLOCKS,
+                BlockNumberOrTag::Latest,
+                &[PRIORITY_FEE_PERCENTILE],
+            )
+            .await;
+        match history.map(|history| {
+            gas_price_from_history(&history, self.config.priority_fee_markup_percent())
+        }) {
+            Ok(Some(gas_price)) => return Ok(gas_price),
+            Ok(None) => debug!("empty fee history, falling back to eth_gasPrice"),
+            Err(error) => debug!(%error, "eth_feeHistory failed, falling back to eth_gasPrice"),
+        }
+        provider.get_gas_price().await.wrap_err("eth_gasPrice failed")
+    }
+}
+
+/// The next block's base fee plus the median of the sampled blocks' priority fees, marked up by
+/// `markup_percent`. `None` if the history has no base fee or no rewards.
+fn gas_price_from_history(history: &FeeHistory, markup_percent: u16) -> Option<u128> {
+    let base_fee = history.next_block_base_fee()?;
+    let mut rewards: Vec<u128> =
+        history.reward.as_ref()?.iter().filter_map(|block| block.first().copied()).collect();
+    if rewards.is_empty() {
+        return None;
+    }
+    rewards.sort_unstable();
+    let priority_fee = rewards[rewards.len() / 2];
+    let marked_up = priority_fee.saturating_mul(100 + u128::from(markup_percent)) / 100;
+    Some(base_fee.saturating_add(marked_up))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(base_fees: Vec<u128>, rewards: Option<Vec<Vec<u128>>>) -> FeeHistory {
+        FeeHistory { base_fee_per_gas: base_fees, reward: rewards, ..Default::default() }
+    }
+
+    #[test]
+    fn gas_price_marks_up_median_priority_fee() {
+        let history = history(vec![90, 100, 110], Some(vec![vec![30], vec![10], vec![20]]));
+        // Next base fee 110, median priority fee 20 marked up by 50%.
+        assert_eq!(gas_price_from_history(&history, 50), Some(140));
+        assert_eq!(gas_price_from_history(&history, 0), Some(130));
+    }
+
+    #[test]
+    fn incomplete_history_has_no_gas_price() {
+        assert_eq!(gas_price_from_history(&history(vec![], Some(vec![vec![1]])), 0), None);
+        assert_eq!(gas_price_from_history(&history(vec![100], None), 0), None);
+        assert_eq!(gas_price_from_history(&history(vec![100], Some(vec![vec![]])), 0), None);
+    }
+
+    #[test]
+    fn order_gas_cost_scales_gas_price() {
+        let cache = GasPriceCache::new(150_000, None, Some(50_000));
+        assert_eq!(cache.order_gas_cost(), None);
+        assert_eq!(cache.bundle_gas_cost(), None);
+        cache.set(metrics::GasChain::Rollup, 2_000_000_000);
+        assert_eq!(cache.order_gas_cost(), Some(300_000_000_000_000));
+        assert_eq!(cache.bundle_gas_cost(), Some(100_000_000_000_000));
+    }
+
+    #[test]
+    fn host_gas_cost_uses_host_gas_price() {
+        let cache = GasPriceCache::new(150_000, Some(80_000), None);
+        cache.set(metrics::GasChain::Rollup, 2_000_000_000);
+        assert_eq!(cache.host_order_gas_cost(), None);
+        cache.set(metrics::GasChain::Host, 30_000_000_000);
+        assert_eq!(cache.host_order_gas_cost(), Some(U256::from(2_400_000_000_000_000u128)));
+        assert_eq!(cache.order_gas_cost(), Some(300_000_000_000_000));
+
+        let disabled = GasPriceCache::new(150_000, None, None);
+        disabled.set(metrics::GasChain::Host, 30_000_000_000);
+        assert_eq!(disabled.host_order_gas_cost(), None);
+    }
+}
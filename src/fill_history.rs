@@ -0,0 +1,268 @@
+use crate::ledger::RealizedFill;
+use alloy::primitives::{Address, B256, U256};
+use lru::LruCache;
+use serde::Serialize;
+use signet_types::SignedOrder;
+use std::{
+    collections::VecDeque,
+    fmt::Write,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Maximum number of fills kept for export. The oldest fill is dropped when a new one is submitted
+/// past this.
+const MAX_FILL_RECORDS: usize = 10_000;
+
+/// Number of selected orders whose estimated profit is remembered until they are submitted.
+const MAX_ESTIMATES: NonZeroUsize = NonZeroUsize::new(4096).unwrap();
+
+/// One USD in normalized 18-decimal USD.
+const USD: f64 = 1e18;
+
+/// Header line of the CSV export, matching the fields of [`FillRecord`].
+const CSV_HEADER: &str = "order_hash,owner,inputs,outputs,submitted_at,target_block,\
+                          estimated_profit_usd,confirmed_block,tx_hash,gas_paid,realized_profit_usd";
+
+/// An amount of a token paid or received on a fill, in base units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) struct TokenAmount {
+    chain_id: u64,
+    token: Address,
+    amount: U256,
+}
+
+impl std::fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.chain_id, self.token, self.amount)
+    }
+}
+
+/// A fill submitted by this filler, completed as it is confirmed and its realized profit is
+/// recorded by the PnL ledger.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct FillRecord {
+    order_hash: B256,
+    /// The order's owner, the filler's counterparty.
+    owner: Address,
+    /// Tokens the owner pays the filler on the rollup.
+    inputs: Vec<TokenAmount>,
+    /// Tokens the filler pays the order's recipients.
+    outputs: Vec<TokenAmount>,
+    /// When the fill was submitted, in seconds since the Unix epoch.
+    submitted_at: u64,
+    /// The last block the fill's bundles targeted.
+    target_block: u64,
+    /// Profit net of estimated gas when the order was selected, in USD.
+    estimated_profit_usd: Option<f64>,
+    /// The block the fill was seen filled at.
+    confirmed_block: Option<u64>,
+    /// The rollup transaction the fill landed in.
+    tx_hash: Option<B256>,
+    /// Rollup gas counted against this fill, in the rollup's native token. Gas is counted once per
+    /// transaction, against the first of its fills recorded.
+    gas_paid: Option<U256>,
+    /// Realized profit net of rollup gas, in USD, as recorded by the PnL ledger.
+    realized_profit_usd: Option<f64>,
+}
+
+impl FillRecord {
+    /// The record as a CSV line, without the trailing newline. Token amounts are written as
+    /// `chain_id:token:amount`, separated by `;`, and missing values are left empty.
+    fn to_csv(&self) -> String {
+        let tokens = |amounts: &[TokenAmount]| {
+            amounts.iter().map(ToString::to_string).collect::<Vec<_>>().join(";")
+        };
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        [
+            self.order_hash.to_string(),
+            self.owner.to_string(),
+            tokens(&self.inputs),
+            tokens(&self.outputs),
+            self.submitted_at.to_string(),
+            self.target_block.to_string(),
+            optional(self.estimated_profit_usd.map(|profit| profit.to_string())),
+            optional(self.confirmed_block.map(|block| block.to_string())),
+            optional(self.tx_hash.map(|tx_hash| tx_hash.to_string())),
+            optional(self.gas_paid.map(|gas_paid| gas_paid.to_string())),
+            optional(self.realized_profit_usd.map(|profit| profit.to_string())),
+        ]
+        .join(",")
+    }
+}
+
+#[derive(Debug)]
+struct HistoryInner {
+    ru_chain_id: u64,
+    records: VecDeque<FillRecord>,
+    /// Estimated profit of recently selected orders, in normalized 18-decimal USD.
+    estimates: LruCache<B256, i128>,
+}
+
+impl HistoryInner {
+    /// The latest record of a submission of the order, if it is still held.
+    fn latest_mut(&mut self, order_hash: &B256) -> Option<&mut FillRecord> {
+        self.records.iter_mut().rev().find(|record| record.order_hash == *order_hash)
+    }
+}
+
+/// History of the fills this filler submitted, for offline analysis: each submission's order,
+/// counterparty, tokens, and estimated profit, completed with the block, gas, and realized profit
+/// once it is confirmed. Exported as JSONL or CSV by the admin API.
+///
+/// Shared between the filler loop (which records fills) and the admin API. Holds the latest
+/// [`MAX_FILL_RECORDS`] submissions in memory, so the history starts over on restart.
+#[derive(Debug, Clone)]
+pub(crate) struct FillHistory {
+    inner: Arc<Mutex<HistoryInner>>,
+}
+
+impl FillHistory {
+    pub(crate) fn new(ru_chain_id: u64) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HistoryInner {
+                ru_chain_id,
+                records: VecDeque::new(),
+                estimates: LruCache::new(MAX_ESTIMATES),
+            })),
+        }
+    }
+
+    /// Remembers the estimated profit of an order selected for filling, in normalized 18-decimal
+    /// USD, for when it is submitted.
+    pub(crate) fn record_selected(&self, order_hash: B256, margin: i128) {
+        self.inner.lock().unwrap().estimates.put(order_hash, margin);
+    }
+
+    /// Records a successful submission of a fill of `order`, whose bundles target blocks up to
+    /// `target_block`.
+    pub(crate) fn record_submitted(&self, order: &SignedOrder, target_block: u64) {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock set before UNIX epoch")
+            .as_secs();
+        let mut inner = self.inner.lock().unwrap();
+        let ru_chain_id = inner.ru_chain_id;
+        let record = FillRecord {
+            order_hash: *order.order_hash(),
+            owner: order.permit().owner,
+            inputs: order
+                .permit()
+                .permit
+                .permitted
+                .iter()
+                .map(|input| TokenAmount {
+                    chain_id: ru_chain_id,
+                    token: input.token,
+                    amount: input.amount,
+                })
+                .collect(),
+            outputs: order
+                .outputs()
+                .iter()
+                .map(|output| TokenAmount {
+                    chain_id: u64::from(output.chainId),
+                    token: output.token,
+                    amount: output.amount,
+                })
+                .collect(),
+            submitted_at,
+            target_block,
+            estimated_profit_usd: inner
+                .estimates
+                .peek(order.order_hash())
+                .map(|margin| *margin as f64 / USD),
+            confirmed_block: None,
+            tx_hash: None,
+            gas_paid: None,
+            realized_profit_usd: None,
+        };
+        if inner.records.len() == MAX_FILL_RECORDS {
+            inner.records.pop_front();
+        }
+        inner.records.push_back(record);
+    }
+
+    /// Records that a submitted fill was seen filled at `block_number`.
+    pub(crate) fn record_confirmed(&self, order_hash: &B256, block_number: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.estimates.pop(order_hash);
+        if let Some(record) = inner.latest_mut(order_hash) {
+            record.confirmed_block = Some(block_number);
+        }
+    }
+
+    /// Records a confirmed fill's transaction, gas, and realized profit from the PnL ledger.
+    pub(crate) fn record_realized(&self, order_hash: &B256, realized: RealizedFill) {
+        if let Some(record) = self.inner.lock().unwrap().latest_mut(order_hash) {
+            record.tx_hash = Some(realized.tx_hash);
+            record.gas_paid = Some(realized.gas_paid);
+            record.realized_profit_usd = Some(realized.pnl_usd);
+        }
+    }
+
+    /// The history as JSON lines, oldest submission first.
+    pub(crate) fn jsonl(&self) -> String {
+        self.inner.lock().unwrap().records.iter().fold(String::new(), |mut jsonl, record| {
+            let line = serde_json::to_string(record).expect("fill records serialize to JSON");
+            jsonl.push_str(&line);
+            jsonl.push('\n');
+            jsonl
+        })
+    }
+
+    /// The history as CSV with a header line, oldest submission first.
+    pub(crate) fn csv(&self) -> String {
+        let mut csv = format!("{CSV_HEADER}\n");
+        for record in &self.inner.lock().unwrap().records {
+            let _ = writeln!(csv, "{}", record.to_csv());
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+
+    #[test]
+    fn submissions_are_completed_on_confirmation_and_exported() {
+        let order = OrderBuilder::default()
+            .with_input(Address::repeat_byte(0xaa), U256::from(100))
+            .with_output(Address::repeat_byte(0xbb), U256::from(90), Address::repeat_byte(0x01), 1)
+            .build();
+        let order_hash = *order.order_hash();
+        let history = FillHistory::new(7);
+        history.record_selected(order_hash, 5 * 10_i128.pow(18));
+        history.record_submitted(&order, 12);
+        history.record_confirmed(&order_hash, 11);
+        history.record_realized(
+            &order_hash,
+            RealizedFill {
+                tx_hash: B256::repeat_byte(0x11),
+                gas_paid: U256::from(3),
+                pnl_usd: 4.5,
+            },
+        );
+
+        let record = history.inner.lock().unwrap().records[0].clone();
+        assert_eq!(record.inputs[0].to_string(), format!("7:{}:100", Address::repeat_byte(0xaa)));
+        assert_eq!(record.estimated_profit_usd, Some(5.0));
+        assert_eq!(record.confirmed_block, Some(11));
+        assert_eq!(record.realized_profit_usd, Some(4.5));
+
+        let jsonl = history.jsonl();
+        assert_eq!(jsonl.lines().count(), 1);
+        let json: serde_json::Value = serde_json::from_str(jsonl.trim_end()).unwrap();
+        assert_eq!(json["realized_profit_usd"], 4.5);
+
+        let csv = history.csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(fields.len(), CSV_HEADER.split(',').count());
+        assert_eq!(&fields[6..], ["5", "11", &B256::repeat_byte(0x11).to_string(), "3", "4.5"]);
+    }
+}
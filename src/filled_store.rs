@@ -0,0 +1,162 @@
+use alloy::primitives::B256;
+use eyre::{Result, WrapErr};
+use init4_bin_base::deps::tracing::warn;
+use std::{
+    num::NonZeroUsize,
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// On-disk record of the orders the filler has seen filled, backing the in-memory filled-order
+/// cache so it survives restarts. Without it, every order still in the transaction cache after a
+/// deploy has its Permit2 nonce checked again before the filler knows it is filled.
+///
+/// Each order hash is stored with the time it was recorded and the order's deadline. On startup
+/// the most recent unexpired entries, up to the cache's capacity, are loaded into the cache and
+/// the rest are pruned. The store is pruned the same way again each time as many orders as the
+/// cache holds have been recorded, so it never holds more than twice the cache's capacity.
+#[derive(Debug, Clone)]
+pub(crate) struct FilledOrderStore {
+    db: sled::Db,
+    /// How many of the most recent unexpired orders are kept when pruning.
+    capacity: NonZeroUsize,
+    /// Orders recorded since the store was last pruned.
+    unpruned: Arc<AtomicUsize>,
+}
+
+impl FilledOrderStore {
+    /// Opens the store at `path`, creating it if it doesn't exist, keeping up to `capacity`
+    /// orders when pruned.
+    pub(crate) fn open(path: &Path, capacity: NonZeroUsize) -> Result<Self> {
+        let db = sled::open(path)
+            .wrap_err_with(|| format!("failed to open filled-order store at {}", path.display()))?;
+        Ok(Self { db, capacity, unpruned: Arc::default() })
+    }
+
+    /// Loads the `limit` most recently recorded order hashes with their deadlines, oldest first,
+    /// and prunes the rest along with orders whose deadline is before `now` (in seconds). Entries
+    /// written without a deadline never expire.
+    pub(crate) fn load_recent(&self, limit: usize, now: u64) -> Result<Vec<(B256, u64)>> {
+        let mut entries = Vec::new();
+        let mut expired = Vec::new();
+        for entry in self.db.iter() {
+            let (key, value) = entry.wrap_err("failed to read filled-order store")?;
+            let Ok(order_hash) = B256::try_from(key.as_ref()) else {
+                continue;
+            };
+            let (recorded_at_ms, deadline) = value.split_at_checked(8).unwrap_or((&value, &[]));
+            let recorded_at_ms =
+                recorded_at_ms.try_into().map(u64::from_be_bytes).unwrap_or_default();
+            let deadline = deadline.try_into().map(u64::from_be_bytes).unwrap_or(u64::MAX);
+            if deadline < now {
+                expired.push(order_hash);
+            } else {
+                entries.push((recorded_at_ms, order_hash, deadline));
+            }
+        }
+        entries.sort_unstable();
+        let stale = entries.len().saturating_sub(limit);
+        let stale =
+            entries.drain(..stale).map(|(_recorded_at_ms, order_hash, _deadline)| order_hash);
+        for order_hash in expired.into_iter().chain(stale) {
+            self.db.remove(order_hash).wrap_err("failed to prune filled-order store")?;
+        }
+        self.unpruned.store(0, Ordering::Relaxed);
+        Ok(entries
+            .into_iter()
+            .map(|(_recorded_at_ms, order_hash, deadline)| (order_hash, deadline))
+            .collect())
+    }
+
+    /// Records `order_hash`, with the order's `deadline`, as filled, pruning the store once the
+    /// store's capacity of orders has been recorded since it was last pruned. A failed write is
+    /// logged, since the order is still cached in memory and at worst has its nonce checked again
+    /// after a restart.
+    pub(crate) fn insert(&self, order_hash: B256, deadline: u64) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock set before UNIX epoch");
+        let recorded_at_ms = now.as_millis() as u64;
+        let mut value = [0; 16];
+        value[..8].copy_from_slice(&recorded_at_ms.to_be_bytes());
+        value[8..].copy_from_slice(&deadline.to_be_bytes());
+        if let Err(error) = self.db.insert(order_hash, &value) {
+            warn!(%order_hash, %error, "failed to record filled order in store");
+            return;
+        }
+        if self.unpruned.fetch_add(1, Ordering::Relaxed) + 1 < self.capacity.get() {
+            return;
+        }
+        if let Err(error) = self.load_recent(self.capacity.get(), now.as_secs()) {
+            warn!(error = format!("{error:#}"), "failed to prune filled-order store");
+        }
+    }
+
+    /// Forgets `order_hash`, whose fill was undone by a reorg. A failed write is logged, since at
+    /// worst the order is skipped as filled after a restart until it leaves the transaction cache.
+    pub(crate) fn remove(&self, order_hash: B256) {
+        if let Err(error) = self.db.remove(order_hash) {
+            warn!(%order_hash, %error, "failed to remove reorged order from store");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reloads_most_recent_orders() {
+        let path = std::env::temp_dir()
+            .join(format!("signet-filler-filled-orders-{}", std::process::id()));
+        {
+            let store = FilledOrderStore::open(&path, NonZeroUsize::new(16).unwrap()).unwrap();
+            for byte in 1..=3 {
+                store.insert(B256::repeat_byte(byte), 1_000);
+                std::thread::sleep(std::time::Duration::from_millis(2));
+            }
+        }
+        let store = FilledOrderStore::open(&path, NonZeroUsize::new(16).unwrap()).unwrap();
+        assert_eq!(
+            store.load_recent(2, 0).unwrap(),
+            [(B256::repeat_byte(2), 1_000), (B256::repeat_byte(3), 1_000)]
+        );
+        assert_eq!(store.load_recent(10, 0).unwrap().len(), 2);
+        store.insert(B256::repeat_byte(4), 2_000);
+        assert_eq!(store.load_recent(10, 1_500).unwrap(), [(B256::repeat_byte(4), 2_000)]);
+        assert_eq!(store.load_recent(10, 0).unwrap().len(), 1);
+        drop(store);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn prunes_once_capacity_orders_are_recorded() {
+        let path = std::env::temp_dir()
+            .join(format!("signet-filler-filled-orders-pruned-{}", std::process::id()));
+        let store = FilledOrderStore::open(&path, NonZeroUsize::new(3).unwrap()).unwrap();
+        store.insert(B256::repeat_byte(1), 1);
+        for byte in 2..=5 {
+            std::thread::sleep(std::time::Duration::from_millis(2));
+            store.insert(B256::repeat_byte(byte), u64::MAX);
+        }
+        // Recording the third order pruned the expired first; the next prune is due at the sixth.
+        assert_eq!(store.db.len(), 4);
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        store.insert(B256::repeat_byte(6), u64::MAX);
+        assert_eq!(
+            store
+                .db
+                .iter()
+                .keys()
+                .map(|key| B256::try_from(key.unwrap().as_ref()).unwrap())
+                .collect::<Vec<_>>(),
+            [B256::repeat_byte(4), B256::repeat_byte(5), B256::repeat_byte(6)]
+        );
+        drop(store);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
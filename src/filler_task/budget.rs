@@ -0,0 +1,165 @@
+use crate::{
+    CycleBudgetConfig,
+    metrics::{self, BudgetKind},
+};
+use init4_bin_base::deps::tracing::warn;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tokio::time::Instant;
+
+/// Explicit budgets for a single processing cycle: how many orders may be priced, how many
+/// per-order RPC calls may be made, and the wall-clock deadline by which evaluation must stop so
+/// the bundle can still be submitted in time. Once a budget is exhausted the cycle proceeds to
+/// bundle assembly with whatever has already been evaluated.
+///
+/// Counters are atomic because nonce checks run concurrently within the cycle.
+#[derive(Debug)]
+pub(super) struct CycleBudget {
+    max_quotes: Option<usize>,
+    max_rpc_calls: Option<usize>,
+    deadline: Instant,
+    quotes_used: AtomicUsize,
+    rpc_calls_used: AtomicUsize,
+    exhausted: [AtomicBool; 3],
+}
+
+impl CycleBudget {
+    /// Creates the budget for a cycle that must finish evaluating orders by `deadline`.
+    pub(super) fn new(config: &CycleBudgetConfig, deadline: Instant) -> Self {
+        Self {
+            max_quotes: config.max_quotes().map(|max| max.get()),
+            max_rpc_calls: config.max_rpc_calls().map(|max| max.get()),
+            deadline,
+            quotes_used: AtomicUsize::new(0),
+            rpc_calls_used: AtomicUsize::new(0),
+            exhausted: Default::default(),
+        }
+    }
+
+    /// The instant by which order evaluation must stop.
+    pub(super) const fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Consumes one quote, returning `false` (and recording exhaustion) if either the quote or the
+    /// wall-time budget has run out.
+    pub(super) fn try_quote(&self) -> bool {
+        if !self.has_time() {
+            return false;
+        }
+        let used = self.quotes_used.fetch_add(1, Ordering::Relaxed);
+        if self.max_quotes.is_some_and(|max| used >= max) {
+            self.quotes_used.fetch_sub(1, Ordering::Relaxed);
+            self.exhaust(BudgetKind::Quotes);
+            return false;
+        }
+        true
+    }
+
+    /// Reserves up to `requested` RPC calls, returning how many were granted. Fewer than
+    /// `requested` means the RPC call (or wall-time) budget is exhausted.
+    pub(super) fn reserve_rpc_calls(&self, requested: usize) -> usize {
+        if requested == 0 {
+            return 0;
+        }
+        if !self.has_time() {
+            return 0;
+        }
+        let Some(max) = self.max_rpc_calls else {
+            self.rpc_calls_used.fetch_add(requested, Ordering::Relaxed);
+            return requested;
+        };
+        let granted = self
+            .rpc_calls_used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                Some(used + requested.min(max.saturating_sub(used)))
+            })
+            .map(|used| requested.min(max.saturating_sub(used)))
+            .expect("update closure always returns Some");
+        if granted < requested {
+            self.exhaust(BudgetKind::RpcCalls);
+        }
+        granted
+    }
+
+    /// Counts RPC calls that were made unconditionally (e.g. the cycle's balance queries, which
+    /// are needed for any fill) against the budget without refusing them.
+    pub(super) fn record_rpc_calls(&self, count: usize) {
+        self.rpc_calls_used.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Whether wall time remains, recording exhaustion if not.
+    pub(super) fn has_time(&self) -> bool {
+        if Instant::now() >= self.deadline {
+            self.exhaust(BudgetKind::WallTime);
+            return false;
+        }
+        true
+    }
+
+    /// Records that `kind` ran out. The metric and log are emitted at most once per kind per
+    /// cycle. Running out of wall time also counts as a missed processing window.
+    pub(super) fn exhaust(&self, kind: BudgetKind) {
+        if !self.exhausted[kind as usize].swap(true, Ordering::Relaxed) {
+            warn!(
+                budget = kind.as_str(),
+                quotes_used = self.quotes_used.load(Ordering::Relaxed),
+                rpc_calls_used = self.rpc_calls_used.load(Ordering::Relaxed),
+                "cycle budget exhausted, proceeding with orders evaluated so far"
+            );
+            metrics::record_cycle_budget_exhausted(kind);
+            if matches!(kind, BudgetKind::WallTime) {
+                metrics::record_missed_window();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+    use tokio::time::Duration;
+
+    fn budget(max_quotes: Option<usize>, max_rpc_calls: Option<usize>) -> CycleBudget {
+        let config = CycleBudgetConfig::new(
+            max_quotes.and_then(NonZeroUsize::new),
+            max_rpc_calls.and_then(NonZeroUsize::new),
+            None,
+        );
+        CycleBudget::new(&config, Instant::now() + Duration::from_secs(60))
+    }
+
+    #[test]
+    fn quotes_stop_at_limit() {
+        let budget = budget(Some(2), None);
+        assert!(budget.try_quote());
+        assert!(budget.try_quote());
+        assert!(!budget.try_quote());
+        assert!(!budget.try_quote());
+    }
+
+    #[test]
+    fn rpc_calls_are_granted_partially_at_limit() {
+        let budget = budget(None, Some(5));
+        budget.record_rpc_calls(2);
+        assert_eq!(budget.reserve_rpc_calls(2), 2);
+        assert_eq!(budget.reserve_rpc_calls(4), 1);
+        assert_eq!(budget.reserve_rpc_calls(1), 0);
+    }
+
+    #[test]
+    fn unlimited_budgets_grant_everything() {
+        let budget = budget(None, None);
+        assert!((0..1000).all(|_| budget.try_quote()));
+        assert_eq!(budget.reserve_rpc_calls(1000), 1000);
+    }
+
+    #[test]
+    fn nothing_is_granted_after_deadline() {
+        let config = CycleBudgetConfig::new(None, None, None);
+        let budget = CycleBudget::new(&config, Instant::now());
+        assert!(!budget.has_time());
+        assert!(!budget.try_quote());
+        assert_eq!(budget.reserve_rpc_calls(3), 0);
+    }
+}
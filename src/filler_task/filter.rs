@@ -0,0 +1,146 @@
+use crate::metrics::OrderSkippedReason;
+use signet_types::SignedOrder;
+use std::fmt::Debug;
+
+/// A built-in stage of the order filter pipeline, in the order they run. Custom [`OrderFilter`]s
+/// are placed after one of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterStage {
+    /// Drops orders whose deadline is before the earliest block they could be filled in.
+    Expiry,
+    /// Drops orders known to be filled, from the filled-order cache or an `Order` event, or with
+    /// a pending fill.
+    FilledCache,
+    /// Drops orders from owners on the owner denylist.
+    DeniedOwners,
+    /// Drops structurally malformed orders.
+    Sanity,
+    /// Drops orders with a token the operator's token lists don't allow.
+    TokenLists,
+    /// Drops orders whose output notional is outside the configured bounds.
+    NotionalBounds,
+    /// Drops orders whose Permit2 signature doesn't recover to their owner.
+    Signature,
+}
+
+impl FilterStage {
+    pub(super) const ALL: [Self; 7] = [
+        Self::Expiry,
+        Self::FilledCache,
+        Self::DeniedOwners,
+        Self::Sanity,
+        Self::TokenLists,
+        Self::NotionalBounds,
+        Self::Signature,
+    ];
+}
+
+/// Why an order filter skipped an order, counted as the `reason` label of
+/// `signet.filler.orders_skipped` and recorded in the archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkipReason(pub(super) OrderSkippedReason);
+
+impl SkipReason {
+    /// A skip reason for a custom filter, recorded under `label`.
+    pub const fn custom(label: &'static str) -> Self {
+        Self(OrderSkippedReason::Custom(label))
+    }
+}
+
+/// A stage of the order filter pipeline, which every fetched order passes through before
+/// screening and pricing. Filters should be cheap: they run on every order, every cycle, and
+/// can't make network calls.
+pub trait OrderFilter: Debug + Send + Sync {
+    /// Checks the order, returning why it should be skipped, or `None` to pass it on to the next
+    /// stage.
+    fn check(&self, order: &SignedOrder) -> Option<SkipReason>;
+}
+
+/// The custom filters added to the pipeline, with the built-in stage each runs after.
+#[derive(Debug, Default)]
+pub(super) struct FilterPipeline {
+    custom: Vec<(FilterStage, Box<dyn OrderFilter>)>,
+}
+
+impl FilterPipeline {
+    /// Adds a custom filter after the given built-in stage, and after any custom filters already
+    /// added there.
+    pub(super) fn push(&mut self, after: FilterStage, filter: Box<dyn OrderFilter>) {
+        self.custom.push((after, filter));
+    }
+
+    /// Every stage in the order they run: each built-in stage, as given in `builtins` in
+    /// [`FilterStage::ALL`] order, followed by the custom filters placed after it.
+    pub(super) fn stages<'a>(
+        &'a self,
+        builtins: &'a [impl OrderFilter; FilterStage::ALL.len()],
+    ) -> Vec<&'a dyn OrderFilter> {
+        FilterStage::ALL
+            .iter()
+            .zip(builtins)
+            .flat_map(|(stage, builtin)| {
+                let custom = self
+                    .custom
+                    .iter()
+                    .filter(move |(after, _filter)| after == stage)
+                    .map(|(_after, filter)| filter.as_ref());
+                std::iter::once(builtin as &dyn OrderFilter).chain(custom)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+
+    /// Skips every order under its label.
+    #[derive(Debug)]
+    struct SkipAll(&'static str);
+
+    impl OrderFilter for SkipAll {
+        fn check(&self, _order: &SignedOrder) -> Option<SkipReason> {
+            Some(SkipReason::custom(self.0))
+        }
+    }
+
+    #[test]
+    fn custom_filters_run_after_their_stage() {
+        let order = OrderBuilder::default().build();
+        let builtins = [
+            SkipAll("expiry"),
+            SkipAll("filled-cache"),
+            SkipAll("denied-owners"),
+            SkipAll("sanity"),
+            SkipAll("token-lists"),
+            SkipAll("notional-bounds"),
+            SkipAll("signature"),
+        ];
+        let mut pipeline = FilterPipeline::default();
+        pipeline.push(FilterStage::Signature, Box::new(SkipAll("last")));
+        pipeline.push(FilterStage::Expiry, Box::new(SkipAll("first")));
+        pipeline.push(FilterStage::Expiry, Box::new(SkipAll("second")));
+
+        let labels: Vec<&str> = pipeline
+            .stages(&builtins)
+            .into_iter()
+            .map(|filter| filter.check(&order).unwrap().0.as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "expiry",
+                "first",
+                "second",
+                "filled-cache",
+                "denied-owners",
+                "sanity",
+                "token-lists",
+                "notional-bounds",
+                "signature",
+                "last"
+            ]
+        );
+    }
+}
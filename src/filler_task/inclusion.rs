@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+/// Number of recent submission outcomes the estimate is based on.
+const WINDOW: usize = 200;
+
+/// Estimates the probability that an order in a submitted bundle is filled, from the outcomes of
+/// recent submissions: an order counts as landed once its nonce is consumed, and as missed if it
+/// is still unconsumed after the bundle's target window.
+///
+/// The estimate is the posterior mean under a uniform prior, `(landed + 1) / (outcomes + 2)`, so
+/// it starts at 0.5 and never reaches exactly 0 or 1. Orders filled by a competitor also count as
+/// landed, since the two can't be told apart from the nonce alone.
+#[derive(Debug, Default)]
+pub(super) struct InclusionEstimator {
+    outcomes: VecDeque<bool>,
+}
+
+impl InclusionEstimator {
+    /// Record whether a submitted order landed.
+    pub(super) fn record(&mut self, landed: bool) {
+        if self.outcomes.len() == WINDOW {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(landed);
+    }
+
+    /// Estimated probability that a submitted order lands.
+    pub(super) fn probability(&self) -> f64 {
+        let landed = self.outcomes.iter().filter(|landed| **landed).count();
+        (landed + 1) as f64 / (self.outcomes.len() + 2) as f64
+    }
+}
+
+/// Discounts `margin` (normalized 18-decimal USD) by the probability of the fill landing.
+pub(super) fn expected_value(margin: i128, probability: f64) -> i128 {
+    (margin as f64 * probability) as i128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probability_tracks_recent_outcomes() {
+        let mut estimator = InclusionEstimator::default();
+        assert_eq!(estimator.probability(), 0.5);
+
+        (0..8).for_each(|_| estimator.record(true));
+        estimator.record(false);
+        estimator.record(false);
+        assert_eq!(estimator.probability(), 0.75);
+
+        // Old outcomes fall out of the window.
+        (0..WINDOW).for_each(|_| estimator.record(false));
+        assert_eq!(estimator.probability(), 1.0 / (WINDOW + 2) as f64);
+    }
+
+    #[test]
+    fn expected_value_scales_margin() {
+        assert_eq!(expected_value(1_000, 0.25), 250);
+        assert_eq!(expected_value(-1_000, 0.5), -500);
+    }
+}
@@ -0,0 +1,242 @@
+use super::budget::CycleBudget;
+use crate::{
+    ChainTokenPair, FixedPricingClient, FixedPricingError, InventoryAsset, InventoryConfig,
+    KnownToken, metrics, query_balance,
+};
+use alloy::primitives::{Address, U256};
+use futures_util::future::join_all;
+use init4_bin_base::deps::tracing::{debug, instrument, warn};
+use signet_constants::SignetSystemConstants;
+use signet_types::SignedOrder;
+use std::collections::HashMap;
+
+/// Changes in skew smaller than this are treated as neutral, so rounding noise doesn't flip an
+/// order between bonus and penalty.
+const SKEW_EPSILON: f64 = 1e-9;
+
+/// Basis points in 100%.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// The filler's inventory at the start of a cycle: the USD value held in each [`InventoryAsset`],
+/// summed over both chains. Used to reward fills that move the wallet toward its target
+/// allocation and to demand extra profit from fills that move it away.
+///
+/// The snapshot is taken once per cycle and not updated as orders are selected, so several fills
+/// in the same cycle are each judged against the starting inventory.
+#[derive(Debug)]
+pub(super) struct Inventory {
+    config: InventoryConfig,
+    ru_chain_id: u64,
+    assets: HashMap<ChainTokenPair, InventoryAsset>,
+    holdings: [f64; InventoryAsset::ALL.len()],
+}
+
+impl Inventory {
+    /// Queries the filler's balance of every inventory token on both chains. Returns `None` if any
+    /// query or valuation fails, since a partial snapshot would misstate the skew.
+    #[instrument(skip_all, name = "query_inventory")]
+    pub(super) async fn query(
+        config: InventoryConfig,
+        filler_address: Address,
+        ru_provider: &super::FillProviderType,
+        host_provider: &super::FillProviderType,
+        constants: &SignetSystemConstants,
+        pricing_client: &FixedPricingClient,
+        budget: &CycleBudget,
+    ) -> Option<Self> {
+        let ru_chain_id = constants.ru_chain_id();
+        let assets: HashMap<ChainTokenPair, InventoryAsset> = KnownToken::ALL
+            .iter()
+            .filter_map(|known| Some((known.resolve(constants), known.inventory_asset()?)))
+            .collect();
+        budget.record_rpc_calls(assets.len());
+
+        let balances = join_all(assets.iter().map(|(chain_token, asset)| async move {
+            let provider =
+                if chain_token.chain_id() == ru_chain_id { ru_provider } else { host_provider };
+            let balance = query_balance(provider, filler_address, chain_token.token()).await;
+            (*chain_token, *asset, balance)
+        }))
+        .await;
+
+        let mut holdings = [0.0; InventoryAsset::ALL.len()];
+        for (chain_token, asset, balance) in balances {
+            let balance = match balance {
+                Ok(balance) => balance,
+                Err(error) => {
+                    metrics::record_preflight_query_error(metrics::PreflightQuery::Balance);
+                    warn!(
+                        %chain_token,
+                        error = format!("{error:#}"),
+                        "failed to query inventory balance, skipping inventory adjustment"
+                    );
+                    return None;
+                }
+            };
+            match pricing_client.value(&chain_token.token(), balance) {
+                Ok(value) => holdings[asset as usize] += f64::from(value),
+                Err(error) => {
+                    warn!(%chain_token, %error, "failed to value inventory, skipping adjustment");
+                    return None;
+                }
+            }
+        }
+        debug!(?holdings, "inventory snapshot");
+
+        Some(Self { config, ru_chain_id, assets, holdings })
+    }
+
+    /// Applies the inventory adjustment to `margin`. Returns `None` if the fill increases skew and
+    /// its margin doesn't cover the penalty.
+    pub(super) fn adjusted_margin(
+        &self,
+        margin: i128,
+        order: &SignedOrder,
+        pricing_client: &FixedPricingClient,
+    ) -> Result<Option<i128>, FixedPricingError> {
+        let adjustment = self.adjustment(order, pricing_client)?;
+        let adjusted = margin.checked_add(adjustment).ok_or(FixedPricingError::Overflow)?;
+        Ok((adjustment >= 0 || adjusted >= 0).then_some(adjusted))
+    }
+
+    /// The margin adjustment for filling `order`, in normalized 18-decimal USD: a bonus of
+    /// `bonus_bps` of the output value if the fill reduces inventory skew, minus `penalty_bps` of
+    /// it if the fill increases skew, and zero otherwise. Tokens outside the inventory assets
+    /// don't affect skew.
+    fn adjustment(
+        &self,
+        order: &SignedOrder,
+        pricing_client: &FixedPricingClient,
+    ) -> Result<i128, FixedPricingError> {
+        let mut after = self.holdings;
+        for permitted in &order.permit().permit.permitted {
+            let pair = ChainTokenPair::new(self.ru_chain_id, permitted.token);
+            if let Some(asset) = self.assets.get(&pair) {
+                after[*asset as usize] +=
+                    f64::from(pricing_client.value(&permitted.token, permitted.amount)?);
+            }
+        }
+        for output in order.outputs() {
+            let pair = ChainTokenPair::new(u64::from(output.chainId), output.token);
+            if let Some(asset) = self.assets.get(&pair) {
+                let holding = &mut after[*asset as usize];
+                *holding = (*holding
+                    - f64::from(pricing_client.value(&output.token, output.amount)?))
+                .max(0.0);
+            }
+        }
+
+        let change = skew(&after, &self.config) - skew(&self.holdings, &self.config);
+        let bps = if change < -SKEW_EPSILON {
+            i128::from(self.config.bonus_bps())
+        } else if change > SKEW_EPSILON {
+            -i128::from(self.config.penalty_bps())
+        } else {
+            return Ok(0);
+        };
+        let output_value: i128 = (pricing_client.output_value(order)?
+            / U256::from(BPS_DENOMINATOR))
+        .try_into()
+        .map_err(|_| FixedPricingError::Overflow)?;
+        output_value.checked_mul(bps).ok_or(FixedPricingError::Overflow)
+    }
+}
+
+/// Total distance between the actual and target allocation: the sum over assets of the absolute
+/// difference between each asset's share of total value and its target share. Zero when the
+/// inventory is empty.
+fn skew(holdings: &[f64; InventoryAsset::ALL.len()], config: &InventoryConfig) -> f64 {
+    let total: f64 = holdings.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    InventoryAsset::ALL
+        .iter()
+        .map(|asset| {
+            let share = holdings[*asset as usize] / total;
+            let target = f64::from(config.target_percent(*asset)) / 100.0;
+            (share - target).abs()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+
+    /// One normalized USD.
+    const USD: f64 = 1e18;
+
+    fn constants() -> SignetSystemConstants {
+        SignetSystemConstants::parmigiana()
+    }
+
+    fn inventory(holdings: [f64; 3]) -> Inventory {
+        let constants = constants();
+        Inventory {
+            config: InventoryConfig::new([50, 50, 0], 100, 200),
+            ru_chain_id: constants.ru_chain_id(),
+            assets: KnownToken::ALL
+                .iter()
+                .filter_map(|known| Some((known.resolve(&constants), known.inventory_asset()?)))
+                .collect(),
+            holdings,
+        }
+    }
+
+    /// An order paying `input` rollup WETH (18 decimals) for `output` host USDC (6 decimals).
+    fn weth_for_usdc(input: U256, output: U256) -> SignedOrder {
+        let constants = constants();
+        OrderBuilder::default()
+            .with_input(constants.rollup().tokens().weth(), input)
+            .with_output(
+                constants.host().tokens().usdc(),
+                output,
+                Address::ZERO,
+                constants.host_chain_id(),
+            )
+            .build()
+    }
+
+    fn pricing_client() -> FixedPricingClient {
+        FixedPricingClient::new(&constants(), "parmigiana", 10)
+    }
+
+    #[test]
+    fn skew_is_distance_from_targets() {
+        let config = InventoryConfig::new([50, 50, 0], 0, 0);
+        assert_eq!(skew(&[USD, USD, 0.0], &config), 0.0);
+        assert_eq!(skew(&[USD, 0.0, 0.0], &config), 1.0);
+        assert_eq!(skew(&[0.0, 0.0, 0.0], &config), 0.0);
+    }
+
+    #[test]
+    fn rebalancing_fill_gets_bonus() {
+        // Mostly USD; receiving WETH for USDC moves toward the 50/50 target.
+        let inventory = inventory([9_000.0 * USD, 1_000.0 * USD, 0.0]);
+        // 1 WETH ($3000) in, 3000 USDC out: output value $3000, bonus 100bps = $30.
+        let order =
+            weth_for_usdc(U256::from(10).pow(U256::from(18)), U256::from(3_000_000_000_u64));
+        let adjustment = inventory.adjustment(&order, &pricing_client()).unwrap();
+        assert_eq!(adjustment, 30 * 10_i128.pow(18));
+    }
+
+    #[test]
+    fn skew_worsening_fill_is_penalized() {
+        // Already mostly ETH; receiving more WETH moves away from the target.
+        let inventory = inventory([1_000.0 * USD, 9_000.0 * USD, 0.0]);
+        let order =
+            weth_for_usdc(U256::from(10).pow(U256::from(18)), U256::from(3_000_000_000_u64));
+        let adjustment = inventory.adjustment(&order, &pricing_client()).unwrap();
+        assert_eq!(adjustment, -60 * 10_i128.pow(18));
+
+        // The penalty must be covered by the margin.
+        let penalty = 60 * 10_i128.pow(18);
+        assert_eq!(inventory.adjusted_margin(penalty, &order, &pricing_client()).unwrap(), Some(0));
+        assert_eq!(
+            inventory.adjusted_margin(penalty - 1, &order, &pricing_client()).unwrap(),
+            None
+        );
+    }
+}
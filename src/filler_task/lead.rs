@@ -0,0 +1,146 @@
+use crate::AdaptiveLeadConfig;
+use signet_types::SignedOrder;
+use std::collections::VecDeque;
+use tokio::time::Duration;
+
+/// How much the block lead changes per adjustment.
+const STEP: Duration = Duration::from_millis(100);
+
+/// Consecutive bundles landing in their first target block before the lead is shortened a step.
+/// A single miss lengthens it, so the lead backs off quickly and tightens slowly.
+const HITS_PER_STEP: u32 = 10;
+
+/// Maximum number of submitted bundles awaiting their first target block. Older ones are dropped.
+const MAX_PROBES: usize = 64;
+
+/// A submitted bundle, represented by one of its orders, awaiting its first target block.
+#[derive(Debug, Clone)]
+pub(super) struct Probe {
+    pub(super) order: SignedOrder,
+    pub(super) first_target_block: u64,
+}
+
+/// The block lead, tuned within configured bounds to whether submitted bundles land in their
+/// first target block: a miss lengthens it by [`STEP`], and [`HITS_PER_STEP`] hits in a row
+/// shorten it by the same.
+#[derive(Debug)]
+pub(super) struct AdaptiveLead {
+    config: AdaptiveLeadConfig,
+    current: Duration,
+    consecutive_hits: u32,
+    probes: VecDeque<Probe>,
+}
+
+impl AdaptiveLead {
+    pub(super) fn new(config: AdaptiveLeadConfig, initial: Duration) -> Self {
+        Self {
+            config,
+            current: initial.clamp(config.min(), config.max()),
+            consecutive_hits: 0,
+            probes: VecDeque::new(),
+        }
+    }
+
+    /// The current block lead.
+    pub(super) const fn current(&self) -> Duration {
+        self.current
+    }
+
+    /// Tracks a submitted bundle until its first target block has been produced.
+    pub(super) fn probe(&mut self, order: SignedOrder, first_target_block: u64) {
+        if self.probes.len() == MAX_PROBES {
+            self.probes.pop_front();
+        }
+        self.probes.push_back(Probe { order, first_target_block });
+    }
+
+    /// The probes whose first target block is at or below `head`. They stay tracked until
+    /// settled, so a check interrupted by the cycle deadline is made again next cycle.
+    pub(super) fn due(&self, head: u64) -> Vec<Probe> {
+        self.probes.iter().filter(|probe| probe.first_target_block <= head).cloned().collect()
+    }
+
+    /// Stops tracking a due probe and adjusts the lead for whether its bundle landed in its first
+    /// target block. A probe that was already settled is ignored.
+    pub(super) fn settle(&mut self, probe: &Probe, landed: bool) {
+        let Some(index) = self.probes.iter().position(|tracked| {
+            tracked.first_target_block == probe.first_target_block
+                && tracked.order.order_hash() == probe.order.order_hash()
+        }) else {
+            return;
+        };
+        self.probes.remove(index);
+        self.record(landed);
+    }
+
+    /// Adjusts the lead for a bundle that did or didn't land in its first target block.
+    fn record(&mut self, landed: bool) {
+        if landed {
+            self.consecutive_hits += 1;
+            if self.consecutive_hits >= HITS_PER_STEP {
+                self.consecutive_hits = 0;
+                self.current = self.current.saturating_sub(STEP).max(self.config.min());
+            }
+        } else {
+            self.consecutive_hits = 0;
+            self.current = (self.current + STEP).min(self.config.max());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use tokio::time::timeout;
+
+    #[test]
+    fn backs_off_on_miss_and_tightens_on_hits() {
+        let config =
+            AdaptiveLeadConfig::new(Duration::from_millis(1000), Duration::from_millis(1200));
+        let mut lead = AdaptiveLead::new(config, Duration::from_millis(1100));
+        lead.record(false);
+        assert_eq!(lead.current(), Duration::from_millis(1200));
+        lead.record(false);
+        assert_eq!(lead.current(), Duration::from_millis(1200));
+
+        for _ in 0..HITS_PER_STEP - 1 {
+            lead.record(true);
+        }
+        assert_eq!(lead.current(), Duration::from_millis(1200));
+        lead.record(true);
+        assert_eq!(lead.current(), Duration::from_millis(1100));
+        for _ in 0..HITS_PER_STEP * 5 {
+            lead.record(true);
+        }
+        assert_eq!(lead.current(), Duration::from_millis(1000));
+    }
+
+    #[tokio::test]
+    async fn probes_interrupted_mid_check_are_checked_again() {
+        let config =
+            AdaptiveLeadConfig::new(Duration::from_millis(1000), Duration::from_millis(1400));
+        let mut lead = AdaptiveLead::new(config, Duration::from_millis(1100));
+        for (nonce, first_target_block) in [(1, 10), (2, 11), (3, 20)] {
+            lead.probe(OrderBuilder::default().with_nonce(nonce).build(), first_target_block);
+        }
+
+        // The cycle deadline passes while the due probes are being checked.
+        let checks = async {
+            assert_eq!(lead.due(11).len(), 2);
+            std::future::pending::<()>().await;
+        };
+        assert!(timeout(Duration::ZERO, checks).await.is_err());
+
+        // Nothing was settled, so the next cycle checks the same probes.
+        let due = lead.due(11);
+        assert_eq!(due.len(), 2);
+        lead.settle(&due[0], false);
+        // Settling the same probe again doesn't count the miss twice.
+        lead.settle(&due[0], false);
+        assert_eq!(lead.current(), Duration::from_millis(1200));
+        lead.settle(&due[1], true);
+        assert!(lead.due(11).is_empty());
+        assert_eq!(lead.due(20).len(), 1);
+    }
+}
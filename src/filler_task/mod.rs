@@ -1,56 +1,193 @@
 use crate::{
-    AllowanceCache, FillProviderType, FillerContext, FixedPricingClient, FixedPricingError, metrics,
+    AllowanceCache, ArchiveBuffer, BootstrapMode, ChainWatch, Claim, Coordination,
+    CycleBudgetConfig, Decision, DegradationLadder, DeniedOwners, Dependency, FillHistory,
+    FillProviderType, FillThrottle, FillTransfers, FilledOrderStore, FillerContext,
+    FixedPricingClient, FixedPricingError, GasPriceCache, InventoryConfig, LandedOrders,
+    Leadership, LossLimit, OrderPricing, OrderSelectionPolicy, OrderSource, OwnerReputation,
+    OwnerStanding, PauseSwitch, PnlLedger, QuietSchedule, RiskEngine, SchedulingMode, Screener,
+    SignatureCheck, SpendDecision, SpendPolicy, TokenDiscovery, TokenFilterConfig, ToxicFlow,
+    check_signature, find_order_transaction, metrics,
 };
-use alloy::{primitives::B256, signers::Signer};
-use eyre::{Context, Report, Result, bail};
-use futures_util::{TryStreamExt, future::join_all};
+use alloy::{
+    primitives::{Address, B256, U256},
+    signers::Signer,
+};
+use eyre::{Context, Report, Result, bail, eyre};
+use futures_util::{Stream, StreamExt, TryStreamExt, future::join_all};
 use init4_bin_base::{
     deps::tracing::{Instrument, debug, error, info, info_span, instrument, trace, warn},
     utils::signer::LocalOrAws,
 };
 use lru::LruCache;
+use signet_constants::SignetSystemConstants;
 use signet_orders::{
-    FeePolicySubmitter, FillerOptions, OrderStreamExt, stream::predicates::not_expired_at,
+    FeePolicySubmitter, FillSubmitter, FillerOptions,
+    permit2::{IPermit2, PERMIT2, is_nonce_consumed},
 };
 use signet_tx_cache::TxCache;
 use signet_types::SignedOrder;
 use std::{
     cmp::Reverse,
-    collections::HashSet,
+    collections::{BTreeMap, HashMap, HashSet},
     num::NonZeroUsize,
-    sync::Mutex,
+    pin::pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
     time::{SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     select,
-    time::{Duration, Instant, MissedTickBehavior},
+    time::{Duration, Instant, MissedTickBehavior, timeout_at},
 };
 use tokio_util::sync::CancellationToken;
 
+mod budget;
+use budget::CycleBudget;
+
+mod filter;
+use filter::FilterPipeline;
+pub use filter::{FilterStage, OrderFilter, SkipReason};
+
+mod inclusion;
+use inclusion::{InclusionEstimator, expected_value};
+
+mod inventory;
+use inventory::Inventory;
+
+mod lead;
+use lead::AdaptiveLead;
+
+mod pending;
+use pending::PendingFills;
+
+mod sanity;
+
+mod scoring;
+use scoring::ScoringHook;
+
 mod preflight;
 use preflight::WorkingMap;
 
-const FILLED_ORDERS_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+mod receipt;
+use receipt::{FillWebhook, ReceiptBundle, TargetedSubmitter};
+
+mod retry;
+use retry::RetryingSubmitter;
+
+mod simulation;
+use simulation::{BundleSimulator, SimulatingSubmitter};
+
+mod unprofitable;
+use unprofitable::UnprofitableOrders;
+
+mod wallet;
+use wallet::WalletBalances;
+
+const SUBMITTED_ORDERS_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+const FIRST_SEEN_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+const CANDIDATE_CYCLES_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+const SIGNATURE_CHECKS_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+/// How many rollup blocks, up to the block a fill was confirmed in, are searched for the
+/// transaction it landed in.
+const FILL_TRANSACTION_LOOKBACK_BLOCKS: u64 = 32;
 /// Safety margin added to the Permit2 deadline to cover signing/network latency and clock drift
 /// between the filler and the host chain.
 const DEADLINE_DRIFT_BUFFER_SECS: u64 = 5;
 
-type Filler = signet_orders::Filler<
+type Filler<Source> = signet_orders::Filler<
     LocalOrAws,
-    TxCache,
-    FeePolicySubmitter<FillProviderType, FillProviderType, TxCache>,
+    Source,
+    FeePolicySubmitter<
+        FillProviderType,
+        FillProviderType,
+        SimulatingSubmitter<RetryingSubmitter<TargetedSubmitter<Source>>>,
+    >,
 >;
 
 /// Order filler service that submits fill bundles shortly before each block boundary.
+///
+/// Generic over the [`OrderSource`] orders are fetched from and bundles submitted to; this is the
+/// transaction cache unless constructed with [`FillerTask::with_order_source`].
 #[derive(Debug)]
-pub struct FillerTask {
-    filler: Filler,
-    pricing_client: FixedPricingClient,
+pub struct FillerTask<Source = TxCache> {
+    filler: Filler<Source>,
+    pricing_client: Arc<FixedPricingClient>,
+    /// Decides orders' profitability: the fixed-rate `pricing_client` unless replaced through
+    /// [`FillerTask::with_pricing`].
+    pricing: Arc<dyn OrderPricing>,
     allowance_cache: AllowanceCache,
-    filled_orders: Mutex<LruCache<B256, ()>>,
+    archive: Option<ArchiveBuffer>,
+    fill_throttle: Option<FillThrottle>,
+    spend_policy: Option<SpendPolicy>,
+    quiet_schedule: QuietSchedule,
+    pause: PauseSwitch,
+    cycle: AtomicU64,
+    /// Orders known to be filled, keyed by order hash.
+    filled_orders: Mutex<LruCache<B256, FilledOrder>>,
+    filled_order_store: Option<FilledOrderStore>,
+    coordination: Option<Coordination>,
+    leadership: Option<Leadership>,
+    landed_orders: LandedOrders,
+    chain_watch: ChainWatch,
+    /// Orders in successfully submitted bundles, keyed by order hash, with the instant by which
+    /// the bundle's target window has passed and the order should have been filled.
+    submitted_orders: Mutex<LruCache<B256, Instant>>,
+    pending_fills: Mutex<PendingFills>,
+    /// When each recent order was first fetched, for first-seen-first-filled selection.
+    first_seen: Mutex<LruCache<B256, Instant>>,
+    /// Whether each recent order's Permit2 signature was found valid, if signatures are checked.
+    /// The order hash covers the signature, so a result holds for as long as the order exists.
+    signature_checks: Option<Mutex<LruCache<B256, bool>>>,
+    order_selection_policy: OrderSelectionPolicy,
+    /// How many cycles each recent order has been a profitable candidate, for the aging boost.
+    candidate_cycles: Mutex<LruCache<B256, u32>>,
+    aging_boost_percent: Option<u16>,
+    reputation: OwnerReputation,
+    toxic_flow: Option<ToxicFlow>,
+    loss_limit: Option<LossLimit>,
+    risk: RiskEngine,
+    ledger: PnlLedger,
+    fill_history: FillHistory,
+    trusted_owners: HashSet<Address>,
+    denied_owners: DeniedOwners,
+    screener: Option<Screener>,
+    scoring_hook: Option<ScoringHook>,
+    degradation: DegradationLadder,
+    /// The orders from the last successful fetch, evaluated in a dry run while the order source
+    /// is down.
+    order_snapshot: Mutex<Option<Vec<SignedOrder>>>,
+    inclusion: Mutex<InclusionEstimator>,
+    min_expected_profit: Option<i128>,
+    min_margin_bps: Option<u16>,
+    unprofitable: Option<Mutex<UnprofitableOrders>>,
+    gas_prices: Option<GasPriceCache>,
+    token_discovery: Option<TokenDiscovery>,
+    simulation_min_notional: Option<U256>,
+    min_order_notional: Option<U256>,
+    max_order_notional: Option<U256>,
+    token_filter: TokenFilterConfig,
+    filter_pipeline: FilterPipeline,
+    fill_webhook: Option<FillWebhook>,
     target_blocks: u8,
     max_orders_per_bundle: Option<NonZeroUsize>,
+    bootstrap_mode: BootstrapMode,
+    scheduling_mode: SchedulingMode,
+    cycle_budget: CycleBudgetConfig,
+    inventory: Option<InventoryConfig>,
+    wallet_balances: WalletBalances,
     block_lead_duration: Duration,
+    /// How long fill signatures stay valid after signing.
+    signature_lifetime: Duration,
+    adaptive_lead: Option<Mutex<AdaptiveLead>>,
+    /// How often orders are evaluated into `ready_orders`, if evaluation runs apart from
+    /// submission.
+    evaluation_interval: Option<Duration>,
+    /// The latest continuous evaluation's selected orders, with their margins.
+    ready_orders: Mutex<Vec<(i128, SignedOrder)>>,
+    min_submission_lead: Duration,
+    follow_up_leads: Vec<Duration>,
     slot_duration: u64,
     host_start_timestamp: u64,
     app_start_instant: Instant,
@@ -58,46 +195,175 @@ pub struct FillerTask {
 }
 
 impl FillerTask {
-    /// Create a new filler from configuration.
+    /// Create a new filler from configuration, using the transaction cache as its order source.
     pub fn new(context: &FillerContext) -> Self {
+        Self::with_order_source(context, context.tx_cache().clone())
+    }
+}
+
+impl<Source: OrderSource> FillerTask<Source> {
+    /// Create a new filler from configuration that fetches orders from, and submits fill bundles
+    /// to, `order_source`.
+    pub fn with_order_source(context: &FillerContext, order_source: Source) -> Self {
+        let mut pricing_client = FixedPricingClient::new(
+            context.constants().system(),
+            context.chain_name(),
+            context.max_loss_percent(),
+        )
+        .with_token_table(context.token_table());
+        if let Some(twap_prices) = context.twap_prices() {
+            pricing_client = pricing_client.with_twap_prices(twap_prices.clone());
+        }
+        if let Some(spot_prices) = context.spot_prices() {
+            pricing_client = pricing_client.with_spot_prices(spot_prices.clone());
+        }
+        if let Some(max_divergence_bps) = context.price_cross_check_bps() {
+            pricing_client = pricing_client.with_cross_check(max_divergence_bps);
+        }
+        if let Some(ttl) = context.quote_cache_ttl() {
+            pricing_client = pricing_client.with_quote_cache(ttl);
+        }
+        if !context.volatility_haircuts().is_empty() {
+            pricing_client =
+                pricing_client.with_volatility_haircuts(context.volatility_haircuts().clone());
+        }
+        let pricing_client = Arc::new(pricing_client);
+
+        let simulator = context.simulation_min_notional().map(|_| {
+            BundleSimulator::new(
+                context.ru_provider().clone(),
+                context.host_provider().clone(),
+                context.signer().address(),
+                pricing_client.clone(),
+                context.constants().system().host().tokens().weth(),
+            )
+        });
         let submitter = FeePolicySubmitter::new(
             context.ru_provider().clone(),
             context.host_provider().clone(),
-            context.tx_cache().clone(),
+            SimulatingSubmitter::new(
+                RetryingSubmitter::new(
+                    TargetedSubmitter::new(order_source.clone()),
+                    *context.submission_retry(),
+                ),
+                simulator,
+            ),
             context.constants().system().clone(),
         );
 
+        let fill_webhook = context.fill_webhook_url().and_then(|url| {
+            FillWebhook::new(url.clone(), context.signer().clone())
+                .inspect_err(|error| {
+                    warn!(error = format!("{error:#}"), "fill webhook disabled");
+                })
+                .ok()
+        });
+
+        let scoring_hook = context.scoring_hook().and_then(|config| {
+            ScoringHook::new(config, context.signer().address())
+                .inspect_err(|error| {
+                    warn!(error = format!("{error:#}"), "scoring hook disabled");
+                })
+                .ok()
+        });
+
         let target_blocks = context.target_blocks();
         let slot_duration = context.constants().system().host().slot_duration();
-        let block_lead_duration = context.block_lead_duration();
-        // Keep the Permit2 signature valid from signing time (`block_lead_duration` before block N)
-        // through the end of block N+target_blocks-1, with an extra buffer for signing/network
-        // latency and clock drift.
-        let deadline_offset = block_lead_duration.as_secs()
-            + u64::from(target_blocks) * slot_duration
-            + DEADLINE_DRIFT_BUFFER_SECS;
+        // With an adaptive block lead, slots are scheduled from its upper bound, and each cycle
+        // waits from there until the current lead.
+        let block_lead_duration = context
+            .adaptive_lead()
+            .map_or(context.block_lead_duration(), |adaptive_lead| adaptive_lead.max());
+        // Unless configured, keep the Permit2 signature valid from signing time
+        // (`block_lead_duration` before block N) through the end of block N+target_blocks-1, with
+        // an extra buffer for signing/network latency and clock drift.
+        let deadline_offset = context.fill_deadline_offset().map_or_else(
+            || {
+                block_lead_duration.as_secs()
+                    + u64::from(target_blocks) * slot_duration
+                    + DEADLINE_DRIFT_BUFFER_SECS
+            },
+            |offset| offset.as_secs(),
+        );
         let filler = Filler::new(
             context.signer().clone(),
-            context.tx_cache().clone(),
+            order_source,
             submitter,
             context.constants().system().clone(),
             FillerOptions::new().with_deadline_offset(deadline_offset),
         );
 
-        let pricing_client = FixedPricingClient::new(
-            context.constants().system(),
-            context.chain_name(),
-            context.max_loss_percent(),
-        );
-
         Self {
             filler,
+            pricing: pricing_client.clone(),
             pricing_client,
             allowance_cache: context.allowance_cache().clone(),
-            filled_orders: Mutex::new(LruCache::new(FILLED_ORDERS_CACHE_SIZE)),
+            archive: context.archive_buffer().cloned(),
+            fill_throttle: context.fill_throttle().cloned(),
+            spend_policy: context.spend_policy().cloned(),
+            quiet_schedule: context.quiet_schedule().clone(),
+            pause: context.pause().clone(),
+            cycle: AtomicU64::new(0),
+            filled_orders: Mutex::new(load_filled_orders(
+                context.filled_order_store(),
+                context.filled_orders_cache_size(),
+            )),
+            filled_order_store: context.filled_order_store().cloned(),
+            coordination: context.coordination().cloned(),
+            leadership: context.leadership().cloned(),
+            landed_orders: context.landed_orders().clone(),
+            chain_watch: context.chain_watch().clone(),
+            submitted_orders: Mutex::new(LruCache::new(SUBMITTED_ORDERS_CACHE_SIZE)),
+            pending_fills: Mutex::new(PendingFills::default()),
+            first_seen: Mutex::new(LruCache::new(FIRST_SEEN_CACHE_SIZE)),
+            signature_checks: context
+                .verify_signatures()
+                .then(|| Mutex::new(LruCache::new(SIGNATURE_CHECKS_CACHE_SIZE))),
+            order_selection_policy: context.order_selection_policy(),
+            candidate_cycles: Mutex::new(LruCache::new(CANDIDATE_CYCLES_CACHE_SIZE)),
+            aging_boost_percent: context.aging_boost_percent(),
+            reputation: context.reputation().clone(),
+            toxic_flow: context.toxic_flow().cloned(),
+            loss_limit: context.loss_limit().cloned(),
+            risk: context.risk().clone(),
+            ledger: context.ledger().clone(),
+            fill_history: context.fill_history().clone(),
+            trusted_owners: context.trusted_owners().iter().copied().collect(),
+            denied_owners: context.denied_owners().clone(),
+            screener: context.screener().cloned(),
+            scoring_hook,
+            degradation: context.degradation().clone(),
+            order_snapshot: Mutex::new(None),
+            inclusion: Mutex::new(InclusionEstimator::default()),
+            min_expected_profit: context.min_expected_profit(),
+            min_margin_bps: context.min_margin_bps(),
+            unprofitable: context
+                .unprofitable_max_backoff_slots()
+                .map(|max_interval| Mutex::new(UnprofitableOrders::new(max_interval))),
+            gas_prices: context.gas_prices().cloned(),
+            token_discovery: context.token_discovery().cloned(),
+            simulation_min_notional: context.simulation_min_notional(),
+            min_order_notional: context.min_order_notional(),
+            max_order_notional: context.max_order_notional(),
+            token_filter: context.token_filter().clone(),
+            filter_pipeline: FilterPipeline::default(),
+            fill_webhook,
             target_blocks,
             max_orders_per_bundle: context.max_orders_per_bundle(),
+            bootstrap_mode: context.bootstrap_mode(),
+            scheduling_mode: context.scheduling_mode(),
+            cycle_budget: *context.cycle_budget(),
+            inventory: context.inventory().copied(),
+            wallet_balances: WalletBalances::new(context),
             block_lead_duration,
+            signature_lifetime: Duration::from_secs(deadline_offset),
+            adaptive_lead: context
+                .adaptive_lead()
+                .map(|config| Mutex::new(AdaptiveLead::new(config, context.block_lead_duration()))),
+            evaluation_interval: context.evaluation_interval(),
+            ready_orders: Mutex::new(Vec::new()),
+            min_submission_lead: context.min_submission_lead(),
+            follow_up_leads: context.follow_up_leads().to_vec(),
             slot_duration,
             host_start_timestamp: context.constants().system().host().start_timestamp(),
             app_start_instant: context.app_start_instant(),
@@ -105,6 +371,25 @@ impl FillerTask {
         }
     }
 
+    /// Adds a custom stage to the order filter pipeline, run after the built-in `after` stage and
+    /// any custom stages already added there. Orders it skips are counted and archived under its
+    /// [`SkipReason`].
+    pub fn with_order_filter(
+        mut self,
+        after: FilterStage,
+        filter: impl OrderFilter + 'static,
+    ) -> Self {
+        self.filter_pipeline.push(after, Box::new(filter));
+        self
+    }
+
+    /// Decides orders' profitability with `pricing` instead of the fixed-rate pricing client, such
+    /// as to drive the pipeline with scripted results in tests.
+    pub fn with_pricing(mut self, pricing: impl OrderPricing + 'static) -> Self {
+        self.pricing = Arc::new(pricing);
+        self
+    }
+
     /// Run the filler task to completion.
     ///
     /// Spawns the filler loop as a tokio task and supervises it, returning `Ok(())` on graceful
@@ -130,11 +415,28 @@ impl FillerTask {
             slot_duration_secs = self.slot_duration,
             block_lead_duration_ms = %self.block_lead_duration.as_millis(),
             target_blocks_count = self.target_blocks,
+            scheduling_mode = self.scheduling_mode.as_str(),
+            evaluation_interval_ms = ?self.evaluation_interval.map(|interval| interval.as_millis()),
             "starting filler task"
         );
+        let schedule = async {
+            match self.scheduling_mode {
+                SchedulingMode::Clock => self.run_clock_schedule().await,
+                SchedulingMode::Headers => self.run_header_schedule().await,
+            }
+        };
+        match self.evaluation_interval {
+            Some(interval) => {
+                tokio::join!(self.run_evaluation_loop(interval), schedule);
+            }
+            None => schedule.await,
+        }
+    }
 
+    /// Runs a cycle at the block lead before each slot boundary, computed from the host chain's
+    /// start timestamp and slot duration.
+    async fn run_clock_schedule(&self) {
         let slot_duration = Duration::from_secs(self.slot_duration);
-        let staleness_threshold = Duration::from_millis(100).max(self.block_lead_duration / 4);
         let first_tick = self.submission_anchor_instant();
         let mut interval = tokio::time::interval_at(first_tick, slot_duration);
         interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
@@ -142,6 +444,8 @@ impl FillerTask {
         // The first tick fires immediately with a large elapsed time; consume it.
         interval.tick().await;
 
+        self.run_bootstrap_cycle().await;
+
         loop {
             select! {
                 biased;
@@ -150,98 +454,815 @@ impl FillerTask {
                     break;
                 }
                 ticked_at = interval.tick() => {
-                    metrics::record_uptime(self.app_start_instant.elapsed());
-                    let staleness = ticked_at.elapsed();
-                    if staleness > staleness_threshold {
-                        warn!(
-                            staleness_ms = %staleness.as_millis(),
-                            "missed processing window, skipping cycle"
-                        );
-                        metrics::record_missed_window();
-                        continue;
-                    }
-                    if let Err(error) = self.process_orders().await {
-                        error!(%error, "error processing orders");
+                    if !self.run_slot(ticked_at).await {
+                        break;
                     }
                 }
             }
         }
     }
 
-    #[instrument(skip(self))]
-    async fn process_orders(&self) -> Result<()> {
+    /// Runs a cycle at the block lead before the boundary one slot after each new rollup block
+    /// header's timestamp, so the schedule follows the chain's actual block times rather than the
+    /// local clock's idea of them. No cycle runs for a slot whose header doesn't arrive.
+    async fn run_header_schedule(&self) {
+        let slot_duration = Duration::from_secs(self.slot_duration);
+        let mut heads = self.chain_watch.subscribe();
+        let mut last_boundary = None;
+
+        self.run_bootstrap_cycle().await;
+
+        loop {
+            let changed = select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("filler task cancelled");
+                    break;
+                }
+                changed = tokio::time::timeout(slot_duration * 2, heads.changed()) => changed,
+            };
+            if changed.is_err() {
+                warn!("no rollup block header for two slots, waiting for the next one");
+                metrics::record_missed_window();
+                continue;
+            }
+            let Some(head) = *heads.borrow_and_update() else {
+                continue;
+            };
+            let block_boundary =
+                instant_at(UNIX_EPOCH + Duration::from_secs(head.timestamp + self.slot_duration));
+            // A header replacing the head at the same height points at the same boundary.
+            if last_boundary.is_some_and(|last_boundary| block_boundary <= last_boundary) {
+                continue;
+            }
+            last_boundary = Some(block_boundary);
+            let start_at =
+                block_boundary.checked_sub(self.block_lead_duration).unwrap_or(block_boundary);
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => break,
+                _ = tokio::time::sleep_until(start_at) => {}
+            }
+            if !self.run_slot(start_at).await {
+                break;
+            }
+        }
+    }
+
+    /// Runs the cycles for the slot whose boundary is the block lead after `start_at`, unless the
+    /// task woke too long after `start_at` to act on it. Returns `false` if cancelled.
+    async fn run_slot(&self, start_at: Instant) -> bool {
+        metrics::record_uptime(self.app_start_instant.elapsed());
+        let staleness_threshold = Duration::from_millis(100).max(self.block_lead_duration / 4);
+        let staleness = start_at.elapsed();
+        if staleness > staleness_threshold {
+            warn!(staleness_ms = %staleness.as_millis(), "missed processing window, skipping cycle");
+            metrics::record_missed_window();
+            return true;
+        }
+        let block_boundary = start_at + self.block_lead_duration;
+        if !self.wait_for_adaptive_lead(block_boundary).await {
+            return false;
+        }
+        let quiet = self.in_quiet_period(block_boundary);
+        if let Err(error) = self.process_orders(block_boundary, quiet).await {
+            error!(%error, "error processing orders");
+        }
+        self.run_follow_up_attempts(block_boundary, quiet).await;
+        true
+    }
+
+    /// Waits until the current adaptive block lead before `block_boundary`, if the adaptive lead
+    /// is enabled. Returns `false` if cancelled while waiting.
+    async fn wait_for_adaptive_lead(&self, block_boundary: Instant) -> bool {
+        let Some(adaptive_lead) = &self.adaptive_lead else {
+            return true;
+        };
+        let lead = adaptive_lead.lock().unwrap().current();
+        metrics::record_block_lead(lead);
+        let Some(start_at) = block_boundary.checked_sub(lead) else {
+            return true;
+        };
+        select! {
+            biased;
+            _ = self.cancellation_token.cancelled() => false,
+            _ = tokio::time::sleep_until(start_at) => true,
+        }
+    }
+
+    /// Tunes the adaptive block lead, if enabled, by checking whether bundles whose first target
+    /// block has been produced landed in it. A bundle counts as landed if its probe order's nonce
+    /// was consumed by that block, whoever filled it.
+    async fn tune_block_lead(&self) {
+        let Some(adaptive_lead) = &self.adaptive_lead else {
+            return;
+        };
+        let Some(head) = self.chain_watch.head() else {
+            return;
+        };
+        let due = adaptive_lead.lock().unwrap().due(head);
+        if due.is_empty() {
+            return;
+        }
+        let checks = join_all(
+            due.iter()
+                .map(|probe| self.is_nonce_consumed(&probe.order, Some(probe.first_target_block))),
+        )
+        .await;
+        let mut adaptive_lead = adaptive_lead.lock().unwrap();
+        for (probe, check) in due.iter().zip(checks) {
+            match check {
+                Ok(landed) => adaptive_lead.settle(probe, landed),
+                Err(error) => {
+                    warn!(
+                        %error,
+                        "failed to check bundle inclusion for the adaptive block lead, checking \
+                         again next cycle"
+                    );
+                    metrics::record_nonce_check_error();
+                }
+            }
+        }
+        debug!(lead_ms = %adaptive_lead.current().as_millis(), "tuned block lead");
+    }
+
+    /// Runs a further cycle at each configured follow-up lead before `block_boundary`, picking up
+    /// orders that arrived since the slot's earlier attempts. Orders submitted in an earlier
+    /// attempt are left out as pending fills. An attempt whose time has already passed, because
+    /// an earlier one ran long, is skipped.
+    async fn run_follow_up_attempts(&self, block_boundary: Instant, dry_run: bool) {
+        for lead in &self.follow_up_leads {
+            let Some(attempt_at) = block_boundary.checked_sub(*lead) else {
+                continue;
+            };
+            if Instant::now() >= attempt_at {
+                debug!(lead_ms = %lead.as_millis(), "skipping follow-up attempt, already past it");
+                continue;
+            }
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => return,
+                _ = tokio::time::sleep_until(attempt_at) => {}
+            }
+            debug!(lead_ms = %lead.as_millis(), "running follow-up submission attempt");
+            if let Err(error) = self.process_orders(block_boundary, dry_run).await {
+                error!(%error, "error processing orders in follow-up attempt");
+            }
+        }
+    }
+
+    /// Runs one cycle straight away, rather than idling for up to a full slot until the first
+    /// submission anchor, so a freshly started filler warms its filled-order cache and token
+    /// budgets and can act on the very next boundary. In dry-run mode nothing is submitted.
+    ///
+    /// With continuous evaluation, the evaluation loop's first pass warms up instead.
+    async fn run_bootstrap_cycle(&self) {
+        if self.evaluation_interval.is_some() {
+            return;
+        }
+        let dry_run = match self.bootstrap_mode {
+            BootstrapMode::Off => return,
+            BootstrapMode::DryRun => true,
+            BootstrapMode::Live => false,
+        };
+        info!(mode = self.bootstrap_mode.as_str(), "running bootstrap cycle");
+        let block_boundary = next_block_boundary(
+            self.submission_anchor_instant() + self.block_lead_duration,
+            Instant::now(),
+            Duration::from_secs(self.slot_duration),
+        );
+        let dry_run = self.in_quiet_period(block_boundary) || dry_run;
+        select! {
+            biased;
+            _ = self.cancellation_token.cancelled() => {
+                debug!("bootstrap cycle cancelled");
+            }
+            result = self.process_orders(block_boundary, dry_run) => {
+                if let Err(error) = result {
+                    error!(%error, "error processing orders in bootstrap cycle");
+                }
+            }
+        }
+    }
+
+    /// Runs one processing cycle for the slot ending at `block_boundary`. When `dry_run` is set,
+    /// orders are fetched, scored, and checked but no bundle is submitted and no fill-rate
+    /// throttle capacity is consumed. A cycle that had to fall back to the last order snapshot is
+    /// always a dry run.
+    ///
+    /// With continuous evaluation, the cycle submits the orders in the ready-to-fill set instead
+    /// of evaluating them itself.
+    #[instrument(skip(self, block_boundary))]
+    async fn process_orders(&self, block_boundary: Instant, dry_run: bool) -> Result<()> {
         let _cycle_guard = metrics::CycleGuard::new();
+        if self.evaluation_interval.is_some() {
+            let dry_run = self.is_dry_run(dry_run);
+            let orders_to_fill = self.take_ready_orders(dry_run);
+            self.submit_selected_orders(orders_to_fill, block_boundary, dry_run).await;
+            return Ok(());
+        }
+        let budget = CycleBudget::new(&self.cycle_budget, self.evaluation_deadline(block_boundary));
+        self.start_evaluation(&budget).await;
 
-        let scored = self.fetch_and_score_orders().await?;
+        let scored = self.fetch_and_score_orders(&budget).await?;
         if scored.is_empty() {
             return Ok(());
         }
+        let dry_run = self.is_dry_run(dry_run);
 
-        let orders_to_fill = self.select_fillable_orders(scored).await;
+        let nonce_check_started = Instant::now();
+        let orders_to_fill = self.select_fillable_orders(scored, dry_run, &budget).await;
+        metrics::record_stage_duration(
+            metrics::PipelineStage::NonceCheck,
+            nonce_check_started.elapsed(),
+        );
         if orders_to_fill.is_empty() {
             info!("no fillable orders after budget and nonce checks");
             return Ok(());
         }
-
-        self.submit_bundles(orders_to_fill).await;
+        self.submit_selected_orders(orders_to_fill, block_boundary, dry_run).await;
         Ok(())
     }
 
-    /// Fetches orders from the tx cache, filters out known-filled orders, scores by profitability,
-    /// and returns candidates sorted most-profitable-first.
+    /// Starts an evaluation pass: advances the cycle count, evicts stale filled-order cache
+    /// entries, confirms the inclusion of earlier submissions, and refreshes wallet balances.
+    async fn start_evaluation(&self, budget: &CycleBudget) {
+        self.cycle.fetch_add(1, Ordering::Relaxed);
+        self.evict_reorged_fills();
+        self.evict_expired_fills();
+        // Inclusion checks left unfinished at the deadline are picked up again next cycle: pending
+        // fills and lead probes are only forgotten once they have been checked.
+        let inclusion_checks = async {
+            self.confirm_pending_fills().await;
+            self.tune_block_lead().await;
+        };
+        if timeout_at(budget.deadline(), inclusion_checks).await.is_err() {
+            budget.exhaust(metrics::BudgetKind::WallTime);
+        }
+        self.settle_markouts();
+        if let Some(loss_limit) = &self.loss_limit {
+            loss_limit.roll_over();
+        }
+        self.wallet_balances.refresh();
+    }
+
+    /// Whether a cycle must not submit: because it was asked to be a dry run, submission is
+    /// paused, this replica is a standby, or the order source is down so only the last snapshot
+    /// could be evaluated.
+    fn is_dry_run(&self, dry_run: bool) -> bool {
+        let paused = self.pause.state();
+        if let Some(paused) = &paused {
+            info!(reason = %paused.reason, "submission paused, evaluating orders without submitting");
+        }
+        let standby = self.leadership.as_ref().is_some_and(|leadership| !leadership.is_leader());
+        if standby {
+            debug!("standing by for the leader, evaluating orders without submitting");
+        }
+        dry_run
+            || paused.is_some()
+            || standby
+            || self.degradation.is_degraded(Dependency::OrderSource)
+    }
+
+    /// Runs an evaluation pass every `interval`, fetching, pricing, and nonce-checking orders
+    /// into the ready-to-fill set that submission cycles draw on, so pricing and RPC latency is
+    /// off the submission path. Each pass replaces the set.
+    async fn run_evaluation_loop(&self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("evaluation loop cancelled");
+                    break;
+                }
+                _ = ticker.tick() => {
+                    if let Err(error) = self.evaluate_ready_orders(interval).await {
+                        error!(%error, "error evaluating orders");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Evaluates orders into the ready-to-fill set, within `interval` or the configured maximum
+    /// cycle wall time if shorter. Fill-rate throttle capacity is reserved at submission instead.
     #[instrument(skip_all)]
-    async fn fetch_and_score_orders(&self) -> Result<Vec<(i128, SignedOrder)>> {
-        let mut orders_count = 0_u64;
-        let mut orders_after_expiry_filter = 0_u64;
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("system clock set before UNIX epoch")
-            .as_secs();
-        // Subtract the drift buffer for symmetry with the sign-side deadline, so an order whose
-        // deadline is within the buffer of the first target block isn't prematurely dropped here
-        // while the sign path would still have accepted it.
-        let earliest_fill_timestamp =
-            (now + self.block_lead_duration.as_secs()).saturating_sub(DEADLINE_DRIFT_BUFFER_SECS);
-
-        let not_expired = not_expired_at(move || earliest_fill_timestamp);
-        let not_expired_with_metric = move |order: &SignedOrder| {
-            let kept = not_expired(order);
-            if !kept {
-                trace!(
+    async fn evaluate_ready_orders(&self, interval: Duration) -> Result<()> {
+        let wall_time = self.cycle_budget.max_wall_time().map_or(interval, |max| max.min(interval));
+        let budget = CycleBudget::new(&self.cycle_budget, Instant::now() + wall_time);
+        self.start_evaluation(&budget).await;
+        let scored = self.fetch_and_score_orders(&budget).await;
+        let ready = match scored {
+            Ok(scored) if !scored.is_empty() => {
+                let nonce_check_started = Instant::now();
+                let ready = self.select_fillable_orders(scored, true, &budget).await;
+                metrics::record_stage_duration(
+                    metrics::PipelineStage::NonceCheck,
+                    nonce_check_started.elapsed(),
+                );
+                ready
+            }
+            Ok(_empty) => Vec::new(),
+            Err(error) => {
+                self.ready_orders.lock().unwrap().clear();
+                metrics::record_ready_orders(0);
+                return Err(error);
+            }
+        };
+        debug!(ready = ready.len(), "evaluated ready-to-fill orders");
+        metrics::record_ready_orders(ready.len());
+        *self.ready_orders.lock().unwrap() = ready;
+        Ok(())
+    }
+
+    /// The ready-to-fill orders still fillable now: those not expired by the next target block,
+    /// seen filled, pending, or from an owner denied since they were evaluated. Each order dropped
+    /// is recorded as skipped. Outside a dry run, fill-rate throttle capacity is reserved for each
+    /// order taken.
+    fn take_ready_orders(&self, dry_run: bool) -> Vec<(i128, SignedOrder)> {
+        let ready = self.ready_orders.lock().unwrap().clone();
+        let earliest_fill_timestamp = self.earliest_fill_timestamp();
+        ready
+            .into_iter()
+            .filter(|(_margin, order)| {
+                let reason = self
+                    .check_expiry(order, earliest_fill_timestamp)
+                    .or_else(|| self.filled_or_pending(order))
+                    .or_else(|| self.check_owner_denied(order));
+                if let Some(reason) = reason {
+                    self.record_skip(order, reason);
+                }
+                reason.is_none()
+            })
+            .filter(|(_margin, order)| dry_run || self.acquire_fill_capacity(order))
+            .collect()
+    }
+
+    /// Submits the orders selected for the cycle ending at `block_boundary`, most profitable
+    /// first, after dropping those whose bundles wouldn't cover their gas and those claimed by
+    /// another replica. Does nothing but log in a dry run.
+    async fn submit_selected_orders(
+        &self,
+        orders_to_fill: Vec<(i128, SignedOrder)>,
+        block_boundary: Instant,
+        dry_run: bool,
+    ) {
+        if orders_to_fill.is_empty() {
+            return;
+        }
+        let orders_to_fill = self.drop_unprofitable_bundle_orders(orders_to_fill, dry_run);
+        if orders_to_fill.is_empty() {
+            return;
+        }
+        if dry_run {
+            info!(orders_to_fill = orders_to_fill.len(), "dry run, skipping bundle submission");
+            return;
+        }
+        let orders_to_fill = self.claim_orders(orders_to_fill).await;
+        if orders_to_fill.is_empty() {
+            return;
+        }
+
+        self.submit_bundles(orders_to_fill, block_boundary).await;
+    }
+
+    /// Whether the block at `block_boundary` falls in a scheduled quiet period, in which case the
+    /// cycle runs as a dry run. Updates the quiet-period gauge.
+    fn in_quiet_period(&self, block_boundary: Instant) -> bool {
+        let period = self.quiet_schedule.active_at(block_boundary);
+        metrics::record_quiet_period_active(period.is_some());
+        if let Some(period) = period {
+            info!(%period, "in quiet period, evaluating orders without submitting");
+        }
+        period.is_some()
+    }
+
+    /// The instant by which order evaluation must stop: the configured maximum cycle wall time,
+    /// but never later than the minimum submission lead time before `block_boundary`.
+    fn evaluation_deadline(&self, block_boundary: Instant) -> Instant {
+        let latest = block_boundary.checked_sub(self.min_submission_lead).unwrap_or(block_boundary);
+        self.cycle_budget
+            .max_wall_time()
+            .map_or(latest, |max_wall_time| latest.min(Instant::now() + max_wall_time))
+    }
+
+    /// Checks that the selected orders' total margin covers the fixed gas cost of the bundles
+    /// they'll be chunked into. If it doesn't, keeps the leading orders with the greatest total
+    /// profit after bundle gas, possibly none, and skips the rest, releasing their fill capacity
+    /// outside a dry run. Dropping from the end keeps the kept orders' chunks, and so their
+    /// nonces, contiguous. A no-op unless bundle gas is estimated.
+    fn drop_unprofitable_bundle_orders(
+        &self,
+        mut orders_to_fill: Vec<(i128, SignedOrder)>,
+        dry_run: bool,
+    ) -> Vec<SignedOrder> {
+        if let Some(bundle_gas_cost) =
+            self.gas_prices.as_ref().and_then(GasPriceCache::bundle_gas_cost)
+        {
+            let margins: Vec<i128> =
+                orders_to_fill.iter().map(|(margin, _order)| *margin).collect();
+            let (kept, profit) =
+                profitable_prefix(&margins, bundle_gas_cost, self.max_orders_per_bundle);
+            if kept < orders_to_fill.len() {
+                info!(
+                    selected = orders_to_fill.len(),
+                    kept,
+                    profit,
+                    bundle_gas_cost,
+                    "bundle gas exceeds total margin, dropping least-preferred orders"
+                );
+                for (_margin, order) in orders_to_fill.split_off(kept) {
+                    self.record_skip(&order, metrics::OrderSkippedReason::UnprofitableBundle);
+                    if !dry_run {
+                        self.release_fill_capacity(&order);
+                    }
+                }
+            }
+        }
+        orders_to_fill.into_iter().map(|(_margin, order)| order).collect()
+    }
+
+    /// Claims each order in the in-flight set shared with other filler replicas, until its fill's
+    /// target window has passed, and skips those another replica has claimed or seen filled. An
+    /// order whose claim fails is kept, so a Redis outage doesn't stop fills. A no-op unless
+    /// replicas coordinate through Redis.
+    async fn claim_orders(&self, orders_to_fill: Vec<SignedOrder>) -> Vec<SignedOrder> {
+        let Some(coordination) = &self.coordination else {
+            return orders_to_fill;
+        };
+        let ttl = self.block_lead_duration
+            + Duration::from_secs(self.slot_duration * (u64::from(self.target_blocks) + 1));
+        let claims = join_all(
+            orders_to_fill.iter().map(|order| coordination.claim_order(*order.order_hash(), ttl)),
+        )
+        .await;
+        orders_to_fill
+            .into_iter()
+            .zip(claims)
+            .filter(|(order, claim)| match claim {
+                Ok(Claim::Claimed) => true,
+                Ok(Claim::HeldByPeer) => {
+                    debug!(order_hash = %order.order_hash(), "order claimed by another replica");
+                    self.record_skip(order, metrics::OrderSkippedReason::ClaimedByPeer);
+                    self.release_fill_capacity(order);
+                    false
+                }
+                Ok(Claim::Filled) => {
+                    debug!(order_hash = %order.order_hash(), "order filled per another replica");
+                    // The peer's fill block is unknown, but no later than the current head.
+                    let block_number = self.chain_watch.head().unwrap_or(u64::MAX);
+                    let filled = FilledOrder::new(order, block_number);
+                    self.filled_orders.lock().unwrap().put(*order.order_hash(), filled);
+                    self.record_skip(order, metrics::OrderSkippedReason::AlreadyFilled);
+                    self.release_fill_capacity(order);
+                    false
+                }
+                Err(error) => {
+                    warn!(
+                        order_hash = %order.order_hash(),
+                        error = format!("{error:#}"),
+                        "failed to claim order, submitting anyway"
+                    );
+                    metrics::record_coordination_error();
+                    true
+                }
+            })
+            .map(|(order, _claim)| order)
+            .collect()
+    }
+
+    /// Releases this replica's claims on orders it claimed but won't submit after all, so other
+    /// replicas needn't wait for the claims to expire. A no-op unless replicas coordinate through
+    /// Redis.
+    async fn release_claims(&self, orders: &[SignedOrder]) {
+        let Some(coordination) = &self.coordination else {
+            return;
+        };
+        let releases =
+            join_all(orders.iter().map(|order| coordination.release_order(*order.order_hash())))
+                .await;
+        for (order, result) in orders.iter().zip(releases) {
+            if let Err(error) = result {
+                warn!(
                     order_hash = %order.order_hash(),
-                    deadline = %order.permit().permit.deadline,
-                    earliest_fill_timestamp,
-                    "skipping expired order"
+                    error = format!("{error:#}"),
+                    "failed to release order claim"
                 );
-                metrics::record_order_skipped(metrics::OrderSkippedReason::Expired);
+                metrics::record_coordination_error();
             }
-            kept
+        }
+    }
+
+    /// Counts this cycle towards the order's candidate cycles and, under profit-max selection with
+    /// the aging boost, returns `margin` boosted for each earlier cycle it was a candidate in.
+    fn aged_margin(&self, order: &SignedOrder, margin: i128) -> i128 {
+        let Some(boost_percent) = self.aging_boost_percent else {
+            return margin;
         };
-        let filled_orders = &self.filled_orders;
-        let not_in_filled_cache = move |order: &SignedOrder| {
-            let cached = filled_orders.lock().unwrap().contains(order.order_hash());
-            if cached {
-                trace!(order_hash = %order.order_hash(), "skipping cached filled order");
-                metrics::record_order_skipped(metrics::OrderSkippedReason::AlreadyFilled);
-                false
-            } else {
-                true
+        if self.order_selection_policy != OrderSelectionPolicy::ProfitMax {
+            return margin;
+        }
+        let mut candidate_cycles = self.candidate_cycles.lock().unwrap();
+        let cycles = candidate_cycles.get_or_insert_mut(*order.order_hash(), || 0);
+        let unfilled_cycles = *cycles;
+        *cycles = cycles.saturating_add(1);
+        boosted_margin(margin, unfilled_cycles, boost_percent)
+    }
+
+    /// Estimated host gas cost of an order with host-chain outputs, in normalized 18-decimal USD,
+    /// valuing host ETH at the host WETH price. Ignored, like the rollup gas cost, until the fee
+    /// oracle's first host estimate, or if host ETH can't be valued.
+    fn host_order_gas_cost(&self) -> i128 {
+        let Some(wei) = self.gas_prices.as_ref().and_then(GasPriceCache::host_order_gas_cost)
+        else {
+            return 0;
+        };
+        let host_weth = self.filler.constants().host().tokens().weth();
+        match self.pricing_client.value(&host_weth, wei) {
+            Ok(value) => i128::try_from(value).unwrap_or(i128::MAX),
+            Err(error) => {
+                warn!(%error, "failed to value host gas cost, ignoring it");
+                0
             }
+        }
+    }
+
+    /// Snapshots the filler's inventory for this cycle, if inventory-aware pricing is configured.
+    async fn query_inventory(&self, budget: &CycleBudget) -> Option<Inventory> {
+        let config = self.inventory?;
+        Inventory::query(
+            config,
+            self.filler.signer().address(),
+            self.filler.submitter().ru_provider(),
+            self.filler.submitter().host_provider(),
+            self.filler.constants(),
+            &self.pricing_client,
+            budget,
+        )
+        .await
+    }
+
+    /// The earliest timestamp at which a fill submitted now could land: that of the first block,
+    /// by the host chain's slot schedule, still at least the minimum submission lead away. An
+    /// order whose Permit2 deadline is earlier can never be filled. The drift buffer is
+    /// subtracted for symmetry with the sign-side deadline, so an order whose deadline is within
+    /// the buffer of the first target block isn't prematurely dropped here while the sign path
+    /// would still have accepted it.
+    fn earliest_fill_timestamp(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock set before UNIX epoch");
+        let target_timestamp = slot_boundary_at_or_after(
+            now + self.min_submission_lead,
+            self.host_start_timestamp,
+            self.slot_duration,
+        );
+        target_timestamp.saturating_sub(DEADLINE_DRIFT_BUFFER_SECS)
+    }
+
+    /// Runs a built-in stage of the order filter pipeline on the order, returning why it should
+    /// be skipped, if it should.
+    fn check_stage(
+        &self,
+        stage: FilterStage,
+        order: &SignedOrder,
+        earliest_fill_timestamp: u64,
+    ) -> Option<metrics::OrderSkippedReason> {
+        match stage {
+            FilterStage::Expiry => self.check_expiry(order, earliest_fill_timestamp),
+            FilterStage::FilledCache => self.filled_or_pending(order),
+            FilterStage::DeniedOwners => self.check_owner_denied(order),
+            FilterStage::Sanity => self.check_sanity(order),
+            FilterStage::TokenLists => self.check_tokens(order),
+            FilterStage::NotionalBounds => self.check_notional(order),
+            FilterStage::Signature => self.check_order_signature(order),
+        }
+    }
+
+    /// Whether the order expires before `earliest_fill_timestamp`.
+    fn check_expiry(
+        &self,
+        order: &SignedOrder,
+        earliest_fill_timestamp: u64,
+    ) -> Option<metrics::OrderSkippedReason> {
+        if !order.is_expired_at(earliest_fill_timestamp) {
+            return None;
+        }
+        trace!(
+            order_hash = %order.order_hash(),
+            deadline = %order.permit().permit.deadline,
+            earliest_fill_timestamp,
+            "skipping expired order"
+        );
+        Some(metrics::OrderSkippedReason::Expired)
+    }
+
+    /// Whether the order is known to be filled, from the filled-order cache or an `Order` event,
+    /// or has a pending fill.
+    fn filled_or_pending(&self, order: &SignedOrder) -> Option<metrics::OrderSkippedReason> {
+        if self.filled_orders.lock().unwrap().contains(order.order_hash()) {
+            trace!(order_hash = %order.order_hash(), "skipping cached filled order");
+            Some(metrics::OrderSkippedReason::AlreadyFilled)
+        } else if let Some(block_number) = self.landed_orders.take(order) {
+            trace!(order_hash = %order.order_hash(), "skipping order seen landing");
+            let bundles = self.pending_fills.lock().unwrap().bundles(order.order_hash());
+            self.send_fill_receipt(order, &bundles, block_number);
+            self.record_filled(order, block_number);
+            Some(metrics::OrderSkippedReason::AlreadyFilled)
+        } else if self.pending_fills.lock().unwrap().is_pending(order.order_hash(), Instant::now())
+        {
+            trace!(order_hash = %order.order_hash(), "skipping order with a pending fill");
+            Some(metrics::OrderSkippedReason::PendingFill)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the order's owner is on the owner denylist.
+    fn check_owner_denied(&self, order: &SignedOrder) -> Option<metrics::OrderSkippedReason> {
+        let owner = order.permit().owner;
+        if !self.denied_owners.contains(&owner) {
+            return None;
+        }
+        debug!(order_hash = %order.order_hash(), %owner, "skipping order from denied owner");
+        Some(metrics::OrderSkippedReason::DeniedOwner)
+    }
+
+    /// The orders none of whose parties are screened, when screening is configured. Orders with
+    /// a screened party, or a party that couldn't be screened, are skipped.
+    async fn screen_orders(&self, orders: Vec<SignedOrder>) -> Vec<SignedOrder> {
+        let Some(screener) = &self.screener else {
+            return orders;
         };
+        let screening_started = Instant::now();
+        let results = join_all(orders.iter().map(|order| screener.screened_party(order))).await;
+        let orders = orders
+            .into_iter()
+            .zip(results)
+            .filter_map(|(order, result)| match result {
+                Ok(None) => Some(order),
+                Ok(Some(party)) => {
+                    warn!(order_hash = %order.order_hash(), %party, "skipping order with screened party");
+                    self.record_skip(&order, metrics::OrderSkippedReason::Screened);
+                    None
+                }
+                Err(_) => {
+                    debug!(order_hash = %order.order_hash(), "skipping order that couldn't be screened");
+                    self.record_skip(&order, metrics::OrderSkippedReason::ScreeningFailed);
+                    None
+                }
+            })
+            .collect();
+        metrics::record_stage_duration(
+            metrics::PipelineStage::Screening,
+            screening_started.elapsed(),
+        );
+        orders
+    }
+
+    /// Whether the order fails the sanity checks, recording the defect if so.
+    fn check_sanity(&self, order: &SignedOrder) -> Option<metrics::OrderSkippedReason> {
+        let constants = self.filler.constants();
+        let defect =
+            sanity::find_defect(order, [constants.ru_chain_id(), constants.host_chain_id()])?;
+        debug!(order_hash = %order.order_hash(), defect = defect.as_str(), "skipping malformed order");
+        metrics::record_malformed_order(defect);
+        Some(metrics::OrderSkippedReason::Malformed)
+    }
+
+    /// Whether an input or output token of the order isn't allowed by the operator's token lists.
+    fn check_tokens(&self, order: &SignedOrder) -> Option<metrics::OrderSkippedReason> {
+        let (input, token) = sanity::find_disallowed_token(order, &self.token_filter)?;
+        debug!(
+            order_hash = %order.order_hash(),
+            %token,
+            side = if input { "input" } else { "output" },
+            "skipping order with a disallowed token"
+        );
+        Some(metrics::OrderSkippedReason::DisallowedToken)
+    }
+
+    /// Whether the order's output notional is outside the configured bounds. An order whose
+    /// outputs can't be valued is left to the pricing stage.
+    fn check_notional(&self, order: &SignedOrder) -> Option<metrics::OrderSkippedReason> {
+        if self.min_order_notional.is_none() && self.max_order_notional.is_none() {
+            return None;
+        }
+        let notional = self.pricing_client.output_value(order).ok()?;
+        let reason =
+            sanity::check_notional(notional, self.min_order_notional, self.max_order_notional)?;
+        debug!(
+            order_hash = %order.order_hash(),
+            %notional,
+            reason = reason.as_str(),
+            "skipping order outside the notional bounds"
+        );
+        Some(reason)
+    }
+
+    /// Whether the order's Permit2 signature doesn't recover to its owner. Never skips if
+    /// signatures aren't checked or the owner is trusted. Permit2 accepts contract-wallet
+    /// (ERC-1271) signatures that can't be checked locally, so such orders only pass with the
+    /// check disabled or from trusted owners.
+    fn check_order_signature(&self, order: &SignedOrder) -> Option<metrics::OrderSkippedReason> {
+        let signature_checks = self.signature_checks.as_ref()?;
+        let valid = has_valid_signature(
+            order,
+            self.filler.constants(),
+            &self.trusted_owners,
+            signature_checks,
+        );
+        (!valid).then_some(metrics::OrderSkippedReason::InvalidSignature)
+    }
+
+    /// Fetches orders from the tx cache (or, if that fails, takes the last fetched orders), filters
+    /// out expired and known-filled orders, scores by profitability until the cycle's quote
+    /// budget runs out, and returns candidates sorted most-profitable-first. Margins are net of
+    /// the fee oracle's rollup (and, for orders with host outputs, host) gas cost estimates, if
+    /// enabled. With inventory-aware pricing, margins include the inventory snapshot's bonus or
+    /// penalty, and skew-increasing orders whose margin doesn't cover the penalty are skipped.
+    #[instrument(skip_all)]
+    async fn fetch_and_score_orders(
+        &self,
+        budget: &CycleBudget,
+    ) -> Result<Vec<(i128, SignedOrder)>> {
+        let mut orders_count = 0_u64;
+        let mut skipped: BTreeMap<&'static str, u64> = BTreeMap::new();
+        let earliest_fill_timestamp = self.earliest_fill_timestamp();
 
-        let orders: Vec<SignedOrder> = self
-            .filler
-            .get_orders()
-            .inspect_ok(|_| orders_count += 1)
-            .filter_orders(not_expired_with_metric)
-            .inspect_ok(|_| orders_after_expiry_filter += 1)
-            .filter_orders(not_in_filled_cache)
-            .try_collect()
+        let fetched_at = SystemTime::now();
+        let fetch_started = Instant::now();
+        let mut fetched_hashes = Vec::new();
+        let fetch = self.filler.get_orders().inspect_ok(|order| {
+            orders_count += 1;
+            if let Some(archive) = &self.archive {
+                archive.record_order(order);
+                fetched_hashes.push(*order.order_hash());
+            }
+        });
+        let fetched = collect_until(fetch, budget.deadline())
             .await
             .inspect_err(|_| metrics::record_fetch_order_error())
-            .wrap_err("failed to fetch orders")?;
+            .wrap_err("failed to fetch orders");
+        metrics::record_stage_duration(metrics::PipelineStage::Fetch, fetch_started.elapsed());
+        let fetched = match fetched {
+            Ok((orders, true)) => {
+                metrics::record_orders_fetched(orders_count);
+                if let Some(archive) = &self.archive {
+                    archive.record_intake(
+                        self.cycle.load(Ordering::Relaxed),
+                        fetched_at,
+                        fetch_started.elapsed(),
+                        fetched_hashes,
+                    );
+                }
+                self.fall_back_to_snapshot(Ok(orders))?
+            }
+            // A partial fetch neither replaces the snapshot nor marks the order source healthy.
+            Ok((orders, false)) => {
+                warn!(
+                    orders_fetched = orders.len(),
+                    "order fetch ran past the cycle deadline, evaluating the orders fetched so far"
+                );
+                budget.exhaust(metrics::BudgetKind::WallTime);
+                orders
+            }
+            Err(error) => self.fall_back_to_snapshot(Err(error))?,
+        };
+        orders_count = fetched.len() as u64;
+        {
+            let now = Instant::now();
+            let mut first_seen = self.first_seen.lock().unwrap();
+            for order in &fetched {
+                first_seen.get_or_insert(*order.order_hash(), || now);
+            }
+        }
 
-        metrics::record_orders_fetched(orders_count);
+        let filter_started = Instant::now();
+        let orders: Vec<SignedOrder> = {
+            let builtins = FilterStage::ALL.map(|stage| BuiltinFilter {
+                task: self,
+                stage,
+                earliest_fill_timestamp,
+            });
+            let filters = self.filter_pipeline.stages(&builtins);
+            fetched
+                .into_iter()
+                .filter(|order| {
+                    let reason = filters.iter().find_map(|filter| filter.check(order));
+                    if let Some(reason) = reason {
+                        *skipped.entry(reason.0.as_str()).or_default() += 1;
+                        self.record_skip(order, reason.0);
+                    }
+                    reason.is_none()
+                })
+                .collect()
+        };
+        metrics::record_stage_duration(metrics::PipelineStage::Filter, filter_started.elapsed());
+        let unscreened_count = orders.len();
+        let orders = self.screen_orders(orders).await;
 
         if orders.is_empty() {
             if orders_count == 0 {
@@ -249,50 +1270,287 @@ impl FillerTask {
             } else {
                 info!(
                     orders_count,
-                    expired = orders_count - orders_after_expiry_filter,
-                    already_filled = orders_after_expiry_filter,
+                    ?skipped,
+                    screened = unscreened_count,
                     "all fetched orders filtered out"
                 );
             }
             return Ok(Vec::new());
         }
 
-        let mut scored: Vec<(i128, SignedOrder)> = orders
-            .into_iter()
-            .filter_map(|order| match self.pricing_client.profitability(&order) {
-                Ok(Some(margin)) => Some((margin, order)),
-                Ok(None) => {
-                    trace!(order_hash = %order.order_hash(), "order exceeds max loss threshold");
-                    metrics::record_order_skipped(metrics::OrderSkippedReason::ExceedsMaxLoss);
-                    None
+        let pricing_started = Instant::now();
+        let inventory = self.query_inventory(budget).await;
+        let inventory = inventory.as_ref();
+        let inclusion_probability = self.inclusion.lock().unwrap().probability();
+        metrics::record_inclusion_probability(inclusion_probability);
+
+        // Until the fee oracle's first estimate, gas cost is ignored as it is when disabled.
+        let order_gas_cost = self
+            .gas_prices
+            .as_ref()
+            .and_then(|gas_prices| gas_prices.order_gas_cost())
+            .unwrap_or_default();
+        let host_order_gas_cost = self.host_order_gas_cost();
+        let host_chain_id = self.filler.constants().host_chain_id();
+
+        // Without market prices, don't accept any loss on fixed-rate quotes.
+        let break_even_only = self.degradation.is_degraded(Dependency::PriceApi);
+
+        let cycle = self.cycle.load(Ordering::Relaxed);
+        let mut scored: Vec<(i128, SignedOrder)> = Vec::with_capacity(orders.len());
+        for order in orders {
+            if self.reputation.standing(order.permit().owner) == OwnerStanding::Skipped {
+                trace!(
+                    order_hash = %order.order_hash(),
+                    owner = %order.permit().owner,
+                    "skipping order from owner with poor reputation"
+                );
+                self.record_skip(&order, metrics::OrderSkippedReason::PoorOwnerReputation);
+                continue;
+            }
+            if self.toxic_flow_standing(order.permit().owner) == OwnerStanding::Skipped {
+                trace!(
+                    order_hash = %order.order_hash(),
+                    owner = %order.permit().owner,
+                    "skipping order from owner with toxic flow"
+                );
+                self.record_skip(&order, metrics::OrderSkippedReason::ToxicFlow);
+                continue;
+            }
+            if self.unprofitable.as_ref().is_some_and(|unprofitable| {
+                unprofitable.lock().unwrap().is_backing_off(order.order_hash(), cycle)
+            }) {
+                trace!(order_hash = %order.order_hash(), "skipping recently unprofitable order");
+                self.record_skip(&order, metrics::OrderSkippedReason::UnprofitableBackoff);
+                continue;
+            }
+            // Cached quotes are free, so they don't count towards the quote budget.
+            if self.pricing.has_cached_quote(order.order_hash()) {
+                metrics::record_quote_cache_hit();
+            } else if !budget.try_quote() {
+                self.record_skip(&order, metrics::OrderSkippedReason::CycleBudgetExhausted);
+                continue;
+            }
+            let profitability = self.pricing.profitability(&order).await;
+            if let (Some(unprofitable), Ok(margin)) = (&self.unprofitable, &profitability) {
+                let mut unprofitable = unprofitable.lock().unwrap();
+                match margin {
+                    Some(_) => unprofitable.record_profitable(order.order_hash()),
+                    None => unprofitable.record_unprofitable(*order.order_hash(), cycle),
                 }
-                Err(FixedPricingError::UnknownToken(token)) => {
-                    warn!(order_hash = %order.order_hash(), %token, "unknown token, skipping");
-                    metrics::record_order_skipped(metrics::OrderSkippedReason::UnknownToken);
-                    None
+            }
+            match profitability {
+                Ok(Some(margin)) => {
+                    let has_host_outputs = order
+                        .outputs()
+                        .iter()
+                        .any(|output| u64::from(output.chainId) == host_chain_id);
+                    let gas_cost = order_gas_cost.saturating_add(if has_host_outputs {
+                        host_order_gas_cost
+                    } else {
+                        0
+                    });
+                    let gross_margin = margin;
+                    let margin = gross_margin.saturating_sub(gas_cost);
+                    self.record_decision(
+                        order.order_hash(),
+                        Decision::Priced { gross_margin, gas_cost, margin },
+                    );
+                    if break_even_only && margin < 0 {
+                        trace!(order_hash = %order.order_hash(), margin, "loss while degraded");
+                        self.record_skip(&order, metrics::OrderSkippedReason::DegradedPricing);
+                        continue;
+                    }
+                    if !self.meets_min_margin(&order, margin) {
+                        continue;
+                    }
+                    let Some(inventory) = inventory else {
+                        if self.meets_min_expected_profit(&order, margin, inclusion_probability) {
+                            scored.push((margin, order));
+                        }
+                        continue;
+                    };
+                    match inventory.adjusted_margin(margin, &order, &self.pricing_client) {
+                        Ok(Some(adjusted)) => {
+                            if self.meets_min_expected_profit(
+                                &order,
+                                adjusted,
+                                inclusion_probability,
+                            ) {
+                                scored.push((adjusted, order));
+                            }
+                        }
+                        Ok(None) => {
+                            trace!(
+                                order_hash = %order.order_hash(),
+                                margin,
+                                "margin does not cover inventory skew penalty"
+                            );
+                            self.record_skip(&order, metrics::OrderSkippedReason::InventorySkew);
+                        }
+                        Err(error) => {
+                            warn!(
+                                order_hash = %order.order_hash(),
+                                %error,
+                                "inventory adjustment failed"
+                            );
+                            metrics::record_pricing_error();
+                            self.record_decision(order.order_hash(), Decision::PricingFailed);
+                        }
+                    }
                 }
-                Err(error) => {
-                    warn!(order_hash = %order.order_hash(), %error, "profitability check failed");
-                    metrics::record_pricing_error();
-                    None
+                Ok(None) => {
+                    trace!(order_hash = %order.order_hash(), "order exceeds max loss threshold");
+                    self.record_skip(&order, metrics::OrderSkippedReason::ExceedsMaxLoss);
                 }
-            })
-            .collect();
+                Err(error) => match error.downcast_ref::<FixedPricingError>() {
+                    Some(FixedPricingError::UnknownToken(token)) => {
+                        warn!(order_hash = %order.order_hash(), %token, "unknown token, skipping");
+                        if let Some(discovery) = &self.token_discovery {
+                            discovery.observe(*token);
+                        }
+                        self.record_skip(&order, metrics::OrderSkippedReason::UnknownToken);
+                    }
+                    Some(FixedPricingError::PricesDisagree) => {
+                        debug!(
+                            order_hash = %order.order_hash(),
+                            "pricing cross-check failed, skipping"
+                        );
+                        self.record_skip(&order, metrics::OrderSkippedReason::PriceDisagreement);
+                    }
+                    Some(FixedPricingError::StalePrice(token)) => {
+                        debug!(
+                            order_hash = %order.order_hash(),
+                            %token,
+                            "stale spot price, skipping"
+                        );
+                        self.record_skip(&order, metrics::OrderSkippedReason::StalePrice);
+                    }
+                    _ => {
+                        warn!(
+                            order_hash = %order.order_hash(),
+                            %error,
+                            "profitability check failed"
+                        );
+                        metrics::record_pricing_error();
+                        self.record_decision(order.order_hash(), Decision::PricingFailed);
+                    }
+                },
+            }
+        }
+        metrics::record_stage_duration(metrics::PipelineStage::Pricing, pricing_started.elapsed());
 
+        let (scored, conflicting) = split_nonce_conflicts(scored);
+        for order in &conflicting {
+            debug!(
+                order_hash = %order.order_hash(),
+                owner = %order.permit().owner,
+                nonce = %order.permit().permit.nonce,
+                "skipping order sharing a Permit2 nonce with a more profitable order"
+            );
+            self.record_skip(order, metrics::OrderSkippedReason::NonceConflict);
+        }
+        let (mut scored, priorities) = self.apply_scoring_hook(scored, budget).await;
         if scored.is_empty() {
             info!(orders_count, "no profitable orders");
             return Ok(Vec::new());
         }
 
-        scored.sort_by_key(|entry| Reverse(entry.0));
+        // Highest scoring-hook priority first; within a priority, in the selection policy's
+        // order, except that orders from trusted owners go first and orders from owners with
+        // recent failures or markout losses go last.
+        // With the aging boost, orders passed over in earlier cycles rank as if more profitable.
+        let now = Instant::now();
+        scored.sort_by_cached_key(|(margin, order)| {
+            let owner = order.permit().owner;
+            let deprioritized = self.reputation.standing(owner) == OwnerStanding::Deprioritized
+                || self.toxic_flow_standing(owner) == OwnerStanding::Deprioritized;
+            let first_seen =
+                self.first_seen.lock().unwrap().peek(order.order_hash()).copied().unwrap_or(now);
+            let ranking_margin = self.aged_margin(order, *margin);
+            (
+                Reverse(priorities.get(order.order_hash()).copied().unwrap_or_default()),
+                !self.trusted_owners.contains(&owner),
+                deprioritized,
+                selection_key(self.order_selection_policy, first_seen, ranking_margin),
+            )
+        });
         Ok(scored)
     }
 
+    /// Sends the profitable candidates to the scoring hook, if configured, and keeps those it
+    /// accepts, along with the priority it gave each. If the hook fails or doesn't answer by the
+    /// cycle deadline, every candidate is skipped, or kept with no priority if the hook fails
+    /// open.
+    async fn apply_scoring_hook(
+        &self,
+        scored: Vec<(i128, SignedOrder)>,
+        budget: &CycleBudget,
+    ) -> (Vec<(i128, SignedOrder)>, HashMap<B256, i64>) {
+        let Some(scoring_hook) = &self.scoring_hook else {
+            return (scored, HashMap::new());
+        };
+        if scored.is_empty() {
+            return (scored, HashMap::new());
+        }
+        let scoring_started = Instant::now();
+        let cycle = self.cycle.load(Ordering::Relaxed);
+        let result = timeout_at(budget.deadline(), scoring_hook.score(cycle, &scored))
+            .await
+            .unwrap_or_else(|_| {
+                budget.exhaust(metrics::BudgetKind::WallTime);
+                Err(eyre!("scoring hook didn't respond by the cycle deadline"))
+            });
+        metrics::record_stage_duration(metrics::PipelineStage::Scoring, scoring_started.elapsed());
+        let priorities = match result {
+            Ok(priorities) => priorities,
+            Err(error) => {
+                warn!(
+                    candidates = scored.len(),
+                    error = format!("{error:#}"),
+                    "scoring hook failed"
+                );
+                metrics::record_scoring_hook_error();
+                if scoring_hook.fails_open() {
+                    return (scored, HashMap::new());
+                }
+                for (_margin, order) in &scored {
+                    self.record_skip(order, metrics::OrderSkippedReason::ScoringFailed);
+                }
+                return (Vec::new(), HashMap::new());
+            }
+        };
+        let (accepted, rejected): (Vec<_>, Vec<_>) = scored
+            .into_iter()
+            .partition(|(_margin, order)| priorities.contains_key(order.order_hash()));
+        for (_margin, order) in &rejected {
+            trace!(order_hash = %order.order_hash(), "scoring hook rejected order");
+            self.record_skip(order, metrics::OrderSkippedReason::ScoringRejected);
+        }
+        (accepted, priorities)
+    }
+
     /// Builds a per-cycle budget map and checks Permit2 nonces, then selects orders that pass both
-    /// budget and nonce checks in profitability order.
+    /// budget and nonce checks in profitability order, with their margins.
+    ///
+    /// Nonce checks are limited by the cycle's RPC budget (the least profitable candidates are
+    /// dropped first) and by its wall-time deadline; candidates whose nonce check doesn't finish
+    /// in time are skipped rather than submitted unchecked.
     #[instrument(skip_all, fields(scored_len = scored.len()))]
-    async fn select_fillable_orders(&self, scored: Vec<(i128, SignedOrder)>) -> Vec<SignedOrder> {
-        let (mut working_map, filled_hashes) = tokio::join!(
+    async fn select_fillable_orders(
+        &self,
+        mut scored: Vec<(i128, SignedOrder)>,
+        dry_run: bool,
+        budget: &CycleBudget,
+    ) -> Vec<(i128, SignedOrder)> {
+        let nonce_checks_granted = budget.reserve_rpc_calls(scored.len());
+        for (_margin, order) in scored.split_off(nonce_checks_granted) {
+            self.record_skip(&order, metrics::OrderSkippedReason::CycleBudgetExhausted);
+        }
+
+        // Pin every nonce check to one block, so each filled order is known to be filled as of it.
+        let block_number = self.chain_watch.head();
+        let (mut working_map, nonce_checks) = tokio::join!(
             WorkingMap::build(
                 &scored,
                 self.filler.signer().address(),
@@ -300,40 +1558,152 @@ impl FillerTask {
                 self.filler.submitter().host_provider(),
                 self.filler.constants(),
                 &self.allowance_cache,
+                budget,
             ),
-            async {
-                join_all(scored.iter().map(|(_margin, order)| self.check_filled(order)))
-                    .await
-                    .into_iter()
-                    .flatten()
-                    .collect::<HashSet<B256>>()
-            },
+            join_all(scored.iter().map(|(_margin, order)| async move {
+                let check = self.check_filled(order, block_number);
+                (*order.order_hash(), timeout_at(budget.deadline(), check).await)
+            })),
         );
+        let mut filled_hashes = HashSet::new();
+        let mut unchecked_hashes = HashSet::new();
+        for (order_hash, result) in nonce_checks {
+            match result {
+                Ok(Some(filled_hash)) => {
+                    filled_hashes.insert(filled_hash);
+                }
+                Ok(None) => {}
+                Err(_elapsed) => {
+                    unchecked_hashes.insert(order_hash);
+                }
+            }
+        }
+        if !unchecked_hashes.is_empty() {
+            budget.exhaust(metrics::BudgetKind::WallTime);
+        }
 
+        let mut positions = self.risk.cycle();
         let mut orders_to_fill = Vec::new();
-        for (_margin, order) in scored {
+        for (margin, order) in scored {
             if filled_hashes.contains(order.order_hash()) {
                 continue;
             }
 
+            if unchecked_hashes.contains(order.order_hash()) {
+                self.record_skip(&order, metrics::OrderSkippedReason::CycleBudgetExhausted);
+                continue;
+            }
+
             if !working_map.can_fill(&order) {
                 trace!(
                     order_hash = %order.order_hash(),
                     "insufficient filler balance or allowance, skipping"
                 );
-                metrics::record_order_skipped(
-                    metrics::OrderSkippedReason::InsufficientFillerBalance,
-                );
+                self.record_skip(&order, metrics::OrderSkippedReason::InsufficientFillerBalance);
+                continue;
+            }
+
+            match positions.exceeded_limit(&order, &self.pricing_client) {
+                Ok(None) => {}
+                Ok(Some(chain_token)) => {
+                    debug!(
+                        order_hash = %order.order_hash(),
+                        token = %chain_token,
+                        "fill would exceed position limit, skipping"
+                    );
+                    self.record_skip(&order, metrics::OrderSkippedReason::PositionLimit);
+                    continue;
+                }
+                Err(error) => {
+                    warn!(order_hash = %order.order_hash(), %error, "failed to value position");
+                    metrics::record_pricing_error();
+                    self.record_decision(order.order_hash(), Decision::PricingFailed);
+                    continue;
+                }
+            }
+
+            if !dry_run && !self.acquire_fill_capacity(&order) {
                 continue;
             }
 
             working_map.accept_order(&order);
-            orders_to_fill.push(order);
+            positions.accept(&order);
+            self.record_decision(order.order_hash(), Decision::Selected { margin });
+            self.fill_history.record_selected(*order.order_hash(), margin);
+            orders_to_fill.push((margin, order));
         }
 
         orders_to_fill
     }
 
+    /// Checks the order's notional value against the hot-key spend policy and reserves fill-rate
+    /// throttle capacity for it, recording a skip and returning `false` if the order needs (or
+    /// was refused) operator approval or a throttle limit would be exceeded. Always succeeds when
+    /// neither is configured.
+    fn acquire_fill_capacity(&self, order: &SignedOrder) -> bool {
+        if self.fill_throttle.is_none() && self.spend_policy.is_none() {
+            return true;
+        }
+        let notional = match self.pricing_client.output_value(order) {
+            Ok(notional) => notional,
+            Err(error) => {
+                warn!(order_hash = %order.order_hash(), %error, "failed to value order outputs");
+                metrics::record_pricing_error();
+                self.record_decision(order.order_hash(), Decision::PricingFailed);
+                return false;
+            }
+        };
+        let order_hash = *order.order_hash();
+        if let Some(spend_policy) = &self.spend_policy {
+            match spend_policy.evaluate(order_hash, order.permit().owner, notional) {
+                SpendDecision::Allowed => {}
+                SpendDecision::Approved => {
+                    info!(%order_hash, %notional, "filling operator-approved order");
+                }
+                SpendDecision::AwaitingApproval(limit) => {
+                    info!(
+                        %order_hash,
+                        %notional,
+                        limit = limit.as_str(),
+                        "order exceeds hot-key spend limit, awaiting operator approval"
+                    );
+                    self.record_skip(order, metrics::OrderSkippedReason::AwaitingApproval);
+                    return false;
+                }
+                SpendDecision::Rejected => {
+                    debug!(%order_hash, "order rejected by operator");
+                    self.record_skip(order, metrics::OrderSkippedReason::ApprovalRejected);
+                    return false;
+                }
+            }
+        }
+        if let Some(Err(limit)) = self
+            .fill_throttle
+            .as_ref()
+            .map(|throttle| throttle.try_acquire(order_hash, order.permit().owner, notional))
+        {
+            debug!(%order_hash, limit = limit.as_str(), "fill-rate throttled");
+            self.record_skip(order, metrics::OrderSkippedReason::FillRateThrottled);
+            return false;
+        }
+        if let Some(spend_policy) = &self.spend_policy {
+            spend_policy.commit(order_hash, notional);
+        }
+        true
+    }
+
+    /// Releases the capacity acquired by [`Self::acquire_fill_capacity`] for a selected order that
+    /// won't be submitted after all: dropped, claimed by another replica, skipped too close to the
+    /// block boundary, or failed to submit.
+    fn release_fill_capacity(&self, order: &SignedOrder) {
+        if let Some(fill_throttle) = &self.fill_throttle {
+            fill_throttle.release(*order.order_hash());
+        }
+        if let Some(spend_policy) = &self.spend_policy {
+            spend_policy.release(*order.order_hash());
+        }
+    }
+
     /// Chunks orders by `max_orders_per_bundle` and submits each chunk sequentially so the most
     /// profitable chunk acquires the lowest nonce via `CachedNonceManager`. Relies on the builder
     /// ordering a sender's txs by nonce within a block for profitability ordering to hold.
@@ -342,28 +1712,68 @@ impl FillerTask {
     /// cached nonce on every call regardless of submission outcome, so if chunk K fails the
     /// builder sees a nonce gap at K and chunks K+1.. cannot land anyway - continuing would just
     /// waste RPC calls and muddle metrics.
+    ///
+    /// Each chunk is only submitted while at least `min_submission_lead` remains before
+    /// `block_boundary`; a bundle submitted later than that would land after the boundary and
+    /// waste gas, so the rest of the slot's submissions are skipped instead.
+    ///
+    /// Orders that aren't submitted, or fail to submit, have their fill capacity and claims
+    /// released.
     #[instrument(skip_all, fields(orders_to_fill = orders_to_fill.len()))]
-    async fn submit_bundles(&self, orders_to_fill: Vec<SignedOrder>) {
+    async fn submit_bundles(&self, orders_to_fill: Vec<SignedOrder>, block_boundary: Instant) {
         debug_assert!(!orders_to_fill.is_empty(), "orders_to_fill is empty");
         let chunks = chunk_orders(orders_to_fill, self.max_orders_per_bundle);
         let chunk_count = chunks.len();
         if self.max_orders_per_bundle.is_some() {
             metrics::record_chunks_per_cycle(chunk_count as f64);
         }
+        // Transient submission failures may be retried until the same cutoff.
+        self.filler.submitter().submitter().inner().set_deadline(
+            block_boundary.checked_sub(self.min_submission_lead).unwrap_or(block_boundary),
+        );
+        let mut timings = SubmissionTimings::default();
         let mut successful_chunks = 0_usize;
-        for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+        let mut stopped_late = false;
+        let mut chunks = chunks.into_iter().enumerate();
+        let mut unsubmitted = Vec::new();
+        for (chunk_index, chunk) in chunks.by_ref() {
+            let remaining_lead = block_boundary.saturating_duration_since(Instant::now());
+            if remaining_lead < self.min_submission_lead {
+                warn!(
+                    remaining_lead_ms = %remaining_lead.as_millis(),
+                    min_submission_lead_ms = %self.min_submission_lead.as_millis(),
+                    chunk_index,
+                    chunk_count,
+                    "too close to block boundary, skipping submission for this slot"
+                );
+                metrics::record_late_submission_skipped();
+                stopped_late = true;
+                unsubmitted = chunk;
+                break;
+            }
+            for order in &chunk {
+                let bundled = Decision::Bundled { chunk: chunk_index, chunks: chunk_count };
+                self.record_decision(order.order_hash(), bundled);
+            }
             let span = info_span!(
                 "submit_one_bundle",
                 orders_in_bundle = chunk.len(),
                 chunk_index = chunk_index,
                 chunk_count = chunk_count,
             );
-            if !self.submit_one_bundle(chunk).instrument(span).await {
+            if !self.submit_one_bundle(chunk, &mut timings).instrument(span).await {
                 break;
             }
             successful_chunks += 1;
         }
-        if successful_chunks > 0 && successful_chunks < chunk_count {
+        let unsubmitted: Vec<SignedOrder> =
+            unsubmitted.into_iter().chain(chunks.flat_map(|(_chunk_index, chunk)| chunk)).collect();
+        for order in &unsubmitted {
+            self.release_fill_capacity(order);
+        }
+        self.release_claims(&unsubmitted).await;
+        timings.record();
+        if !stopped_late && successful_chunks > 0 && successful_chunks < chunk_count {
             warn!(
                 successful_chunks,
                 chunk_count,
@@ -373,8 +1783,13 @@ impl FillerTask {
         }
     }
 
-    /// Submits a single fill bundle and records metrics. Returns `true` on success.
-    async fn submit_one_bundle(&self, orders: Vec<SignedOrder>) -> bool {
+    /// Submits a single fill bundle and records metrics, adding the time spent signing and
+    /// submitting it to `timings`. Returns `true` on success.
+    async fn submit_one_bundle(
+        &self,
+        orders: Vec<SignedOrder>,
+        timings: &mut SubmissionTimings,
+    ) -> bool {
         debug_assert!(!orders.is_empty(), "orders is empty");
         let orders_in_bundle = orders.len();
         // Record attempted-bundle size regardless of submission outcome so the histogram and
@@ -382,21 +1797,205 @@ impl FillerTask {
         // carried by the `BUNDLES` counter's `result` label.
         metrics::record_orders_in_bundle(orders_in_bundle as u64);
         metrics::record_orders_per_bundle(orders_in_bundle as f64);
-        match self.filler.fill(orders, self.target_blocks).await {
+        let order_hashes: Vec<B256> = orders.iter().map(|order| *order.order_hash()).collect();
+        let submitted = orders.clone();
+        let mut bundles: Vec<ReceiptBundle> = Vec::new();
+        self.filler.submitter().submitter().require_simulation(self.requires_simulation(&orders));
+        let submission_start = Instant::now();
+        // Equivalent to `Filler::fill`, split so the two steps can be timed separately.
+        let result = match self.filler.sign_fills(orders).await {
+            Ok(orders_and_fills) => {
+                timings.bundle_build += submission_start.elapsed();
+                let send_start = Instant::now();
+                let result = self
+                    .filler
+                    .submitter()
+                    .submit_fills(orders_and_fills, self.target_blocks)
+                    .await
+                    .map_err(Report::new);
+                timings.submission += send_start.elapsed();
+                result
+            }
+            Err(error) => {
+                timings.bundle_build += submission_start.elapsed();
+                Err(Report::new(error))
+            }
+        };
+        let success = match result {
             Ok(responses) => {
+                bundles = responses
+                    .iter()
+                    .map(|targeted| ReceiptBundle {
+                        bundle_id: targeted.response.id,
+                        block_number: targeted.block_number,
+                    })
+                    .collect();
                 info!(
-                    bundle_ids = ?responses.iter().map(|response| response.id).collect::<Vec<_>>(),
+                    bundle_ids = ?bundles.iter().map(|bundle| bundle.bundle_id).collect::<Vec<_>>(),
                     orders_in_bundle,
                     "successfully submitted fill bundle"
                 );
+                let first_target_block = bundles.iter().map(|bundle| bundle.block_number).min();
+                if let (Some(adaptive_lead), Some(first_target_block), Some(order)) =
+                    (&self.adaptive_lead, first_target_block, submitted.first())
+                {
+                    adaptive_lead.lock().unwrap().probe(order.clone(), first_target_block);
+                }
                 metrics::record_bundle(metrics::SubmissionResult::Success);
+                metrics::record_bundle_submission_duration(
+                    submission_start.elapsed(),
+                    metrics::SubmissionResult::Success,
+                );
                 true
             }
             Err(error) => {
-                warn!(%error, orders_in_bundle, "failed to fill orders");
+                warn!(error = format!("{error:#}"), orders_in_bundle, "failed to fill orders");
                 metrics::record_bundle(metrics::SubmissionResult::Failure);
+                metrics::record_bundle_submission_duration(
+                    submission_start.elapsed(),
+                    metrics::SubmissionResult::Failure,
+                );
                 false
             }
+        };
+        if success {
+            // The bundle targets `target_blocks` blocks from roughly the next boundary; allow one
+            // extra slot before expecting the fill to be visible on-chain.
+            let fill_expected_by = Instant::now()
+                + self.block_lead_duration
+                + Duration::from_secs(self.slot_duration * (u64::from(self.target_blocks) + 1));
+            let mut submitted_orders = self.submitted_orders.lock().unwrap();
+            let mut pending_fills = self.pending_fills.lock().unwrap();
+            let signatures_expire_at = submission_start + self.signature_lifetime;
+            let last_target_block =
+                bundles.iter().map(|bundle| bundle.block_number).max().unwrap_or_default();
+            for order in submitted {
+                submitted_orders.put(*order.order_hash(), fill_expected_by);
+                self.risk.add_pending(&order, signatures_expire_at);
+                self.fill_history.record_submitted(&order, last_target_block);
+                pending_fills.insert(order, signatures_expire_at, bundles.clone());
+            }
+        } else {
+            for order in &submitted {
+                self.release_fill_capacity(order);
+            }
+            self.release_claims(&submitted).await;
+        }
+        for order_hash in &order_hashes {
+            self.record_decision(order_hash, Decision::Submitted { success });
+        }
+        success
+    }
+
+    /// Passes through successfully fetched orders, keeping them as the snapshot for later cycles.
+    /// If the fetch failed, reports the order source degraded and falls back to the snapshot,
+    /// failing only if there is none yet.
+    fn fall_back_to_snapshot(&self, fetched: Result<Vec<SignedOrder>>) -> Result<Vec<SignedOrder>> {
+        let mut snapshot = self.order_snapshot.lock().unwrap();
+        match fetched {
+            Ok(orders) => {
+                self.degradation.set(Dependency::OrderSource, false);
+                *snapshot = Some(orders.clone());
+                Ok(orders)
+            }
+            Err(error) => {
+                let Some(orders) = snapshot.clone() else {
+                    return Err(error);
+                };
+                warn!(
+                    error = format!("{error:#}"),
+                    snapshot_orders = orders.len(),
+                    "evaluating last fetched orders without submitting"
+                );
+                self.degradation.set(Dependency::OrderSource, true);
+                Ok(orders)
+            }
+        }
+    }
+
+    /// Whether the bundle must pass simulation before submission: some order from an owner who
+    /// isn't trusted has an output notional of at least the configured minimum, or can't be
+    /// valued.
+    fn requires_simulation(&self, orders: &[SignedOrder]) -> bool {
+        let Some(min_notional) = self.simulation_min_notional else {
+            return false;
+        };
+        let mut untrusted =
+            orders.iter().filter(|order| !self.trusted_owners.contains(&order.permit().owner));
+        untrusted.any(|order| {
+            self.pricing_client
+                .output_value(order)
+                .map_or(true, |notional| notional >= min_notional)
+        })
+    }
+
+    /// Records a skipped order in metrics and, when archival is enabled, in the archive.
+    fn record_skip(&self, order: &SignedOrder, reason: metrics::OrderSkippedReason) {
+        self.record_decision(order.order_hash(), Decision::Skipped { reason: reason.as_str() });
+        metrics::record_order_skipped(reason);
+    }
+
+    /// Returns whether `margin`, net of gas, is at least the configured share of the order's input
+    /// value, recording a skip if not. An order whose inputs can't be valued doesn't qualify.
+    fn meets_min_margin(&self, order: &SignedOrder, margin: i128) -> bool {
+        let Some(min_margin_bps) = self.min_margin_bps else {
+            return true;
+        };
+        let meets = self
+            .pricing_client
+            .input_value(order)
+            .is_ok_and(|input| meets_min_margin_bps(margin, input, min_margin_bps));
+        if !meets {
+            trace!(order_hash = %order.order_hash(), margin, min_margin_bps, "margin below minimum");
+            self.record_skip(order, metrics::OrderSkippedReason::BelowMinMargin);
+        }
+        meets
+    }
+
+    /// Discounts `margin` by the estimated probability of the fill landing, and returns whether
+    /// the expected value meets the configured minimum, recording a skip if not. The ranking
+    /// doesn't change, since every order in a cycle is discounted by the same probability.
+    fn meets_min_expected_profit(
+        &self,
+        order: &SignedOrder,
+        margin: i128,
+        inclusion_probability: f64,
+    ) -> bool {
+        let expected = expected_value(margin, inclusion_probability);
+        trace!(order_hash = %order.order_hash(), margin, expected, "scored order");
+        if self.min_expected_profit.is_some_and(|min| expected < min) {
+            trace!(order_hash = %order.order_hash(), expected, "expected value below minimum");
+            self.record_skip(order, metrics::OrderSkippedReason::LowExpectedValue);
+            return false;
+        }
+        true
+    }
+
+    /// Records a decision about an order in the archive for the current cycle, if enabled.
+    fn record_decision(&self, order_hash: &B256, decision: Decision) {
+        if let Some(archive) = &self.archive {
+            archive.record_decision(self.cycle.load(Ordering::Relaxed), *order_hash, decision);
+        }
+    }
+
+    /// If the order was in a successfully submitted bundle whose target window has passed but its
+    /// nonce is still unconsumed, counts a failure against the owner's reputation: the fill most
+    /// likely reverted because the owner's transfer failed or the nonce was invalidated.
+    fn check_unfilled_submission(&self, order: &SignedOrder) {
+        let mut submitted_orders = self.submitted_orders.lock().unwrap();
+        let overdue = submitted_orders
+            .peek(order.order_hash())
+            .is_some_and(|fill_expected_by| Instant::now() >= *fill_expected_by);
+        if overdue {
+            submitted_orders.pop(order.order_hash());
+            self.inclusion.lock().unwrap().record(false);
+            warn!(
+                order_hash = %order.order_hash(),
+                owner = %order.permit().owner,
+                "order still unfilled after its bundle's target window, penalizing owner"
+            );
+            metrics::record_unfilled_submission();
+            self.reputation.record_failure(order.permit().owner);
         }
     }
 
@@ -414,15 +2013,11 @@ impl FillerTask {
         now_instant - elapsed
     }
 
-    /// Checks whether the order's Permit2 nonce has been consumed on the rollup chain. Returns
-    /// `Some(order_hash)` if filled, `None` if unfilled or on RPC error.
-    async fn check_filled(&self, order: &SignedOrder) -> Option<B256> {
-        let is_filled = match signet_orders::permit2::is_order_nonce_consumed(
-            self.filler.submitter().ru_provider(),
-            order,
-        )
-        .await
-        {
+    /// Checks whether the order's Permit2 nonce has been consumed on the rollup chain, as of
+    /// `block_number` if given and the latest block otherwise. Returns `Some(order_hash)` if
+    /// filled, `None` if unfilled or on RPC error.
+    async fn check_filled(&self, order: &SignedOrder, block_number: Option<u64>) -> Option<B256> {
+        let is_filled = match self.is_nonce_consumed(order, block_number).await {
             Ok(consumed) => consumed,
             Err(error) => {
                 warn!(
@@ -437,13 +2032,586 @@ impl FillerTask {
 
         if is_filled {
             trace!(order_hash = %order.order_hash(), "order already filled");
-            self.filled_orders.lock().unwrap().put(*order.order_hash(), ());
-            metrics::record_order_skipped(metrics::OrderSkippedReason::AlreadyFilled);
+            // Without a known head, the fill is treated as above every fork point.
+            self.record_filled(order, block_number.unwrap_or(u64::MAX));
+            self.record_skip(order, metrics::OrderSkippedReason::AlreadyFilled);
             Some(*order.order_hash())
         } else {
+            self.check_unfilled_submission(order);
             None
         }
     }
+
+    /// Whether the order's Permit2 nonce has been consumed on the rollup chain, as of
+    /// `block_number` if given and the latest block otherwise.
+    async fn is_nonce_consumed(
+        &self,
+        order: &SignedOrder,
+        block_number: Option<u64>,
+    ) -> Result<bool, alloy::contract::Error> {
+        let permit = order.permit();
+        let permit2 = IPermit2::new(PERMIT2, self.filler.submitter().ru_provider());
+        let mut call = permit2.nonceBitmap(permit.owner, permit.permit.nonce >> 8);
+        if let Some(block_number) = block_number {
+            call = call.block(block_number.into());
+        }
+        Ok(is_nonce_consumed(call.call().await?, permit.permit.nonce))
+    }
+
+    /// Confirms whether the fills of pending orders landed, once the last rollup block their
+    /// bundle targets has been produced. A landed order moves to the filled-order cache, and its
+    /// fill receipt is sent if the fill webhook is enabled. One that didn't land is dropped from
+    /// the pending set, so it is submitted again this cycle if still fillable, and counted as an
+    /// inclusion failure. A fill whose check fails stays pending, to
+    /// be checked again next cycle.
+    async fn confirm_pending_fills(&self) {
+        let Some(head) = self.chain_watch.head() else {
+            return;
+        };
+        let due = self.pending_fills.lock().unwrap().due(head);
+        if due.is_empty() {
+            return;
+        }
+        let checks =
+            join_all(due.iter().map(|order| self.is_nonce_consumed(order, Some(head)))).await;
+        let now = unix_time_secs();
+        for (order, check) in due.iter().zip(checks) {
+            match check {
+                Ok(true) => {
+                    if self.fill_webhook.is_some() {
+                        let bundles =
+                            self.pending_fills.lock().unwrap().bundles(order.order_hash());
+                        if let Some(block_number) = self.landed_block(order, &bundles).await {
+                            self.send_fill_receipt(order, &bundles, block_number);
+                        }
+                    }
+                    self.record_filled(order, head);
+                }
+                Ok(false) => {
+                    self.pending_fills.lock().unwrap().remove(order.order_hash());
+                    self.risk.remove_pending(order.order_hash());
+                    let reason = if order.permit().permit.deadline < U256::from(now) {
+                        metrics::InclusionFailureReason::OrderExpired
+                    } else {
+                        metrics::InclusionFailureReason::NotIncluded
+                    };
+                    info!(
+                        order_hash = %order.order_hash(),
+                        head,
+                        reason = reason.as_str(),
+                        "submitted fill didn't land in its target blocks"
+                    );
+                    metrics::record_inclusion_failure(reason);
+                }
+                Err(error) => {
+                    warn!(
+                        order_hash = %order.order_hash(),
+                        %error,
+                        "failed to confirm pending fill, checking again next cycle"
+                    );
+                    metrics::record_nonce_check_error();
+                }
+            }
+        }
+    }
+
+    /// The first of the blocks targeted by `bundles` as of which the order's Permit2 nonce is
+    /// consumed, which is the block the fill landed in if one of them filled it. `None` if the
+    /// nonce is consumed as of none of them, so the order was filled some other way, or a check
+    /// fails.
+    async fn landed_block(&self, order: &SignedOrder, bundles: &[ReceiptBundle]) -> Option<u64> {
+        let mut target_blocks: Vec<u64> =
+            bundles.iter().map(|bundle| bundle.block_number).collect();
+        target_blocks.sort_unstable();
+        for block_number in target_blocks {
+            match self.is_nonce_consumed(order, Some(block_number)).await {
+                Ok(true) => return Some(block_number),
+                Ok(false) => {}
+                Err(error) => {
+                    warn!(
+                        order_hash = %order.order_hash(),
+                        %error,
+                        "failed to find the block a fill landed in, not sending its receipt"
+                    );
+                    metrics::record_nonce_check_error();
+                    return None;
+                }
+            }
+        }
+        None
+    }
+
+    /// Sends a receipt to the fill webhook, if enabled, for the order's fill that landed in
+    /// `block_number`, naming the bundle of `bundles` that targeted it. Nothing is sent if none
+    /// did, since the order wasn't filled by this filler.
+    fn send_fill_receipt(&self, order: &SignedOrder, bundles: &[ReceiptBundle], block_number: u64) {
+        let Some(fill_webhook) = &self.fill_webhook else {
+            return;
+        };
+        match bundles.iter().find(|bundle| bundle.block_number == block_number) {
+            Some(bundle) => fill_webhook.notify(*order.order_hash(), *bundle),
+            None => debug!(
+                order_hash = %order.order_hash(),
+                block_number,
+                "order landed outside its bundles' target blocks, not sending a fill receipt"
+            ),
+        }
+    }
+
+    /// Records a newly found filled order, seen filled at `block_number`: counts it as landed if
+    /// this filler submitted it, and adds it to the filled-order cache, the persistent store, and
+    /// the set shared with other replicas, whichever are enabled.
+    fn record_filled(&self, order: &SignedOrder, block_number: u64) {
+        let order_hash = *order.order_hash();
+        self.pending_fills.lock().unwrap().remove(&order_hash);
+        self.risk.remove_pending(&order_hash);
+        if self.submitted_orders.lock().unwrap().pop(&order_hash).is_some() {
+            self.inclusion.lock().unwrap().record(true);
+            self.track_markout(order);
+            self.record_fill_pnl(order);
+            self.fill_history.record_confirmed(&order_hash, block_number);
+            self.record_decision(&order_hash, Decision::Confirmed { block_number });
+            self.realize_fill(order, block_number);
+        }
+        let filled = FilledOrder::new(order, block_number);
+        self.filled_orders.lock().unwrap().put(order_hash, filled);
+        if let Some(store) = &self.filled_order_store {
+            store.insert(order_hash, filled.deadline);
+        }
+        if let Some(coordination) = &self.coordination {
+            let coordination = coordination.clone();
+            tokio::spawn(async move {
+                if let Err(error) = coordination.mark_filled(order_hash).await {
+                    warn!(%order_hash, error = format!("{error:#}"), "failed to share filled order");
+                    metrics::record_coordination_error();
+                }
+            });
+        }
+    }
+
+    /// Standing of the owner under toxic-flow detection; always good when it's disabled.
+    fn toxic_flow_standing(&self, owner: Address) -> OwnerStanding {
+        self.toxic_flow
+            .as_ref()
+            .map_or(OwnerStanding::Good, |toxic_flow| toxic_flow.standing(owner))
+    }
+
+    /// Starts tracking a landed fill of this filler's for its markout, valuing the outputs paid
+    /// as of now. A fill whose outputs can't be valued isn't tracked.
+    fn track_markout(&self, order: &SignedOrder) {
+        let Some(toxic_flow) = &self.toxic_flow else {
+            return;
+        };
+        match self.pricing_client.output_value(order) {
+            Ok(paid) => toxic_flow.track(order.clone(), paid),
+            Err(error) => {
+                debug!(order_hash = %order.order_hash(), %error, "can't value fill for markout");
+            }
+        }
+    }
+
+    /// Records a confirmed fill of this filler's in the PnL ledger, and its realized profit in the
+    /// fill history, in the background, from the receipt of the rollup transaction it landed in,
+    /// searched for in the blocks up to `block_number`. A fill whose transaction isn't found isn't
+    /// recorded.
+    fn realize_fill(&self, order: &SignedOrder, block_number: u64) {
+        let provider = self.filler.submitter().ru_provider().clone();
+        let orders_address = self.filler.constants().rollup().orders();
+        let filler = self.filler.signer().address();
+        let pricing_client = Arc::clone(&self.pricing_client);
+        let ledger = self.ledger.clone();
+        let fill_history = self.fill_history.clone();
+        let order = order.clone();
+        tokio::spawn(async move {
+            let order_hash = *order.order_hash();
+            let transfers = async {
+                let Some(tx_hash) = find_order_transaction(
+                    &provider,
+                    orders_address,
+                    &order,
+                    block_number,
+                    FILL_TRANSACTION_LOOKBACK_BLOCKS,
+                )
+                .await?
+                else {
+                    return Ok(None);
+                };
+                FillTransfers::fetch(&provider, tx_hash, &order, filler).await
+            };
+            match transfers.await {
+                Ok(Some(transfers)) => match ledger.record(&order, &transfers, &pricing_client) {
+                    Ok(realized) => {
+                        debug!(%order_hash, pnl_usd = realized.pnl_usd, "recorded realized fill PnL");
+                        fill_history.record_realized(&order_hash, realized);
+                    }
+                    Err(error) => {
+                        warn!(%order_hash, %error, "failed to value fill for the PnL ledger");
+                    }
+                },
+                Ok(None) => debug!(%order_hash, "fill transaction not found for the PnL ledger"),
+                Err(error) => warn!(
+                    %order_hash,
+                    error = format!("{error:#}"),
+                    "failed to read fill transaction for the PnL ledger"
+                ),
+            }
+        });
+    }
+
+    /// Adds a landed fill of this filler's to the daily loss, estimating its PnL as the value of
+    /// the inputs received less the value of the outputs paid, as of now. A fill that can't be
+    /// valued isn't counted.
+    fn record_fill_pnl(&self, order: &SignedOrder) {
+        let Some(loss_limit) = &self.loss_limit else {
+            return;
+        };
+        let values = self
+            .pricing_client
+            .input_value(order)
+            .and_then(|received| Ok((received, self.pricing_client.output_value(order)?)));
+        match values {
+            Ok((received, paid)) => {
+                loss_limit.record((f64::from(received) - f64::from(paid)) / 1e18);
+            }
+            Err(error) => {
+                debug!(order_hash = %order.order_hash(), %error, "can't value fill for daily loss");
+            }
+        }
+    }
+
+    /// Marks out the landed fills whose markout delay has passed, revaluing the inputs received
+    /// at current prices, and adds each markout to its owner's. A fill whose inputs can't be
+    /// valued is dropped.
+    fn settle_markouts(&self) {
+        let Some(toxic_flow) = &self.toxic_flow else {
+            return;
+        };
+        for pending in toxic_flow.take_due() {
+            let order = &pending.order;
+            let received = match self.pricing_client.input_value(order) {
+                Ok(received) => received,
+                Err(error) => {
+                    debug!(order_hash = %order.order_hash(), %error, "can't value fill for markout");
+                    continue;
+                }
+            };
+            let markout = pending.markout(received);
+            debug!(
+                order_hash = %order.order_hash(),
+                owner = %order.permit().owner,
+                markout_usd = markout,
+                "marked out landed fill"
+            );
+            metrics::record_fill_markout(markout);
+            toxic_flow.record(order.permit().owner, markout);
+        }
+    }
+
+    /// Evicts filled-order cache entries for orders whose deadline has passed, so they don't take
+    /// up room in the cache that orders still in the transaction cache could use.
+    fn evict_expired_fills(&self) {
+        let now = unix_time_secs();
+        let mut filled_orders = self.filled_orders.lock().unwrap();
+        let expired: Vec<B256> = filled_orders
+            .iter()
+            .filter(|(_order_hash, filled)| filled.deadline < now)
+            .map(|(order_hash, _filled)| *order_hash)
+            .collect();
+        for order_hash in &expired {
+            filled_orders.pop(order_hash);
+        }
+        if !expired.is_empty() {
+            debug!(expired = expired.len(), "evicted expired orders from filled-order cache");
+        }
+    }
+
+    /// Evicts filled-order cache entries seen filled at or above the fork point of any rollup
+    /// reorg since the last cycle, since the reorg may have undone their fills, and forgets them
+    /// in the persistent store and the set shared with other replicas. An evicted order that is
+    /// still filled is found filled again by its nonce check.
+    fn evict_reorged_fills(&self) {
+        let Some(fork) = self.chain_watch.take_fork() else {
+            return;
+        };
+        let evicted: Vec<B256> = {
+            let mut filled_orders = self.filled_orders.lock().unwrap();
+            let evicted: Vec<B256> = filled_orders
+                .iter()
+                .filter(|(_order_hash, filled)| filled.block_number >= fork)
+                .map(|(order_hash, _filled)| *order_hash)
+                .collect();
+            for order_hash in &evicted {
+                filled_orders.pop(order_hash);
+            }
+            evicted
+        };
+        info!(fork, evicted = evicted.len(), "evicted filled orders after rollup reorg");
+        metrics::record_filled_cache_reorg_evictions(evicted.len());
+        if let Some(store) = &self.filled_order_store {
+            for order_hash in &evicted {
+                store.remove(*order_hash);
+            }
+        }
+        if let Some(coordination) = &self.coordination {
+            let coordination = coordination.clone();
+            tokio::spawn(async move {
+                for order_hash in evicted {
+                    if let Err(error) = coordination.forget_filled(order_hash).await {
+                        warn!(%order_hash, error = format!("{error:#}"), "failed to unshare filled order");
+                        metrics::record_coordination_error();
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// A built-in stage of the order filter pipeline, run against the filler task's state.
+#[derive(Debug)]
+struct BuiltinFilter<'a, Source> {
+    task: &'a FillerTask<Source>,
+    stage: FilterStage,
+    earliest_fill_timestamp: u64,
+}
+
+impl<Source: OrderSource> OrderFilter for BuiltinFilter<'_, Source> {
+    fn check(&self, order: &SignedOrder) -> Option<SkipReason> {
+        self.task.check_stage(self.stage, order, self.earliest_fill_timestamp).map(SkipReason)
+    }
+}
+
+/// The current Unix time in seconds.
+fn unix_time_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock set before UNIX epoch")
+        .as_secs()
+}
+
+/// The timestamp, in seconds, of the first slot boundary at or after `time` (since the Unix
+/// epoch), given the chain's start timestamp and slot duration in seconds.
+fn slot_boundary_at_or_after(time: Duration, start_timestamp: u64, slot_duration: u64) -> u64 {
+    let Some(elapsed) = time.checked_sub(Duration::from_secs(start_timestamp)) else {
+        return start_timestamp;
+    };
+    let slot_duration_ms = u128::from(slot_duration) * 1000;
+    let slots = elapsed.as_millis().div_ceil(slot_duration_ms);
+    start_timestamp + slot_duration * slots as u64
+}
+
+/// A filled-order cache entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FilledOrder {
+    /// The rollup block the order was seen filled at, so the entry can be evicted if a reorg
+    /// replaces that block.
+    block_number: u64,
+    /// The order's Permit2 deadline in seconds. Once it has passed, the order can no longer be
+    /// fetched as fillable, so the entry is evicted.
+    deadline: u64,
+}
+
+impl FilledOrder {
+    fn new(order: &SignedOrder, block_number: u64) -> Self {
+        Self { block_number, deadline: order.permit().permit.deadline.saturating_to() }
+    }
+}
+
+/// The filled-order cache, holding up to `capacity` orders, seeded from the persistent store if
+/// one is configured. A store that can't be read leaves the cache empty, as without a store.
+/// Stored orders were filled before this process started, so they are recorded at block 0, below
+/// any reorg whose fork point is found.
+fn load_filled_orders(
+    store: Option<&FilledOrderStore>,
+    capacity: NonZeroUsize,
+) -> LruCache<B256, FilledOrder> {
+    let mut filled_orders = LruCache::new(capacity);
+    let Some(store) = store else {
+        return filled_orders;
+    };
+    match store.load_recent(capacity.get(), unix_time_secs()) {
+        Ok(entries) => {
+            info!(orders = entries.len(), "loaded filled orders from store");
+            for (order_hash, deadline) in entries {
+                filled_orders.put(order_hash, FilledOrder { block_number: 0, deadline });
+            }
+        }
+        Err(error) => {
+            warn!(error = format!("{error:#}"), "failed to load filled orders from store");
+        }
+    }
+    filled_orders
+}
+
+/// Time a cycle spent building and submitting its bundles, summed over every chunk.
+#[derive(Debug, Default)]
+struct SubmissionTimings {
+    bundle_build: Duration,
+    submission: Duration,
+}
+
+impl SubmissionTimings {
+    fn record(self) {
+        metrics::record_stage_duration(metrics::PipelineStage::BundleBuild, self.bundle_build);
+        metrics::record_stage_duration(metrics::PipelineStage::Submission, self.submission);
+    }
+}
+
+/// Collects `items` until the stream ends or `deadline` passes. Returns the items collected and
+/// whether the stream ended in time, or the first error.
+async fn collect_until<T, E>(
+    items: impl Stream<Item = Result<T, E>>,
+    deadline: Instant,
+) -> Result<(Vec<T>, bool), E> {
+    let mut items = pin!(items);
+    let mut collected = Vec::new();
+    loop {
+        match timeout_at(deadline, items.next()).await {
+            Ok(Some(item)) => collected.push(item?),
+            Ok(None) => return Ok((collected, true)),
+            Err(_elapsed) => return Ok((collected, false)),
+        }
+    }
+}
+
+/// The [`Instant`] at which the system clock will read, or read, `time`.
+fn instant_at(time: SystemTime) -> Instant {
+    let now = Instant::now();
+    match time.duration_since(SystemTime::now()) {
+        Ok(until) => now + until,
+        Err(error) => now.checked_sub(error.duration()).unwrap_or(now),
+    }
+}
+
+/// Returns the first block boundary strictly after `now`, given any past boundary `anchor` and the
+/// slot duration.
+fn next_block_boundary(anchor: Instant, now: Instant, slot_duration: Duration) -> Instant {
+    let elapsed = now.saturating_duration_since(anchor);
+    let slots_elapsed = elapsed.as_nanos() / slot_duration.as_nanos();
+    anchor + slot_duration * (slots_elapsed as u32 + 1)
+}
+
+/// Whether the order's Permit2 signature recovers to its owner, taken from `signature_checks` if
+/// the order was checked before and cached there otherwise. Orders from `trusted_owners` are
+/// taken as valid without being checked or cached.
+fn has_valid_signature(
+    order: &SignedOrder,
+    constants: &SignetSystemConstants,
+    trusted_owners: &HashSet<Address>,
+    signature_checks: &Mutex<LruCache<B256, bool>>,
+) -> bool {
+    if trusted_owners.contains(&order.permit().owner) {
+        return true;
+    }
+    let order_hash = *order.order_hash();
+    let cached = signature_checks.lock().unwrap().get(&order_hash).copied();
+    cached.unwrap_or_else(|| {
+        let valid = match check_signature(order, constants) {
+            SignatureCheck::Valid => true,
+            SignatureCheck::Mismatch(signer) => {
+                debug!(
+                    %order_hash,
+                    owner = %order.permit().owner,
+                    %signer,
+                    "order signature recovers to another address"
+                );
+                false
+            }
+            SignatureCheck::Invalid(error) => {
+                debug!(%order_hash, %error, "malformed order signature");
+                false
+            }
+        };
+        signature_checks.lock().unwrap().put(order_hash, valid);
+        valid
+    })
+}
+
+/// Whether `margin` is at least `min_margin_bps` basis points of `input_value`, both in normalized
+/// 18-decimal USD.
+pub(crate) fn meets_min_margin_bps(margin: i128, input_value: U256, min_margin_bps: u16) -> bool {
+    let min_margin = input_value.saturating_mul(U256::from(min_margin_bps)) / U256::from(10_000);
+    i128::try_from(min_margin).is_ok_and(|min_margin| margin >= min_margin)
+}
+
+/// `margin` boosted by `boost_percent` for each of `unfilled_cycles`. Losses aren't boosted.
+fn boosted_margin(margin: i128, unfilled_cycles: u32, boost_percent: u16) -> i128 {
+    if margin <= 0 {
+        return margin;
+    }
+    let boost = i128::from(unfilled_cycles) * i128::from(boost_percent);
+    margin.saturating_mul(100 + boost) / 100
+}
+
+/// Sort key ranking an order among candidates of the same owner priority under `policy`: by
+/// margin, or by when it was first seen and then by margin.
+const fn selection_key(
+    policy: OrderSelectionPolicy,
+    first_seen: Instant,
+    margin: i128,
+) -> (Option<Instant>, Reverse<i128>) {
+    let first_seen = match policy {
+        OrderSelectionPolicy::ProfitMax => None,
+        OrderSelectionPolicy::Fifo => Some(first_seen),
+    };
+    (first_seen, Reverse(margin))
+}
+
+/// Splits `scored` into the orders to keep and those sharing an owner and Permit2 nonce with a
+/// more profitable order. Permit2 consumes the nonce on the first transfer, so only one such
+/// order can be filled and the others would revert their bundle. Ties go to the order listed
+/// first, and kept orders stay in their given order.
+fn split_nonce_conflicts(
+    scored: Vec<(i128, SignedOrder)>,
+) -> (Vec<(i128, SignedOrder)>, Vec<SignedOrder>) {
+    let nonce_key = |order: &SignedOrder| (order.permit().owner, order.permit().permit.nonce);
+    let mut best: HashMap<(Address, U256), (i128, usize)> = HashMap::new();
+    for (index, (margin, order)) in scored.iter().enumerate() {
+        best.entry(nonce_key(order))
+            .and_modify(|best| {
+                if *margin > best.0 {
+                    *best = (*margin, index);
+                }
+            })
+            .or_insert((*margin, index));
+    }
+    let mut kept = Vec::with_capacity(best.len());
+    let mut conflicting = Vec::new();
+    for (index, (margin, order)) in scored.into_iter().enumerate() {
+        if best[&nonce_key(&order)].1 == index {
+            kept.push((margin, order));
+        } else {
+            conflicting.push(order);
+        }
+    }
+    (kept, conflicting)
+}
+
+/// The number of leading `margins` to fill, and their total profit after `bundle_gas_cost` for
+/// each bundle of at most `cap` orders they're chunked into. All of them if that total isn't a
+/// loss; otherwise the most profitable prefix, which is empty if every prefix is a loss.
+fn profitable_prefix(
+    margins: &[i128],
+    bundle_gas_cost: i128,
+    cap: Option<NonZeroUsize>,
+) -> (usize, i128) {
+    let profit = |count: usize, total_margin: i128| {
+        let bundles = cap.map_or(1, |cap| count.div_ceil(cap.get()));
+        total_margin.saturating_sub(bundle_gas_cost.saturating_mul(bundles as i128))
+    };
+    let mut best = (0, 0);
+    let mut total_margin = 0_i128;
+    for (index, margin) in margins.iter().enumerate() {
+        total_margin = total_margin.saturating_add(*margin);
+        let count = index + 1;
+        let count_profit = profit(count, total_margin);
+        if count == margins.len() && count_profit >= 0 {
+            return (count, count_profit);
+        }
+        if count_profit > best.1 {
+            best = (count, count_profit);
+        }
+    }
+    best
 }
 
 /// Splits `orders` into chunks of at most `cap` while preserving order. Returns a single chunk
@@ -499,6 +2667,129 @@ mod tests {
         orders.iter().map(|order| order.permit().permit.nonce.saturating_to()).collect()
     }
 
+    #[test]
+    fn next_block_boundary_is_strictly_after_now() {
+        let anchor = Instant::now();
+        let slot = Duration::from_secs(12);
+        assert_eq!(next_block_boundary(anchor, anchor, slot), anchor + slot);
+        assert_eq!(
+            next_block_boundary(anchor, anchor + Duration::from_secs(30), slot),
+            anchor + Duration::from_secs(36)
+        );
+        assert_eq!(
+            next_block_boundary(anchor, anchor + Duration::from_secs(36), slot),
+            anchor + Duration::from_secs(48)
+        );
+    }
+
+    #[tokio::test]
+    async fn collect_until_keeps_items_before_deadline() {
+        let deadline = Instant::now() + Duration::from_millis(50);
+        let items = futures_util::stream::iter([Ok::<_, ()>(1), Ok(2)]);
+        assert_eq!(collect_until(items, deadline).await, Ok((vec![1, 2], true)));
+
+        let stalled =
+            futures_util::stream::iter([Ok::<_, ()>(1)]).chain(futures_util::stream::pending());
+        assert_eq!(collect_until(stalled, deadline).await, Ok((vec![1], false)));
+
+        let failed = futures_util::stream::iter([Ok(1), Err("failed"), Ok(3)]);
+        assert_eq!(collect_until(failed, deadline).await, Err("failed"));
+    }
+
+    #[test]
+    fn slot_boundary_rounds_up_to_schedule() {
+        let boundary =
+            |secs: f64| slot_boundary_at_or_after(Duration::from_secs_f64(secs), 100, 12);
+        assert_eq!(boundary(50.0), 100);
+        assert_eq!(boundary(100.0), 100);
+        assert_eq!(boundary(100.5), 112);
+        assert_eq!(boundary(112.0), 112);
+        assert_eq!(boundary(123.9), 124);
+    }
+
+    #[test]
+    fn instant_at_follows_system_clock() {
+        let slot = Duration::from_secs(12);
+        let ahead = instant_at(SystemTime::now() + slot).saturating_duration_since(Instant::now());
+        assert!(ahead > slot - Duration::from_secs(1) && ahead <= slot);
+        let past = instant_at(SystemTime::now() - slot);
+        let behind = Instant::now().saturating_duration_since(past);
+        assert!(behind >= slot && behind < slot + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn min_margin_scales_with_input_value() {
+        let input = U256::from(1_000_000);
+        // 50 bps of 1,000,000 is 5,000.
+        assert!(meets_min_margin_bps(5_000, input, 50));
+        assert!(!meets_min_margin_bps(4_999, input, 50));
+        assert!(meets_min_margin_bps(0, input, 0));
+        assert!(!meets_min_margin_bps(-1, input, 0));
+        assert!(!meets_min_margin_bps(i128::MAX, U256::MAX, 10_000));
+    }
+
+    #[test]
+    fn selection_key_orders_by_policy() {
+        let now = Instant::now();
+        let earlier = now - Duration::from_secs(12);
+        let profit_max =
+            |first_seen, margin| selection_key(OrderSelectionPolicy::ProfitMax, first_seen, margin);
+        let fifo =
+            |first_seen, margin| selection_key(OrderSelectionPolicy::Fifo, first_seen, margin);
+        assert!(profit_max(now, 20) < profit_max(earlier, 10));
+        assert!(fifo(earlier, 10) < fifo(now, 20));
+        assert!(fifo(now, 20) < fifo(now, 10));
+    }
+
+    #[test]
+    fn aging_boosts_profitable_margins() {
+        assert_eq!(boosted_margin(1_000, 0, 50), 1_000);
+        assert_eq!(boosted_margin(1_000, 3, 50), 2_500);
+        assert_eq!(boosted_margin(-1_000, 3, 50), -1_000);
+        // A small order passed over for long enough outranks a larger fresh one.
+        assert!(boosted_margin(400, 4, 50) > boosted_margin(1_000, 0, 50));
+    }
+
+    #[test]
+    fn nonce_conflicts_keep_the_most_profitable_order() {
+        let scored = vec![
+            (10, build_order(1)),
+            (5, build_order(2)),
+            (30, build_order(1)),
+            (30, build_order(1)),
+            (7, build_order(3)),
+        ];
+        let (kept, conflicting) = split_nonce_conflicts(scored);
+        let kept: Vec<(i128, u64)> = kept
+            .iter()
+            .map(|(margin, order)| (*margin, order.permit().permit.nonce.to::<u64>()))
+            .collect();
+        assert_eq!(kept, vec![(5, 2), (30, 1), (7, 3)]);
+        assert_eq!(ids(&conflicting), vec![1, 1]);
+
+        // The same nonce from different owners doesn't conflict.
+        let other_owner = SignedOrder::new(
+            Permit2Batch { owner: Address::repeat_byte(0x01), ..build_order(1).permit().clone() },
+            vec![],
+        );
+        let (kept, conflicting) =
+            split_nonce_conflicts(vec![(10, build_order(1)), (20, other_owner)]);
+        assert_eq!(kept.len(), 2);
+        assert!(conflicting.is_empty());
+    }
+
+    #[test]
+    fn profitable_prefix_covers_bundle_gas() {
+        // A profitable total is filled in full, even with a loss-making order.
+        assert_eq!(profitable_prefix(&[50, 30, -10], 20, None), (3, 50));
+        // Otherwise the trailing loss is dropped.
+        assert_eq!(profitable_prefix(&[15, 10, -30], 20, None), (2, 5));
+        // Each chunk pays bundle gas; a second bundle for one more order isn't worth it.
+        assert_eq!(profitable_prefix(&[10, 5, 3], 10, NonZeroUsize::new(2)), (2, 5));
+        // Nothing covers the bundle gas.
+        assert_eq!(profitable_prefix(&[5, 5], 20, None), (0, 0));
+    }
+
     #[test]
     fn chunk_orders_returns_single_chunk_when_cap_unset() {
         let chunks = chunk_orders(distinguishable_orders(12), None);
@@ -547,4 +2838,18 @@ mod tests {
         assert_eq!(ids(&chunks[1]), vec![1]);
         assert_eq!(ids(&chunks[2]), vec![2]);
     }
+
+    #[test]
+    fn trusted_owners_orders_bypass_the_signature_check() {
+        let constants = SignetSystemConstants::parmigiana();
+        let trusted = Address::repeat_byte(0x01);
+        let trusted_owners = HashSet::from([trusted]);
+        let signature_checks = Mutex::new(LruCache::new(NonZeroUsize::new(8).unwrap()));
+        let order = crate::testing::OrderBuilder::default().with_owner(trusted).build();
+        assert!(has_valid_signature(&order, &constants, &trusted_owners, &signature_checks));
+        assert!(signature_checks.lock().unwrap().is_empty());
+
+        assert!(!has_valid_signature(&order, &constants, &HashSet::new(), &signature_checks));
+        assert_eq!(signature_checks.lock().unwrap().peek(order.order_hash()), Some(&false));
+    }
 }
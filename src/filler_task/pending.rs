@@ -0,0 +1,142 @@
+use super::receipt::ReceiptBundle;
+use alloy::primitives::B256;
+use lru::LruCache;
+use signet_types::SignedOrder;
+use std::num::NonZeroUsize;
+use tokio::time::Instant;
+
+/// Maximum number of pending fills tracked.
+const CAPACITY: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+
+#[derive(Debug)]
+struct PendingFill {
+    order: SignedOrder,
+    /// When the fill's signatures expire.
+    expires_at: Instant,
+    /// The bundle sent for each rollup block the fill targets.
+    bundles: Vec<ReceiptBundle>,
+}
+
+impl PendingFill {
+    /// The last rollup block the fill's bundle targets.
+    fn last_target_block(&self) -> u64 {
+        self.bundles.iter().map(|bundle| bundle.block_number).max().unwrap_or_default()
+    }
+}
+
+/// Orders in successfully submitted bundles whose fill signatures are still valid, with the
+/// instant the signatures expire and the bundle sent for each rollup block they target. Such an order is
+/// left out of later cycles until it is seen filled, its bundle's last target block passes
+/// without it landing, or its signatures expire, so one fill isn't submitted again while the
+/// first bundle can still land.
+#[derive(Debug)]
+pub(super) struct PendingFills {
+    orders: LruCache<B256, PendingFill>,
+}
+
+impl Default for PendingFills {
+    fn default() -> Self {
+        Self { orders: LruCache::new(CAPACITY) }
+    }
+}
+
+impl PendingFills {
+    /// Records that the order was submitted as `bundles`, one per target rollup block, with fill
+    /// signatures valid until `expires_at`.
+    pub(super) fn insert(
+        &mut self,
+        order: SignedOrder,
+        expires_at: Instant,
+        bundles: Vec<ReceiptBundle>,
+    ) {
+        let pending = PendingFill { order, expires_at, bundles };
+        self.orders.put(*pending.order.order_hash(), pending);
+    }
+
+    /// Whether a fill for the order is pending at `now`. An expired fill is forgotten.
+    pub(super) fn is_pending(&mut self, order_hash: &B256, now: Instant) -> bool {
+        match self.orders.peek(order_hash) {
+            Some(pending) if now < pending.expires_at => true,
+            Some(_) => {
+                self.orders.pop(order_hash);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// The orders whose bundles' last target block is at or below `head`, so whether their fills
+    /// landed can be confirmed.
+    pub(super) fn due(&self, head: u64) -> Vec<SignedOrder> {
+        self.orders
+            .iter()
+            .filter(|(_order_hash, pending)| pending.last_target_block() <= head)
+            .map(|(_order_hash, pending)| pending.order.clone())
+            .collect()
+    }
+
+    /// The bundles the order's pending fill was submitted as, if it has one.
+    pub(super) fn bundles(&self, order_hash: &B256) -> Vec<ReceiptBundle> {
+        self.orders.peek(order_hash).map(|pending| pending.bundles.clone()).unwrap_or_default()
+    }
+
+    /// Forgets the order's pending fill, once it is seen filled or found not to have landed.
+    pub(super) fn remove(&mut self, order_hash: &B256) {
+        self.orders.pop(order_hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use alloy::primitives::{Address, U256};
+    use tokio::time::Duration;
+    use uuid::Uuid;
+
+    fn build_order(nonce: u64) -> SignedOrder {
+        OrderBuilder::default().with_input(Address::ZERO, U256::ZERO).with_nonce(nonce).build()
+    }
+
+    /// One bundle for each rollup block up to `last_target_block`, from the block before it.
+    fn build_bundles(last_target_block: u64) -> Vec<ReceiptBundle> {
+        (last_target_block - 1..=last_target_block)
+            .map(|block_number| ReceiptBundle {
+                bundle_id: Uuid::from_u128(block_number.into()),
+                block_number,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pending_until_signatures_expire() {
+        let mut pending = PendingFills::default();
+        let order = build_order(1);
+        let order_hash = *order.order_hash();
+        let now = Instant::now();
+        assert!(!pending.is_pending(&order_hash, now));
+        pending.insert(order.clone(), now + Duration::from_secs(30), build_bundles(10));
+        assert!(pending.is_pending(&order_hash, now));
+        assert!(!pending.is_pending(&order_hash, now + Duration::from_secs(30)));
+        assert!(!pending.is_pending(&order_hash, now));
+
+        pending.insert(order, now + Duration::from_secs(30), build_bundles(10));
+        assert_eq!(pending.bundles(&order_hash), build_bundles(10));
+        pending.remove(&order_hash);
+        assert!(!pending.is_pending(&order_hash, now));
+        assert!(pending.bundles(&order_hash).is_empty());
+    }
+
+    #[test]
+    fn due_once_last_target_block_passes() {
+        let mut pending = PendingFills::default();
+        let expires_at = Instant::now() + Duration::from_secs(30);
+        pending.insert(build_order(1), expires_at, build_bundles(10));
+        pending.insert(build_order(2), expires_at, build_bundles(12));
+        assert!(pending.due(9).is_empty());
+        let due = pending.due(10);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].permit().permit.nonce, U256::from(1));
+        assert_eq!(pending.due(12).len(), 2);
+    }
+}
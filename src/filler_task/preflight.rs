@@ -1,3 +1,4 @@
+use super::budget::CycleBudget;
 use crate::{AllowanceCache, ChainTokenPair, metrics, query_balance};
 use alloy::primitives::{Address, U256};
 use futures_util::{StreamExt, stream::FuturesUnordered};
@@ -53,6 +54,8 @@ pub(super) struct WorkingMap {
 impl WorkingMap {
     /// Builds a working map for the output tokens needed by the given candidates. Balances are
     /// queried fresh from the chain; allowances are copied from the background-refreshed cache.
+    /// Balance queries are counted against the cycle's RPC budget but never refused, since no
+    /// order can be filled without them.
     #[instrument(skip_all, name = "build_working_map", fields(candidates_len = candidates.len()))]
     pub(super) async fn build(
         candidates: &[(i128, SignedOrder)],
@@ -61,6 +64,7 @@ impl WorkingMap {
         host_provider: &super::FillProviderType,
         constants: &SignetSystemConstants,
         allowance_cache: &AllowanceCache,
+        budget: &CycleBudget,
     ) -> Self {
         let ru_chain_id = constants.ru_chain_id();
         let host_chain_id = constants.host_chain_id();
@@ -92,6 +96,7 @@ impl WorkingMap {
                 (chain_token, allowance)
             })
             .collect();
+        budget.record_rpc_calls(allowances.len());
 
         // Query fresh balances concurrently, pairing each with its cached allowance.
         let inner = allowances
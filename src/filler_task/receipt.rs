@@ -0,0 +1,178 @@
+use crate::metrics;
+use alloy::{
+    hex,
+    primitives::{Address, B256, Signature},
+    signers::Signer,
+};
+use eyre::{Result, WrapErr};
+use init4_bin_base::{
+    deps::tracing::{debug, warn},
+    utils::signer::LocalOrAws,
+};
+use serde::Serialize;
+use signet_bundle::SignetEthBundle;
+use signet_orders::BundleSubmitter;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use uuid::Uuid;
+
+/// Timeout for sending a single receipt to the fill webhook.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Header carrying the filler's hex-encoded EIP-191 signature over the receipt body.
+const SIGNATURE_HEADER: &str = "x-signet-filler-signature";
+
+/// A submitter's response to a bundle, tagged with the block the bundle targeted.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Targeted<Response> {
+    pub(super) block_number: u64,
+    pub(super) response: Response,
+}
+
+/// Wraps a [`BundleSubmitter`], tagging each response with the bundle's target block, so that a
+/// fill's bundle IDs can be matched to the blocks they target.
+#[derive(Debug, Clone)]
+pub(super) struct TargetedSubmitter<Submitter> {
+    inner: Submitter,
+}
+
+impl<Submitter> TargetedSubmitter<Submitter> {
+    pub(super) const fn new(inner: Submitter) -> Self {
+        Self { inner }
+    }
+}
+
+impl<Submitter> BundleSubmitter for TargetedSubmitter<Submitter>
+where
+    Submitter: BundleSubmitter + Send + Sync,
+    Submitter::Response: Send,
+{
+    type Response = Targeted<Submitter::Response>;
+    type Error = Submitter::Error;
+
+    async fn submit_bundle(&self, bundle: SignetEthBundle) -> Result<Self::Response, Self::Error> {
+        let block_number = bundle.bundle.block_number;
+        let response = self.inner.submit_bundle(bundle).await?;
+        Ok(Targeted { block_number, response })
+    }
+}
+
+/// A bundle submitted for a fill, and the rollup block it targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(super) struct ReceiptBundle {
+    pub(super) bundle_id: Uuid,
+    pub(super) block_number: u64,
+}
+
+/// Body of a fill receipt: an order filled on-chain by one of the filler's bundles, and the
+/// bundle and rollup block it landed in.
+#[derive(Debug, Serialize)]
+struct FillReceipt {
+    filler: Address,
+    confirmed_at_ms: u64,
+    order_hash: B256,
+    #[serde(flatten)]
+    bundle: ReceiptBundle,
+}
+
+#[derive(Debug)]
+struct WebhookInner {
+    client: reqwest::Client,
+    url: reqwest::Url,
+    signer: LocalOrAws,
+}
+
+/// Sends a signed receipt of each of the filler's fills seen landing on-chain to the configured
+/// webhook, so order originators can reconcile fills without watching the chain.
+///
+/// Receipts are sent in the background and never delay the filler loop; a receipt that fails to
+/// send is logged and counted, not retried.
+#[derive(Debug, Clone)]
+pub(super) struct FillWebhook {
+    inner: Arc<WebhookInner>,
+}
+
+impl FillWebhook {
+    pub(super) fn new(url: reqwest::Url, signer: LocalOrAws) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .wrap_err("failed to build fill webhook client")?;
+        Ok(Self { inner: Arc::new(WebhookInner { client, url, signer }) })
+    }
+
+    /// Sends a receipt for a fill of `order_hash` that landed in `bundle`.
+    pub(super) fn notify(&self, order_hash: B256, bundle: ReceiptBundle) {
+        let inner = Arc::clone(&self.inner);
+        let confirmed_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock set before UNIX epoch")
+            .as_millis() as u64;
+        tokio::spawn(async move {
+            let receipt =
+                FillReceipt { filler: inner.signer.address(), confirmed_at_ms, order_hash, bundle };
+            match inner.send(&receipt).await {
+                Ok(()) => {
+                    debug!(%order_hash, bundle_id = %bundle.bundle_id, "sent fill receipt");
+                    metrics::record_fill_receipt(metrics::SubmissionResult::Success);
+                }
+                Err(error) => {
+                    warn!(error = format!("{error:#}"), "failed to send fill receipt");
+                    metrics::record_fill_receipt(metrics::SubmissionResult::Failure);
+                }
+            }
+        });
+    }
+}
+
+impl WebhookInner {
+    async fn send(&self, receipt: &FillReceipt) -> Result<()> {
+        let (body, signature) = sign_receipt(&self.signer, receipt).await?;
+        self.client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, hex::encode_prefixed(signature.as_bytes()))
+            .body(body)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .wrap_err("fill webhook request failed")?;
+        Ok(())
+    }
+}
+
+/// Serializes the receipt and signs the exact body bytes as an EIP-191 personal message, so the
+/// recipient can recover the filler's address from the signature.
+async fn sign_receipt(
+    signer: &(impl Signer + Sync),
+    receipt: &FillReceipt,
+) -> Result<(Vec<u8>, Signature)> {
+    let body = serde_json::to_vec(receipt).wrap_err("failed to serialize fill receipt")?;
+    let signature = signer.sign_message(&body).await.wrap_err("failed to sign fill receipt")?;
+    Ok((body, signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+
+    #[tokio::test]
+    async fn receipt_signature_recovers_filler() {
+        let signer = PrivateKeySigner::random();
+        let receipt = FillReceipt {
+            filler: signer.address(),
+            confirmed_at_ms: 1,
+            order_hash: B256::repeat_byte(0x11),
+            bundle: ReceiptBundle { bundle_id: Uuid::from_u128(7), block_number: 100 },
+        };
+        let (body, signature) = sign_receipt(&signer, &receipt).await.unwrap();
+        assert_eq!(signature.recover_address_from_msg(&body).unwrap(), signer.address());
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["block_number"], 100);
+        assert_eq!(json["bundle_id"], Uuid::from_u128(7).to_string());
+        assert_eq!(json["order_hash"], B256::repeat_byte(0x11).to_string());
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{SubmissionRetryConfig, initialization::is_transient_reqwest_error, metrics};
+use core::error::Error;
+use init4_bin_base::deps::tracing::{debug, warn};
+use signet_bundle::SignetEthBundle;
+use signet_orders::BundleSubmitter;
+use signet_tx_cache::TxCacheError;
+use std::{
+    io::ErrorKind,
+    sync::{Arc, Mutex},
+};
+use tokio::time::{Instant, sleep};
+
+/// Wraps the order source's [`BundleSubmitter`], retrying submissions that fail with a transient
+/// error after a doubling backoff, for as long as the configured retry count and the current
+/// submission deadline allow.
+///
+/// Only the final step of a fill - sending the already signed bundle - is retried. Signing the
+/// fill transactions consumes nonces, so retrying the whole fill would leave a nonce gap.
+#[derive(Debug, Clone)]
+pub(super) struct RetryingSubmitter<Submitter> {
+    inner: Submitter,
+    config: SubmissionRetryConfig,
+    deadline: Arc<Mutex<Instant>>,
+}
+
+impl<Submitter> RetryingSubmitter<Submitter> {
+    pub(super) fn new(inner: Submitter, config: SubmissionRetryConfig) -> Self {
+        Self { inner, config, deadline: Arc::new(Mutex::new(Instant::now())) }
+    }
+
+    /// Set the instant after which no retry may start. Shared with every clone.
+    pub(super) fn set_deadline(&self, deadline: Instant) {
+        *self.deadline.lock().unwrap() = deadline;
+    }
+}
+
+impl<Submitter> BundleSubmitter for RetryingSubmitter<Submitter>
+where
+    Submitter: BundleSubmitter + Send + Sync,
+    Submitter::Response: Send,
+{
+    type Response = Submitter::Response;
+    type Error = Submitter::Error;
+
+    async fn submit_bundle(&self, bundle: SignetEthBundle) -> Result<Self::Response, Self::Error> {
+        let mut backoff = self.config.initial_backoff();
+        let mut retries = 0;
+        loop {
+            let error = match self.inner.submit_bundle(bundle.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+            let transient = is_transient(&error);
+            metrics::record_bundle_submission_error(transient);
+            if !transient {
+                return Err(error);
+            }
+            let deadline = *self.deadline.lock().unwrap();
+            if retries >= self.config.max_retries() || Instant::now() + backoff > deadline {
+                warn!(%error, retries, "transient bundle submission error, not retrying");
+                return Err(error);
+            }
+            retries += 1;
+            debug!(
+                %error,
+                retry = retries,
+                backoff_ms = backoff.as_millis(),
+                "transient bundle submission error, retrying"
+            );
+            metrics::record_bundle_submission_retry();
+            sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
+
+/// Whether a submission error is worth retrying: a transaction cache request that timed out,
+/// failed to connect, or got a 5xx or 429 response, or an equivalent I/O error.
+fn is_transient(error: &(dyn Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(TxCacheError::Reqwest(error)) = error.downcast_ref::<TxCacheError>() {
+            return is_transient_reqwest_error(error);
+        }
+        if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+            return is_transient_reqwest_error(error);
+        }
+        if let Some(error) = error.downcast_ref::<std::io::Error>() {
+            return matches!(
+                error.kind(),
+                ErrorKind::TimedOut
+                    | ErrorKind::Interrupted
+                    | ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+            );
+        }
+        current = error.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::rpc::types::mev::EthSendBundle;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::time::Duration;
+
+    /// Fails with `error_kind` for the first `failures` submissions, then succeeds.
+    #[derive(Debug)]
+    struct FlakySubmitter {
+        failures: u32,
+        error_kind: ErrorKind,
+        attempts: AtomicU32,
+    }
+
+    impl BundleSubmitter for FlakySubmitter {
+        type Response = u32;
+        type Error = std::io::Error;
+
+        async fn submit_bundle(&self, _: SignetEthBundle) -> Result<u32, std::io::Error> {
+            let attempt = self.attempts.fetch_add(1, Ordering::Relaxed) + 1;
+            if attempt <= self.failures {
+                return Err(self.error_kind.into());
+            }
+            Ok(attempt)
+        }
+    }
+
+    fn submitter(failures: u32, error_kind: ErrorKind) -> RetryingSubmitter<FlakySubmitter> {
+        let submitter = RetryingSubmitter::new(
+            FlakySubmitter { failures, error_kind, attempts: AtomicU32::new(0) },
+            SubmissionRetryConfig::new(2, Duration::from_millis(1)),
+        );
+        submitter.set_deadline(Instant::now() + Duration::from_secs(10));
+        submitter
+    }
+
+    fn bundle() -> SignetEthBundle {
+        SignetEthBundle::new(EthSendBundle::default(), vec![])
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_retried_up_to_max() {
+        assert_eq!(submitter(2, ErrorKind::TimedOut).submit_bundle(bundle()).await.unwrap(), 3);
+        assert!(submitter(3, ErrorKind::TimedOut).submit_bundle(bundle()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn permanent_errors_and_deadline_stop_retries() {
+        let permanent = submitter(1, ErrorKind::PermissionDenied);
+        assert!(permanent.submit_bundle(bundle()).await.is_err());
+        assert_eq!(permanent.inner.attempts.load(Ordering::Relaxed), 1);
+
+        let late = submitter(1, ErrorKind::TimedOut);
+        late.set_deadline(Instant::now());
+        assert!(late.submit_bundle(bundle()).await.is_err());
+        assert_eq!(late.inner.attempts.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    TokenFilterConfig,
+    metrics::{OrderDefect, OrderSkippedReason},
+};
+use alloy::primitives::{Address, U256};
+use signet_types::SignedOrder;
+use std::collections::HashSet;
+
+/// Finds the first structural defect that makes the order unfit to fill, if any: missing inputs
+/// or outputs, zero amounts, an input token permitted twice, an output paying the zero address,
+/// or an output on a chain other than `chain_ids`. These are checked before any pricing or RPC
+/// work, since such an order would fail to price, fail its bundle, or pay out for nothing.
+pub(super) fn find_defect(order: &SignedOrder, chain_ids: [u64; 2]) -> Option<OrderDefect> {
+    let permitted = &order.permit().permit.permitted;
+    if permitted.is_empty() {
+        return Some(OrderDefect::NoInputs);
+    }
+    if order.outputs().is_empty() {
+        return Some(OrderDefect::NoOutputs);
+    }
+    if permitted.iter().any(|input| input.amount.is_zero())
+        || order.outputs().iter().any(|output| output.amount.is_zero())
+    {
+        return Some(OrderDefect::ZeroAmount);
+    }
+    let mut tokens = HashSet::with_capacity(permitted.len());
+    if !permitted.iter().all(|input| tokens.insert(input.token)) {
+        return Some(OrderDefect::DuplicateInput);
+    }
+    if order.outputs().iter().any(|output| output.recipient == Address::ZERO) {
+        return Some(OrderDefect::ZeroRecipient);
+    }
+    if !order.outputs().iter().all(|output| chain_ids.contains(&u64::from(output.chainId))) {
+        return Some(OrderDefect::UnknownChain);
+    }
+    None
+}
+
+/// Finds the first input or output token of the order that the operator's token lists don't
+/// allow, if any, returning whether it is an input along with the token.
+pub(super) fn find_disallowed_token(
+    order: &SignedOrder,
+    token_filter: &TokenFilterConfig,
+) -> Option<(bool, Address)> {
+    let input = order
+        .permit()
+        .permit
+        .permitted
+        .iter()
+        .map(|input| input.token)
+        .find(|token| !token_filter.allows_input(token))
+        .map(|token| (true, token));
+    input.or_else(|| {
+        order
+            .outputs()
+            .iter()
+            .map(|output| output.token)
+            .find(|token| !token_filter.allows_output(token))
+            .map(|token| (false, token))
+    })
+}
+
+/// Checks an order's output notional, in normalized 18-decimal USD, against the configured
+/// bounds, returning the skip reason if it is below the minimum or above the maximum.
+pub(super) fn check_notional(
+    notional: U256,
+    min: Option<U256>,
+    max: Option<U256>,
+) -> Option<OrderSkippedReason> {
+    if min.is_some_and(|min| notional < min) {
+        Some(OrderSkippedReason::BelowMinNotional)
+    } else if max.is_some_and(|max| notional > max) {
+        Some(OrderSkippedReason::AboveMaxNotional)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+
+    const CHAIN_IDS: [u64; 2] = [519, 1];
+
+    fn build_order(inputs: &[(u8, u64)], outputs: &[(u64, u8, u64)]) -> SignedOrder {
+        let builder = inputs.iter().fold(OrderBuilder::default(), |builder, (token, amount)| {
+            builder.with_input(Address::repeat_byte(*token), U256::from(*amount))
+        });
+        outputs
+            .iter()
+            .fold(builder, |builder, (amount, recipient, chain_id)| {
+                builder.with_output(
+                    Address::repeat_byte(0x11),
+                    U256::from(*amount),
+                    Address::repeat_byte(*recipient),
+                    *chain_id,
+                )
+            })
+            .build()
+    }
+
+    #[test]
+    fn detects_structural_defects() {
+        let defect = |inputs, outputs| find_defect(&build_order(inputs, outputs), CHAIN_IDS);
+        assert_eq!(defect(&[(1, 100)], &[(90, 2, 519)]), None);
+        assert_eq!(defect(&[(1, 100), (3, 5)], &[(90, 2, 519), (1, 2, 1)]), None);
+        assert_eq!(defect(&[], &[(90, 2, 519)]), Some(OrderDefect::NoInputs));
+        assert_eq!(defect(&[(1, 100)], &[]), Some(OrderDefect::NoOutputs));
+        assert_eq!(defect(&[(1, 0)], &[(90, 2, 519)]), Some(OrderDefect::ZeroAmount));
+        assert_eq!(defect(&[(1, 100)], &[(0, 2, 519)]), Some(OrderDefect::ZeroAmount));
+        assert_eq!(defect(&[(1, 100), (1, 5)], &[(90, 2, 519)]), Some(OrderDefect::DuplicateInput));
+        assert_eq!(defect(&[(1, 100)], &[(90, 0, 519)]), Some(OrderDefect::ZeroRecipient));
+        assert_eq!(defect(&[(1, 100)], &[(90, 2, 7)]), Some(OrderDefect::UnknownChain));
+    }
+
+    #[test]
+    fn enforces_notional_bounds() {
+        let (min, max) = (Some(U256::from(10)), Some(U256::from(1000)));
+        assert_eq!(check_notional(U256::from(10), min, max), None);
+        assert_eq!(check_notional(U256::from(1000), min, max), None);
+        assert_eq!(
+            check_notional(U256::from(9), min, max),
+            Some(OrderSkippedReason::BelowMinNotional)
+        );
+        assert_eq!(
+            check_notional(U256::from(1001), min, max),
+            Some(OrderSkippedReason::AboveMaxNotional)
+        );
+        assert_eq!(check_notional(U256::MAX, None, None), None);
+    }
+
+    #[test]
+    fn enforces_token_lists_per_side() {
+        let token = Address::repeat_byte;
+        // Outputs always pay token 0x11.
+        let order = build_order(&[(1, 100), (3, 5)], &[(90, 2, 519)]);
+        let disallowed = |filter: TokenFilterConfig| find_disallowed_token(&order, &filter);
+        assert_eq!(disallowed(TokenFilterConfig::default()), None);
+
+        let allow = |tokens: &[u8]| Some(tokens.iter().map(|byte| token(*byte)).collect());
+        let deny = |tokens: &[u8]| tokens.iter().map(|byte| token(*byte)).collect();
+        let filter = TokenFilterConfig::new(allow(&[1, 3]), deny(&[]), allow(&[0x11]), deny(&[]));
+        assert_eq!(disallowed(filter), None);
+        let filter = TokenFilterConfig::new(allow(&[1]), deny(&[]), None, deny(&[]));
+        assert_eq!(disallowed(filter), Some((true, token(3))));
+        let filter = TokenFilterConfig::new(None, deny(&[3]), None, deny(&[]));
+        assert_eq!(disallowed(filter), Some((true, token(3))));
+        // Lists apply per side: denying token 0x11 as an input doesn't affect outputs.
+        let filter = TokenFilterConfig::new(None, deny(&[0x11]), None, deny(&[1]));
+        assert_eq!(disallowed(filter), None);
+        let filter = TokenFilterConfig::new(None, deny(&[]), None, deny(&[0x11]));
+        assert_eq!(disallowed(filter), Some((false, token(0x11))));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::ScoringHookConfig;
+use alloy::primitives::{Address, B256};
+use eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use signet_types::SignedOrder;
+use std::collections::HashMap;
+
+/// A profitable candidate order as sent to the scoring hook.
+#[derive(Debug, Serialize)]
+struct Candidate<'a> {
+    order_hash: &'a B256,
+    owner: Address,
+    /// Margin net of gas, in normalized 18-decimal USD, as a decimal string.
+    margin: String,
+    order: &'a SignedOrder,
+}
+
+/// Body POSTed to the scoring hook each cycle.
+#[derive(Debug, Serialize)]
+struct ScoringRequest<'a> {
+    filler: Address,
+    cycle: u64,
+    candidates: Vec<Candidate<'a>>,
+}
+
+/// The scoring hook's decision on one candidate.
+#[derive(Debug, Deserialize)]
+struct ScoringDecision {
+    order_hash: B256,
+    accept: bool,
+    /// Candidates with a higher priority are filled first; unset is 0.
+    #[serde(default)]
+    priority: i64,
+}
+
+/// The scoring hook's response: a decision per candidate. Candidates it leaves out are rejected.
+#[derive(Debug, Deserialize)]
+struct ScoringResponse {
+    decisions: Vec<ScoringDecision>,
+}
+
+impl ScoringResponse {
+    /// The priority of each accepted candidate, by order hash.
+    fn into_priorities(self) -> HashMap<B256, i64> {
+        self.decisions
+            .into_iter()
+            .filter(|decision| decision.accept)
+            .map(|decision| (decision.order_hash, decision.priority))
+            .collect()
+    }
+}
+
+/// Sends each cycle's profitable candidates to an external scoring service in one request, so
+/// operators can accept, reject, and prioritize them with strategy logic kept outside the filler.
+#[derive(Debug)]
+pub(super) struct ScoringHook {
+    client: reqwest::Client,
+    url: reqwest::Url,
+    filler: Address,
+    fail_open: bool,
+}
+
+impl ScoringHook {
+    pub(super) fn new(config: &ScoringHookConfig, filler: Address) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout())
+            .build()
+            .wrap_err("failed to build scoring hook client")?;
+        Ok(Self { client, url: config.url().clone(), filler, fail_open: config.fail_open() })
+    }
+
+    /// Whether candidates are kept unscored, rather than skipped, when the hook fails.
+    pub(super) const fn fails_open(&self) -> bool {
+        self.fail_open
+    }
+
+    /// Scores the candidates, returning the priority of each one the hook accepts, by order hash.
+    pub(super) async fn score(
+        &self,
+        cycle: u64,
+        candidates: &[(i128, SignedOrder)],
+    ) -> Result<HashMap<B256, i64>> {
+        let request = ScoringRequest {
+            filler: self.filler,
+            cycle,
+            candidates: candidates
+                .iter()
+                .map(|(margin, order)| Candidate {
+                    order_hash: order.order_hash(),
+                    owner: order.permit().owner,
+                    margin: margin.to_string(),
+                    order,
+                })
+                .collect(),
+        };
+        let response: ScoringResponse = self
+            .client
+            .post(self.url.clone())
+            .json(&request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .wrap_err("scoring hook request failed")?
+            .json()
+            .await
+            .wrap_err("failed to parse scoring hook response")?;
+        Ok(response.into_priorities())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepted_decisions_keep_their_priority() {
+        let hash = B256::repeat_byte;
+        let response: ScoringResponse = serde_json::from_value(serde_json::json!({
+            "decisions": [
+                { "order_hash": hash(1), "accept": true, "priority": 7 },
+                { "order_hash": hash(2), "accept": true },
+                { "order_hash": hash(3), "accept": false, "priority": 9 },
+            ]
+        }))
+        .unwrap();
+        let priorities = response.into_priorities();
+        assert_eq!(priorities.len(), 2);
+        assert_eq!(priorities[&hash(1)], 7);
+        assert_eq!(priorities[&hash(2)], 0);
+        assert!(!priorities.contains_key(&hash(3)));
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{FillProviderType, FixedPricingClient, IERC20, metrics};
+use alloy::{
+    consensus::TxEnvelope,
+    eips::eip2718::Decodable2718,
+    primitives::{Address, B256, Bytes, U256, address, keccak256},
+    providers::Provider,
+    rpc::types::{
+        Log, TransactionRequest,
+        simulate::{SimBlock, SimulatePayload},
+    },
+    sol_types::SolEvent,
+};
+use eyre::{Result, WrapErr};
+use futures_util::lock::Mutex;
+use init4_bin_base::deps::tracing::{debug, warn};
+use signet_bundle::SignetEthBundle;
+use signet_constants::NATIVE_TOKEN_ADDRESS;
+use signet_orders::BundleSubmitter;
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use tokio::try_join;
+
+/// Address that `eth_simulateV1` logs native transfers from when tracing transfers (ERC-7528).
+const NATIVE_TRANSFER_ADDRESS: Address = address!("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+
+/// Outcome of simulating a bundle: `Err` with the reason it was rejected.
+type SimulationOutcome = Result<(), String>;
+
+/// Error returned by [`SimulatingSubmitter`].
+#[derive(Debug, thiserror::Error)]
+pub(super) enum SimulatingSubmitterError<E> {
+    #[error(transparent)]
+    Submission(E),
+    #[error("fill simulation rejected the bundle: {0}")]
+    Rejected(String),
+}
+
+/// Simulates fill bundles against a fork of current rollup and host state, and values the
+/// filler's resulting token transfers.
+#[derive(Debug)]
+pub(super) struct BundleSimulator {
+    ru_provider: FillProviderType,
+    host_provider: FillProviderType,
+    filler_address: Address,
+    pricing_client: Arc<FixedPricingClient>,
+    /// Host native transfers are valued as this token, since the native token address is priced
+    /// as the rollup's USD token.
+    host_weth: Address,
+}
+
+impl BundleSimulator {
+    pub(super) const fn new(
+        ru_provider: FillProviderType,
+        host_provider: FillProviderType,
+        filler_address: Address,
+        pricing_client: Arc<FixedPricingClient>,
+        host_weth: Address,
+    ) -> Self {
+        Self { ru_provider, host_provider, filler_address, pricing_client, host_weth }
+    }
+
+    /// Simulates the bundle's rollup and host transactions, returning `Err` with the reason if a
+    /// transaction reverts or the filler's net token transfers lose more than the maximum loss.
+    /// The outer error is a failure to simulate at all.
+    async fn check(&self, bundle: &SignetEthBundle) -> Result<SimulationOutcome> {
+        let (ru_logs, host_logs) = try_join!(
+            self.simulate(&self.ru_provider, &bundle.bundle.txs, "rollup"),
+            self.simulate(&self.host_provider, &bundle.host_txs, "host"),
+        )?;
+        let (ru_logs, host_logs) = match (ru_logs, host_logs) {
+            (Ok(ru_logs), Ok(host_logs)) => (ru_logs, host_logs),
+            (Err(reason), _) | (_, Err(reason)) => return Ok(Err(reason)),
+        };
+        let mut deltas = BTreeMap::new();
+        tally(&mut deltas, &ru_logs, self.filler_address, NATIVE_TOKEN_ADDRESS);
+        tally(&mut deltas, &host_logs, self.filler_address, self.host_weth);
+        let (received, paid) = self.value_deltas(&deltas)?;
+        debug!(%received, %paid, "simulated fill");
+        if self.pricing_client.within_max_loss(received, paid)? {
+            Ok(Ok(()))
+        } else {
+            Ok(Err(format!("simulated fill receives {received} for {paid} (normalized USD)")))
+        }
+    }
+
+    /// Simulates `txs` in a single block on top of the latest state, returning their logs, or
+    /// `Err` with the reason if one reverts.
+    async fn simulate(
+        &self,
+        provider: &FillProviderType,
+        txs: &[Bytes],
+        chain: &str,
+    ) -> Result<Result<Vec<Log>, String>> {
+        if txs.is_empty() {
+            return Ok(Ok(Vec::new()));
+        }
+        let calls = txs
+            .iter()
+            .map(|tx| {
+                let envelope = TxEnvelope::decode_2718(&mut tx.as_ref())
+                    .wrap_err_with(|| format!("failed to decode {chain} bundle transaction"))?;
+                Ok(TransactionRequest::from_transaction_with_sender(envelope, self.filler_address))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let payload = SimulatePayload {
+            block_state_calls: vec![SimBlock {
+                block_overrides: None,
+                state_overrides: None,
+                calls,
+            }],
+            trace_transfers: true,
+            validation: false,
+            return_full_transactions: false,
+        };
+        let blocks = provider
+            .simulate(&payload)
+            .await
+            .wrap_err_with(|| format!("eth_simulateV1 failed on the {chain} chain"))?;
+        let mut logs = Vec::new();
+        for (index, call) in blocks.into_iter().flat_map(|block| block.calls).enumerate() {
+            if !call.status {
+                let error = call.error.map(|error| error.message).unwrap_or_default();
+                return Ok(Err(format!("{chain} transaction {index} reverted: {error}")));
+            }
+            logs.extend(call.logs);
+        }
+        Ok(Ok(logs))
+    }
+
+    /// Values the net amount of each token received and paid, in normalized 18-decimal USD. A
+    /// token paid out that can't be valued fails the check; one received is conservatively
+    /// valued at zero.
+    fn value_deltas(&self, deltas: &BTreeMap<Address, (U256, U256)>) -> Result<(U256, U256)> {
+        let mut received = U256::ZERO;
+        let mut paid = U256::ZERO;
+        for (token, (token_received, token_paid)) in deltas {
+            if token_received >= token_paid {
+                let value = self.pricing_client.value(token, token_received - token_paid);
+                received = received.saturating_add(value.unwrap_or_default());
+            } else {
+                let value = self
+                    .pricing_client
+                    .value(token, token_paid - token_received)
+                    .wrap_err_with(|| format!("failed to value simulated payment of {token}"))?;
+                paid = paid.saturating_add(value);
+            }
+        }
+        Ok((received, paid))
+    }
+}
+
+/// Adds the amount of each token transferred to and from `account` in `logs` to `deltas`, as
+/// `(received, paid)`. Native transfers are recorded as `native_token`.
+fn tally(
+    deltas: &mut BTreeMap<Address, (U256, U256)>,
+    logs: &[Log],
+    account: Address,
+    native_token: Address,
+) {
+    for log in logs {
+        if log.topic0() != Some(&IERC20::Transfer::SIGNATURE_HASH) {
+            continue;
+        }
+        // ERC721 transfers share the signature but index the token ID, so fail to decode.
+        let Ok(transfer) = IERC20::Transfer::decode_log(&log.inner) else {
+            continue;
+        };
+        let token = if transfer.address == NATIVE_TRANSFER_ADDRESS {
+            native_token
+        } else {
+            transfer.address
+        };
+        let IERC20::Transfer { from, to, value } = transfer.data;
+        if from == to {
+            continue;
+        }
+        if to == account {
+            let (received, _) = deltas.entry(token).or_default();
+            *received = received.saturating_add(value);
+        } else if from == account {
+            let (_, paid) = deltas.entry(token).or_default();
+            *paid = paid.saturating_add(value);
+        }
+    }
+}
+
+/// Wraps a [`BundleSubmitter`], simulating each bundle before submission while simulation is
+/// required, and rejecting it if the simulation reverts, loses more than the maximum loss, or
+/// can't be run.
+///
+/// A fill submits the same transactions once per target block, concurrently; the first
+/// submission simulates and the rest reuse its result.
+#[derive(Debug, Clone)]
+pub(super) struct SimulatingSubmitter<Submitter> {
+    inner: Submitter,
+    simulator: Option<Arc<BundleSimulator>>,
+    required: Arc<AtomicBool>,
+    /// The most recently simulated bundle's transaction hash and result.
+    last: Arc<Mutex<Option<(B256, SimulationOutcome)>>>,
+}
+
+impl<Submitter> SimulatingSubmitter<Submitter> {
+    /// A submitter that never simulates if `simulator` is `None`.
+    pub(super) fn new(inner: Submitter, simulator: Option<BundleSimulator>) -> Self {
+        Self {
+            inner,
+            simulator: simulator.map(Arc::new),
+            required: Default::default(),
+            last: Default::default(),
+        }
+    }
+
+    /// Set whether the following bundles must pass simulation before they are submitted.
+    pub(super) fn require_simulation(&self, required: bool) {
+        self.required.store(required, Ordering::Relaxed);
+    }
+
+    pub(super) const fn inner(&self) -> &Submitter {
+        &self.inner
+    }
+
+    async fn simulation_result(
+        &self,
+        simulator: &BundleSimulator,
+        bundle: &SignetEthBundle,
+    ) -> SimulationOutcome {
+        let key = keccak256(
+            bundle.bundle.txs.iter().chain(&bundle.host_txs).flatten().copied().collect::<Vec<_>>(),
+        );
+        let mut last = self.last.lock().await;
+        if let Some((_, result)) = last.as_ref().filter(|(last_key, _)| *last_key == key) {
+            return result.clone();
+        }
+        let result = match simulator.check(bundle).await {
+            Ok(Ok(())) => {
+                metrics::record_fill_simulation(metrics::SimulationResult::Passed);
+                Ok(())
+            }
+            Ok(Err(reason)) => {
+                metrics::record_fill_simulation(metrics::SimulationResult::Rejected);
+                warn!(%reason, "fill simulation rejected bundle");
+                Err(reason)
+            }
+            Err(error) => {
+                metrics::record_fill_simulation(metrics::SimulationResult::Failed);
+                warn!(error = format!("{error:#}"), "failed to simulate fill bundle");
+                Err(format!("simulation failed: {error:#}"))
+            }
+        };
+        *last = Some((key, result.clone()));
+        result
+    }
+}
+
+impl<Submitter> BundleSubmitter for SimulatingSubmitter<Submitter>
+where
+    Submitter: BundleSubmitter + Send + Sync,
+    Submitter::Response: Send,
+{
+    type Response = Submitter::Response;
+    type Error = SimulatingSubmitterError<Submitter::Error>;
+
+    async fn submit_bundle(&self, bundle: SignetEthBundle) -> Result<Self::Response, Self::Error> {
+        let simulator = self.simulator.as_ref().filter(|_| self.required.load(Ordering::Relaxed));
+        if let Some(simulator) = simulator {
+            self.simulation_result(simulator, &bundle)
+                .await
+                .map_err(SimulatingSubmitterError::Rejected)?;
+        }
+        self.inner.submit_bundle(bundle).await.map_err(SimulatingSubmitterError::Submission)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::LogData;
+
+    const ACCOUNT: Address = Address::repeat_byte(0xaa);
+    const OTHER: Address = Address::repeat_byte(0xbb);
+    const TOKEN: Address = Address::repeat_byte(0x11);
+    const NATIVE: Address = Address::repeat_byte(0x22);
+
+    fn transfer(token: Address, from: Address, to: Address, value: u64) -> Log {
+        let data = IERC20::Transfer { from, to, value: U256::from(value) }.encode_log_data();
+        Log { inner: alloy::primitives::Log { address: token, data }, ..Default::default() }
+    }
+
+    #[test]
+    fn tally_nets_transfers_to_and_from_account() {
+        let logs = [
+            transfer(TOKEN, OTHER, ACCOUNT, 100),
+            transfer(TOKEN, ACCOUNT, OTHER, 30),
+            transfer(NATIVE_TRANSFER_ADDRESS, ACCOUNT, OTHER, 5),
+            // Transfers between other accounts and to self are ignored.
+            transfer(TOKEN, OTHER, OTHER, 1_000),
+            transfer(TOKEN, ACCOUNT, ACCOUNT, 1_000),
+            Log {
+                inner: alloy::primitives::Log {
+                    address: TOKEN,
+                    data: LogData::new_unchecked(vec![B256::ZERO], Bytes::new()),
+                },
+                ..Default::default()
+            },
+        ];
+        let mut deltas = BTreeMap::new();
+        tally(&mut deltas, &logs, ACCOUNT, NATIVE);
+        assert_eq!(deltas.get(&TOKEN), Some(&(U256::from(100), U256::from(30))));
+        assert_eq!(deltas.get(&NATIVE), Some(&(U256::ZERO, U256::from(5))));
+        assert_eq!(deltas.len(), 2);
+    }
+}
@@ -0,0 +1,81 @@
+use alloy::primitives::B256;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+
+/// Maximum number of unprofitable orders tracked.
+const CAPACITY: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+
+#[derive(Debug, Clone, Copy)]
+struct Backoff {
+    /// Consecutive cycles in which the order was priced and found unprofitable.
+    misses: u32,
+    /// The first cycle in which the order is priced again.
+    recheck_cycle: u64,
+}
+
+/// Orders found unprofitable, re-checked at exponentially growing intervals so dead orders stop
+/// being priced every cycle. After its nth consecutive unprofitable result an order is next priced
+/// `2^(n-1)` cycles later (1, 2, 4, 8, ...), capped at `max_interval` cycles. There is one cycle
+/// per slot, so intervals are effectively in slots.
+#[derive(Debug)]
+pub(super) struct UnprofitableOrders {
+    max_interval: u64,
+    orders: LruCache<B256, Backoff>,
+}
+
+impl UnprofitableOrders {
+    pub(super) fn new(max_interval: u64) -> Self {
+        Self { max_interval, orders: LruCache::new(CAPACITY) }
+    }
+
+    /// Whether the order should not be priced in `cycle`.
+    pub(super) fn is_backing_off(&self, order_hash: &B256, cycle: u64) -> bool {
+        self.orders.peek(order_hash).is_some_and(|backoff| cycle < backoff.recheck_cycle)
+    }
+
+    /// Records that the order was found unprofitable in `cycle`, doubling its re-check interval.
+    pub(super) fn record_unprofitable(&mut self, order_hash: B256, cycle: u64) {
+        let misses = self.orders.peek(&order_hash).map_or(1, |backoff| backoff.misses + 1);
+        let interval = 1_u64.checked_shl(misses - 1).unwrap_or(u64::MAX).min(self.max_interval);
+        self.orders
+            .put(order_hash, Backoff { misses, recheck_cycle: cycle.saturating_add(interval) });
+    }
+
+    /// Records that the order was found profitable, resetting its backoff.
+    pub(super) fn record_profitable(&mut self, order_hash: &B256) {
+        self.orders.pop(order_hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recheck_interval_doubles_up_to_max() {
+        let mut unprofitable = UnprofitableOrders::new(4);
+        let order = B256::repeat_byte(1);
+        let mut cycle = 10;
+        for expected_interval in [1, 2, 4, 4, 4] {
+            unprofitable.record_unprofitable(order, cycle);
+            for skipped in 1..expected_interval {
+                assert!(unprofitable.is_backing_off(&order, cycle + skipped));
+            }
+            cycle += expected_interval;
+            assert!(!unprofitable.is_backing_off(&order, cycle));
+        }
+    }
+
+    #[test]
+    fn profitable_result_resets_backoff() {
+        let mut unprofitable = UnprofitableOrders::new(64);
+        let order = B256::repeat_byte(1);
+        (0..5).for_each(|cycle| unprofitable.record_unprofitable(order, cycle));
+        assert!(unprofitable.is_backing_off(&order, 5));
+
+        unprofitable.record_profitable(&order);
+        assert!(!unprofitable.is_backing_off(&order, 5));
+        unprofitable.record_unprofitable(order, 5);
+        assert!(!unprofitable.is_backing_off(&order, 6));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    ChainTokenPair, FillProviderType, FillerContext, KnownToken, RiskEngine, metrics, query_balance,
+};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use futures_util::future::join_all;
+use init4_bin_base::deps::tracing::{Instrument, debug, debug_span};
+use std::sync::Arc;
+
+/// A balance in base units, converted to whole tokens for export.
+fn whole_tokens(balance: U256, decimals: u8) -> f64 {
+    f64::from(balance) / 10_f64.powi(i32::from(decimals))
+}
+
+#[derive(Debug)]
+struct WalletInner {
+    /// Every known token and token-table entry, with its decimals.
+    tokens: Vec<(ChainTokenPair, u8)>,
+    filler_address: Address,
+    ru_chain_id: u64,
+    ru_provider: FillProviderType,
+    host_provider: FillProviderType,
+    risk: RiskEngine,
+}
+
+/// Exports the filler's balance of each token on the rollup and host as the
+/// `signet.filler.wallet_balance` gauge, in whole tokens, so inventory burn-down can be watched on
+/// dashboards, and records them as the inventory of the risk engine's positions.
+///
+/// Balances are refreshed once per cycle in the background, so the queries never delay the
+/// filler loop or count against the cycle budget. A balance that fails to query keeps its last
+/// value.
+#[derive(Debug, Clone)]
+pub(super) struct WalletBalances {
+    inner: Arc<WalletInner>,
+}
+
+impl WalletBalances {
+    pub(super) fn new(context: &FillerContext) -> Self {
+        let constants = context.constants().system();
+        let mut tokens: Vec<(ChainTokenPair, u8)> = KnownToken::ALL
+            .iter()
+            .map(|known| (known.resolve(constants), known.decimals()))
+            .collect();
+        for entry in context.token_table() {
+            let chain_token = ChainTokenPair::new(entry.chain_id(), entry.address());
+            if tokens.iter().all(|(known, _decimals)| *known != chain_token) {
+                tokens.push((chain_token, entry.decimals()));
+            }
+        }
+        let inner = WalletInner {
+            tokens,
+            filler_address: context.signer().address(),
+            ru_chain_id: constants.ru_chain_id(),
+            ru_provider: context.ru_provider().clone(),
+            host_provider: context.host_provider().clone(),
+            risk: context.risk().clone(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Queries every balance in the background and updates the gauges.
+    pub(super) fn refresh(&self) {
+        let inner = Arc::clone(&self.inner);
+        tokio::spawn(
+            async move {
+                join_all(inner.tokens.iter().map(|(chain_token, decimals)| {
+                    let inner = &inner;
+                    async move {
+                        let provider = if chain_token.chain_id() == inner.ru_chain_id {
+                            &inner.ru_provider
+                        } else {
+                            &inner.host_provider
+                        };
+                        match query_balance(provider, inner.filler_address, chain_token.token())
+                            .await
+                        {
+                            Ok(balance) => {
+                                metrics::record_wallet_balance(
+                                    *chain_token,
+                                    whole_tokens(balance, *decimals),
+                                );
+                                inner.risk.set_inventory(*chain_token, balance, *decimals);
+                            }
+                            Err(error) => debug!(
+                                %chain_token,
+                                error = format!("{error:#}"),
+                                "failed to query wallet balance"
+                            ),
+                        }
+                    }
+                }))
+                .await;
+            }
+            .instrument(debug_span!("refresh_wallet_balances")),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_tokens_scales_by_decimals() {
+        assert_eq!(whole_tokens(U256::from(2_500_000), 6), 2.5);
+        assert_eq!(whole_tokens(U256::from(10).pow(U256::from(18)), 18), 1.0);
+        assert_eq!(whole_tokens(U256::ZERO, 8), 0.0);
+    }
+}
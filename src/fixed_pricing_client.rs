@@ -1,8 +1,14 @@
-use alloy::primitives::{Address, U256};
+use crate::{OfflineConfig, SpotPriceCache, TokenMetadata, TwapPriceCache};
+use alloy::primitives::{Address, B256, U256};
 use init4_bin_base::deps::tracing::{instrument, trace};
+use lru::LruCache;
 use signet_constants::SignetSystemConstants;
 use signet_types::SignedOrder;
-use std::collections::HashMap;
+use std::{collections::HashMap, num::NonZeroUsize, sync::Mutex};
+use tokio::time::{Duration, Instant};
+
+/// Maximum number of orders whose quotes are cached.
+const QUOTE_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
 
 /// Metadata for a known token used in value normalization.
 #[derive(Debug, Clone, Copy)]
@@ -11,6 +17,41 @@ struct TokenInfo {
     price_usd: U256,
 }
 
+/// Where market prices are taken from when valuing tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PriceSource {
+    /// A token's TWAP if it has one (with its quote token at its spot price), otherwise its spot
+    /// price.
+    Market,
+    /// TWAPs only, with quote tokens at their fixed prices.
+    Twap,
+    /// Spot prices only.
+    Spot,
+}
+
+/// Recent profitability results by order hash, reused until they are `ttl` old. Only successful
+/// results are cached, so orders that couldn't be priced are retried every cycle.
+#[derive(Debug)]
+struct QuoteCache {
+    ttl: Duration,
+    quotes: Mutex<LruCache<B256, (Instant, Option<i128>)>>,
+}
+
+impl QuoteCache {
+    fn get(&self, order_hash: &B256) -> Option<Option<i128>> {
+        self.quotes
+            .lock()
+            .unwrap()
+            .get(order_hash)
+            .filter(|(quoted_at, _)| quoted_at.elapsed() < self.ttl)
+            .map(|(_, quote)| *quote)
+    }
+
+    fn insert(&self, order_hash: B256, quote: Option<i128>) {
+        self.quotes.lock().unwrap().put(order_hash, (Instant::now(), quote));
+    }
+}
+
 /// Errors from the fixed pricing acceptable loss check.
 #[derive(Debug, Clone, Copy, thiserror::Error)]
 pub(crate) enum FixedPricingError {
@@ -26,19 +67,54 @@ pub(crate) enum FixedPricingError {
     /// A calculation overflowed.
     #[error("arithmetic overflow")]
     Overflow,
+    /// The TWAP and spot valuations of the order diverge beyond the cross-check tolerance, or
+    /// only one of them can price it.
+    #[error("TWAP and spot valuations disagree")]
+    PricesDisagree,
+    /// The token's spot price has gone stale and stale prices are rejected rather than replaced
+    /// by the token's fixed price.
+    #[error("stale spot price for token: {0}")]
+    StalePrice(Address),
 }
 
 /// Pricing client that normalizes token values using hardcoded exchange rates and checks that the
 /// filler's loss does not exceed a configurable percentage.
+///
+/// Inputs and outputs are only ever compared after normalizing to USD, so orders whose inputs and
+/// outputs use different tokens are valued through the rate table rather than by raw amount. An
+/// order with any token that has no rate or market price fails with
+/// [`FixedPricingError::UnknownToken`] instead of being compared unnormalized.
+///
+/// Market prices take precedence over the hardcoded rates when available. Tokens with a fresh
+/// Uniswap V3 TWAP in the [`TwapPriceCache`] are priced relative to their quote token's USD price
+/// (which also prices tokens with no hardcoded rate), and known tokens with a fresh price in the
+/// [`SpotPriceCache`] use it directly. A token whose spot price has gone stale falls back to its
+/// hardcoded rate, or fails with [`FixedPricingError::StalePrice`] if the cache rejects stale
+/// prices.
+///
+/// With cross-validation enabled, an acceptable order is also valued using only TWAPs and only
+/// spot prices (each falling back to the hardcoded rates), and rejected unless the two agree,
+/// so a single bad price source can't cause a losing fill.
+///
+/// With volatility haircuts, listed tokens are valued worse for the filler when quoting an
+/// order, so that a fill stays within the maximum loss if prices move before it lands.
+///
+/// With a quote cache, an order's result is reused for the cache's TTL instead of being priced
+/// again every cycle.
 #[derive(Debug)]
 pub(crate) struct FixedPricingClient {
     max_loss_percent: u8,
     token_info: HashMap<Address, TokenInfo>,
+    twap_prices: Option<TwapPriceCache>,
+    spot_prices: Option<SpotPriceCache>,
+    cross_check_bps: Option<u16>,
+    volatility_haircuts: HashMap<Address, u16>,
+    quote_cache: Option<QuoteCache>,
 }
 
 impl FixedPricingClient {
     /// Returns the wrapped native token address for the given chain name, if one is defined.
-    fn wrapped_token_address(chain_name: &str) -> Option<Address> {
+    pub(crate) fn wrapped_token_address(chain_name: &str) -> Option<Address> {
         match chain_name {
             "parmigiana" => Some(signet_constants::parmigiana::WRAPPED),
             "mainnet" => Some(signet_constants::mainnet::WRAPPED),
@@ -77,7 +153,130 @@ impl FixedPricingClient {
             token_info.insert(wrapped, TokenInfo { decimals: 18, price_usd: U256::from(1) });
         }
 
-        Self { max_loss_percent, token_info }
+        Self {
+            max_loss_percent,
+            token_info,
+            twap_prices: None,
+            spot_prices: None,
+            cross_check_bps: None,
+            volatility_haircuts: HashMap::new(),
+            quote_cache: None,
+        }
+    }
+
+    /// Create a [`FixedPricingClient`] with the pricing configuration of the offline `replay` and
+    /// `backtest` subcommands.
+    pub(crate) fn for_offline(config: &OfflineConfig) -> Self {
+        let pricing_client =
+            Self::new(config.constants().system(), config.chain_name(), config.max_loss_percent())
+                .with_token_table(config.token_table());
+        if config.volatility_haircuts().is_empty() {
+            return pricing_client;
+        }
+        pricing_client.with_volatility_haircuts(config.volatility_haircuts().clone())
+    }
+
+    /// Add the tokens in `table` to the fixed-price table, overriding the built-in rate of any
+    /// token already in it.
+    pub(crate) fn with_token_table(mut self, table: &[TokenMetadata]) -> Self {
+        self.token_info.extend(table.iter().map(|token| {
+            let info =
+                TokenInfo { decimals: token.decimals(), price_usd: U256::from(token.price_usd()) };
+            (token.address(), info)
+        }));
+        self
+    }
+
+    /// Price tokens from `twap_prices` whenever a fresh TWAP is available.
+    pub(crate) fn with_twap_prices(mut self, twap_prices: TwapPriceCache) -> Self {
+        self.twap_prices = Some(twap_prices);
+        self
+    }
+
+    /// Price known tokens from `spot_prices` whenever a fresh price is available.
+    pub(crate) fn with_spot_prices(mut self, spot_prices: SpotPriceCache) -> Self {
+        self.spot_prices = Some(spot_prices);
+        self
+    }
+
+    /// Only accept orders whose TWAP and spot valuations are within `max_divergence_bps` of each
+    /// other.
+    pub(crate) const fn with_cross_check(mut self, max_divergence_bps: u16) -> Self {
+        self.cross_check_bps = Some(max_divergence_bps);
+        self
+    }
+
+    /// When quoting orders, value each token in `haircuts` that many basis points worse for the
+    /// filler: lower when received, higher when paid out.
+    pub(crate) fn with_volatility_haircuts(mut self, haircuts: HashMap<Address, u16>) -> Self {
+        self.volatility_haircuts = haircuts;
+        self
+    }
+
+    /// Reuse each order's profitability result for `ttl` before pricing it again.
+    pub(crate) fn with_quote_cache(mut self, ttl: Duration) -> Self {
+        self.quote_cache =
+            Some(QuoteCache { ttl, quotes: Mutex::new(LruCache::new(QUOTE_CACHE_SIZE)) });
+        self
+    }
+
+    /// Whether [`Self::profitability`] would return a cached result for `order_hash`.
+    pub(crate) fn has_cached_quote(&self, order_hash: &B256) -> bool {
+        self.quote_cache.as_ref().is_some_and(|cache| cache.get(order_hash).is_some())
+    }
+
+    /// The hardcoded whole-USD price of `token`, if it has one.
+    pub(crate) fn fixed_price_usd(&self, token: &Address) -> Option<U256> {
+        self.token_info.get(token).map(|token_info| token_info.price_usd)
+    }
+
+    /// The market USD price per whole `token` from `source`, and the token's decimals.
+    fn market_price(&self, token: &Address, source: PriceSource) -> Option<(f64, u8)> {
+        match source {
+            PriceSource::Market => self.twap_price(token, true).or_else(|| self.spot_price(token)),
+            PriceSource::Twap => self.twap_price(token, false),
+            PriceSource::Spot => self.spot_price(token),
+        }
+    }
+
+    /// The USD price of `token` from its fresh TWAP, if it has one quoted in a priced token. The
+    /// quote token is valued at its spot price if `spot_quote` is set and it has one, otherwise at
+    /// its fixed price.
+    fn twap_price(&self, token: &Address, spot_quote: bool) -> Option<(f64, u8)> {
+        let twap = self.twap_prices.as_ref()?.get(token)?;
+        let quote = twap.quote_token();
+        let quote_price_usd = spot_quote
+            .then(|| self.spot_prices.as_ref().and_then(|prices| prices.get(&quote)))
+            .flatten()
+            .or_else(|| self.fixed_price_usd(&quote).map(f64::from))?;
+        Some((quote_price_usd * twap.quote_per_base(), twap.base_decimals()))
+    }
+
+    /// The fresh spot price of a known `token`, if it has one.
+    fn spot_price(&self, token: &Address) -> Option<(f64, u8)> {
+        let price_usd = self.spot_prices.as_ref()?.get(token)?;
+        Some((price_usd, self.token_info.get(token)?.decimals))
+    }
+
+    /// Normalizes `amount` of `token` using its market price from `source`, if it has one.
+    /// Computed as `amount * price_usd_e18 / 10^decimals`.
+    fn market_normalized(
+        &self,
+        token: &Address,
+        amount: U256,
+        source: PriceSource,
+    ) -> Option<Result<U256, FixedPricingError>> {
+        let (price_usd, decimals) = self.market_price(token, source)?;
+        // Saturating float-to-int cast; prices beyond ~3.4e20 USD are not meaningful anyway.
+        let price_usd_e18 = (price_usd * 1e18) as u128;
+        Some(
+            U256::from(10_u64)
+                .checked_pow(U256::from(decimals))
+                .and_then(|scale| {
+                    amount.checked_mul(U256::from(price_usd_e18)).map(|value| value / scale)
+                })
+                .ok_or(FixedPricingError::Overflow),
+        )
     }
 
     /// Computes the filler's profit margin for an order in normalized 18-decimal USD.
@@ -90,6 +289,19 @@ impl FixedPricingClient {
         &self,
         order: &SignedOrder,
     ) -> Result<Option<i128>, FixedPricingError> {
+        let Some(cache) = &self.quote_cache else {
+            return self.quote(order);
+        };
+        if let Some(quote) = cache.get(order.order_hash()) {
+            trace!(?quote, "using cached quote");
+            return Ok(quote);
+        }
+        let quote = self.quote(order)?;
+        cache.insert(*order.order_hash(), quote);
+        Ok(quote)
+    }
+
+    fn quote(&self, order: &SignedOrder) -> Result<Option<i128>, FixedPricingError> {
         if order.permit().permit.permitted.is_empty() {
             return Err(FixedPricingError::NoInputs);
         }
@@ -97,50 +309,17 @@ impl FixedPricingClient {
             return Err(FixedPricingError::NoOutputs);
         }
 
-        // Normalize a raw token amount to an 18-decimal USD-equivalent value and add it to the
-        // running total, returning the new total:
-        // `running_total + (amount * price_usd * 10^(18 - decimals))`
-        let try_sum = |running_total: U256,
-                       (token_address, amount): (&Address, U256)|
-         -> Result<U256, FixedPricingError> {
-            let token_info = self
-                .token_info
-                .get(token_address)
-                .ok_or(FixedPricingError::UnknownToken(*token_address))?;
-            18_u8
-                .checked_sub(token_info.decimals)
-                .map(U256::from)
-                .and_then(|exponent| U256::from(10_u64).checked_pow(exponent))
-                .and_then(|scale| token_info.price_usd.checked_mul(scale))
-                .and_then(|multiplier| amount.checked_mul(multiplier))
-                .and_then(|normalized_amount| running_total.checked_add(normalized_amount))
-                .ok_or(FixedPricingError::Overflow)
-        };
-
-        let normalized_total_input = order
-            .permit()
-            .permit
-            .permitted
-            .iter()
-            .map(|permitted| (&permitted.token, permitted.amount))
-            .try_fold(U256::ZERO, try_sum)?;
-        let normalized_total_output = order
-            .outputs()
-            .iter()
-            .map(|output| (&output.token, output.amount))
-            .try_fold(U256::ZERO, try_sum)?;
-
-        // Acceptable if inputs/outputs >= (100 - max_loss)%, i.e. inputs * 100 >= outputs * (100 - max_loss)
-        let lhs = normalized_total_input
-            .checked_mul(U256::from(100))
-            .ok_or(FixedPricingError::Overflow)?;
-        let rhs = 100_u8
-            .checked_sub(self.max_loss_percent)
-            .map(U256::from)
-            .and_then(|percent| normalized_total_output.checked_mul(percent))
-            .ok_or(FixedPricingError::Overflow)?;
-
-        if lhs < rhs {
+        let permitted = &order.permit().permit.permitted;
+        let normalized_total_input = self.haircut_total(
+            permitted.iter().map(|permitted| (&permitted.token, permitted.amount)),
+            true,
+        )?;
+        let normalized_total_output = self.haircut_total(
+            order.outputs().iter().map(|output| (&output.token, output.amount)),
+            false,
+        )?;
+
+        if !self.within_max_loss(normalized_total_input, normalized_total_output)? {
             trace!(
                 %normalized_total_input,
                 %normalized_total_output,
@@ -156,6 +335,10 @@ impl FixedPricingClient {
             normalized_total_output.try_into().map_err(|_| FixedPricingError::Overflow)?;
         let margin = input_i128.checked_sub(output_i128).ok_or(FixedPricingError::Overflow)?;
 
+        if let Some(max_divergence_bps) = self.cross_check_bps {
+            self.cross_check(order, max_divergence_bps)?;
+        }
+
         trace!(
             %normalized_total_input,
             %normalized_total_output,
@@ -166,6 +349,167 @@ impl FixedPricingClient {
 
         Ok(Some(margin))
     }
+
+    /// Checks that the order's inputs and outputs are each valued within `max_divergence_bps`
+    /// of each other by TWAPs alone and by spot prices alone.
+    fn cross_check(
+        &self,
+        order: &SignedOrder,
+        max_divergence_bps: u16,
+    ) -> Result<(), FixedPricingError> {
+        let valuations = |source| -> Result<(U256, U256), FixedPricingError> {
+            Ok((self.input_value_from(order, source)?, self.output_value_from(order, source)?))
+        };
+        let ((twap_input, twap_output), (spot_input, spot_output)) =
+            match (valuations(PriceSource::Twap), valuations(PriceSource::Spot)) {
+                (Ok(twap), Ok(spot)) => (twap, spot),
+                (Err(FixedPricingError::UnknownToken(_)), _)
+                | (_, Err(FixedPricingError::UnknownToken(_))) => {
+                    trace!("order can't be valued by both TWAP and spot prices");
+                    return Err(FixedPricingError::PricesDisagree);
+                }
+                (Err(error), _) | (_, Err(error)) => return Err(error),
+            };
+        if diverges(twap_input, spot_input, max_divergence_bps)
+            || diverges(twap_output, spot_output, max_divergence_bps)
+        {
+            trace!(
+                %twap_input,
+                %spot_input,
+                %twap_output,
+                %spot_output,
+                max_divergence_bps,
+                "TWAP and spot valuations disagree"
+            );
+            return Err(FixedPricingError::PricesDisagree);
+        }
+        Ok(())
+    }
+
+    /// The total value of the order's inputs - what the filler receives - in normalized 18-decimal
+    /// USD.
+    pub(crate) fn input_value(&self, order: &SignedOrder) -> Result<U256, FixedPricingError> {
+        self.input_value_from(order, PriceSource::Market)
+    }
+
+    /// [`Self::input_value`], using market prices from `source`.
+    fn input_value_from(
+        &self,
+        order: &SignedOrder,
+        source: PriceSource,
+    ) -> Result<U256, FixedPricingError> {
+        self.normalized_total(
+            order
+                .permit()
+                .permit
+                .permitted
+                .iter()
+                .map(|permitted| (&permitted.token, permitted.amount)),
+            source,
+        )
+    }
+
+    /// Whether receiving `input` in exchange for paying `output` (both normalized 18-decimal USD)
+    /// is within the maximum acceptable loss, i.e. `input / output >= (100 - max_loss)%`.
+    pub(crate) fn within_max_loss(
+        &self,
+        input: U256,
+        output: U256,
+    ) -> Result<bool, FixedPricingError> {
+        let lhs = input.checked_mul(U256::from(100)).ok_or(FixedPricingError::Overflow)?;
+        let rhs = 100_u8
+            .checked_sub(self.max_loss_percent)
+            .map(U256::from)
+            .and_then(|percent| output.checked_mul(percent))
+            .ok_or(FixedPricingError::Overflow)?;
+        Ok(lhs >= rhs)
+    }
+
+    /// The total value of the order's outputs - what the filler pays out - in normalized
+    /// 18-decimal USD.
+    pub(crate) fn output_value(&self, order: &SignedOrder) -> Result<U256, FixedPricingError> {
+        self.output_value_from(order, PriceSource::Market)
+    }
+
+    fn output_value_from(
+        &self,
+        order: &SignedOrder,
+        source: PriceSource,
+    ) -> Result<U256, FixedPricingError> {
+        self.normalized_total(
+            order.outputs().iter().map(|output| (&output.token, output.amount)),
+            source,
+        )
+    }
+
+    /// The value of `amount` of `token` in normalized 18-decimal USD.
+    pub(crate) fn value(&self, token: &Address, amount: U256) -> Result<U256, FixedPricingError> {
+        self.normalized_total([(token, amount)], PriceSource::Market)
+    }
+
+    /// Sums the values of `amounts` like [`Self::normalized_total`], moving each token's value
+    /// against the filler by its volatility haircut: down for tokens `received`, up for tokens
+    /// paid out.
+    fn haircut_total<'a>(
+        &self,
+        amounts: impl IntoIterator<Item = (&'a Address, U256)>,
+        received: bool,
+    ) -> Result<U256, FixedPricingError> {
+        amounts.into_iter().try_fold(U256::ZERO, |running_total, (token_address, amount)| {
+            let value = self.value(token_address, amount)?;
+            let haircut = match self.volatility_haircuts.get(token_address) {
+                Some(bps) => {
+                    value.checked_mul(U256::from(*bps)).ok_or(FixedPricingError::Overflow)?
+                        / U256::from(10_000)
+                }
+                None => U256::ZERO,
+            };
+            let value = if received {
+                value - haircut
+            } else {
+                value.checked_add(haircut).ok_or(FixedPricingError::Overflow)?
+            };
+            running_total.checked_add(value).ok_or(FixedPricingError::Overflow)
+        })
+    }
+
+    /// Sums raw token amounts as 18-decimal USD-equivalent values, normalizing each as
+    /// `amount * price_usd * 10^(18 - decimals)`.
+    fn normalized_total<'a>(
+        &self,
+        amounts: impl IntoIterator<Item = (&'a Address, U256)>,
+        source: PriceSource,
+    ) -> Result<U256, FixedPricingError> {
+        amounts.into_iter().try_fold(U256::ZERO, |running_total, (token_address, amount)| {
+            if let Some(normalized_amount) = self.market_normalized(token_address, amount, source) {
+                return running_total
+                    .checked_add(normalized_amount?)
+                    .ok_or(FixedPricingError::Overflow);
+            }
+            if self.spot_prices.as_ref().is_some_and(|prices| prices.rejects(token_address)) {
+                return Err(FixedPricingError::StalePrice(*token_address));
+            }
+            let token_info = self
+                .token_info
+                .get(token_address)
+                .ok_or(FixedPricingError::UnknownToken(*token_address))?;
+            18_u8
+                .checked_sub(token_info.decimals)
+                .map(U256::from)
+                .and_then(|exponent| U256::from(10_u64).checked_pow(exponent))
+                .and_then(|scale| token_info.price_usd.checked_mul(scale))
+                .and_then(|multiplier| amount.checked_mul(multiplier))
+                .and_then(|normalized_amount| running_total.checked_add(normalized_amount))
+                .ok_or(FixedPricingError::Overflow)
+        })
+    }
+}
+
+/// Whether `a` and `b` differ by more than `max_divergence_bps` of the larger.
+fn diverges(a: U256, b: U256, max_divergence_bps: u16) -> bool {
+    let difference = if a > b { a - b } else { b - a };
+    difference.saturating_mul(U256::from(10_000))
+        > a.max(b).saturating_mul(U256::from(max_divergence_bps))
 }
 
 #[cfg(test)]
@@ -210,6 +554,19 @@ mod tests {
         )
     }
 
+    #[test]
+    fn volatility_haircuts_value_tokens_against_the_filler() {
+        let usdc = SignetSystemConstants::parmigiana().host().tokens().usdc();
+        let client = parmigiana_client(10).with_volatility_haircuts(HashMap::from([(usdc, 100)]));
+        // 1% off the 1,000 USDC received and 1% on the 990 USDC paid out.
+        let margin = client.profitability(&usdc_order(1_000_000_000, 990_000_000)).unwrap();
+        assert_eq!(margin, Some(-9_900_000_000_000_000_000));
+        assert_eq!(
+            parmigiana_client(10).profitability(&usdc_order(1_000_000_000, 990_000_000)).unwrap(),
+            Some(10_000_000_000_000_000_000)
+        );
+    }
+
     // -- max_loss_percent = 0: input must be >= output --
 
     #[test]
@@ -344,6 +701,23 @@ mod tests {
         assert!(matches!(client.profitability(&order), Err(FixedPricingError::NoOutputs)));
     }
 
+    #[test]
+    fn token_table_adds_and_overrides_tokens() {
+        let unknown = Address::repeat_byte(0xFF);
+        let weth = SignetSystemConstants::parmigiana().host().tokens().weth();
+        let table: Vec<TokenMetadata> = serde_json::from_str(&format!(
+            r#"[
+                {{"chain_id": 1, "address": "{unknown}", "decimals": 6, "price_usd": 2}},
+                {{"chain_id": 1, "address": "{weth}", "decimals": 18, "price_usd": 4000}}
+            ]"#
+        ))
+        .unwrap();
+        let client = parmigiana_client(0).with_token_table(&table);
+        let one_e18 = U256::from(10_u64).pow(U256::from(18));
+        assert_eq!(client.value(&unknown, U256::from(1_000_000)).unwrap(), one_e18 * U256::from(2));
+        assert_eq!(client.value(&weth, one_e18).unwrap(), one_e18 * U256::from(4000));
+    }
+
     #[test]
     fn unknown_token_returns_error() {
         let client = parmigiana_client(10);
@@ -429,4 +803,171 @@ mod tests {
         // Equal USD value: margin = 0
         assert_eq!(client.profitability(&order).unwrap(), Some(0));
     }
+
+    // -- TWAP pricing --
+
+    #[test]
+    fn twap_price_overrides_fixed_rate() {
+        let constants = SignetSystemConstants::parmigiana();
+        let weth = constants.host().tokens().weth();
+        let usdc = constants.host().tokens().usdc();
+        let twap_prices = TwapPriceCache::default();
+        let client = parmigiana_client(0).with_twap_prices(twap_prices.clone());
+        let one_weth = U256::from(1_000_000_000_000_000_000_u64);
+
+        // Without a TWAP, WETH uses the fixed $3000 rate.
+        assert_eq!(client.value(&weth, one_weth).unwrap(), U256::from(3000) * one_weth);
+
+        twap_prices.insert_fresh(weth, usdc, 2500.0, 18);
+        assert_eq!(client.value(&weth, one_weth).unwrap(), U256::from(2500) * one_weth);
+
+        // Tokens with no fixed rate can be priced from a TWAP alone.
+        let unknown = Address::repeat_byte(0xaa);
+        assert!(matches!(
+            client.value(&unknown, U256::from(1_000_000)),
+            Err(FixedPricingError::UnknownToken(_))
+        ));
+        twap_prices.insert_fresh(unknown, usdc, 0.5, 6);
+        assert_eq!(
+            client.value(&unknown, U256::from(1_000_000)).unwrap(),
+            one_weth / U256::from(2)
+        );
+    }
+
+    #[test]
+    fn spot_price_overrides_fixed_rate() {
+        let constants = SignetSystemConstants::parmigiana();
+        let weth = constants.host().tokens().weth();
+        let usdc = constants.host().tokens().usdc();
+        let spot_prices = SpotPriceCache::new(Duration::from_secs(60));
+        let twap_prices = TwapPriceCache::default();
+        let client = parmigiana_client(0)
+            .with_spot_prices(spot_prices.clone())
+            .with_twap_prices(twap_prices.clone());
+        let one_weth = U256::from(1_000_000_000_000_000_000_u64);
+
+        spot_prices.insert(weth, 3500.0);
+        assert_eq!(client.value(&weth, one_weth).unwrap(), U256::from(3500) * one_weth);
+
+        // Spot prices only apply to tokens with known decimals.
+        let unknown = Address::repeat_byte(0xaa);
+        spot_prices.insert(unknown, 1.0);
+        assert!(client.value(&unknown, U256::from(1)).is_err());
+
+        // A TWAP takes precedence, priced against the quote token's spot price.
+        spot_prices.insert(usdc, 0.5);
+        twap_prices.insert_fresh(weth, usdc, 2000.0, 18);
+        assert_eq!(client.value(&weth, one_weth).unwrap(), U256::from(1000) * one_weth);
+    }
+
+    #[test]
+    fn stale_spot_price_is_rejected_under_reject_policy() {
+        let weth = SignetSystemConstants::parmigiana().host().tokens().weth();
+        let spot_prices = SpotPriceCache::new(Duration::ZERO)
+            .with_stale_price_policy(crate::StalePricePolicy::Reject);
+        let client = parmigiana_client(0).with_spot_prices(spot_prices.clone());
+        let one_weth = U256::from(1_000_000_000_000_000_000_u64);
+        // Never priced by the API: the fixed rate applies.
+        assert_eq!(client.value(&weth, one_weth).unwrap(), U256::from(3000) * one_weth);
+
+        spot_prices.insert(weth, 3500.0);
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        assert!(matches!(
+            client.value(&weth, one_weth),
+            Err(FixedPricingError::StalePrice(token)) if token == weth
+        ));
+    }
+
+    #[test]
+    fn quote_cache_reuses_results_within_ttl() {
+        let constants = SignetSystemConstants::parmigiana();
+        let weth = constants.host().tokens().weth();
+        let usdc = constants.host().tokens().usdc();
+        let spot_prices = SpotPriceCache::new(Duration::from_secs(60));
+        let cached = parmigiana_client(100)
+            .with_spot_prices(spot_prices.clone())
+            .with_quote_cache(Duration::from_secs(60));
+        let uncached = parmigiana_client(100)
+            .with_spot_prices(spot_prices.clone())
+            .with_quote_cache(Duration::ZERO);
+        // 1 WETH in, 3000 USDC out.
+        let order = SignedOrder::new(
+            Permit2Batch {
+                permit: PermitBatchTransferFrom {
+                    permitted: vec![TokenPermissions {
+                        token: weth,
+                        amount: U256::from(1_000_000_000_000_000_000_u64),
+                    }],
+                    nonce: U256::ZERO,
+                    deadline: U256::ZERO,
+                },
+                owner: Address::ZERO,
+                signature: Bytes::from([0; 65]),
+            },
+            vec![Output {
+                token: usdc,
+                amount: U256::from(3_000_000_000_u64),
+                recipient: Address::ZERO,
+                chainId: 0,
+            }],
+        );
+
+        assert!(!cached.has_cached_quote(order.order_hash()));
+        assert_eq!(cached.profitability(&order).unwrap(), Some(0));
+        assert_eq!(uncached.profitability(&order).unwrap(), Some(0));
+        assert!(cached.has_cached_quote(order.order_hash()));
+        assert!(!uncached.has_cached_quote(order.order_hash()));
+
+        spot_prices.insert(weth, 3100.0);
+        assert_eq!(cached.profitability(&order).unwrap(), Some(0));
+        assert_eq!(uncached.profitability(&order).unwrap(), Some(100 * 10_i128.pow(18)));
+    }
+
+    #[test]
+    fn cross_check_rejects_diverging_valuations() {
+        let constants = SignetSystemConstants::parmigiana();
+        let weth = constants.host().tokens().weth();
+        let usdc = constants.host().tokens().usdc();
+        let spot_prices = SpotPriceCache::new(Duration::from_secs(60));
+        let twap_prices = TwapPriceCache::default();
+        let client = parmigiana_client(100)
+            .with_spot_prices(spot_prices.clone())
+            .with_twap_prices(twap_prices.clone())
+            .with_cross_check(100);
+        // 1 WETH in, 2500 USDC out.
+        let order = SignedOrder::new(
+            Permit2Batch {
+                permit: PermitBatchTransferFrom {
+                    permitted: vec![TokenPermissions {
+                        token: weth,
+                        amount: U256::from(1_000_000_000_000_000_000_u64),
+                    }],
+                    nonce: U256::ZERO,
+                    deadline: U256::ZERO,
+                },
+                owner: Address::ZERO,
+                signature: Bytes::from([0; 65]),
+            },
+            vec![Output {
+                token: usdc,
+                amount: U256::from(2_500_000_000_u64),
+                recipient: Address::ZERO,
+                chainId: 0,
+            }],
+        );
+
+        // With neither price, both valuations fall back to the fixed rates and agree.
+        assert!(client.profitability(&order).unwrap().is_some());
+
+        // A TWAP alone is checked against the fixed rate.
+        twap_prices.insert_fresh(weth, usdc, 2500.0, 18);
+        assert!(matches!(client.profitability(&order), Err(FixedPricingError::PricesDisagree)));
+
+        // Within 1% of each other.
+        spot_prices.insert(weth, 2520.0);
+        assert_eq!(client.profitability(&order).unwrap(), Some(0));
+
+        spot_prices.insert(weth, 2600.0);
+        assert!(matches!(client.profitability(&order), Err(FixedPricingError::PricesDisagree)));
+    }
 }
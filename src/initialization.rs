@@ -1,11 +1,19 @@
 use crate::{
-    AllowanceCache, ChainTokenPair, Config, FillProviderType, KnownToken,
+    AdaptiveLeadConfig, AdminConfig, AllowanceCache, ApprovalPolicy, ArchiveBuffer, ArchiveConfig,
+    BootstrapMode, BridgeConfig, ChainTokenPair, ChainWatch, Config, Coordination,
+    CoordinationConfig, CycleBudgetConfig, DegradationLadder, DeniedOwners, FeeOracleConfig,
+    FileOrderSource, FillHistory, FillProviderType, FillThrottle, FilledOrderStore, GasPriceCache,
+    InventoryConfig, KnownToken, LandedOrders, Leadership, LossLimit, LowBalanceConfig,
+    OrderSelectionPolicy, OwnerReputation, PauseSwitch, PnlLedger, PriceApiConfig, QuietSchedule,
+    RiskEngine, SchedulingMode, ScoringHookConfig, Screener, SpendPolicy, SpendPolicyConfig,
+    SpotPriceCache, SubmissionRetryConfig, TokenDiscovery, TokenDiscoveryConfig, TokenFilterConfig,
+    TokenMetadata, ToxicFlow, TwapConfig, TwapPriceCache,
     metrics::{self, ConnectionTarget},
     query_balance,
 };
 use alloy::{
     network::EthereumWallet,
-    primitives::Address,
+    primitives::{Address, U256},
     providers::{Provider, ProviderBuilder},
     rpc::client::BuiltInConnectionString,
     signers::Signer,
@@ -25,7 +33,10 @@ use init4_bin_base::{
 use signet_constants::{SignetConstants, SignetSystemConstants};
 use signet_tx_cache::TxCache;
 use std::{
+    collections::HashMap,
+    net::SocketAddr,
     num::NonZeroUsize,
+    path::Path,
     sync::{
         LazyLock,
         atomic::{AtomicUsize, Ordering},
@@ -47,6 +58,29 @@ pub struct FillerContext {
     ru_provider: FillProviderType,
     tx_cache: TxCache,
     allowance_cache: AllowanceCache,
+    archive_buffer: Option<ArchiveBuffer>,
+    fill_throttle: Option<FillThrottle>,
+    spend_policy: Option<SpendPolicy>,
+    quiet_schedule: QuietSchedule,
+    pause: PauseSwitch,
+    loss_limit: Option<LossLimit>,
+    risk: RiskEngine,
+    ledger: PnlLedger,
+    fill_history: FillHistory,
+    reputation: OwnerReputation,
+    toxic_flow: Option<ToxicFlow>,
+    denied_owners: DeniedOwners,
+    degradation: DegradationLadder,
+    twap_prices: Option<TwapPriceCache>,
+    spot_prices: Option<SpotPriceCache>,
+    gas_prices: Option<GasPriceCache>,
+    token_discovery: Option<TokenDiscovery>,
+    screener: Option<Screener>,
+    filled_order_store: Option<FilledOrderStore>,
+    coordination: Option<Coordination>,
+    leadership: Option<Leadership>,
+    landed_orders: LandedOrders,
+    chain_watch: ChainWatch,
 }
 
 impl FillerContext {
@@ -83,6 +117,70 @@ impl FillerContext {
             } => result.wrap_err("initialization failure")?,
         };
         let allowance_cache = AllowanceCache::new();
+        let archive_buffer = config.archive().map(|_| ArchiveBuffer::default());
+        let fill_throttle = config.fill_throttle().copied().map(FillThrottle::new);
+        let spend_policy = config.spend_policy().cloned().map(SpendPolicy::new);
+        let quiet_schedule =
+            QuietSchedule::new(config.quiet_periods(), config.constants().system());
+        let reputation = OwnerReputation::new(*config.reputation());
+        let toxic_flow = config.toxic_flow().copied().map(ToxicFlow::new);
+        let pause = PauseSwitch::default();
+        let loss_limit =
+            config.loss_limit().map(|loss_limit| LossLimit::new(*loss_limit, pause.clone()));
+        let risk =
+            RiskEngine::new(config.constants().system().ru_chain_id(), config.position_limits());
+        let ledger = PnlLedger::new(config.constants().system().ru_chain_id());
+        let fill_history = FillHistory::new(config.constants().system().ru_chain_id());
+        let denied_owners = DeniedOwners::new(config.denied_owners().iter().copied());
+        let degradation = DegradationLadder::default();
+        // Discovered tokens are priced from TWAPs, so discovery needs the cache even without
+        // configured pools.
+        let twap_prices = (config.twap().is_some() || config.token_discovery().is_some())
+            .then(TwapPriceCache::default);
+        let spot_prices = config.price_api().map(SpotPriceCache::for_config);
+        let gas_prices = config.fee_oracle().map(|fee_oracle| {
+            GasPriceCache::new(
+                fee_oracle.gas_per_order(),
+                fee_oracle.host_gas_per_order(),
+                fee_oracle.gas_per_bundle(),
+            )
+        });
+        let token_discovery = config.token_discovery().copied().map(TokenDiscovery::new);
+        let screener = config.screening().map(Screener::new).transpose()?;
+        let filled_order_store = config
+            .filled_order_store()
+            .map(|path| FilledOrderStore::open(path, config.filled_orders_cache_size()))
+            .transpose()?;
+        let coordination = match config.coordination() {
+            Some(coordination) => {
+                let coordination = Coordination::connect(coordination, signer.address()).await?;
+                info!(?coordination, "coordinating with filler replicas through Redis");
+                Some(coordination)
+            }
+            None => None,
+        };
+        let leadership = config
+            .coordination()
+            .and_then(CoordinationConfig::leader_lease)
+            .map(|_| Leadership::default());
+        let landed_orders = LandedOrders::default();
+        let order_backfill_blocks = config.order_backfill_blocks();
+        if order_backfill_blocks > 0 {
+            let orders_address = config.constants().system().rollup().orders();
+            match landed_orders.backfill(&ru_provider, orders_address, order_backfill_blocks).await
+            {
+                Ok(landed) => {
+                    info!(
+                        landed,
+                        blocks = order_backfill_blocks,
+                        "backfilled recent rollup orders"
+                    );
+                }
+                Err(error) => {
+                    warn!(error = format!("{error:#}"), "failed to backfill recent rollup orders");
+                }
+            }
+        }
 
         Ok(Self {
             config,
@@ -93,6 +191,29 @@ impl FillerContext {
             ru_provider,
             tx_cache,
             allowance_cache,
+            archive_buffer,
+            fill_throttle,
+            spend_policy,
+            quiet_schedule,
+            pause,
+            loss_limit,
+            risk,
+            ledger,
+            fill_history,
+            reputation,
+            toxic_flow,
+            denied_owners,
+            degradation,
+            twap_prices,
+            spot_prices,
+            gas_prices,
+            token_discovery,
+            screener,
+            filled_order_store,
+            coordination,
+            leadership,
+            landed_orders,
+            chain_watch: ChainWatch::default(),
         })
     }
 
@@ -112,14 +233,114 @@ impl FillerContext {
         self.config.block_lead_duration()
     }
 
+    pub(crate) const fn adaptive_lead(&self) -> Option<AdaptiveLeadConfig> {
+        self.config.adaptive_lead()
+    }
+
+    pub(crate) fn follow_up_leads(&self) -> &[Duration] {
+        self.config.follow_up_leads()
+    }
+
+    pub(crate) const fn min_submission_lead(&self) -> Duration {
+        self.config.min_submission_lead()
+    }
+
     pub(crate) const fn max_loss_percent(&self) -> u8 {
         self.config.max_loss_percent()
     }
 
+    pub(crate) const fn min_expected_profit(&self) -> Option<i128> {
+        self.config.min_expected_profit()
+    }
+
+    pub(crate) const fn token_filter(&self) -> &TokenFilterConfig {
+        self.config.token_filter()
+    }
+
+    pub(crate) const fn volatility_haircuts(&self) -> &HashMap<Address, u16> {
+        self.config.volatility_haircuts()
+    }
+
+    pub(crate) const fn min_margin_bps(&self) -> Option<u16> {
+        self.config.min_margin_bps()
+    }
+
     pub(crate) const fn target_blocks(&self) -> u8 {
         self.config.target_blocks()
     }
 
+    pub(crate) const fn fill_deadline_offset(&self) -> Option<Duration> {
+        self.config.fill_deadline_offset()
+    }
+
+    pub(crate) const fn bootstrap_mode(&self) -> BootstrapMode {
+        self.config.bootstrap_mode()
+    }
+
+    pub(crate) const fn evaluation_interval(&self) -> Option<Duration> {
+        self.config.evaluation_interval()
+    }
+
+    pub(crate) const fn scheduling_mode(&self) -> SchedulingMode {
+        self.config.scheduling_mode()
+    }
+
+    pub(crate) const fn order_selection_policy(&self) -> OrderSelectionPolicy {
+        self.config.order_selection_policy()
+    }
+
+    pub(crate) const fn aging_boost_percent(&self) -> Option<u16> {
+        self.config.aging_boost_percent()
+    }
+
+    pub(crate) const fn cycle_budget(&self) -> &CycleBudgetConfig {
+        self.config.cycle_budget()
+    }
+
+    pub(crate) const fn inventory(&self) -> Option<&InventoryConfig> {
+        self.config.inventory()
+    }
+
+    /// The file-backed order source configured via `SIGNET_FILLER_ORDER_FILE`, if any. When
+    /// `None`, the filler uses the transaction cache.
+    pub fn file_order_source(&self) -> Option<FileOrderSource> {
+        self.config.order_file().map(|orders_path| {
+            FileOrderSource::new(
+                orders_path.to_path_buf(),
+                self.config.bundle_file().map(Path::to_path_buf),
+            )
+        })
+    }
+
+    pub(crate) const fn filled_order_store(&self) -> Option<&FilledOrderStore> {
+        self.filled_order_store.as_ref()
+    }
+
+    pub(crate) const fn coordination(&self) -> Option<&Coordination> {
+        self.coordination.as_ref()
+    }
+
+    pub(crate) fn leader_lease(&self) -> Option<Duration> {
+        self.config.coordination().and_then(CoordinationConfig::leader_lease)
+    }
+
+    /// Whether this replica holds the leader lease, if leader election is enabled.
+    pub(crate) const fn leadership(&self) -> Option<&Leadership> {
+        self.leadership.as_ref()
+    }
+
+    pub(crate) const fn landed_orders(&self) -> &LandedOrders {
+        &self.landed_orders
+    }
+
+    pub(crate) const fn filled_orders_cache_size(&self) -> NonZeroUsize {
+        self.config.filled_orders_cache_size()
+    }
+
+    pub(crate) const fn chain_watch(&self) -> &ChainWatch {
+        &self.chain_watch
+    }
+
     pub(crate) const fn max_orders_per_bundle(&self) -> Option<NonZeroUsize> {
         self.config.max_orders_per_bundle()
     }
@@ -148,9 +369,169 @@ impl FillerContext {
         &self.allowance_cache
     }
 
-    /// The port for the healthcheck HTTP server.
-    pub const fn healthcheck_port(&self) -> u16 {
-        self.config.healthcheck_port()
+    pub(crate) const fn archive_config(&self) -> Option<&ArchiveConfig> {
+        self.config.archive()
+    }
+
+    pub(crate) const fn archive_buffer(&self) -> Option<&ArchiveBuffer> {
+        self.archive_buffer.as_ref()
+    }
+
+    pub(crate) const fn fill_throttle(&self) -> Option<&FillThrottle> {
+        self.fill_throttle.as_ref()
+    }
+
+    pub(crate) const fn spend_policy(&self) -> Option<&SpendPolicy> {
+        self.spend_policy.as_ref()
+    }
+
+    pub(crate) const fn quiet_schedule(&self) -> &QuietSchedule {
+        &self.quiet_schedule
+    }
+
+    pub(crate) const fn pause(&self) -> &PauseSwitch {
+        &self.pause
+    }
+
+    pub(crate) const fn loss_limit(&self) -> Option<&LossLimit> {
+        self.loss_limit.as_ref()
+    }
+
+    pub(crate) const fn risk(&self) -> &RiskEngine {
+        &self.risk
+    }
+
+    pub(crate) const fn ledger(&self) -> &PnlLedger {
+        &self.ledger
+    }
+
+    pub(crate) const fn fill_history(&self) -> &FillHistory {
+        &self.fill_history
+    }
+
+    pub(crate) const fn spend_policy_config(&self) -> Option<&SpendPolicyConfig> {
+        self.config.spend_policy()
+    }
+
+    pub(crate) const fn reputation(&self) -> &OwnerReputation {
+        &self.reputation
+    }
+
+    pub(crate) const fn toxic_flow(&self) -> Option<&ToxicFlow> {
+        self.toxic_flow.as_ref()
+    }
+
+    pub(crate) const fn denied_owners(&self) -> &DeniedOwners {
+        &self.denied_owners
+    }
+
+    pub(crate) const fn degradation(&self) -> &DegradationLadder {
+        &self.degradation
+    }
+
+    pub(crate) fn trusted_owners(&self) -> &[Address] {
+        self.config.trusted_owners()
+    }
+
+    pub(crate) const fn verify_signatures(&self) -> bool {
+        self.config.verify_signatures()
+    }
+
+    pub(crate) const fn submission_retry(&self) -> &SubmissionRetryConfig {
+        self.config.submission_retry()
+    }
+
+    pub(crate) const fn twap_config(&self) -> Option<&TwapConfig> {
+        self.config.twap()
+    }
+
+    pub(crate) const fn twap_prices(&self) -> Option<&TwapPriceCache> {
+        self.twap_prices.as_ref()
+    }
+
+    pub(crate) const fn price_api_config(&self) -> Option<&PriceApiConfig> {
+        self.config.price_api()
+    }
+
+    pub(crate) fn token_table(&self) -> &[TokenMetadata] {
+        self.config.token_table()
+    }
+
+    pub(crate) const fn approval_policy(&self) -> ApprovalPolicy {
+        self.config.approval_policy()
+    }
+
+    pub(crate) const fn price_cross_check_bps(&self) -> Option<u16> {
+        self.config.price_cross_check_bps()
+    }
+
+    pub(crate) const fn quote_cache_ttl(&self) -> Option<Duration> {
+        self.config.quote_cache_ttl()
+    }
+
+    pub(crate) const fn unprofitable_max_backoff_slots(&self) -> Option<u64> {
+        self.config.unprofitable_max_backoff_slots()
+    }
+
+    pub(crate) const fn spot_prices(&self) -> Option<&SpotPriceCache> {
+        self.spot_prices.as_ref()
+    }
+
+    pub(crate) const fn fee_oracle_config(&self) -> Option<&FeeOracleConfig> {
+        self.config.fee_oracle()
+    }
+
+    pub(crate) const fn bridge_config(&self) -> Option<&BridgeConfig> {
+        self.config.bridge()
+    }
+
+    pub(crate) const fn low_balance_config(&self) -> Option<&LowBalanceConfig> {
+        self.config.low_balance()
+    }
+
+    pub(crate) const fn gas_prices(&self) -> Option<&GasPriceCache> {
+        self.gas_prices.as_ref()
+    }
+
+    pub(crate) const fn token_discovery_config(&self) -> Option<&TokenDiscoveryConfig> {
+        self.config.token_discovery()
+    }
+
+    pub(crate) const fn simulation_min_notional(&self) -> Option<U256> {
+        self.config.simulation_min_notional()
+    }
+
+    pub(crate) const fn min_order_notional(&self) -> Option<U256> {
+        self.config.min_order_notional()
+    }
+
+    pub(crate) const fn scoring_hook(&self) -> Option<&ScoringHookConfig> {
+        self.config.scoring_hook()
+    }
+
+    pub(crate) const fn max_order_notional(&self) -> Option<U256> {
+        self.config.max_order_notional()
+    }
+
+    pub(crate) const fn fill_webhook_url(&self) -> Option<&reqwest::Url> {
+        self.config.fill_webhook_url()
+    }
+
+    pub(crate) const fn token_discovery(&self) -> Option<&TokenDiscovery> {
+        self.token_discovery.as_ref()
+    }
+
+    pub(crate) const fn screener(&self) -> Option<&Screener> {
+        self.screener.as_ref()
+    }
+
+    pub(crate) const fn admin_config(&self) -> Option<&AdminConfig> {
+        self.config.admin()
+    }
+
+    /// The address for the healthcheck HTTP server.
+    pub const fn healthcheck_address(&self) -> SocketAddr {
+        self.config.healthcheck_address()
     }
 }
 
@@ -331,7 +712,7 @@ fn is_transient_transport_error(err: &RpcError<TransportErrorKind>) -> bool {
     }
 }
 
-fn is_transient_reqwest_error(err: &reqwest::Error) -> bool {
+pub(crate) fn is_transient_reqwest_error(err: &reqwest::Error) -> bool {
     if err.is_timeout() || err.is_connect() || err.is_request() {
         return true;
     }
@@ -0,0 +1,144 @@
+use crate::{Coordination, FillerContext, metrics};
+use init4_bin_base::deps::tracing::{debug, info, warn};
+use std::sync::{Arc, Mutex};
+use tokio::{
+    select,
+    time::{Duration, Instant},
+};
+use tokio_util::sync::CancellationToken;
+
+/// How many times per lease the leader renews it, so a renewal or two can fail without the lease
+/// lapsing.
+const RENEWALS_PER_LEASE: u32 = 3;
+
+/// Whether this replica holds the leader lease, shared between the [`LeaderElectionTask`] that
+/// renews it and the filler task, which only submits bundles while it does.
+///
+/// Leadership is tracked as the instant the lease is known to last until, measured from before
+/// the renewal was sent, so this replica stops acting as leader no later than Redis expires its
+/// lease, even if renewals start failing.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Leadership {
+    lease_until: Arc<Mutex<Option<Instant>>>,
+}
+
+impl Leadership {
+    /// Whether this replica currently holds the leader lease.
+    pub(crate) fn is_leader(&self) -> bool {
+        self.lease_until.lock().unwrap().is_some_and(|lease_until| Instant::now() < lease_until)
+    }
+
+    fn set(&self, lease_until: Option<Instant>) {
+        *self.lease_until.lock().unwrap() = lease_until;
+    }
+}
+
+#[derive(Debug)]
+struct ElectionState {
+    coordination: Coordination,
+    lease: Duration,
+    leadership: Leadership,
+}
+
+/// Background task that competes for the leader lease among filler replicas sharing a Redis,
+/// taking it when it is free and renewing it while held. Standbys keep running their cycles as
+/// dry runs, so they take over with warm caches once the leader's lease lapses. The lease is
+/// released on shutdown so a standby doesn't have to wait for it to lapse.
+#[derive(Debug)]
+pub struct LeaderElectionTask {
+    state: Option<ElectionState>,
+    cancellation_token: CancellationToken,
+}
+
+impl LeaderElectionTask {
+    /// Create the task. It does nothing if leader election is disabled.
+    pub fn new(context: &FillerContext) -> Self {
+        let cancellation_token = context.cancellation_token().clone();
+        let (Some(coordination), Some(lease), Some(leadership)) =
+            (context.coordination(), context.leader_lease(), context.leadership())
+        else {
+            return Self { state: None, cancellation_token };
+        };
+        let state = ElectionState {
+            coordination: coordination.clone(),
+            lease,
+            leadership: leadership.clone(),
+        };
+        Self { state: Some(state), cancellation_token }
+    }
+
+    /// Run the lease acquisition and renewal loop.
+    pub async fn run(self) {
+        let Some(state) = self.state else {
+            debug!("leader election disabled");
+            return;
+        };
+        info!(
+            instance_id = state.coordination.instance_id(),
+            lease_secs = state.lease.as_secs(),
+            "leader election enabled"
+        );
+        let mut interval = tokio::time::interval(state.lease / RENEWALS_PER_LEASE);
+        let mut was_leader = false;
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("leader election task cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    state.renew().await;
+                    let is_leader = state.leadership.is_leader();
+                    if is_leader != was_leader {
+                        if is_leader {
+                            info!("became leader, submitting bundles");
+                        } else {
+                            warn!("lost leadership, standing by");
+                        }
+                        was_leader = is_leader;
+                    }
+                    metrics::record_leader(is_leader);
+                }
+            }
+        }
+        if was_leader {
+            state.leadership.set(None);
+            match state.coordination.release_lease().await {
+                Ok(()) => info!("released leader lease"),
+                Err(error) => warn!(error = format!("{error:#}"), "failed to release leader lease"),
+            }
+        }
+    }
+}
+
+impl ElectionState {
+    /// Takes or renews the lease. On an error, leadership is left to lapse at the end of the
+    /// current lease rather than dropped at once, since Redis may still hold it for this replica.
+    async fn renew(&self) {
+        let sent_at = Instant::now();
+        match self.coordination.acquire_lease(self.lease).await {
+            Ok(true) => self.leadership.set(Some(sent_at + self.lease)),
+            Ok(false) => self.leadership.set(None),
+            Err(error) => {
+                warn!(error = format!("{error:#}"), "failed to renew leader lease");
+                metrics::record_coordination_error();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leadership_lapses_with_lease() {
+        let leadership = Leadership::default();
+        assert!(!leadership.is_leader());
+        leadership.set(Some(Instant::now() + Duration::from_secs(60)));
+        assert!(leadership.clone().is_leader());
+        leadership.set(Some(Instant::now() - Duration::from_millis(1)));
+        assert!(!leadership.is_leader());
+    }
+}
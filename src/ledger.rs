@@ -0,0 +1,278 @@
+use crate::{
+    ChainTokenPair, FillProviderType, FixedPricingClient, FixedPricingError, IERC20, metrics,
+};
+use alloy::{
+    primitives::{Address, B256, U256},
+    providers::Provider,
+    rpc::types::Log,
+    sol_types::SolEvent,
+};
+use eyre::{Result, WrapErr};
+use lru::LruCache;
+use serde::Serialize;
+use signet_constants::NATIVE_TOKEN_ADDRESS;
+use signet_types::SignedOrder;
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+/// Number of recent fill transactions remembered, so gas paid by a transaction that filled
+/// several orders is only counted once.
+const COUNTED_TRANSACTIONS: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
+/// One USD in normalized 18-decimal USD.
+const USD: f64 = 1e18;
+
+/// What the filler received and paid for a confirmed fill, from the rollup transaction it landed
+/// in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FillTransfers {
+    /// The rollup transaction that transferred the order's inputs to the filler.
+    tx_hash: B256,
+    /// The amount of each rollup token the owner transferred to the filler.
+    received: Vec<(Address, U256)>,
+    /// Gas paid for the transaction, in the rollup's native token.
+    gas_paid: U256,
+}
+
+impl FillTransfers {
+    /// Reads the transfers for a fill of `order` from the receipt of the rollup transaction it
+    /// landed in. Returns `None` if there is no receipt for the transaction.
+    pub(crate) async fn fetch(
+        provider: &FillProviderType,
+        tx_hash: B256,
+        order: &SignedOrder,
+        filler: Address,
+    ) -> Result<Option<Self>> {
+        let Some(receipt) = provider
+            .get_transaction_receipt(tx_hash)
+            .await
+            .wrap_err("failed to fetch fill transaction receipt")?
+        else {
+            return Ok(None);
+        };
+        let gas_paid = U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price);
+        Ok(Some(Self {
+            tx_hash,
+            received: received_from(receipt.inner.logs(), order.permit().owner, filler),
+            gas_paid,
+        }))
+    }
+}
+
+/// The amount of each token transferred from `owner` to `filler` in `logs`.
+fn received_from(logs: &[Log], owner: Address, filler: Address) -> Vec<(Address, U256)> {
+    let mut received: Vec<(Address, U256)> = Vec::new();
+    for log in logs {
+        if log.topic0() != Some(&IERC20::Transfer::SIGNATURE_HASH) {
+            continue;
+        }
+        let Ok(transfer) = IERC20::Transfer::decode_log(&log.inner) else {
+            continue;
+        };
+        if transfer.from != owner || transfer.to != filler {
+            continue;
+        }
+        match received.iter_mut().find(|(token, _amount)| *token == transfer.address) {
+            Some((_token, amount)) => *amount = amount.saturating_add(transfer.value),
+            None => received.push((transfer.address, transfer.value)),
+        }
+    }
+    received
+}
+
+/// A fill's realized profit as recorded by the ledger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RealizedFill {
+    pub(crate) tx_hash: B256,
+    /// Rollup gas counted against the fill: zero if it was already counted for another fill from
+    /// the same transaction.
+    pub(crate) gas_paid: U256,
+    pub(crate) pnl_usd: f64,
+}
+
+/// Cumulative amounts of a token received and paid on confirmed fills, as served by the admin API.
+/// Amounts are in base units.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct TokenFlow {
+    chain_id: u64,
+    token: Address,
+    received: U256,
+    paid: U256,
+}
+
+/// The ledger's totals, as served by the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LedgerSnapshot {
+    fills: u64,
+    /// Realized profit of every fill recorded, in USD, net of gas.
+    realized_pnl_usd: f64,
+    /// Rollup gas paid, in the rollup's native token.
+    gas_paid: U256,
+    tokens: Vec<TokenFlow>,
+}
+
+#[derive(Debug)]
+struct LedgerInner {
+    ru_chain_id: u64,
+    fills: u64,
+    realized_pnl: f64,
+    gas_paid: U256,
+    /// `(received, paid)` of each token, in base units.
+    tokens: HashMap<ChainTokenPair, (U256, U256)>,
+    counted_transactions: LruCache<B256, ()>,
+}
+
+/// Realized PnL of the filler's confirmed fills. Each fill's realized profit is the value of the
+/// inputs the owner actually transferred to the filler, read from the rollup transaction's
+/// `Transfer` logs, less the value of the outputs paid and of the rollup gas paid for that
+/// transaction, valued at prices when the fill is recorded. Amounts received and paid are also
+/// kept per token.
+///
+/// Shared between the filler loop (which records fills) and the admin API. Held in memory, so the
+/// ledger starts over on restart.
+#[derive(Debug, Clone)]
+pub(crate) struct PnlLedger {
+    inner: Arc<Mutex<LedgerInner>>,
+}
+
+impl PnlLedger {
+    pub(crate) fn new(ru_chain_id: u64) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LedgerInner {
+                ru_chain_id,
+                fills: 0,
+                realized_pnl: 0.0,
+                gas_paid: U256::ZERO,
+                tokens: HashMap::new(),
+                counted_transactions: LruCache::new(COUNTED_TRANSACTIONS),
+            })),
+        }
+    }
+
+    /// Records a confirmed fill of `order` and returns its realized profit. Gas is only
+    /// counted for the first fill recorded from a transaction. Nothing is recorded if a token
+    /// paid, or the gas, can't be valued; tokens received that can't be valued count as zero.
+    pub(crate) fn record(
+        &self,
+        order: &SignedOrder,
+        transfers: &FillTransfers,
+        pricing_client: &FixedPricingClient,
+    ) -> Result<RealizedFill, FixedPricingError> {
+        let mut inner = self.inner.lock().unwrap();
+        let gas_paid = if inner.counted_transactions.contains(&transfers.tx_hash) {
+            U256::ZERO
+        } else {
+            transfers.gas_paid
+        };
+        let received_value =
+            transfers.received.iter().fold(U256::ZERO, |total, (token, amount)| {
+                total.saturating_add(pricing_client.value(token, *amount).unwrap_or_default())
+            });
+        let paid_value = pricing_client
+            .output_value(order)?
+            .saturating_add(pricing_client.value(&NATIVE_TOKEN_ADDRESS, gas_paid)?);
+        let pnl = (f64::from(received_value) - f64::from(paid_value)) / USD;
+
+        inner.counted_transactions.put(transfers.tx_hash, ());
+        inner.fills += 1;
+        inner.realized_pnl += pnl;
+        inner.gas_paid = inner.gas_paid.saturating_add(gas_paid);
+        let ru_chain_id = inner.ru_chain_id;
+        for (token, amount) in &transfers.received {
+            let (received, _paid) =
+                inner.tokens.entry(ChainTokenPair::new(ru_chain_id, *token)).or_default();
+            *received = received.saturating_add(*amount);
+        }
+        for output in order.outputs() {
+            let chain_token = ChainTokenPair::new(u64::from(output.chainId), output.token);
+            let (_received, paid) = inner.tokens.entry(chain_token).or_default();
+            *paid = paid.saturating_add(output.amount);
+        }
+        metrics::record_realized_pnl(pnl, inner.realized_pnl);
+        Ok(RealizedFill { tx_hash: transfers.tx_hash, gas_paid, pnl_usd: pnl })
+    }
+
+    /// The ledger's totals, with tokens by chain and address.
+    pub(crate) fn snapshot(&self) -> LedgerSnapshot {
+        let inner = self.inner.lock().unwrap();
+        let mut tokens: Vec<TokenFlow> = inner
+            .tokens
+            .iter()
+            .map(|(chain_token, (received, paid))| TokenFlow {
+                chain_id: chain_token.chain_id(),
+                token: chain_token.token(),
+                received: *received,
+                paid: *paid,
+            })
+            .collect();
+        tokens.sort_by_key(|flow| (flow.chain_id, flow.token));
+        LedgerSnapshot {
+            fills: inner.fills,
+            realized_pnl_usd: inner.realized_pnl,
+            gas_paid: inner.gas_paid,
+            tokens,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use signet_constants::SignetSystemConstants;
+
+    const OWNER: Address = Address::repeat_byte(0x01);
+    const FILLER: Address = Address::repeat_byte(0x02);
+    const ONE: u128 = 10_u128.pow(18);
+
+    fn transfer(token: Address, from: Address, to: Address, value: u128) -> Log {
+        let data = IERC20::Transfer { from, to, value: U256::from(value) }.encode_log_data();
+        Log { inner: alloy::primitives::Log { address: token, data }, ..Default::default() }
+    }
+
+    #[test]
+    fn fills_realize_received_less_paid_and_gas_once_per_transaction() {
+        let constants = SignetSystemConstants::parmigiana();
+        let pricing_client = FixedPricingClient::new(&constants, "parmigiana", 10);
+        let weth = constants.rollup().tokens().weth();
+        let usdc = constants.host().tokens().usdc();
+        // 1 WETH ($3000) in, 2900 USDC out.
+        let order = OrderBuilder::default()
+            .with_owner(OWNER)
+            .with_input(weth, U256::from(ONE))
+            .with_output(usdc, U256::from(2_900_000_000_u64), OWNER, constants.host_chain_id())
+            .build();
+        let logs = [
+            transfer(weth, OWNER, FILLER, ONE),
+            // Transfers involving anyone else don't count.
+            transfer(weth, Address::ZERO, FILLER, ONE),
+            transfer(weth, FILLER, OWNER, ONE),
+        ];
+        let received = received_from(&logs, OWNER, FILLER);
+        assert_eq!(received, vec![(weth, U256::from(ONE))]);
+
+        // $10 of rollup gas.
+        let transfers = FillTransfers {
+            tx_hash: B256::repeat_byte(0x11),
+            received,
+            gas_paid: U256::from(10 * ONE),
+        };
+        let ledger = PnlLedger::new(constants.ru_chain_id());
+        assert_eq!(ledger.record(&order, &transfers, &pricing_client).unwrap().pnl_usd, 90.0);
+        // Another order filled by the same transaction doesn't pay its gas again.
+        let realized = ledger.record(&order, &transfers, &pricing_client).unwrap();
+        assert_eq!((realized.pnl_usd, realized.gas_paid), (100.0, U256::ZERO));
+
+        let snapshot = ledger.snapshot();
+        assert_eq!(snapshot.fills, 2);
+        assert_eq!(snapshot.realized_pnl_usd, 190.0);
+        assert_eq!(snapshot.gas_paid, U256::from(10 * ONE));
+        let weth_flow = snapshot.tokens.iter().find(|flow| flow.token == weth).unwrap();
+        assert_eq!(weth_flow.received, U256::from(2 * ONE));
+        let usdc_flow = snapshot.tokens.iter().find(|flow| flow.token == usdc).unwrap();
+        assert_eq!(usdc_flow.paid, U256::from(5_800_000_000_u64));
+    }
+}
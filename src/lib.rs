@@ -22,7 +22,11 @@ use alloy::{
     },
 };
 use eyre::{Result, WrapErr};
-use init4_bin_base::deps::tracing::{debug, info};
+use init4_bin_base::{
+    deps::tracing::{debug, info},
+    utils::otlp::OtelGuard,
+};
+use metrics_exporter_prometheus::PrometheusBuilder;
 use tokio::{
     select,
     signal::unix::{SignalKind, signal},
@@ -33,32 +37,168 @@ mod chain_token_pair;
 pub(crate) use chain_token_pair::{ChainTokenPair, KnownToken};
 
 mod config;
-pub use config::{Config, config_from_env, env_var_info};
+pub use config::{
+    AdaptiveLeadConfig, AdminConfig, ApprovalPolicy, ArchiveConfig, BootstrapMode, BridgeConfig,
+    Config, CoordinationConfig, CycleBudgetConfig, DecodeConfig, DiscoveryMode, FeeOracleConfig,
+    FillThrottleConfig, InventoryAsset, InventoryConfig, LossLimitConfig, LowBalanceConfig,
+    LowBalanceThreshold, OfflineConfig, OrderSelectionPolicy, PositionLimit, PriceApi,
+    PriceApiConfig, QuietPeriod, ReputationConfig, SchedulingMode, ScoringHookConfig,
+    ScreeningConfig, SpendPolicyConfig, StalePricePolicy, SubmissionRetryConfig,
+    TokenDiscoveryConfig, TokenFilterConfig, TokenMetadata, ToxicFlowConfig, TwapConfig, TwapPool,
+    config_from_env, decode_config_from_env, env_var_info, offline_config_from_env,
+};
 
 mod allowance;
 pub(crate) use allowance::AllowanceCache;
 pub use allowance::AllowanceRefreshTask;
 
+mod decode;
+pub use decode::decode_order;
+pub(crate) use decode::{SignatureCheck, check_signature};
+
+mod replay;
+pub use replay::replay_journal;
+
+mod backtest;
+pub use backtest::run_backtest;
+
 mod erc20;
 pub(crate) use erc20::{IERC20, query_balance};
 
 mod filler_task;
-pub use filler_task::FillerTask;
+pub use filler_task::{FillerTask, FilterStage, OrderFilter, SkipReason};
 
 mod metrics;
 
 mod fixed_pricing_client;
 use fixed_pricing_client::{FixedPricingClient, FixedPricingError};
 
+mod pricing;
+pub use pricing::OrderPricing;
+
 mod initialization;
 pub use initialization::FillerContext;
 
+mod degradation;
+pub(crate) use degradation::{DegradationLadder, Dependency};
+
+mod reputation;
+pub(crate) use reputation::{OwnerReputation, OwnerStanding};
+
+mod toxic_flow;
+pub(crate) use toxic_flow::ToxicFlow;
+
+mod ledger;
+pub(crate) use ledger::{FillTransfers, PnlLedger};
+
+mod fill_history;
+pub(crate) use fill_history::FillHistory;
+
+mod risk;
+pub(crate) use risk::RiskEngine;
+
+mod loss_limit;
+pub(crate) use loss_limit::LossLimit;
+
+mod denied_owners;
+pub(crate) use denied_owners::DeniedOwners;
+
+mod screening;
+pub(crate) use screening::Screener;
+
+mod order_source;
+pub use order_source::{FileOrderSource, FileOrderSourceError, OrderSource};
+
+mod twap;
+pub(crate) use twap::TwapPriceCache;
+pub use twap::TwapRefreshTask;
+
+mod spot_price;
+pub(crate) use spot_price::SpotPriceCache;
+pub use spot_price::SpotPriceRefreshTask;
+
+mod discovery;
+pub(crate) use discovery::TokenDiscovery;
+pub use discovery::TokenDiscoveryTask;
+
+mod coordination;
+pub(crate) use coordination::{Claim, Coordination};
+
+mod order_events;
+pub use order_events::OrderEventTask;
+pub(crate) use order_events::{LandedOrders, find_order_transaction};
+
+mod reorg;
+pub(crate) use reorg::ChainWatch;
+pub use reorg::ReorgWatchTask;
+
+mod leader;
+pub use leader::LeaderElectionTask;
+pub(crate) use leader::Leadership;
+
+mod filled_store;
+pub(crate) use filled_store::FilledOrderStore;
+
+mod fee_oracle;
+pub use fee_oracle::FeeOracleTask;
+pub(crate) use fee_oracle::GasPriceCache;
+
+mod bridge;
+pub use bridge::BridgeTask;
+
+mod low_balance;
+pub use low_balance::LowBalanceTask;
+
+mod throttle;
+pub(crate) use throttle::FillThrottle;
+
+mod quiet;
+pub(crate) use quiet::QuietSchedule;
+
+mod pause;
+pub(crate) use pause::PauseSwitch;
+
+mod spend_policy;
+pub(crate) use spend_policy::{SpendDecision, SpendPolicy};
+
 mod service;
 pub use service::serve_healthcheck;
 
+mod probes;
+
+mod capacity;
+
+mod admin;
+pub use admin::AdminService;
+
+mod archive;
+pub use archive::ArchiveTask;
+pub(crate) use archive::{ArchiveBuffer, Decision};
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 pub(crate) type FillProviderType =
     FillProvider<JoinFill<JoinedRecommendedFillers, WalletFiller<EthereumWallet>>, RootProvider>;
 
+/// Initialize tracing (including OTLP export when configured), install the Prometheus metrics
+/// recorder, and serve its metrics and the exemplar histograms on the configured metrics address.
+///
+/// This replaces `init4_bin_base::init4()` so the metrics listener can be bound to a specific
+/// interface rather than always listening on all interfaces, and can serve the OpenMetrics
+/// exemplar histograms alongside the exporter's metrics.
+pub fn init_telemetry(config: &Config) -> Result<Option<OtelGuard>> {
+    let guard = init4_bin_base::utils::tracing::init_tracing();
+    let handle = PrometheusBuilder::new()
+        .install_recorder()
+        .wrap_err("failed to install prometheus metrics recorder")?;
+    service::spawn_metrics_server(handle, config.metrics_address())?;
+    // Install the AWS-LC TLS provider for rustls if no other provider has been installed yet,
+    // matching `init4_bin_base::init4()`.
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    Ok(guard)
+}
+
 /// Register signal handlers for graceful shutdown, returning a
 /// [`CancellationToken`] that is cancelled on SIGINT or SIGTERM.
 pub fn handle_signals() -> Result<CancellationToken> {
@@ -0,0 +1,160 @@
+use crate::{LossLimitConfig, PauseSwitch, metrics};
+use init4_bin_base::deps::tracing::{error, info};
+use serde::Serialize;
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The reason the daily loss limit pauses submission with. Only a pause with this reason is
+/// lifted at the daily reset, so an operator's own pause is left alone.
+const PAUSE_REASON: &str = "daily loss limit reached";
+
+/// The day's loss so far, as served by the admin API.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct DailyLoss {
+    /// Net PnL of the day's landed fills in USD: negative for a loss.
+    pnl_usd: f64,
+    max_loss_usd: f64,
+    /// Whether the limit was reached today.
+    breached: bool,
+}
+
+#[derive(Debug)]
+struct LossLimitInner {
+    config: LossLimitConfig,
+    /// The loss day being tracked, counted in days since the first reset after the Unix epoch.
+    day: u64,
+    pnl: f64,
+    breached: bool,
+}
+
+/// Daily loss limit: the estimated PnL of each landed fill of the filler's, the value of the
+/// inputs received less the value of the outputs paid when the fill is confirmed, accumulates
+/// over a day starting at the configured UTC reset time. Once the day's net loss reaches the
+/// limit, bundle submission is paused through the shared [`PauseSwitch`] and an error is logged,
+/// and the pause is lifted at the next reset unless an operator has resumed or re-paused
+/// submission in the meantime.
+///
+/// Held in memory: a restart starts the day's loss over from zero.
+#[derive(Debug, Clone)]
+pub(crate) struct LossLimit {
+    inner: Arc<Mutex<LossLimitInner>>,
+    pause: PauseSwitch,
+}
+
+impl LossLimit {
+    pub(crate) fn new(config: LossLimitConfig, pause: PauseSwitch) -> Self {
+        let day = loss_day(unix_time_secs(), config.reset_minute());
+        metrics::record_daily_pnl(0.0);
+        Self {
+            inner: Arc::new(Mutex::new(LossLimitInner { config, day, pnl: 0.0, breached: false })),
+            pause,
+        }
+    }
+
+    /// Adds a landed fill's PnL, in USD, to the day's, pausing submission if the day's loss
+    /// reaches the limit.
+    pub(crate) fn record(&self, pnl: f64) {
+        self.record_at(pnl, unix_time_secs());
+    }
+
+    fn record_at(&self, pnl: f64, timestamp: u64) {
+        self.roll_over_at(timestamp);
+        let mut inner = self.inner.lock().unwrap();
+        inner.pnl += pnl;
+        metrics::record_daily_pnl(inner.pnl);
+        if inner.breached || -inner.pnl < inner.config.max_loss() {
+            return;
+        }
+        inner.breached = true;
+        metrics::record_loss_limit_breach();
+        error!(
+            pnl_usd = inner.pnl,
+            max_loss_usd = inner.config.max_loss(),
+            "daily loss limit reached, pausing bundle submission until the daily reset"
+        );
+        self.pause.pause(PAUSE_REASON);
+    }
+
+    /// Starts a new day if the reset time has passed, lifting the daily loss limit's pause.
+    pub(crate) fn roll_over(&self) {
+        self.roll_over_at(unix_time_secs());
+    }
+
+    fn roll_over_at(&self, timestamp: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let day = loss_day(timestamp, inner.config.reset_minute());
+        if day <= inner.day {
+            return;
+        }
+        inner.day = day;
+        inner.pnl = 0.0;
+        metrics::record_daily_pnl(0.0);
+        if std::mem::take(&mut inner.breached) && self.pause.resume_if(PAUSE_REASON) {
+            info!("daily loss reset, resuming bundle submission");
+        }
+    }
+
+    /// The day's loss so far.
+    pub(crate) fn daily_loss(&self) -> DailyLoss {
+        self.roll_over();
+        let inner = self.inner.lock().unwrap();
+        DailyLoss {
+            pnl_usd: inner.pnl,
+            max_loss_usd: inner.config.max_loss(),
+            breached: inner.breached,
+        }
+    }
+}
+
+/// The loss day `timestamp` falls in, for days starting `reset_minute` minutes after midnight UTC.
+const fn loss_day(timestamp: u64, reset_minute: u16) -> u64 {
+    timestamp.saturating_sub(reset_minute as u64 * 60) / SECONDS_PER_DAY
+}
+
+/// The current Unix time in seconds.
+fn unix_time_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock set before UNIX epoch")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breach_pauses_until_the_daily_reset() {
+        let pause = PauseSwitch::default();
+        // Days reset at 06:00 UTC.
+        let loss_limit = LossLimit::new(LossLimitConfig::new(100.0, 6 * 60), pause.clone());
+        let morning = 100 * SECONDS_PER_DAY + 7 * 3600;
+        loss_limit.inner.lock().unwrap().day = loss_day(morning, 6 * 60);
+
+        loss_limit.record_at(30.0, morning);
+        loss_limit.record_at(-120.0, morning);
+        assert_eq!(pause.state(), None);
+        loss_limit.record_at(-10.0, morning);
+        assert_eq!(pause.state().unwrap().reason, PAUSE_REASON);
+
+        // Still the same loss day just before the next reset.
+        let next_reset = 101 * SECONDS_PER_DAY + 6 * 3600;
+        loss_limit.roll_over_at(next_reset - 1);
+        assert!(pause.state().is_some());
+        loss_limit.roll_over_at(next_reset);
+        assert_eq!(pause.state(), None);
+        assert_eq!(loss_limit.inner.lock().unwrap().pnl, 0.0);
+
+        // An operator's pause isn't lifted at the reset.
+        loss_limit.record_at(-100.0, next_reset);
+        assert_eq!(pause.state().unwrap().reason, PAUSE_REASON);
+        assert!(pause.resume());
+        assert!(pause.pause("incident"));
+        loss_limit.roll_over_at(next_reset + SECONDS_PER_DAY);
+        assert_eq!(pause.state().unwrap().reason, "incident");
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    ChainTokenPair, FillProviderType, FillerContext, FixedPricingClient, IERC20, LowBalanceConfig,
+    LowBalanceThreshold, metrics, query_balance,
+};
+use alloy::{
+    primitives::{Address, U256},
+    signers::Signer,
+};
+use eyre::{Result, WrapErr, eyre};
+use init4_bin_base::deps::tracing::{debug, info, instrument, warn};
+use signet_constants::{NATIVE_TOKEN_ADDRESS, SignetSystemConstants};
+use std::collections::HashSet;
+use tokio::{select, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+/// How often the watched balances are checked.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long to wait for a top-up transaction to be included before giving up on it.
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Amount whose value is used as the token's unit price when sizing a top-up, so a token with a
+/// zero balance can still be sized.
+const REFERENCE_AMOUNT: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// The amount of a token to top up by so its balance is worth twice `min_value`, given its
+/// current `value` and the value of [`REFERENCE_AMOUNT`] of it.
+fn top_up_amount(min_value: U256, value: U256, reference_value: U256) -> U256 {
+    let deficit = min_value.saturating_mul(U256::from(2)).saturating_sub(value);
+    if reference_value.is_zero() {
+        return U256::ZERO;
+    }
+    deficit.saturating_mul(REFERENCE_AMOUNT) / reference_value
+}
+
+#[derive(Debug)]
+struct LowBalanceState {
+    config: LowBalanceConfig,
+    filler_address: Address,
+    constants: SignetSystemConstants,
+    host_provider: FillProviderType,
+    ru_provider: FillProviderType,
+    pricing_client: FixedPricingClient,
+    /// Tokens currently below their threshold, so only the transition to low is warned about.
+    low: HashSet<ChainTokenPair>,
+}
+
+/// Background task that watches the filler's balances of the configured tokens, alerting through
+/// a log warning and the `signet.filler.low_balance` gauge when one is worth less than its
+/// threshold. If a treasury is configured, a low ERC20 balance is topped up to twice its
+/// threshold by pulling from the treasury with `transferFrom`, up to the allowance the treasury
+/// has granted the filler.
+#[derive(Debug)]
+pub struct LowBalanceTask {
+    state: Option<LowBalanceState>,
+    cancellation_token: CancellationToken,
+}
+
+impl LowBalanceTask {
+    /// Create the task. It does nothing if no low-balance thresholds are configured.
+    pub fn new(context: &FillerContext) -> Self {
+        let cancellation_token = context.cancellation_token().clone();
+        let Some(config) = context.low_balance_config() else {
+            return Self { state: None, cancellation_token };
+        };
+        let constants = context.constants().system().clone();
+        let mut pricing_client =
+            FixedPricingClient::new(&constants, context.chain_name(), context.max_loss_percent())
+                .with_token_table(context.token_table());
+        if let Some(twap_prices) = context.twap_prices() {
+            pricing_client = pricing_client.with_twap_prices(twap_prices.clone());
+        }
+        if let Some(spot_prices) = context.spot_prices() {
+            pricing_client = pricing_client.with_spot_prices(spot_prices.clone());
+        }
+        info!(
+            tokens = config.thresholds().len(),
+            treasury = ?config.treasury(),
+            "low-balance alerts enabled"
+        );
+        let state = LowBalanceState {
+            config: config.clone(),
+            filler_address: context.signer().address(),
+            constants,
+            host_provider: context.host_provider().clone(),
+            ru_provider: context.ru_provider().clone(),
+            pricing_client,
+            low: HashSet::new(),
+        };
+        Self { state: Some(state), cancellation_token }
+    }
+
+    /// Run the periodic balance check loop.
+    pub async fn run(self) {
+        let Some(mut state) = self.state else {
+            debug!("low-balance alerts disabled");
+            return;
+        };
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("low-balance task cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    state.check().await;
+                }
+            }
+        }
+    }
+}
+
+impl LowBalanceState {
+    /// Checks every watched balance, alerting on and topping up those that are low.
+    #[instrument(skip_all)]
+    async fn check(&mut self) {
+        for threshold in self.config.thresholds().to_vec() {
+            let chain_token = ChainTokenPair::new(threshold.chain_id(), threshold.token());
+            let (balance, value) = match self.balance(chain_token).await {
+                Ok(balance) => balance,
+                Err(error) => {
+                    warn!(
+                        token = %chain_token,
+                        error = format!("{error:#}"),
+                        "failed to check balance for low-balance alert"
+                    );
+                    continue;
+                }
+            };
+            let low = value < threshold.min_value();
+            metrics::record_low_balance(chain_token, low);
+            if !low {
+                if self.low.remove(&chain_token) {
+                    info!(token = %chain_token, %value, "balance recovered above threshold");
+                }
+                continue;
+            }
+            if self.low.insert(chain_token) {
+                warn!(
+                    token = %chain_token,
+                    %balance,
+                    %value,
+                    min_value = %threshold.min_value(),
+                    "balance below low-balance threshold"
+                );
+            }
+            if let Some(treasury) = self.config.treasury() {
+                self.top_up(treasury, &threshold, value).await;
+            }
+        }
+    }
+
+    /// The filler's balance of `chain_token`, and its value in normalized 18-decimal USD.
+    async fn balance(&self, chain_token: ChainTokenPair) -> Result<(U256, U256)> {
+        let balance = query_balance(
+            self.provider(chain_token.chain_id()),
+            self.filler_address,
+            chain_token.token(),
+        )
+        .await
+        .wrap_err("failed to query balance")?;
+        let value = self
+            .pricing_client
+            .value(&chain_token.token(), balance)
+            .wrap_err("failed to value balance")?;
+        Ok((balance, value))
+    }
+
+    /// Tops up a low balance from `treasury`, logging and counting the outcome. Native balances
+    /// can't be pulled with an allowance, so are only alerted on.
+    async fn top_up(&self, treasury: Address, threshold: &LowBalanceThreshold, value: U256) {
+        let chain_token = ChainTokenPair::new(threshold.chain_id(), threshold.token());
+        if chain_token.token() == NATIVE_TOKEN_ADDRESS {
+            debug!(token = %chain_token, "native balances can't be topped up from the treasury");
+            return;
+        }
+        match self.transfer_from_treasury(treasury, threshold, value).await {
+            Ok(amount) if amount.is_zero() => {
+                debug!(token = %chain_token, "treasury has nothing available to top up with");
+            }
+            Ok(amount) => {
+                info!(token = %chain_token, %amount, %treasury, "topped up balance from treasury");
+                metrics::record_treasury_top_up(chain_token, metrics::SubmissionResult::Success);
+            }
+            Err(error) => {
+                warn!(
+                    token = %chain_token,
+                    %treasury,
+                    error = format!("{error:#}"),
+                    "failed to top up balance from treasury"
+                );
+                metrics::record_treasury_top_up(chain_token, metrics::SubmissionResult::Failure);
+            }
+        }
+    }
+
+    /// Pulls enough of the token from `treasury` to bring the balance to twice its threshold,
+    /// capped at the treasury's balance and its allowance to the filler. Returns the amount
+    /// moved, which is zero if the treasury has none available.
+    async fn transfer_from_treasury(
+        &self,
+        treasury: Address,
+        threshold: &LowBalanceThreshold,
+        value: U256,
+    ) -> Result<U256> {
+        let reference_value = self
+            .pricing_client
+            .value(&threshold.token(), REFERENCE_AMOUNT)
+            .wrap_err("failed to price token")?;
+        let wanted = top_up_amount(threshold.min_value(), value, reference_value);
+        let erc20 = IERC20::new(threshold.token(), self.provider(threshold.chain_id()));
+        let allowance = erc20
+            .allowance(treasury, self.filler_address)
+            .call()
+            .await
+            .wrap_err("failed to query treasury allowance")?;
+        let available =
+            erc20.balanceOf(treasury).call().await.wrap_err("failed to query treasury balance")?;
+        let amount = wanted.min(allowance).min(available);
+        if amount.is_zero() {
+            return Ok(amount);
+        }
+        let receipt = erc20
+            .transferFrom(treasury, self.filler_address, amount)
+            .send()
+            .await
+            .wrap_err("failed to send treasury transfer")?
+            .with_timeout(Some(TRANSACTION_TIMEOUT))
+            .get_receipt()
+            .await
+            .wrap_err("treasury transfer not included")?;
+        if !receipt.status() {
+            return Err(eyre!("treasury transfer reverted"));
+        }
+        Ok(amount)
+    }
+
+    const fn provider(&self, chain_id: u64) -> &FillProviderType {
+        if chain_id == self.constants.ru_chain_id() {
+            &self.ru_provider
+        } else {
+            &self.host_provider
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_up_amount_reaches_twice_threshold() {
+        let usd = |dollars: u64| U256::from(dollars) * REFERENCE_AMOUNT;
+        // $1 per reference amount, $30 held against a $100 threshold: top up $170 worth.
+        assert_eq!(top_up_amount(usd(100), usd(30), usd(1)), usd(170));
+        // An empty balance is sized from the unit price alone.
+        assert_eq!(top_up_amount(usd(100), U256::ZERO, usd(4)), usd(50));
+        assert_eq!(top_up_amount(usd(100), usd(250), usd(1)), U256::ZERO);
+        assert_eq!(top_up_amount(usd(100), usd(30), U256::ZERO), U256::ZERO);
+    }
+}
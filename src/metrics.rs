@@ -1,34 +1,124 @@
+use crate::{ChainTokenPair, Dependency, InventoryAsset};
 use core::time::Duration;
+use init4_bin_base::deps::{
+    opentelemetry::trace::TraceContextExt, tracing::Span,
+    tracing_opentelemetry::OpenTelemetrySpanExt,
+};
 use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
+use prometheus_client::{
+    encoding::text::encode,
+    metrics::{exemplar::HistogramWithExemplars, family::Family, histogram::exponential_buckets},
+    registry::Registry,
+};
 use std::sync::LazyLock;
 use tokio::time::Instant;
 
 // Metric names
 const UPTIME_SECONDS: &str = "signet.filler.uptime_seconds";
+const QUIET_PERIOD_ACTIVE: &str = "signet.filler.quiet_period_active";
+const PAUSED: &str = "signet.filler.paused";
+const DAILY_PNL_USD: &str = "signet.filler.daily_pnl_usd";
+const LOSS_LIMIT_BREACHES: &str = "signet.filler.loss_limit_breaches";
+const DEGRADED: &str = "signet.filler.degraded";
+const INCLUSION_PROBABILITY: &str = "signet.filler.inclusion_probability";
 const CYCLES: &str = "signet.filler.cycles";
 const ORDERS_FETCHED: &str = "signet.filler.orders_fetched";
 const ORDERS_SKIPPED: &str = "signet.filler.orders_skipped";
 const ORDERS_IN_BUNDLES: &str = "signet.filler.orders_in_bundles";
 const BUNDLES: &str = "signet.filler.bundles";
+const BUNDLE_SUBMISSION_ERRORS: &str = "signet.filler.bundle_submission_errors";
+const BUNDLE_SUBMISSION_RETRIES: &str = "signet.filler.bundle_submission_retries";
 const NONCE_CHECK_ERRORS: &str = "signet.filler.nonce_check_errors";
+const ORDER_EVENT_SUBSCRIPTION_ERRORS: &str = "signet.filler.order_event_subscription_errors";
+const HEADER_SUBSCRIPTION_ERRORS: &str = "signet.filler.header_subscription_errors";
+const REORGS: &str = "signet.filler.reorgs";
+const FILLED_CACHE_REORG_EVICTIONS: &str = "signet.filler.filled_cache_reorg_evictions";
+const COORDINATION_ERRORS: &str = "signet.filler.coordination_errors";
+const LEADER: &str = "signet.filler.leader";
+const BLOCK_LEAD_MS: &str = "signet.filler.block_lead_ms";
+const READY_ORDERS: &str = "signet.filler.ready_orders";
 const PRICING_ERRORS: &str = "signet.filler.pricing_errors";
 const FETCH_ORDER_ERRORS: &str = "signet.filler.fetch_order_errors";
 const CONNECTION_RETRY_ATTEMPTS: &str = "signet.filler.connection_retry_attempts";
 const PREFLIGHT_QUERY_ERRORS: &str = "signet.filler.preflight_query_errors";
 const MISSED_WINDOWS: &str = "signet.filler.missed_windows";
-const CYCLE_DURATION_SECONDS: &str = "signet.filler.cycle_duration_seconds";
+const LATE_SUBMISSIONS_SKIPPED: &str = "signet.filler.late_submissions_skipped";
+const CYCLE_BUDGET_EXHAUSTED: &str = "signet.filler.cycle_budget_exhausted";
+const UNFILLED_SUBMISSIONS: &str = "signet.filler.unfilled_submissions";
+const INCLUSION_FAILURES: &str = "signet.filler.inclusion_failures";
+const MALFORMED_ORDERS: &str = "signet.filler.malformed_orders";
+const SCREENING_ERRORS: &str = "signet.filler.screening_errors";
+const SCORING_HOOK_ERRORS: &str = "signet.filler.scoring_hook_errors";
+const TWAP_REFRESH_ERRORS: &str = "signet.filler.twap_refresh_errors";
+const SPOT_PRICE_REFRESH_ERRORS: &str = "signet.filler.spot_price_refresh_errors";
+const QUOTE_CACHE_HITS: &str = "signet.filler.quote_cache_hits";
+const ROLLUP_GAS_PRICE: &str = "signet.filler.rollup_gas_price";
+const HOST_GAS_PRICE: &str = "signet.filler.host_gas_price";
+const FEE_ORACLE_ERRORS: &str = "signet.filler.fee_oracle_errors";
+const TOKEN_DISCOVERY_ERRORS: &str = "signet.filler.token_discovery_errors";
+const FILL_SIMULATIONS: &str = "signet.filler.fill_simulations";
 const ORDERS_PER_BUNDLE: &str = "signet.filler.orders_per_bundle";
+const FILL_MARKOUT_USD: &str = "signet.filler.fill_markout_usd";
+const FILL_REALIZED_PNL_USD: &str = "signet.filler.fill_realized_pnl_usd";
+const REALIZED_PNL_USD: &str = "signet.filler.realized_pnl_usd";
 const CHUNKS_PER_CYCLE: &str = "signet.filler.chunks_per_cycle";
+const ARCHIVE_UPLOADS: &str = "signet.filler.archive_uploads";
+const ARCHIVE_RECORDS_DROPPED: &str = "signet.filler.archive_records_dropped";
+const FILL_RECEIPTS: &str = "signet.filler.fill_receipts";
+const APPROVALS: &str = "signet.filler.approvals";
+const BRIDGE_TRANSFERS: &str = "signet.filler.bridge_transfers";
+const LOW_BALANCE: &str = "signet.filler.low_balance";
+const WALLET_BALANCE: &str = "signet.filler.wallet_balance";
+const NET_POSITION: &str = "signet.filler.net_position";
+const TREASURY_TOP_UPS: &str = "signet.filler.treasury_top_ups";
+const PIPELINE_STAGE_DURATION_SECONDS: &str = "signet.filler.pipeline_stage_duration_seconds";
 
 /// Force evaluation to register all metric descriptions with the exporter.
 pub(crate) static DESCRIPTIONS: LazyLock<()> = LazyLock::new(|| {
     describe_gauge!(UPTIME_SECONDS, "Seconds since signet-filler started");
+    describe_gauge!(
+        QUIET_PERIOD_ACTIVE,
+        "1 while the current cycle falls in a scheduled quiet period (orders are evaluated but no \
+        bundles are submitted), 0 otherwise"
+    );
+    describe_gauge!(
+        PAUSED,
+        "1 while bundle submission is paused (orders are evaluated but no bundles are \
+        submitted), 0 otherwise"
+    );
+    describe_gauge!(
+        DAILY_PNL_USD,
+        "Net estimated USD PnL of the filler's landed fills since the last daily loss reset \
+        (only exported with a daily loss limit set)"
+    );
+    describe_counter!(
+        LOSS_LIMIT_BREACHES,
+        "Times the daily loss limit was reached, pausing bundle submission until the next reset"
+    );
+    describe_gauge!(
+        DEGRADED,
+        "1 while the filler is running on a fallback for a dependency, 0 otherwise (label: \
+        dependency = price-api / order-source)"
+    );
+    describe_gauge!(
+        INCLUSION_PROBABILITY,
+        "Estimated probability that an order in a submitted bundle is filled, from recent \
+        submission outcomes"
+    );
     describe_counter!(CYCLES, "Processing cycles completed");
     describe_counter!(ORDERS_FETCHED, "Orders fetched from tx cache");
     describe_counter!(
         ORDERS_SKIPPED,
         "Orders skipped (label: reason = already-filled / expired / exceeds-max-loss / \
-        unknown-token / insufficient-filler-balance)"
+        unknown-token / insufficient-filler-balance / fill-rate-throttled / \
+        cycle-budget-exhausted / poor-owner-reputation / inventory-skew / awaiting-approval / \
+        approval-rejected / low-expected-value / price-disagreement / unprofitable-backoff / \
+        stale-price / degraded-pricing / below-min-margin / unprofitable-bundle / \
+        claimed-by-peer / pending-fill / invalid-signature / malformed / \
+        disallowed-token / denied-owner / screened / screening-failed / \
+        toxic-flow / below-min-notional / above-max-notional / scoring-rejected / \
+        scoring-failed / nonce-conflict / position-limit, or a custom order filter's \
+        label)"
     );
     describe_counter!(
         ORDERS_IN_BUNDLES,
@@ -36,7 +126,49 @@ pub(crate) static DESCRIPTIONS: LazyLock<()> = LazyLock::new(|| {
         submission outcome; pair with BUNDLES result=failure to gauge lost volume)"
     );
     describe_counter!(BUNDLES, "Bundle submissions (label: result = success / failure)");
+    describe_counter!(
+        BUNDLE_SUBMISSION_ERRORS,
+        "Failed attempts to send a bundle to the order source, per target block (label: kind = \
+        transient / permanent)"
+    );
+    describe_counter!(
+        BUNDLE_SUBMISSION_RETRIES,
+        "Bundle sends retried after a transient error, per target block"
+    );
     describe_counter!(NONCE_CHECK_ERRORS, "Errors querying Permit2 nonce bitmap");
+    describe_counter!(
+        ORDER_EVENT_SUBSCRIPTION_ERRORS,
+        "Rollup order event subscriptions that failed or ended and were reopened"
+    );
+    describe_counter!(
+        HEADER_SUBSCRIPTION_ERRORS,
+        "Rollup block header subscriptions that failed or ended and were reopened"
+    );
+    describe_counter!(REORGS, "Rollup reorgs detected by the block header subscription");
+    describe_counter!(
+        FILLED_CACHE_REORG_EVICTIONS,
+        "Filled-order cache entries evicted because a reorg replaced the block they were seen \
+         filled at"
+    );
+    describe_counter!(
+        COORDINATION_ERRORS,
+        "Errors reading or updating the Redis state shared with filler replicas"
+    );
+    describe_gauge!(
+        LEADER,
+        "Whether this replica holds the leader lease and submits bundles, 1 or 0 (only exported \
+        with leader election enabled)"
+    );
+    describe_gauge!(
+        BLOCK_LEAD_MS,
+        "Current block lead in milliseconds, tuned to observed inclusion (only exported with the \
+        adaptive block lead enabled)"
+    );
+    describe_gauge!(
+        READY_ORDERS,
+        "Orders in the ready-to-fill set after the latest evaluation pass (only exported with \
+        continuous evaluation enabled)"
+    );
     describe_counter!(PRICING_ERRORS, "Errors during profitability evaluation");
     describe_counter!(FETCH_ORDER_ERRORS, "Errors fetching orders from tx cache");
     describe_counter!(
@@ -50,23 +182,239 @@ pub(crate) static DESCRIPTIONS: LazyLock<()> = LazyLock::new(|| {
     );
     describe_counter!(
         MISSED_WINDOWS,
-        "Processing cycles skipped because the processing window was missed"
+        "Processing cycles that missed their processing window: skipped for starting too late, \
+        or cut short by the cycle deadline"
+    );
+    describe_counter!(
+        LATE_SUBMISSIONS_SKIPPED,
+        "Fill bundle submissions skipped because order processing left less than the minimum \
+        submission lead time before the block boundary"
+    );
+    describe_counter!(
+        CYCLE_BUDGET_EXHAUSTED,
+        "Processing cycles that exhausted a per-cycle budget and proceeded with the orders \
+        evaluated so far (label: budget = quotes / rpc-calls / wall-time)"
+    );
+    describe_counter!(
+        UNFILLED_SUBMISSIONS,
+        "Orders still unfilled after a successfully submitted fill bundle's target window, each \
+        counted as a failure against the order owner's reputation"
+    );
+    describe_counter!(
+        INCLUSION_FAILURES,
+        "Orders in a submitted fill bundle still unfilled once the bundle's last target block was \
+        produced (label: reason = not-included / order-expired)"
+    );
+    describe_counter!(
+        MALFORMED_ORDERS,
+        "Orders rejected by the sanity checks before pricing (label: reason = no-inputs / \
+        no-outputs / zero-amount / duplicate-input / zero-recipient / unknown-chain)"
+    );
+    describe_counter!(
+        SCREENING_ERRORS,
+        "Failed screening API lookups of order owners or output recipients"
+    );
+    describe_counter!(
+        SCORING_HOOK_ERRORS,
+        "Scoring hook requests that failed or timed out (only exported with a scoring hook \
+        configured)"
+    );
+    describe_counter!(
+        TWAP_REFRESH_ERRORS,
+        "Errors resolving or reading Uniswap V3 pool TWAPs (the previous price is kept until stale)"
+    );
+    describe_counter!(
+        SPOT_PRICE_REFRESH_ERRORS,
+        "Failed requests to the configured price API (the previous prices are kept until stale)"
+    );
+    describe_gauge!(
+        ROLLUP_GAS_PRICE,
+        "Estimated rollup gas price in wei, including the priority fee markup"
+    );
+    describe_gauge!(
+        HOST_GAS_PRICE,
+        "Estimated host gas price in wei, including the priority fee markup"
+    );
+    describe_counter!(
+        FEE_ORACLE_ERRORS,
+        "Failed gas price estimates, by chain: rollup / host (the previous estimate is kept)"
+    );
+    describe_counter!(
+        TOKEN_DISCOVERY_ERRORS,
+        "RPC errors evaluating discovered tokens (the token is retried on the next interval)"
+    );
+    describe_histogram!(
+        PIPELINE_STAGE_DURATION_SECONDS,
+        "Time spent in each stage of a processing cycle (label: stage = fetch / filter / screening / \
+         pricing / nonce-check / bundle-build / submission); stages a cycle doesn't reach, and \
+         screening without screening configured, are not recorded"
+    );
+    describe_counter!(
+        FILL_SIMULATIONS,
+        "Fill bundles simulated before submission, labelled by result (passed, rejected: reverted \
+         or outside the maximum loss, failed: the simulation itself errored)"
+    );
+    describe_counter!(
+        QUOTE_CACHE_HITS,
+        "Orders whose profitability was taken from the quote cache instead of priced again"
     );
-    describe_histogram!(CYCLE_DURATION_SECONDS, "Duration of each processing cycle");
     describe_histogram!(ORDERS_PER_BUNDLE, "Orders in submitted bundles");
+    describe_histogram!(
+        FILL_MARKOUT_USD,
+        "USD markout of each landed fill: the value of the inputs received, at the markout delay, \
+        less the value of the outputs paid (only exported with toxic-flow detection enabled)"
+    );
+    describe_histogram!(
+        FILL_REALIZED_PNL_USD,
+        "Realized USD profit of each confirmed fill: the value of the inputs transferred to the \
+        filler, less the value of the outputs paid and the rollup gas paid"
+    );
+    describe_gauge!(
+        REALIZED_PNL_USD,
+        "Cumulative realized USD profit of the filler's confirmed fills since startup"
+    );
     describe_histogram!(
         CHUNKS_PER_CYCLE,
         "Number of fill bundle chunks submitted per cycle (1 when orders fit in a single bundle, \
         more when MAX_ORDERS_PER_BUNDLE forces chunking)"
     );
+    describe_counter!(
+        ARCHIVE_UPLOADS,
+        "Archive batch uploads to object storage (label: result = success / failure)"
+    );
+    describe_counter!(
+        ARCHIVE_RECORDS_DROPPED,
+        "Archive records dropped because the in-memory archive buffer was full"
+    );
+    describe_counter!(
+        FILL_RECEIPTS,
+        "Fill receipts sent to the fill webhook (label: result = success / failure)"
+    );
+    describe_counter!(
+        BRIDGE_TRANSFERS,
+        "Inventory transfers from the host chain onto the rollup through Passage (labels: asset = \
+        usd / eth / btc, result = success / failure)"
+    );
+    describe_gauge!(
+        WALLET_BALANCE,
+        "The filler's balance of each known or token-table token, in whole tokens, refreshed \
+        every cycle (label: token = the token's name, or chain:address)"
+    );
+    describe_gauge!(
+        NET_POSITION,
+        "The filler's net position in each token, in whole tokens: its balance plus the inputs \
+        of pending fills less their outputs (label: token = the token's name, or chain:address)"
+    );
+    describe_gauge!(
+        LOW_BALANCE,
+        "Whether the filler's balance of a watched token is below its low-balance threshold, 1 or \
+        0 (label: token = the token's name, or chain:address)"
+    );
+    describe_counter!(
+        TREASURY_TOP_UPS,
+        "Low balances topped up from the treasury (labels: token = the token's name or \
+        chain:address, result = success / failure)"
+    );
+    describe_counter!(
+        APPROVALS,
+        "Permit2 approval transactions sent under the approval policy (label: result = success / \
+        failure)"
+    );
 });
 
+/// Histograms carrying trace-ID exemplars, so an anomalous bucket links straight to the trace of
+/// the cycle or submission that landed in it. The `metrics` facade has no exemplar support, so
+/// these are kept in an OpenMetrics registry served alongside the Prometheus exporter. Exemplars
+/// are only attached when OTLP tracing is enabled.
+struct ExemplarHistograms {
+    registry: Registry,
+    cycle_duration: HistogramWithExemplars<TraceLabel>,
+    bundle_submission_duration: Family<ResultLabel, HistogramWithExemplars<TraceLabel>>,
+}
+
+// Exemplar histogram names, under the registry's `signet_filler` prefix.
+const CYCLE_DURATION_SECONDS: &str = "cycle_duration_seconds";
+const BUNDLE_SUBMISSION_DURATION_SECONDS: &str = "bundle_submission_duration_seconds";
+
+type TraceLabel = [(&'static str, String); 1];
+type ResultLabel = [(&'static str, &'static str); 1];
+
+static EXEMPLAR_HISTOGRAMS: LazyLock<ExemplarHistograms> = LazyLock::new(|| {
+    let cycle_duration = HistogramWithExemplars::new(latency_buckets());
+    let bundle_submission_duration: Family<ResultLabel, HistogramWithExemplars<TraceLabel>> =
+        Family::new_with_constructor(|| HistogramWithExemplars::new(latency_buckets()));
+    let mut registry = Registry::with_prefix("signet_filler");
+    registry.register(
+        CYCLE_DURATION_SECONDS,
+        "Duration of each processing cycle",
+        cycle_duration.clone(),
+    );
+    registry.register(
+        BUNDLE_SUBMISSION_DURATION_SECONDS,
+        "Duration of each fill bundle submission, from signing through the tx-cache response \
+        (label: result = success / failure)",
+        bundle_submission_duration.clone(),
+    );
+    ExemplarHistograms { registry, cycle_duration, bundle_submission_duration }
+});
+
+/// 5ms to ~41s, covering quick submissions through cycles that overrun a slot.
+fn latency_buckets() -> impl Iterator<Item = f64> {
+    exponential_buckets(0.005, 2.0, 14)
+}
+
+/// The trace ID of the current span as an exemplar label, if it belongs to an exported trace.
+fn trace_exemplar() -> Option<TraceLabel> {
+    let context = Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    span_context.is_valid().then(|| [("trace_id", span_context.trace_id().to_string())])
+}
+
+/// Render the exemplar histograms in the OpenMetrics text format.
+pub(crate) fn render_exemplar_histograms() -> String {
+    let mut rendered = String::new();
+    encode(&mut rendered, &EXEMPLAR_HISTOGRAMS.registry).expect("writing to a string doesn't fail");
+    rendered
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum OrderSkippedReason {
     AlreadyFilled,
     Expired,
     ExceedsMaxLoss,
     UnknownToken,
     InsufficientFillerBalance,
+    FillRateThrottled,
+    CycleBudgetExhausted,
+    PoorOwnerReputation,
+    InventorySkew,
+    AwaitingApproval,
+    ApprovalRejected,
+    LowExpectedValue,
+    PriceDisagreement,
+    UnprofitableBackoff,
+    StalePrice,
+    DegradedPricing,
+    BelowMinMargin,
+    UnprofitableBundle,
+    ClaimedByPeer,
+    PendingFill,
+    InvalidSignature,
+    Malformed,
+    DisallowedToken,
+    DeniedOwner,
+    Screened,
+    ScreeningFailed,
+    ToxicFlow,
+    BelowMinNotional,
+    AboveMaxNotional,
+    ScoringRejected,
+    ScoringFailed,
+    NonceConflict,
+    PositionLimit,
+    /// Skipped by a custom order filter, under its label.
+    Custom(&'static str),
 }
 
 impl OrderSkippedReason {
@@ -77,6 +425,101 @@ impl OrderSkippedReason {
             OrderSkippedReason::ExceedsMaxLoss => "exceeds-max-loss",
             OrderSkippedReason::UnknownToken => "unknown-token",
             OrderSkippedReason::InsufficientFillerBalance => "insufficient-filler-balance",
+            OrderSkippedReason::FillRateThrottled => "fill-rate-throttled",
+            OrderSkippedReason::CycleBudgetExhausted => "cycle-budget-exhausted",
+            OrderSkippedReason::PoorOwnerReputation => "poor-owner-reputation",
+            OrderSkippedReason::InventorySkew => "inventory-skew",
+            OrderSkippedReason::AwaitingApproval => "awaiting-approval",
+            OrderSkippedReason::ApprovalRejected => "approval-rejected",
+            OrderSkippedReason::LowExpectedValue => "low-expected-value",
+            OrderSkippedReason::PriceDisagreement => "price-disagreement",
+            OrderSkippedReason::UnprofitableBackoff => "unprofitable-backoff",
+            OrderSkippedReason::StalePrice => "stale-price",
+            OrderSkippedReason::DegradedPricing => "degraded-pricing",
+            OrderSkippedReason::BelowMinMargin => "below-min-margin",
+            OrderSkippedReason::UnprofitableBundle => "unprofitable-bundle",
+            OrderSkippedReason::ClaimedByPeer => "claimed-by-peer",
+            OrderSkippedReason::PendingFill => "pending-fill",
+            OrderSkippedReason::InvalidSignature => "invalid-signature",
+            OrderSkippedReason::Malformed => "malformed",
+            OrderSkippedReason::DisallowedToken => "disallowed-token",
+            OrderSkippedReason::DeniedOwner => "denied-owner",
+            OrderSkippedReason::Screened => "screened",
+            OrderSkippedReason::ScreeningFailed => "screening-failed",
+            OrderSkippedReason::ToxicFlow => "toxic-flow",
+            OrderSkippedReason::BelowMinNotional => "below-min-notional",
+            OrderSkippedReason::AboveMaxNotional => "above-max-notional",
+            OrderSkippedReason::ScoringRejected => "scoring-rejected",
+            OrderSkippedReason::ScoringFailed => "scoring-failed",
+            OrderSkippedReason::NonceConflict => "nonce-conflict",
+            OrderSkippedReason::PositionLimit => "position-limit",
+            OrderSkippedReason::Custom(label) => label,
+        }
+    }
+}
+
+/// A per-cycle resource whose budget can run out.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum BudgetKind {
+    Quotes,
+    RpcCalls,
+    WallTime,
+}
+
+impl BudgetKind {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            BudgetKind::Quotes => "quotes",
+            BudgetKind::RpcCalls => "rpc-calls",
+            BudgetKind::WallTime => "wall-time",
+        }
+    }
+}
+
+/// What makes an order structurally unfit to fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OrderDefect {
+    /// The order permits no input tokens.
+    NoInputs,
+    /// The order has no outputs.
+    NoOutputs,
+    /// An input or output amount is zero.
+    ZeroAmount,
+    /// The same input token is permitted more than once.
+    DuplicateInput,
+    /// An output pays the zero address.
+    ZeroRecipient,
+    /// An output is on neither the rollup nor the host chain.
+    UnknownChain,
+}
+
+impl OrderDefect {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            OrderDefect::NoInputs => "no-inputs",
+            OrderDefect::NoOutputs => "no-outputs",
+            OrderDefect::ZeroAmount => "zero-amount",
+            OrderDefect::DuplicateInput => "duplicate-input",
+            OrderDefect::ZeroRecipient => "zero-recipient",
+            OrderDefect::UnknownChain => "unknown-chain",
+        }
+    }
+}
+
+/// Why a submitted fill didn't land in its bundle's target blocks.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum InclusionFailureReason {
+    /// The bundle wasn't included, and the order is submitted again.
+    NotIncluded,
+    /// The bundle wasn't included, and the order's deadline has since passed.
+    OrderExpired,
+}
+
+impl InclusionFailureReason {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            InclusionFailureReason::NotIncluded => "not-included",
+            InclusionFailureReason::OrderExpired => "order-expired",
         }
     }
 }
@@ -95,6 +538,73 @@ impl SubmissionResult {
     }
 }
 
+#[derive(Clone, Copy)]
+pub(crate) enum PipelineStage {
+    /// Fetching orders from the order source.
+    Fetch,
+    /// Dropping expired and known-filled orders.
+    Filter,
+    /// Screening order owners and output recipients.
+    Screening,
+    /// Querying inventory and scoring orders by profitability.
+    Pricing,
+    /// Waiting on the external scoring hook's decisions.
+    Scoring,
+    /// Checking Permit2 nonces, balances, and allowances, and selecting orders to fill.
+    NonceCheck,
+    /// Signing the Permit2 fills for every bundle.
+    BundleBuild,
+    /// Signing the fill transactions and sending every bundle.
+    Submission,
+}
+
+impl PipelineStage {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            PipelineStage::Fetch => "fetch",
+            PipelineStage::Filter => "filter",
+            PipelineStage::Screening => "screening",
+            PipelineStage::Pricing => "pricing",
+            PipelineStage::Scoring => "scoring",
+            PipelineStage::NonceCheck => "nonce-check",
+            PipelineStage::BundleBuild => "bundle-build",
+            PipelineStage::Submission => "submission",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum SimulationResult {
+    Passed,
+    Rejected,
+    Failed,
+}
+
+impl SimulationResult {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            SimulationResult::Passed => "passed",
+            SimulationResult::Rejected => "rejected",
+            SimulationResult::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum GasChain {
+    Rollup,
+    Host,
+}
+
+impl GasChain {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            GasChain::Rollup => "rollup",
+            GasChain::Host => "host",
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub(crate) enum ConnectionTarget {
     HostProvider,
@@ -117,6 +627,36 @@ pub(crate) fn record_uptime(elapsed: Duration) {
     gauge!(UPTIME_SECONDS).set(elapsed.as_secs_f64());
 }
 
+/// Record whether the current cycle is in a quiet period.
+pub(crate) fn record_quiet_period_active(active: bool) {
+    gauge!(QUIET_PERIOD_ACTIVE).set(if active { 1.0 } else { 0.0 });
+}
+
+/// Record whether bundle submission is paused.
+pub(crate) fn record_paused(paused: bool) {
+    gauge!(PAUSED).set(if paused { 1.0 } else { 0.0 });
+}
+
+/// Record the net PnL, in USD, of the landed fills since the last daily loss reset.
+pub(crate) fn record_daily_pnl(pnl_usd: f64) {
+    gauge!(DAILY_PNL_USD).set(pnl_usd);
+}
+
+/// Record that the daily loss limit was reached.
+pub(crate) fn record_loss_limit_breach() {
+    counter!(LOSS_LIMIT_BREACHES).increment(1);
+}
+
+/// Record whether the filler is running on a fallback for `dependency`.
+pub(crate) fn record_degraded(dependency: Dependency, degraded: bool) {
+    gauge!(DEGRADED, "dependency" => dependency.as_str()).set(if degraded { 1.0 } else { 0.0 });
+}
+
+/// Record the estimated inclusion probability.
+pub(crate) fn record_inclusion_probability(probability: f64) {
+    gauge!(INCLUSION_PROBABILITY).set(probability);
+}
+
 /// RAII guard that records cycle count and duration metrics when dropped.
 pub(crate) struct CycleGuard {
     start: Instant,
@@ -132,7 +672,9 @@ impl CycleGuard {
 impl Drop for CycleGuard {
     fn drop(&mut self) {
         counter!(CYCLES).increment(1);
-        histogram!(CYCLE_DURATION_SECONDS).record(self.start.elapsed().as_secs_f64());
+        EXEMPLAR_HISTOGRAMS
+            .cycle_duration
+            .observe(self.start.elapsed().as_secs_f64(), trace_exemplar());
     }
 }
 
@@ -156,11 +698,62 @@ pub(crate) fn record_bundle(result: SubmissionResult) {
     counter!(BUNDLES, "result" => result.as_str()).increment(1);
 }
 
+/// Record a failed attempt to send a bundle, classified as transient or permanent.
+pub(crate) fn record_bundle_submission_error(transient: bool) {
+    let kind = if transient { "transient" } else { "permanent" };
+    counter!(BUNDLE_SUBMISSION_ERRORS, "kind" => kind).increment(1);
+}
+
+/// Record a bundle send retried after a transient error.
+pub(crate) fn record_bundle_submission_retry() {
+    counter!(BUNDLE_SUBMISSION_RETRIES).increment(1);
+}
+
 /// Record a Permit2 nonce check error.
 pub(crate) fn record_nonce_check_error() {
     counter!(NONCE_CHECK_ERRORS).increment(1);
 }
 
+/// Record an error reading or updating the state shared with filler replicas.
+pub(crate) fn record_coordination_error() {
+    counter!(COORDINATION_ERRORS).increment(1);
+}
+
+/// Record whether this replica holds the leader lease.
+pub(crate) fn record_leader(leader: bool) {
+    gauge!(LEADER).set(if leader { 1.0 } else { 0.0 });
+}
+
+/// Record the current block lead.
+pub(crate) fn record_block_lead(lead: Duration) {
+    gauge!(BLOCK_LEAD_MS).set(lead.as_millis() as f64);
+}
+
+/// Record the size of the ready-to-fill set.
+pub(crate) fn record_ready_orders(count: usize) {
+    gauge!(READY_ORDERS).set(count as f64);
+}
+
+/// Record a failed or ended rollup order event subscription.
+pub(crate) fn record_order_event_subscription_error() {
+    counter!(ORDER_EVENT_SUBSCRIPTION_ERRORS).increment(1);
+}
+
+/// Record a failed or ended rollup block header subscription.
+pub(crate) fn record_header_subscription_error() {
+    counter!(HEADER_SUBSCRIPTION_ERRORS).increment(1);
+}
+
+/// Record a detected rollup reorg.
+pub(crate) fn record_reorg() {
+    counter!(REORGS).increment(1);
+}
+
+/// Record filled-order cache entries evicted after a reorg.
+pub(crate) fn record_filled_cache_reorg_evictions(count: usize) {
+    counter!(FILLED_CACHE_REORG_EVICTIONS).increment(count as u64);
+}
+
 /// Record a pricing evaluation error.
 pub(crate) fn record_pricing_error() {
     counter!(PRICING_ERRORS).increment(1);
@@ -200,12 +793,189 @@ pub(crate) fn record_missed_window() {
     counter!(MISSED_WINDOWS).increment(1);
 }
 
+/// Record a fill bundle submission skipped for lack of lead time before the block boundary.
+pub(crate) fn record_late_submission_skipped() {
+    counter!(LATE_SUBMISSIONS_SKIPPED).increment(1);
+}
+
+/// Record a cycle exhausting one of its budgets.
+pub(crate) fn record_cycle_budget_exhausted(kind: BudgetKind) {
+    counter!(CYCLE_BUDGET_EXHAUSTED, "budget" => kind.as_str()).increment(1);
+}
+
+/// Record an order that remained unfilled after its fill bundle's target window.
+pub(crate) fn record_unfilled_submission() {
+    counter!(UNFILLED_SUBMISSIONS).increment(1);
+}
+
+/// Record a submitted fill that didn't land in its bundle's target blocks.
+pub(crate) fn record_inclusion_failure(reason: InclusionFailureReason) {
+    counter!(INCLUSION_FAILURES, "reason" => reason.as_str()).increment(1);
+}
+
+/// Record an order rejected by the sanity checks.
+pub(crate) fn record_malformed_order(defect: OrderDefect) {
+    counter!(MALFORMED_ORDERS, "reason" => defect.as_str()).increment(1);
+}
+
+/// Record a failed or timed-out scoring hook request.
+pub(crate) fn record_scoring_hook_error() {
+    counter!(SCORING_HOOK_ERRORS).increment(1);
+}
+
+/// Record a failed screening API lookup.
+pub(crate) fn record_screening_error() {
+    counter!(SCREENING_ERRORS).increment(1);
+}
+
+/// Record an error resolving or reading a TWAP pool.
+pub(crate) fn record_twap_refresh_error() {
+    counter!(TWAP_REFRESH_ERRORS).increment(1);
+}
+
+/// Record a failed price API request.
+pub(crate) fn record_spot_price_refresh_error() {
+    counter!(SPOT_PRICE_REFRESH_ERRORS).increment(1);
+}
+
+/// Record the latest gas price estimate for `chain`, in wei.
+pub(crate) fn record_gas_price(chain: GasChain, gas_price: u128) {
+    let name = match chain {
+        GasChain::Rollup => ROLLUP_GAS_PRICE,
+        GasChain::Host => HOST_GAS_PRICE,
+    };
+    gauge!(name).set(gas_price as f64);
+}
+
+/// Record a failed gas price estimate for `chain`.
+pub(crate) fn record_fee_oracle_error(chain: GasChain) {
+    counter!(FEE_ORACLE_ERRORS, "chain" => chain.as_str()).increment(1);
+}
+
+/// Record an RPC error evaluating a discovered token.
+pub(crate) fn record_token_discovery_error() {
+    counter!(TOKEN_DISCOVERY_ERRORS).increment(1);
+}
+
+/// Record the result of simulating a fill bundle before submission.
+pub(crate) fn record_fill_simulation(result: SimulationResult) {
+    counter!(FILL_SIMULATIONS, "result" => result.as_str()).increment(1);
+}
+
+/// Record an order priced from the quote cache.
+pub(crate) fn record_quote_cache_hit() {
+    counter!(QUOTE_CACHE_HITS).increment(1);
+}
+
 /// Record the number of orders in a submitted bundle.
 pub(crate) fn record_orders_per_bundle(count: f64) {
     histogram!(ORDERS_PER_BUNDLE).record(count);
 }
 
+/// Record a landed fill's markout, in USD.
+pub(crate) fn record_fill_markout(markout_usd: f64) {
+    histogram!(FILL_MARKOUT_USD).record(markout_usd);
+}
+
+/// Record a confirmed fill's realized profit and the cumulative total, in USD.
+pub(crate) fn record_realized_pnl(fill_pnl_usd: f64, total_pnl_usd: f64) {
+    histogram!(FILL_REALIZED_PNL_USD).record(fill_pnl_usd);
+    gauge!(REALIZED_PNL_USD).set(total_pnl_usd);
+}
+
 /// Record the number of fill bundles submitted during a cycle.
 pub(crate) fn record_chunks_per_cycle(count: f64) {
     histogram!(CHUNKS_PER_CYCLE).record(count);
 }
+
+/// Record how long a single fill bundle submission took.
+pub(crate) fn record_bundle_submission_duration(elapsed: Duration, result: SubmissionResult) {
+    EXEMPLAR_HISTOGRAMS
+        .bundle_submission_duration
+        .get_or_create(&[("result", result.as_str())])
+        .observe(elapsed.as_secs_f64(), trace_exemplar());
+}
+
+/// Record how long a processing cycle spent in `stage`.
+pub(crate) fn record_stage_duration(stage: PipelineStage, elapsed: Duration) {
+    histogram!(PIPELINE_STAGE_DURATION_SECONDS, "stage" => stage.as_str())
+        .record(elapsed.as_secs_f64());
+}
+
+/// Record an archive batch upload result.
+pub(crate) fn record_archive_upload(result: SubmissionResult) {
+    counter!(ARCHIVE_UPLOADS, "result" => result.as_str()).increment(1);
+}
+
+/// Record an archive record dropped because the buffer was full.
+pub(crate) fn record_archive_record_dropped() {
+    counter!(ARCHIVE_RECORDS_DROPPED).increment(1);
+}
+
+/// Record the result of sending a fill receipt to the fill webhook.
+pub(crate) fn record_fill_receipt(result: SubmissionResult) {
+    counter!(FILL_RECEIPTS, "result" => result.as_str()).increment(1);
+}
+
+/// Record an inventory transfer onto the rollup.
+pub(crate) fn record_bridge_transfer(asset: InventoryAsset, result: SubmissionResult) {
+    counter!(BRIDGE_TRANSFERS, "asset" => asset.as_str(), "result" => result.as_str()).increment(1);
+}
+
+/// Record the filler's balance of `token`, in whole tokens.
+pub(crate) fn record_wallet_balance(token: ChainTokenPair, balance: f64) {
+    gauge!(WALLET_BALANCE, "token" => token.to_string()).set(balance);
+}
+
+/// Record the filler's net position in `token`, in whole tokens.
+pub(crate) fn record_net_position(token: ChainTokenPair, position: f64) {
+    gauge!(NET_POSITION, "token" => token.to_string()).set(position);
+}
+
+/// Record whether the filler's balance of `token` is below its low-balance threshold.
+pub(crate) fn record_low_balance(token: ChainTokenPair, low: bool) {
+    gauge!(LOW_BALANCE, "token" => token.to_string()).set(if low { 1.0 } else { 0.0 });
+}
+
+/// Record a top-up of `token` from the treasury.
+pub(crate) fn record_treasury_top_up(token: ChainTokenPair, result: SubmissionResult) {
+    counter!(TREASURY_TOP_UPS, "token" => token.to_string(), "result" => result.as_str())
+        .increment(1);
+}
+
+/// Record a Permit2 approval transaction.
+pub(crate) fn record_approval(result: SubmissionResult) {
+    counter!(APPROVALS, "result" => result.as_str()).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use init4_bin_base::deps::{
+        opentelemetry::trace::TracerProvider as _,
+        opentelemetry_sdk::trace::SdkTracerProvider,
+        tracing::{info_span, subscriber::with_default},
+        tracing_opentelemetry,
+        tracing_subscriber::{layer::SubscriberExt, registry},
+    };
+
+    #[test]
+    fn latency_histograms_carry_the_trace_id_of_the_current_span() {
+        // Outside an exported trace, no exemplar is attached.
+        record_bundle_submission_duration(Duration::from_millis(1), SubmissionResult::Failure);
+        assert!(!render_exemplar_histograms().contains("trace_id"));
+
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("signet-filler")));
+        let trace_id = with_default(subscriber, || {
+            let span = info_span!("submit_one_bundle");
+            let _entered = span.enter();
+            record_bundle_submission_duration(Duration::from_millis(1), SubmissionResult::Success);
+            trace_exemplar().unwrap()[0].1.clone()
+        });
+        let rendered = render_exemplar_histograms();
+        assert!(rendered.contains(&format!("trace_id=\"{trace_id}\"")), "{rendered}");
+        assert!(rendered.ends_with("# EOF\n"));
+    }
+}
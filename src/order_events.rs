@@ -0,0 +1,252 @@
+use crate::{FillProviderType, FillerContext, metrics};
+use alloy::{
+    primitives::{Address, B256, U256, keccak256},
+    providers::Provider,
+    rpc::types::Filter,
+    sol_types::{SolEvent, SolValue},
+};
+use eyre::{Result, WrapErr};
+use futures_util::StreamExt;
+use init4_bin_base::deps::tracing::{debug, info, trace, warn};
+use lru::LruCache;
+use signet_types::SignedOrder;
+use signet_zenith::RollupOrders;
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+use tokio::{select, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+/// How many landed orders to remember until the filler next sees them.
+const LANDED_ORDERS_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+
+/// How long to wait before resubscribing after the subscription fails or ends.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// Identifies an order by what the rollup Orders contract emits when it lands: its deadline,
+/// inputs, and outputs. The `Order` event carries no order hash or nonce, so landed orders are
+/// matched to fetched ones by this fingerprint.
+fn fingerprint(
+    deadline: U256,
+    inputs: &[RollupOrders::Input],
+    outputs: &[RollupOrders::Output],
+) -> B256 {
+    keccak256((deadline, inputs.to_vec(), outputs.to_vec()).abi_encode())
+}
+
+/// Matches the rollup Orders contract's `Order` events.
+fn order_filter(orders_address: Address) -> Filter {
+    Filter::new().address(orders_address).event_signature(RollupOrders::Order::SIGNATURE_HASH)
+}
+
+/// The fingerprint of a signed order, matching the `Order` event its initiation emits.
+fn order_fingerprint(order: &SignedOrder) -> B256 {
+    let permit = &order.permit().permit;
+    let inputs: Vec<RollupOrders::Input> = permit
+        .permitted
+        .iter()
+        .map(|permitted| RollupOrders::Input { token: permitted.token, amount: permitted.amount })
+        .collect();
+    fingerprint(permit.deadline, &inputs, order.outputs())
+}
+
+/// The hash of the rollup transaction `order` landed in, from the Orders contract's `Order` logs
+/// in the `lookback` blocks up to and including `block_number`, if one is found there.
+pub(crate) async fn find_order_transaction(
+    provider: &FillProviderType,
+    orders_address: Address,
+    order: &SignedOrder,
+    block_number: u64,
+    lookback: u64,
+) -> Result<Option<B256>> {
+    let filter = order_filter(orders_address)
+        .from_block((block_number + 1).saturating_sub(lookback))
+        .to_block(block_number);
+    let logs = provider.get_logs(&filter).await.wrap_err("failed to fetch rollup order events")?;
+    let wanted = order_fingerprint(order);
+    Ok(logs.iter().rev().find_map(|log| {
+        let event = log.log_decode::<RollupOrders::Order>().ok()?;
+        let data = &event.inner.data;
+        (fingerprint(data.deadline, &data.inputs, &data.outputs) == wanted)
+            .then_some(log.transaction_hash)
+            .flatten()
+    }))
+}
+
+/// Orders seen landing on the rollup, by fingerprint, with the block each landed in, shared
+/// between the [`OrderEventTask`] that records them and the filler task, which treats a fetched
+/// order found here as filled without checking its Permit2 nonce.
+#[derive(Debug, Clone)]
+pub(crate) struct LandedOrders {
+    fingerprints: Arc<Mutex<LruCache<B256, u64>>>,
+}
+
+impl Default for LandedOrders {
+    fn default() -> Self {
+        Self { fingerprints: Arc::new(Mutex::new(LruCache::new(LANDED_ORDERS_CACHE_SIZE))) }
+    }
+}
+
+impl LandedOrders {
+    fn record(&self, event: &RollupOrders::Order, block_number: u64) {
+        let fingerprint = fingerprint(event.deadline, &event.inputs, &event.outputs);
+        self.fingerprints.lock().unwrap().put(fingerprint, block_number);
+    }
+
+    /// Records the orders that landed in the last `blocks` rollup blocks, from the Orders
+    /// contract's `Order` logs, so orders filled shortly before startup aren't nonce-checked
+    /// again. Returns how many were found.
+    pub(crate) async fn backfill(
+        &self,
+        provider: &FillProviderType,
+        orders_address: Address,
+        blocks: u64,
+    ) -> Result<usize> {
+        let head =
+            provider.get_block_number().await.wrap_err("failed to fetch rollup block number")?;
+        let filter = order_filter(orders_address)
+            .from_block((head + 1).saturating_sub(blocks))
+            .to_block(head);
+        let logs =
+            provider.get_logs(&filter).await.wrap_err("failed to fetch rollup order events")?;
+        let mut landed = 0;
+        for log in logs {
+            match log.log_decode::<RollupOrders::Order>() {
+                Ok(event) => {
+                    self.record(&event.inner.data, log.block_number.unwrap_or(u64::MAX));
+                    landed += 1;
+                }
+                Err(error) => warn!(%error, "failed to decode rollup order event"),
+            }
+        }
+        Ok(landed)
+    }
+
+    /// Forgets an order whose `Order` event was removed by a reorg.
+    fn remove(&self, event: &RollupOrders::Order) {
+        let fingerprint = fingerprint(event.deadline, &event.inputs, &event.outputs);
+        self.fingerprints.lock().unwrap().pop(&fingerprint);
+    }
+
+    /// The block `order` was seen landing in, if any. A match is forgotten, since the caller
+    /// moves the order into its filled-order cache.
+    pub(crate) fn take(&self, order: &SignedOrder) -> Option<u64> {
+        self.fingerprints.lock().unwrap().pop(&order_fingerprint(order))
+    }
+}
+
+/// Background task that subscribes to the rollup Orders contract's `Order` events over the
+/// rollup WebSocket provider and records each landed order in the shared [`LandedOrders`], so
+/// the filler can skip orders filled since its last cycle without a nonce read each. The nonce
+/// check still covers any order the subscription missed. A failed or closed subscription is
+/// logged, counted, and reopened.
+#[derive(Debug)]
+pub struct OrderEventTask {
+    provider: FillProviderType,
+    orders_address: Address,
+    landed_orders: LandedOrders,
+    cancellation_token: CancellationToken,
+}
+
+impl OrderEventTask {
+    /// Create the task.
+    pub fn new(context: &FillerContext) -> Self {
+        Self {
+            provider: context.ru_provider().clone(),
+            orders_address: context.constants().system().rollup().orders(),
+            landed_orders: context.landed_orders().clone(),
+            cancellation_token: context.cancellation_token().clone(),
+        }
+    }
+
+    /// Run the subscription loop.
+    pub async fn run(self) {
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("order event task cancelled");
+                    break;
+                }
+                result = self.watch() => {
+                    if let Err(error) = result {
+                        warn!(error = format!("{error:#}"), "order event subscription failed");
+                    } else {
+                        warn!("order event subscription ended");
+                    }
+                    metrics::record_order_event_subscription_error();
+                }
+            }
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => break,
+                _ = tokio::time::sleep(RESUBSCRIBE_DELAY) => {}
+            }
+        }
+    }
+
+    /// Subscribes to `Order` events and records each landed order until the subscription ends.
+    async fn watch(&self) -> Result<()> {
+        let subscription = self
+            .provider
+            .subscribe_logs(&order_filter(self.orders_address))
+            .await
+            .wrap_err("failed to subscribe to rollup order events")?;
+        info!(orders = %self.orders_address, "subscribed to rollup order events");
+        let mut stream = subscription.into_stream();
+        while let Some(log) = stream.next().await {
+            match log.log_decode::<RollupOrders::Order>() {
+                Ok(event) if log.removed => {
+                    trace!(tx_hash = ?log.transaction_hash, "order event removed by reorg");
+                    self.landed_orders.remove(&event.inner.data);
+                }
+                Ok(event) => {
+                    trace!(tx_hash = ?log.transaction_hash, "order landed");
+                    // A log without a block number is treated as landing above every fork point.
+                    let block_number = log.block_number.unwrap_or(u64::MAX);
+                    self.landed_orders.record(&event.inner.data, block_number);
+                }
+                Err(error) => warn!(%error, "failed to decode rollup order event"),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+
+    #[test]
+    fn matches_landed_order_by_event_fields() {
+        let token = Address::repeat_byte(0x11);
+        let output = RollupOrders::Output {
+            token,
+            amount: U256::from(90),
+            recipient: Address::repeat_byte(0x22),
+            chainId: 1,
+        };
+        let order = OrderBuilder::default()
+            .with_owner(Address::repeat_byte(0x33))
+            .with_input(token, U256::from(100))
+            .with_output(output.token, output.amount, output.recipient, output.chainId.into())
+            .with_nonce(7)
+            .with_deadline(1_000)
+            .build();
+        let event = RollupOrders::Order {
+            deadline: U256::from(1_000),
+            inputs: vec![RollupOrders::Input { token, amount: U256::from(100) }],
+            outputs: vec![output],
+        };
+        let landed_orders = LandedOrders::default();
+        assert_eq!(landed_orders.take(&order), None);
+        landed_orders.record(&event, 42);
+        assert_eq!(landed_orders.take(&order), Some(42));
+        assert_eq!(landed_orders.take(&order), None);
+        landed_orders.record(&event, 43);
+        landed_orders.remove(&event);
+        assert_eq!(landed_orders.take(&order), None);
+    }
+}
@@ -0,0 +1,263 @@
+use alloy::primitives::keccak256;
+use core::fmt::Debug;
+use futures_util::{Stream, stream};
+use init4_bin_base::deps::tracing::info;
+use signet_bundle::SignetEthBundle;
+use signet_orders::BundleSubmitter;
+use signet_tx_cache::types::BundleResponse;
+use signet_types::SignedOrder;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+use uuid::Uuid;
+
+/// Where the filler fetches orders from and submits fill bundles to.
+///
+/// Blanket-implemented for every type implementing both signet-orders'
+/// [`OrderSource`](signet_orders::OrderSource) and [`BundleSubmitter`] with the transaction
+/// cache's response type: the [`TxCache`](signet_tx_cache::TxCache) in production, a
+/// [`FileOrderSource`] for replaying captured orders, and a mock in tests. The filler pipeline is
+/// generic over this trait, so alternative order feeds don't touch the core loop.
+pub trait OrderSource:
+    signet_orders::OrderSource
+    + BundleSubmitter<Response = BundleResponse>
+    + Clone
+    + Debug
+    + Send
+    + Sync
+    + 'static
+{
+}
+
+impl<T> OrderSource for T where
+    T: signet_orders::OrderSource
+        + BundleSubmitter<Response = BundleResponse>
+        + Clone
+        + Debug
+        + Send
+        + Sync
+        + 'static
+{
+}
+
+/// Errors from [`FileOrderSource`].
+#[derive(Debug, thiserror::Error)]
+pub enum FileOrderSourceError {
+    /// The orders file could not be read, or the bundles file could not be written.
+    #[error("{}: {source}", path.display())]
+    Io {
+        /// The file being accessed.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// An order could not be deserialized.
+    #[error("{}: {context}: {source}", path.display())]
+    Json {
+        /// The file being accessed.
+        path: PathBuf,
+        /// Which entry failed.
+        context: String,
+        /// The underlying error.
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// An order source backed by local files, for replaying captured orders without a transaction
+/// cache.
+///
+/// The orders file is re-read on every fetch, so it can be edited while the filler runs. It holds
+/// either a JSON array of `SignedOrder`s or one JSON order per line (blank lines are ignored), the
+/// format served by the transaction cache and written by the archive. Submitted bundles are
+/// appended as JSON lines to the bundles file if one is set, or only logged otherwise; either way
+/// the bundle ID returned is derived from the bundle's contents.
+#[derive(Debug, Clone)]
+pub struct FileOrderSource {
+    orders_path: PathBuf,
+    bundles_path: Option<PathBuf>,
+}
+
+impl FileOrderSource {
+    /// Create a source reading orders from `orders_path` and appending bundles to `bundles_path`.
+    pub const fn new(orders_path: PathBuf, bundles_path: Option<PathBuf>) -> Self {
+        Self { orders_path, bundles_path }
+    }
+
+    fn read_orders(&self) -> Result<Vec<SignedOrder>, FileOrderSourceError> {
+        let contents = fs::read_to_string(&self.orders_path).map_err(|source| {
+            FileOrderSourceError::Io { path: self.orders_path.clone(), source }
+        })?;
+        let json_error = |context: String, source| FileOrderSourceError::Json {
+            path: self.orders_path.clone(),
+            context,
+            source,
+        };
+        if contents.trim_start().starts_with('[') {
+            return serde_json::from_str(&contents)
+                .map_err(|source| json_error("invalid order array".to_string(), source));
+        }
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|source| {
+                    json_error(format!("invalid order on line {}", index + 1), source)
+                })
+            })
+            .collect()
+    }
+
+    fn write_bundle(&self, bundle: &SignetEthBundle) -> Result<Uuid, FileOrderSourceError> {
+        let json = serde_json::to_string(bundle).expect("bundles serialize to JSON");
+        let hash = keccak256(&json);
+        let id = Uuid::from_bytes(hash[..16].try_into().expect("hash is 32 bytes"));
+
+        let Some(bundles_path) = &self.bundles_path else {
+            info!(bundle_id = %id, txs = bundle.txs().len(), "discarding bundle from file source");
+            return Ok(id);
+        };
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(bundles_path)
+            .and_then(|mut file| writeln!(file, "{json}"))
+            .map_err(|source| FileOrderSourceError::Io { path: bundles_path.clone(), source })?;
+        Ok(id)
+    }
+}
+
+impl signet_orders::OrderSource for FileOrderSource {
+    type Error = FileOrderSourceError;
+
+    fn get_orders(&self) -> impl Stream<Item = Result<SignedOrder, Self::Error>> + Send {
+        let orders: Vec<_> = match self.read_orders() {
+            Ok(orders) => orders.into_iter().map(Ok).collect(),
+            Err(error) => vec![Err(error)],
+        };
+        stream::iter(orders)
+    }
+}
+
+impl BundleSubmitter for FileOrderSource {
+    type Response = BundleResponse;
+    type Error = FileOrderSourceError;
+
+    async fn submit_bundle(&self, bundle: SignetEthBundle) -> Result<Self::Response, Self::Error> {
+        self.write_bundle(&bundle).map(BundleResponse::new)
+    }
+}
+
+/// In-memory order source for tests: serves a fixed set of orders and records submitted bundles.
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MockOrderSource {
+    orders: Vec<SignedOrder>,
+    bundles: std::sync::Arc<std::sync::Mutex<Vec<SignetEthBundle>>>,
+}
+
+#[cfg(test)]
+impl MockOrderSource {
+    pub(crate) fn new(orders: Vec<SignedOrder>) -> Self {
+        Self { orders, bundles: Default::default() }
+    }
+
+    /// Bundles submitted so far.
+    pub(crate) fn bundles(&self) -> Vec<SignetEthBundle> {
+        self.bundles.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl signet_orders::OrderSource for MockOrderSource {
+    type Error = std::io::Error;
+
+    fn get_orders(&self) -> impl Stream<Item = Result<SignedOrder, Self::Error>> + Send {
+        stream::iter(self.orders.clone().into_iter().map(Ok))
+    }
+}
+
+#[cfg(test)]
+impl BundleSubmitter for MockOrderSource {
+    type Response = BundleResponse;
+    type Error = std::io::Error;
+
+    async fn submit_bundle(&self, bundle: SignetEthBundle) -> Result<Self::Response, Self::Error> {
+        let mut bundles = self.bundles.lock().unwrap();
+        bundles.push(bundle);
+        Ok(BundleResponse::new(Uuid::from_u128(bundles.len() as u128)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use alloy::{primitives::Bytes, rpc::types::mev::EthSendBundle};
+    use futures_util::TryStreamExt;
+    use itertools::Itertools;
+    use signet_orders::OrderSource as _;
+
+    fn order(nonce: u64) -> SignedOrder {
+        OrderBuilder::default().with_nonce(nonce).build()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("signet-filler-{}-{name}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn file_source_reads_arrays_and_lines() {
+        let orders = vec![order(1), order(2)];
+        let path = temp_path("orders.json");
+
+        fs::write(&path, serde_json::to_string(&orders).unwrap()).unwrap();
+        let source = FileOrderSource::new(path.clone(), None);
+        assert_eq!(source.get_orders().try_collect::<Vec<_>>().await.unwrap(), orders);
+
+        let lines = orders.iter().map(|order| serde_json::to_string(order).unwrap()).join("\n\n");
+        fs::write(&path, lines).unwrap();
+        assert_eq!(source.get_orders().try_collect::<Vec<_>>().await.unwrap(), orders);
+
+        fs::write(&path, "{}\n").unwrap();
+        let error = source.get_orders().try_collect::<Vec<_>>().await.unwrap_err();
+        assert!(error.to_string().contains("invalid order on line 1"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn file_source_appends_bundles() {
+        let path = temp_path("bundles.jsonl");
+        let source = FileOrderSource::new(temp_path("unused.json"), Some(path.clone()));
+        let bundle = SignetEthBundle::new(EthSendBundle::default(), vec![Bytes::from([1])]);
+
+        let first = source.submit_bundle(bundle.clone()).await.unwrap();
+        let second = source.submit_bundle(bundle).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn mock_source_serves_orders_and_records_bundles() {
+        let source = MockOrderSource::new(vec![order(1)]);
+        let filler = signet_orders::Filler::new(
+            (),
+            source.clone(),
+            (),
+            signet_constants::SignetSystemConstants::parmigiana(),
+            signet_orders::FillerOptions::new(),
+        );
+        assert_eq!(filler.get_orders().try_collect::<Vec<_>>().await.unwrap(), vec![order(1)]);
+
+        source.submit_bundle(SignetEthBundle::new(EthSendBundle::default(), vec![])).await.unwrap();
+        assert_eq!(source.bundles().len(), 1);
+    }
+}
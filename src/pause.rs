@@ -0,0 +1,84 @@
+use crate::metrics;
+use serde::Serialize;
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Why and since when bundle submission is paused.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct PauseState {
+    pub(crate) reason: String,
+    /// When the pause began, in seconds since the Unix epoch.
+    pub(crate) since: u64,
+}
+
+/// Operator pause of bundle submission, shared between the admin API, which pauses and resumes
+/// it, and the filler loop, which keeps evaluating orders while paused but runs every cycle as a
+/// dry run. Lets operators stop filling during an incident without stopping the process, so
+/// metrics, health checks, and caches stay live. The pause is held in memory and is cleared by a
+/// restart.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PauseSwitch {
+    state: Arc<Mutex<Option<PauseState>>>,
+}
+
+impl PauseSwitch {
+    /// Pauses submission for `reason`, returning whether it wasn't already paused. An existing
+    /// pause keeps its original reason.
+    pub(crate) fn pause(&self, reason: impl Into<String>) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.is_some() {
+            return false;
+        }
+        let since = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock set before UNIX epoch")
+            .as_secs();
+        *state = Some(PauseState { reason: reason.into(), since });
+        metrics::record_paused(true);
+        true
+    }
+
+    /// Resumes submission, returning whether it was paused.
+    pub(crate) fn resume(&self) -> bool {
+        let resumed = self.state.lock().unwrap().take().is_some();
+        metrics::record_paused(false);
+        resumed
+    }
+
+    /// Resumes submission if it was paused for `reason`, returning whether it was.
+    pub(crate) fn resume_if(&self, reason: &str) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.as_ref().is_none_or(|state| state.reason != reason) {
+            return false;
+        }
+        *state = None;
+        metrics::record_paused(false);
+        true
+    }
+
+    /// The current pause, if submission is paused.
+    pub(crate) fn state(&self) -> Option<PauseState> {
+        self.state.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_keeps_first_reason_until_resumed() {
+        let pause = PauseSwitch::default();
+        assert_eq!(pause.state(), None);
+        assert!(!pause.resume());
+
+        assert!(pause.pause("incident"));
+        assert!(!pause.clone().pause("another"));
+        assert_eq!(pause.state().unwrap().reason, "incident");
+
+        assert!(pause.resume());
+        assert_eq!(pause.state(), None);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::FixedPricingClient;
+use alloy::primitives::B256;
+use core::fmt::Debug;
+use futures_util::future::{self, BoxFuture};
+use signet_types::SignedOrder;
+use std::error::Error;
+
+/// Decides how profitable orders are to fill, for [`FillerTask`](crate::FillerTask)'s pricing
+/// stage.
+///
+/// The filler prices with its fixed-rate pricing client unless given another through
+/// [`FillerTask::with_pricing`](crate::FillerTask::with_pricing), such as a
+/// [`MockPricingClient`](crate::testing::MockPricingClient) with scripted results. Only the
+/// profitability decision is replaced: valuing notionals, inventory, positions, simulated fills,
+/// and the ledger still uses fixed-rate prices.
+pub trait OrderPricing: Debug + Send + Sync {
+    /// The filler's margin on `order` before gas, in normalized 18-decimal USD, or `None` when
+    /// the order is beyond the maximum acceptable loss.
+    fn profitability<'a>(
+        &'a self,
+        order: &'a SignedOrder,
+    ) -> BoxFuture<'a, Result<Option<i128>, Box<dyn Error + Send + Sync>>>;
+
+    /// Whether pricing `order_hash` again would be served from a cache, in which case it doesn't
+    /// count towards the cycle's quote budget.
+    fn has_cached_quote(&self, _order_hash: &B256) -> bool {
+        false
+    }
+}
+
+impl OrderPricing for FixedPricingClient {
+    fn profitability<'a>(
+        &'a self,
+        order: &'a SignedOrder,
+    ) -> BoxFuture<'a, Result<Option<i128>, Box<dyn Error + Send + Sync>>> {
+        Box::pin(future::ready(FixedPricingClient::profitability(self, order).map_err(Into::into)))
+    }
+
+    fn has_cached_quote(&self, order_hash: &B256) -> bool {
+        FixedPricingClient::has_cached_quote(self, order_hash)
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{FillProviderType, FillerContext, PriceApiConfig, spot_price};
+use alloy::providers::Provider;
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use eyre::{Result, WrapErr};
+use serde::Serialize;
+use signet_tx_cache::TxCache;
+use std::future::Future;
+use tokio::{
+    join,
+    time::{Duration, Instant, timeout},
+};
+
+/// How long each dependency has to respond before it is reported as timed out.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of probing a single dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum ProbeStatus {
+    Ok,
+    Error,
+    Timeout,
+}
+
+/// Status of a single dependency, as served by `GET /healthcheck/deps`.
+#[derive(Debug, Serialize)]
+struct DependencyStatus {
+    name: &'static str,
+    status: ProbeStatus,
+    latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Response body for `GET /healthcheck/deps`.
+#[derive(Debug, Serialize)]
+struct DependenciesResponse {
+    healthy: bool,
+    dependencies: Vec<DependencyStatus>,
+}
+
+/// Handles to the filler's external dependencies, probed on demand by the healthcheck server.
+#[derive(Debug, Clone)]
+pub(crate) struct DependencyProbes {
+    host_provider: FillProviderType,
+    ru_provider: FillProviderType,
+    tx_cache: TxCache,
+    price_api: Option<PriceApiConfig>,
+}
+
+impl DependencyProbes {
+    pub(crate) fn new(context: &FillerContext) -> Self {
+        Self {
+            host_provider: context.host_provider().clone(),
+            ru_provider: context.ru_provider().clone(),
+            tx_cache: context.tx_cache().clone(),
+            price_api: context.price_api_config().cloned(),
+        }
+    }
+
+    /// Probes every dependency concurrently. The price API is only probed if one is configured;
+    /// fixed and TWAP pricing have no dependencies beyond the host RPC.
+    async fn probe_all(&self) -> Vec<DependencyStatus> {
+        let price_api = async {
+            match &self.price_api {
+                Some(config) => Some(
+                    probe("price-api", spot_price::probe(self.tx_cache.client(), config)).await,
+                ),
+                None => None,
+            }
+        };
+        let (host_rpc, rollup_rpc, tx_cache, price_api) = join!(
+            probe("host-rpc", probe_rpc(&self.host_provider)),
+            probe("rollup-rpc", probe_rpc(&self.ru_provider)),
+            probe("tx-cache", self.probe_tx_cache()),
+            price_api,
+        );
+        [Some(host_rpc), Some(rollup_rpc), Some(tx_cache), price_api]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    async fn probe_tx_cache(&self) -> Result<()> {
+        let orders_url = self
+            .tx_cache
+            .url()
+            .join("orders")
+            .wrap_err("failed to construct transaction cache orders URL")?;
+        self.tx_cache.client().head(orders_url).send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+async fn probe_rpc(provider: &FillProviderType) -> Result<()> {
+    provider.get_block_number().await?;
+    Ok(())
+}
+
+/// Runs `check` with [`PROBE_TIMEOUT`], timing how long it takes.
+async fn probe(name: &'static str, check: impl Future<Output = Result<()>>) -> DependencyStatus {
+    probe_within(name, PROBE_TIMEOUT, check).await
+}
+
+async fn probe_within(
+    name: &'static str,
+    limit: Duration,
+    check: impl Future<Output = Result<()>>,
+) -> DependencyStatus {
+    let start = Instant::now();
+    let result = timeout(limit, check).await;
+    let latency_ms = start.elapsed().as_millis() as u64;
+    let (status, error) = match result {
+        Ok(Ok(())) => (ProbeStatus::Ok, None),
+        Ok(Err(error)) => (ProbeStatus::Error, Some(format!("{error:#}"))),
+        Err(_) => (ProbeStatus::Timeout, None),
+    };
+    DependencyStatus { name, status, latency_ms, error }
+}
+
+/// Handler for `GET /healthcheck/deps`: 200 if every dependency responded, 503 otherwise.
+pub(crate) async fn dependencies(State(probes): State<DependencyProbes>) -> Response {
+    let dependencies = probes.probe_all().await;
+    let healthy = dependencies.iter().all(|dependency| dependency.status == ProbeStatus::Ok);
+    let status = if healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(DependenciesResponse { healthy, dependencies })).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eyre::eyre;
+
+    #[tokio::test]
+    async fn probe_reports_errors_and_timeouts() {
+        let ok = probe("ok", async { Ok(()) }).await;
+        assert_eq!(ok.status, ProbeStatus::Ok);
+        assert!(ok.error.is_none());
+
+        let error = probe("error", async { Err(eyre!("connection refused")) }).await;
+        assert_eq!(error.status, ProbeStatus::Error);
+        assert_eq!(error.error.as_deref(), Some("connection refused"));
+
+        let limit = Duration::from_millis(10);
+        let slow = probe_within("slow", limit, std::future::pending()).await;
+        assert_eq!(slow.status, ProbeStatus::Timeout);
+        assert!(slow.latency_ms >= limit.as_millis() as u64);
+        assert!(slow.error.is_none());
+    }
+}
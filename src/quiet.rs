@@ -0,0 +1,86 @@
+use crate::QuietPeriod;
+use signet_constants::SignetSystemConstants;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time::Instant;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The configured quiet periods, resolved against the host chain's slot schedule. Shared by the
+/// filler loop (which evaluates but doesn't submit during a quiet period) and the admin API
+/// (which reports whether one is active).
+#[derive(Debug, Clone)]
+pub(crate) struct QuietSchedule {
+    periods: Vec<QuietPeriod>,
+    host_start_timestamp: u64,
+    slot_duration: u64,
+}
+
+impl QuietSchedule {
+    pub(crate) fn new(periods: &[QuietPeriod], constants: &SignetSystemConstants) -> Self {
+        Self {
+            periods: periods.to_vec(),
+            host_start_timestamp: constants.host().start_timestamp(),
+            slot_duration: constants.host().slot_duration(),
+        }
+    }
+
+    /// The quiet period covering `instant`, if any.
+    pub(crate) fn active_at(&self, instant: Instant) -> Option<QuietPeriod> {
+        let now = Instant::now();
+        let system_time = if instant >= now {
+            SystemTime::now() + instant.duration_since(now)
+        } else {
+            SystemTime::now() - now.duration_since(instant)
+        };
+        let timestamp = system_time
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock set before UNIX epoch")
+            .as_secs();
+        self.active_at_timestamp(timestamp)
+    }
+
+    fn active_at_timestamp(&self, timestamp: u64) -> Option<QuietPeriod> {
+        let minute_of_day = ((timestamp % SECONDS_PER_DAY) / 60) as u16;
+        let slot = timestamp.saturating_sub(self.host_start_timestamp) / self.slot_duration;
+        self.periods.iter().copied().find(|period| period.contains(minute_of_day, slot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(periods: &[&str]) -> QuietSchedule {
+        QuietSchedule {
+            periods: periods.iter().map(|period| period.parse().unwrap()).collect(),
+            host_start_timestamp: 1_000,
+            slot_duration: 12,
+        }
+    }
+
+    #[test]
+    fn daily_periods_wrap_past_midnight() {
+        let schedule = schedule(&["23:30-00:15"]);
+        let day = 100 * SECONDS_PER_DAY;
+        assert!(schedule.active_at_timestamp(day + 23 * 3600 + 45 * 60).is_some());
+        assert!(schedule.active_at_timestamp(day + 10 * 60).is_some());
+        assert!(schedule.active_at_timestamp(day + 15 * 60).is_none());
+        assert!(schedule.active_at_timestamp(day + 12 * 3600).is_none());
+    }
+
+    #[test]
+    fn slot_periods_are_inclusive() {
+        let schedule = schedule(&["slots:10-11"]);
+        assert!(schedule.active_at_timestamp(1_000 + 9 * 12 + 11).is_none());
+        assert!(schedule.active_at_timestamp(1_000 + 10 * 12).is_some());
+        assert!(schedule.active_at_timestamp(1_000 + 11 * 12 + 11).is_some());
+        assert!(schedule.active_at_timestamp(1_000 + 12 * 12).is_none());
+    }
+
+    #[test]
+    fn invalid_periods_are_rejected() {
+        for period in ["25:00-01:00", "01:00-01:00", "slots:5-4", "noon-1pm", "slots:1"] {
+            assert!(period.parse::<QuietPeriod>().is_err(), "{period}");
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{FillProviderType, FillerContext, metrics};
+use alloy::{primitives::B256, providers::Provider};
+use eyre::{Result, WrapErr};
+use futures_util::StreamExt;
+use init4_bin_base::deps::tracing::{debug, info, warn};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+use tokio::{select, sync::watch, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+/// How many recent rollup blocks are tracked to detect reorgs, and how far back a reorg's fork
+/// point is searched for. A deeper reorg is treated as replacing every block.
+const TRACKED_BLOCKS: u64 = 64;
+
+/// How long to wait before resubscribing after the subscription fails or ends.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// How a new block header relates to the tracked chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Observation {
+    /// The block is already tracked.
+    Known,
+    /// The block extends the tracked chain, or follows a gap in it.
+    Extends,
+    /// The block's parent is tracked, but tracked blocks at or above its height are replaced,
+    /// from `fork` up.
+    Replaces { fork: u64 },
+    /// The block's parent isn't the tracked block at that height, so the fork point is further
+    /// back.
+    Diverged,
+}
+
+/// Hashes of the most recent rollup blocks, by number.
+#[derive(Debug, Default)]
+struct TrackedChain {
+    blocks: BTreeMap<u64, B256>,
+}
+
+impl TrackedChain {
+    fn observe(&self, number: u64, hash: B256, parent_hash: B256) -> Observation {
+        if self.blocks.get(&number) == Some(&hash) {
+            return Observation::Known;
+        }
+        let parent = number.checked_sub(1).and_then(|parent| self.blocks.get(&parent));
+        match parent {
+            Some(parent) if *parent != parent_hash => Observation::Diverged,
+            _ if self.blocks.range(number..).next().is_some() => {
+                Observation::Replaces { fork: number }
+            }
+            _ => Observation::Extends,
+        }
+    }
+
+    fn is_tracked(&self, number: u64, hash: B256) -> bool {
+        self.blocks.get(&number) == Some(&hash)
+    }
+
+    /// Drops tracked blocks at or above `fork`.
+    fn truncate(&mut self, fork: u64) {
+        self.blocks.split_off(&fork);
+    }
+
+    fn insert(&mut self, number: u64, hash: B256) {
+        self.blocks.insert(number, hash);
+        self.blocks = self.blocks.split_off(&number.saturating_sub(TRACKED_BLOCKS - 1));
+    }
+}
+
+/// The latest rollup block header seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Head {
+    pub(crate) number: u64,
+    /// The block's timestamp, in seconds since the Unix epoch.
+    pub(crate) timestamp: u64,
+}
+
+/// The latest rollup block and the lowest fork point of any reorg not yet handled, shared
+/// between the [`ReorgWatchTask`] that records them and the filler task, which pins its nonce
+/// checks to the head, invalidates filled-order cache entries at or above a fork, and, when
+/// scheduling from block headers, times each cycle from the head's timestamp.
+#[derive(Debug, Clone)]
+pub(crate) struct ChainWatch {
+    head: watch::Sender<Option<Head>>,
+    fork: Arc<Mutex<Option<u64>>>,
+}
+
+impl Default for ChainWatch {
+    fn default() -> Self {
+        Self { head: watch::Sender::new(None), fork: Arc::default() }
+    }
+}
+
+impl ChainWatch {
+    /// The latest rollup block number seen, if any.
+    pub(crate) fn head(&self) -> Option<u64> {
+        self.head.borrow().map(|head| head.number)
+    }
+
+    /// A receiver notified of each new rollup head.
+    pub(crate) fn subscribe(&self) -> watch::Receiver<Option<Head>> {
+        self.head.subscribe()
+    }
+
+    /// Takes the lowest block replaced by reorgs since the last call, if any.
+    pub(crate) fn take_fork(&self) -> Option<u64> {
+        self.fork.lock().unwrap().take()
+    }
+
+    fn set_head(&self, head: Head) {
+        self.head.send_replace(Some(head));
+    }
+
+    fn record_fork(&self, fork: u64) {
+        let mut pending = self.fork.lock().unwrap();
+        *pending = Some(pending.map_or(fork, |pending| pending.min(fork)));
+    }
+}
+
+/// Background task that follows rollup block headers over the rollup WebSocket provider and
+/// detects reorgs, recording the head and each reorg's fork point in the shared [`ChainWatch`].
+/// When a header doesn't build on the tracked block below it, the new chain's ancestors are
+/// fetched until one matches a tracked block, and the blocks above it are treated as replaced.
+#[derive(Debug)]
+pub struct ReorgWatchTask {
+    provider: FillProviderType,
+    chain_watch: ChainWatch,
+    cancellation_token: CancellationToken,
+}
+
+impl ReorgWatchTask {
+    /// Create the task.
+    pub fn new(context: &FillerContext) -> Self {
+        Self {
+            provider: context.ru_provider().clone(),
+            chain_watch: context.chain_watch().clone(),
+            cancellation_token: context.cancellation_token().clone(),
+        }
+    }
+
+    /// Run the header subscription loop.
+    pub async fn run(self) {
+        let mut chain = TrackedChain::default();
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("reorg watch task cancelled");
+                    break;
+                }
+                result = self.watch(&mut chain) => {
+                    if let Err(error) = result {
+                        warn!(error = format!("{error:#}"), "block header subscription failed");
+                    } else {
+                        warn!("block header subscription ended");
+                    }
+                    metrics::record_header_subscription_error();
+                }
+            }
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => break,
+                _ = tokio::time::sleep(RESUBSCRIBE_DELAY) => {}
+            }
+        }
+    }
+
+    /// Subscribes to new rollup headers and checks each against the tracked chain until the
+    /// subscription ends.
+    async fn watch(&self, chain: &mut TrackedChain) -> Result<()> {
+        let subscription = self
+            .provider
+            .subscribe_blocks()
+            .await
+            .wrap_err("failed to subscribe to rollup block headers")?;
+        info!("subscribed to rollup block headers");
+        let mut stream = subscription.into_stream();
+        while let Some(header) = stream.next().await {
+            let (number, hash, parent_hash, timestamp) = (
+                header.inner.number,
+                header.hash,
+                header.inner.parent_hash,
+                header.inner.timestamp,
+            );
+            let fork = match chain.observe(number, hash, parent_hash) {
+                Observation::Known => continue,
+                Observation::Extends => None,
+                Observation::Replaces { fork } => Some(fork),
+                Observation::Diverged => Some(self.find_fork(chain, parent_hash).await),
+            };
+            if let Some(fork) = fork {
+                warn!(fork, head = number, depth = number + 1 - fork, "rollup reorg detected");
+                metrics::record_reorg();
+                chain.truncate(fork);
+                self.chain_watch.record_fork(fork);
+            }
+            chain.insert(number, hash);
+            self.chain_watch.set_head(Head { number, timestamp });
+        }
+        Ok(())
+    }
+
+    /// Walks back the new chain from `parent_hash` until reaching a tracked block, tracking the
+    /// new chain's blocks on the way, and returns the first replaced height. Returns 0, so every
+    /// height counts as replaced, if no tracked ancestor is found.
+    async fn find_fork(&self, chain: &mut TrackedChain, mut parent_hash: B256) -> u64 {
+        let mut ancestors = Vec::new();
+        let mut fork = 0;
+        for _ in 0..TRACKED_BLOCKS {
+            let block = match self.provider.get_block_by_hash(parent_hash).await {
+                Ok(Some(block)) => block,
+                Ok(None) => break,
+                Err(error) => {
+                    warn!(%error, "failed to fetch block while locating reorg fork point");
+                    break;
+                }
+            };
+            let number = block.header.inner.number;
+            if chain.is_tracked(number, block.header.hash) {
+                fork = number + 1;
+                break;
+            }
+            ancestors.push((number, block.header.hash));
+            parent_hash = block.header.inner.parent_hash;
+        }
+        chain.truncate(fork);
+        for (number, hash) in ancestors.into_iter().rev() {
+            chain.insert(number, hash);
+        }
+        fork
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_replaced_and_diverged_blocks() {
+        let hash = |byte: u8| B256::repeat_byte(byte);
+        let mut chain = TrackedChain::default();
+        assert_eq!(chain.observe(10, hash(10), hash(9)), Observation::Extends);
+        chain.insert(10, hash(10));
+        assert_eq!(chain.observe(11, hash(11), hash(10)), Observation::Extends);
+        chain.insert(11, hash(11));
+        assert_eq!(chain.observe(11, hash(11), hash(10)), Observation::Known);
+
+        // A sibling of block 11 replaces it.
+        assert_eq!(chain.observe(11, hash(0xb1), hash(10)), Observation::Replaces { fork: 11 });
+        // A block 12 not built on the tracked block 11 forks further back.
+        assert_eq!(chain.observe(12, hash(0xb2), hash(0xb1)), Observation::Diverged);
+
+        chain.truncate(11);
+        assert!(chain.is_tracked(10, hash(10)));
+        assert!(!chain.is_tracked(11, hash(11)));
+
+        for number in 12..12 + TRACKED_BLOCKS {
+            chain.insert(number, hash(1));
+        }
+        assert!(!chain.is_tracked(10, hash(10)));
+        assert_eq!(chain.blocks.len() as u64, TRACKED_BLOCKS);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{FixedPricingClient, FixedPricingError, OfflineConfig};
+use alloy::primitives::B256;
+use core::fmt::{self, Display, Formatter};
+use eyre::{Result, WrapErr};
+use serde::Deserialize;
+use signet_types::SignedOrder;
+use std::collections::{BTreeMap, HashMap};
+
+/// One USD in normalized 18-decimal USD.
+const USD: f64 = 1e18;
+
+/// A line of an archived journal. Only the fields needed to replay pricing are read; lines of
+/// other kinds, and other decisions, are ignored.
+#[derive(Debug, Deserialize)]
+struct JournalLine {
+    kind: String,
+    order: Option<SignedOrder>,
+    order_hash: Option<B256>,
+    decision: Option<String>,
+    reason: Option<String>,
+    gross_margin: Option<i128>,
+}
+
+/// How an order was priced, in the journal or on replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    /// Priced within the maximum loss, with its margin before gas in normalized 18-decimal USD.
+    Priced(i128),
+    ExceedsMaxLoss,
+    /// Couldn't be priced, for the given reason.
+    Unpriced(String),
+}
+
+impl Outcome {
+    /// The pricing outcome a journal decision records, if it records one.
+    fn recorded(line: &JournalLine) -> Option<Self> {
+        match (line.decision.as_deref()?, line.reason.as_deref()) {
+            ("priced", _) => line.gross_margin.map(Self::Priced),
+            ("pricing_failed", _) => Some(Self::Unpriced("pricing failed".to_string())),
+            ("skipped", Some("exceeds-max-loss")) => Some(Self::ExceedsMaxLoss),
+            (
+                "skipped",
+                Some(reason @ ("unknown-token" | "price-disagreement" | "stale-price")),
+            ) => Some(Self::Unpriced(reason.to_string())),
+            _ => None,
+        }
+    }
+
+    /// The outcome of pricing `order` with `pricing_client`.
+    fn replayed(order: &SignedOrder, pricing_client: &FixedPricingClient) -> Self {
+        match pricing_client.profitability(order) {
+            Ok(Some(margin)) => Self::Priced(margin),
+            Ok(None) => Self::ExceedsMaxLoss,
+            Err(FixedPricingError::UnknownToken(_)) => Self::Unpriced("unknown-token".to_string()),
+            Err(error) => Self::Unpriced(error.to_string()),
+        }
+    }
+
+    /// Whether the two outcomes are different decisions. Failing to price an order counts as the
+    /// same decision whatever the reason.
+    fn differs_from(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Priced(margin), Self::Priced(other_margin)) => margin != other_margin,
+            (Self::ExceedsMaxLoss, Self::ExceedsMaxLoss)
+            | (Self::Unpriced(_), Self::Unpriced(_)) => false,
+            _ => true,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Priced(margin) => write!(formatter, "priced at ${:.2}", *margin as f64 / USD),
+            Self::ExceedsMaxLoss => write!(formatter, "exceeds max loss"),
+            Self::Unpriced(reason) => write!(formatter, "unpriced ({reason})"),
+        }
+    }
+}
+
+/// An order whose replayed pricing differs from the journal's.
+#[derive(Debug)]
+struct Change {
+    order_hash: B256,
+    recorded: Outcome,
+    replayed: Outcome,
+}
+
+/// How replaying a journal's orders through a pricing configuration compares to the decisions it
+/// recorded.
+#[derive(Debug, Default)]
+struct ReplayReport {
+    replayed: usize,
+    /// Orders in the journal with no pricing decision, such as those skipped before pricing.
+    never_priced: usize,
+    /// Pricing decisions whose order isn't in the journal.
+    missing_orders: usize,
+    changes: Vec<Change>,
+}
+
+impl ReplayReport {
+    /// Replays every order in `journal` that has a recorded pricing decision, comparing the
+    /// latest decision with the result of pricing the order with `pricing_client`.
+    fn replay(journal: &str, pricing_client: &FixedPricingClient) -> Result<Self> {
+        let mut orders = HashMap::new();
+        let mut recorded = BTreeMap::new();
+        for (index, line) in journal.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line: JournalLine = serde_json::from_str(line)
+                .wrap_err_with(|| format!("invalid journal line {}", index + 1))?;
+            match (line.kind.as_str(), &line.order, line.order_hash) {
+                ("order", Some(order), _) => {
+                    orders.insert(*order.order_hash(), order.clone());
+                }
+                ("decision", _, Some(order_hash)) => {
+                    if let Some(outcome) = Outcome::recorded(&line) {
+                        recorded.insert(order_hash, outcome);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut report = Self {
+            never_priced: orders.keys().filter(|hash| !recorded.contains_key(*hash)).count(),
+            ..Self::default()
+        };
+        for (order_hash, recorded) in recorded {
+            let Some(order) = orders.get(&order_hash) else {
+                report.missing_orders += 1;
+                continue;
+            };
+            report.replayed += 1;
+            let replayed = Outcome::replayed(order, pricing_client);
+            if replayed.differs_from(&recorded) {
+                report.changes.push(Change { order_hash, recorded, replayed });
+            }
+        }
+        Ok(report)
+    }
+
+    /// How many changes changed an order's margin, priced an order that wasn't priced, and stopped
+    /// pricing one that was.
+    fn change_counts(&self) -> (usize, usize, usize) {
+        self.changes.iter().fold((0, 0, 0), |(margin, now, no_longer), change| {
+            match (&change.recorded, &change.replayed) {
+                (Outcome::Priced(_), Outcome::Priced(_)) => (margin + 1, now, no_longer),
+                (_, Outcome::Priced(_)) => (margin, now + 1, no_longer),
+                (Outcome::Priced(_), _) => (margin, now, no_longer + 1),
+                _ => (margin, now, no_longer),
+            }
+        })
+    }
+}
+
+impl Display for ReplayReport {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let (margin_changed, now_priced, no_longer_priced) = self.change_counts();
+        writeln!(formatter, "orders replayed:   {}", self.replayed)?;
+        writeln!(formatter, "never priced:      {}", self.never_priced)?;
+        if self.missing_orders > 0 {
+            writeln!(formatter, "missing orders:    {}", self.missing_orders)?;
+        }
+        writeln!(formatter, "unchanged:         {}", self.replayed - self.changes.len())?;
+        writeln!(formatter, "margin changed:    {margin_changed}")?;
+        writeln!(formatter, "now priced:        {now_priced}")?;
+        writeln!(formatter, "no longer priced:  {no_longer_priced}")?;
+        if self.changes.is_empty() {
+            return Ok(());
+        }
+        writeln!(formatter, "changes:")?;
+        for change in &self.changes {
+            writeln!(
+                formatter,
+                "  {}: {} -> {}",
+                change.order_hash, change.recorded, change.replayed
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Replay the orders in an archived decision journal (newline-delimited archive records) through
+/// the pricing configuration in `config`, and print how the pricing decisions would differ from
+/// those recorded to stdout.
+pub fn replay_journal(config: &OfflineConfig, journal: &str) -> Result<()> {
+    let pricing_client = FixedPricingClient::for_offline(config);
+    print!("{}", ReplayReport::replay(journal, &pricing_client)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use alloy::primitives::{Address, U256};
+    use serde_json::json;
+    use signet_constants::SignetSystemConstants;
+
+    const ONE: u128 = 10_u128.pow(18);
+
+    fn order(constants: &SignetSystemConstants, input_token: Address, nonce: u64) -> SignedOrder {
+        // 1 WETH ($3000) in, 2900 USDC out.
+        OrderBuilder::default()
+            .with_input(input_token, U256::from(ONE))
+            .with_output(
+                constants.host().tokens().usdc(),
+                U256::from(2_900_000_000_u64),
+                Address::repeat_byte(0x01),
+                constants.host_chain_id(),
+            )
+            .with_nonce(nonce)
+            .build()
+    }
+
+    #[test]
+    fn replay_reports_decisions_that_differ_from_the_journal() {
+        let constants = SignetSystemConstants::parmigiana();
+        let pricing_client = FixedPricingClient::new(&constants, "parmigiana", 10);
+        let weth = constants.rollup().tokens().weth();
+        let repriced = order(&constants, weth, 1);
+        let now_priced = order(&constants, weth, 2);
+        let unknown = order(&constants, Address::repeat_byte(0xee), 3);
+        let never_priced = order(&constants, weth, 4);
+        // Margins beyond `u64` can't be held in a `serde_json::Value`, so decisions are written
+        // out.
+        let decision = |order: &SignedOrder, fields: &str| {
+            format!(
+                r#"{{"kind":"decision","cycle":1,"order_hash":"{}",{fields}}}"#,
+                order.order_hash()
+            )
+        };
+        let priced = |gross_margin: u128| {
+            format!(r#""decision":"priced","gross_margin":{gross_margin},"gas_cost":0,"margin":0"#)
+        };
+        let mut lines: Vec<String> = [&repriced, &now_priced, &unknown, &never_priced]
+            .into_iter()
+            .map(|order| {
+                json!({ "kind": "order", "observed_at_ms": 0, "order": order }).to_string()
+            })
+            .collect();
+        lines.extend([
+            // Only the latest pricing decision counts.
+            decision(&repriced, &priced(100 * ONE)),
+            decision(&repriced, &priced(50 * ONE)),
+            decision(&now_priced, r#""decision":"skipped","reason":"exceeds-max-loss""#),
+            decision(&unknown, r#""decision":"skipped","reason":"unknown-token""#),
+            decision(&never_priced, r#""decision":"skipped","reason":"expired""#),
+            json!({ "kind": "intake", "cycle": 1 }).to_string(),
+        ]);
+        let journal = lines.join("\n");
+
+        let report = ReplayReport::replay(&journal, &pricing_client).unwrap();
+        assert_eq!((report.replayed, report.never_priced, report.missing_orders), (3, 1, 0));
+        assert_eq!(report.change_counts(), (1, 1, 0));
+        let change = |order: &SignedOrder| {
+            report.changes.iter().find(|change| change.order_hash == *order.order_hash()).unwrap()
+        };
+        assert_eq!(change(&repriced).recorded, Outcome::Priced((50 * ONE) as i128));
+        assert_eq!(change(&repriced).replayed, Outcome::Priced((100 * ONE) as i128));
+        assert_eq!(change(&now_priced).recorded, Outcome::ExceedsMaxLoss);
+        assert_eq!(change(&now_priced).replayed, Outcome::Priced((100 * ONE) as i128));
+    }
+}
@@ -0,0 +1,180 @@
+use crate::ReputationConfig;
+use alloy::primitives::Address;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::time::{Duration, Instant};
+
+/// Penalties below this are dropped from the table entirely.
+const FORGOTTEN_PENALTY: f64 = 0.01;
+
+/// How the filler should treat an owner's orders given their reputation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OwnerStanding {
+    /// No recent failures.
+    Good,
+    /// Recent failures: the owner's orders are considered after everyone else's.
+    Deprioritized,
+    /// Too many recent failures: the owner's orders are skipped.
+    Skipped,
+}
+
+/// An owner's current penalty, as served by the admin API.
+#[derive(Debug, Serialize)]
+pub(crate) struct OwnerPenalty {
+    owner: Address,
+    penalty: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Penalty {
+    value: f64,
+    updated_at: Instant,
+}
+
+impl Penalty {
+    /// The penalty decayed exponentially from `updated_at` to `now`.
+    fn decayed(&self, now: Instant, half_life: Duration) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.value * 0.5_f64.powf(elapsed / half_life.as_secs_f64())
+    }
+}
+
+#[derive(Debug)]
+struct ReputationInner {
+    config: ReputationConfig,
+    penalties: HashMap<Address, Penalty>,
+}
+
+/// Per-owner reputation built from historical outcomes. Each failed order adds one penalty point
+/// to its owner, and penalties decay exponentially with a configurable half-life. Owners with any
+/// outstanding penalty are deprioritized, and owners at or above the skip threshold are skipped.
+///
+/// Shared between the filler loop (which records outcomes and consults standings) and the admin
+/// API (which can list and reset scores). Reputation is held in memory and starts fresh on
+/// restart.
+#[derive(Debug, Clone)]
+pub(crate) struct OwnerReputation {
+    inner: Arc<Mutex<ReputationInner>>,
+}
+
+impl OwnerReputation {
+    pub(crate) fn new(config: ReputationConfig) -> Self {
+        Self { inner: Arc::new(Mutex::new(ReputationInner { config, penalties: HashMap::new() })) }
+    }
+
+    /// Record an order from `owner` that could not be filled (e.g. the fill bundle was submitted
+    /// but the order's nonce was never consumed, because the owner's transfer would revert or
+    /// the nonce was cancelled).
+    pub(crate) fn record_failure(&self, owner: Address) {
+        self.record_failure_at(owner, Instant::now());
+    }
+
+    fn record_failure_at(&self, owner: Address, now: Instant) {
+        let mut inner = self.inner.lock().unwrap();
+        let half_life = inner.config.half_life();
+        let penalty =
+            inner.penalties.entry(owner).or_insert(Penalty { value: 0.0, updated_at: now });
+        *penalty = Penalty { value: penalty.decayed(now, half_life) + 1.0, updated_at: now };
+    }
+
+    /// How the given owner's orders should currently be treated.
+    pub(crate) fn standing(&self, owner: Address) -> OwnerStanding {
+        self.standing_at(owner, Instant::now())
+    }
+
+    fn standing_at(&self, owner: Address, now: Instant) -> OwnerStanding {
+        let inner = self.inner.lock().unwrap();
+        let Some(penalty) = inner.penalties.get(&owner) else {
+            return OwnerStanding::Good;
+        };
+        let value = penalty.decayed(now, inner.config.half_life());
+        if value >= inner.config.skip_threshold() {
+            OwnerStanding::Skipped
+        } else if value >= FORGOTTEN_PENALTY {
+            OwnerStanding::Deprioritized
+        } else {
+            OwnerStanding::Good
+        }
+    }
+
+    /// Clear an owner's penalty, returning whether they had one.
+    pub(crate) fn reset(&self, owner: Address) -> bool {
+        self.inner.lock().unwrap().penalties.remove(&owner).is_some()
+    }
+
+    /// Current decayed penalties for every owner that still has one, highest first. Fully decayed
+    /// entries are pruned.
+    pub(crate) fn penalties(&self) -> Vec<OwnerPenalty> {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        let half_life = inner.config.half_life();
+        inner.penalties.retain(|_, penalty| penalty.decayed(now, half_life) >= FORGOTTEN_PENALTY);
+        let mut penalties: Vec<OwnerPenalty> = inner
+            .penalties
+            .iter()
+            .map(|(owner, penalty)| OwnerPenalty {
+                owner: *owner,
+                penalty: penalty.decayed(now, half_life),
+            })
+            .collect();
+        penalties.sort_by(|a, b| b.penalty.total_cmp(&a.penalty));
+        penalties
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: Address = Address::repeat_byte(0x01);
+    const HALF_LIFE: Duration = Duration::from_secs(3600);
+
+    fn reputation() -> OwnerReputation {
+        OwnerReputation::new(ReputationConfig::new(HALF_LIFE, 2.0))
+    }
+
+    #[test]
+    fn failures_deprioritize_then_skip() {
+        let reputation = reputation();
+        let now = Instant::now();
+        assert_eq!(reputation.standing_at(OWNER, now), OwnerStanding::Good);
+
+        reputation.record_failure_at(OWNER, now);
+        assert_eq!(reputation.standing_at(OWNER, now), OwnerStanding::Deprioritized);
+
+        reputation.record_failure_at(OWNER, now);
+        assert_eq!(reputation.standing_at(OWNER, now), OwnerStanding::Skipped);
+        assert_eq!(reputation.standing_at(Address::ZERO, now), OwnerStanding::Good);
+    }
+
+    #[test]
+    fn penalties_decay_over_time() {
+        let reputation = reputation();
+        let now = Instant::now();
+        reputation.record_failure_at(OWNER, now);
+        reputation.record_failure_at(OWNER, now);
+
+        // After one half-life the penalty has halved to 1.0, below the skip threshold.
+        assert_eq!(reputation.standing_at(OWNER, now + HALF_LIFE), OwnerStanding::Deprioritized);
+        // After many half-lives the owner is forgotten.
+        assert_eq!(reputation.standing_at(OWNER, now + HALF_LIFE * 10), OwnerStanding::Good);
+
+        // A new failure adds to the decayed penalty rather than the original one.
+        reputation.record_failure_at(OWNER, now + HALF_LIFE);
+        assert_eq!(reputation.standing_at(OWNER, now + HALF_LIFE), OwnerStanding::Skipped);
+    }
+
+    #[test]
+    fn reset_clears_penalty() {
+        let reputation = reputation();
+        let now = Instant::now();
+        reputation.record_failure_at(OWNER, now);
+        reputation.record_failure_at(OWNER, now);
+        assert!(reputation.reset(OWNER));
+        assert_eq!(reputation.standing_at(OWNER, now), OwnerStanding::Good);
+        assert!(!reputation.reset(OWNER));
+    }
+}
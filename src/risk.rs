@@ -0,0 +1,334 @@
+use crate::{ChainTokenPair, FixedPricingClient, FixedPricingError, PositionLimit, metrics};
+use alloy::primitives::{Address, B256, U256};
+use serde::Serialize;
+use signet_types::SignedOrder;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::time::Instant;
+
+/// The filler's position in one token, in base units.
+#[derive(Debug, Clone, Copy, Default)]
+struct Position {
+    /// The filler's balance as of the last wallet refresh.
+    inventory: U256,
+    /// Outputs the filler pays on pending fills.
+    pending_out: U256,
+    /// Inputs the filler receives on pending fills.
+    expected_in: U256,
+    /// The token's decimals, once its balance has been queried.
+    decimals: Option<u8>,
+}
+
+impl Position {
+    /// The net position, or zero if it is short.
+    const fn long(&self) -> U256 {
+        self.inventory.saturating_add(self.expected_in).saturating_sub(self.pending_out)
+    }
+
+    /// The net position in whole tokens, if the token's decimals are known.
+    fn whole_tokens(&self) -> Option<f64> {
+        let scale = 10_f64.powi(i32::from(self.decimals?));
+        Some(
+            (f64::from(self.inventory.saturating_add(self.expected_in))
+                - f64::from(self.pending_out))
+                / scale,
+        )
+    }
+}
+
+/// A position in one token, as served by the admin API. Amounts are in base units.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct TokenPosition {
+    chain_id: u64,
+    token: Address,
+    inventory: U256,
+    pending_out: U256,
+    expected_in: U256,
+    /// `inventory + expected_in - pending_out`, as a decimal string since it may be negative.
+    net: String,
+    /// The position's cap, in normalized 18-decimal USD, if one is set.
+    max_value: Option<U256>,
+}
+
+/// The token flows a pending fill will cause once it lands.
+#[derive(Debug)]
+struct PendingFlows {
+    receipts: Vec<(ChainTokenPair, U256)>,
+    payouts: Vec<(ChainTokenPair, U256)>,
+    /// When the fill's signatures expire, after which it can no longer land.
+    expires_at: Instant,
+}
+
+#[derive(Debug)]
+struct RiskInner {
+    ru_chain_id: u64,
+    limits: HashMap<ChainTokenPair, U256>,
+    positions: HashMap<ChainTokenPair, Position>,
+    pending: HashMap<B256, PendingFlows>,
+}
+
+impl RiskInner {
+    /// Applies a pending fill's flows to the positions, adding them if `add` is set and removing
+    /// them otherwise, and updates the gauges of the tokens affected.
+    fn apply(&mut self, flows: &PendingFlows, add: bool) {
+        let apply = |amount: &mut U256, delta: U256| {
+            *amount = if add { amount.saturating_add(delta) } else { amount.saturating_sub(delta) }
+        };
+        for (chain_token, amount) in &flows.receipts {
+            apply(&mut self.positions.entry(*chain_token).or_default().expected_in, *amount);
+        }
+        for (chain_token, amount) in &flows.payouts {
+            apply(&mut self.positions.entry(*chain_token).or_default().pending_out, *amount);
+        }
+        for (chain_token, _amount) in flows.receipts.iter().chain(&flows.payouts) {
+            self.record(*chain_token);
+        }
+    }
+
+    /// Forgets pending fills whose signatures have expired.
+    fn prune(&mut self, now: Instant) {
+        let expired: Vec<B256> = self
+            .pending
+            .iter()
+            .filter(|(_order_hash, flows)| flows.expires_at <= now)
+            .map(|(order_hash, _flows)| *order_hash)
+            .collect();
+        for order_hash in expired {
+            if let Some(flows) = self.pending.remove(&order_hash) {
+                self.apply(&flows, false);
+            }
+        }
+    }
+
+    fn record(&self, chain_token: ChainTokenPair) {
+        if let Some(net) = self.positions.get(&chain_token).and_then(Position::whole_tokens) {
+            metrics::record_net_position(chain_token, net);
+        }
+    }
+}
+
+/// Live net position per token: the filler's balance as of the last wallet refresh, plus the
+/// inputs it will receive on pending fills, less the outputs it will pay on them. Positions are
+/// exported as the `signet.filler.net_position` gauge and served by the admin API, and order
+/// selection skips orders that would take a position past its configured cap.
+///
+/// Shared between the filler loop (which records balances and pending fills, and checks limits)
+/// and the admin API. A fill that lands is counted as pending until its confirmation, and in the
+/// balance from the next refresh, so the position is briefly overstated in between.
+#[derive(Debug, Clone)]
+pub(crate) struct RiskEngine {
+    inner: Arc<Mutex<RiskInner>>,
+}
+
+impl RiskEngine {
+    pub(crate) fn new(ru_chain_id: u64, limits: &[PositionLimit]) -> Self {
+        let limits = limits
+            .iter()
+            .map(|limit| (ChainTokenPair::new(limit.chain_id(), limit.token()), limit.max_value()))
+            .collect();
+        Self {
+            inner: Arc::new(Mutex::new(RiskInner {
+                ru_chain_id,
+                limits,
+                positions: HashMap::new(),
+                pending: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Records the filler's balance of a token.
+    pub(crate) fn set_inventory(&self, chain_token: ChainTokenPair, balance: U256, decimals: u8) {
+        let mut inner = self.inner.lock().unwrap();
+        let position = inner.positions.entry(chain_token).or_default();
+        position.inventory = balance;
+        position.decimals = Some(decimals);
+        inner.record(chain_token);
+    }
+
+    /// Counts a submitted fill of `order` as pending until it is removed or its signatures expire
+    /// at `expires_at`.
+    pub(crate) fn add_pending(&self, order: &SignedOrder, expires_at: Instant) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.prune(Instant::now());
+        let flows = PendingFlows {
+            receipts: receipts(order, inner.ru_chain_id),
+            payouts: order
+                .outputs()
+                .iter()
+                .map(|output| {
+                    (ChainTokenPair::new(u64::from(output.chainId), output.token), output.amount)
+                })
+                .collect(),
+            expires_at,
+        };
+        inner.apply(&flows, true);
+        if let Some(replaced) = inner.pending.insert(*order.order_hash(), flows) {
+            inner.apply(&replaced, false);
+        }
+    }
+
+    /// Stops counting the order's pending fill, once it has landed or is known not to have.
+    pub(crate) fn remove_pending(&self, order_hash: &B256) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(flows) = inner.pending.remove(order_hash) {
+            inner.apply(&flows, false);
+        }
+    }
+
+    /// Every tracked position, by chain and token.
+    pub(crate) fn positions(&self) -> Vec<TokenPosition> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.prune(Instant::now());
+        let mut positions: Vec<TokenPosition> = inner
+            .positions
+            .iter()
+            .map(|(chain_token, position)| {
+                let credit = position.inventory.saturating_add(position.expected_in);
+                let net = if credit >= position.pending_out {
+                    (credit - position.pending_out).to_string()
+                } else {
+                    format!("-{}", position.pending_out - credit)
+                };
+                TokenPosition {
+                    chain_id: chain_token.chain_id(),
+                    token: chain_token.token(),
+                    inventory: position.inventory,
+                    pending_out: position.pending_out,
+                    expected_in: position.expected_in,
+                    net,
+                    max_value: inner.limits.get(chain_token).copied(),
+                }
+            })
+            .collect();
+        positions.sort_by_key(|position| (position.chain_id, position.token));
+        positions
+    }
+
+    /// Starts checking a cycle's selected orders against the position limits.
+    pub(crate) fn cycle(&self) -> CyclePositions<'_> {
+        self.inner.lock().unwrap().prune(Instant::now());
+        CyclePositions { risk: self, selected: HashMap::new() }
+    }
+}
+
+/// The inputs the filler receives on the rollup for filling `order`.
+fn receipts(order: &SignedOrder, ru_chain_id: u64) -> Vec<(ChainTokenPair, U256)> {
+    order
+        .permit()
+        .permit
+        .permitted
+        .iter()
+        .map(|permitted| (ChainTokenPair::new(ru_chain_id, permitted.token), permitted.amount))
+        .collect()
+}
+
+/// The position limits as applied to one cycle's selection: each order selected earlier in the
+/// cycle counts towards the positions of the tokens it pays the filler in.
+#[derive(Debug)]
+pub(crate) struct CyclePositions<'a> {
+    risk: &'a RiskEngine,
+    selected: HashMap<ChainTokenPair, U256>,
+}
+
+impl CyclePositions<'_> {
+    /// The first token whose position filling `order` would take past its cap, if any.
+    pub(crate) fn exceeded_limit(
+        &self,
+        order: &SignedOrder,
+        pricing_client: &FixedPricingClient,
+    ) -> Result<Option<ChainTokenPair>, FixedPricingError> {
+        let inner = self.risk.inner.lock().unwrap();
+        if inner.limits.is_empty() {
+            return Ok(None);
+        }
+        let mut after = self.selected.clone();
+        for (chain_token, amount) in receipts(order, inner.ru_chain_id) {
+            let total = after.entry(chain_token).or_default();
+            *total = total.saturating_add(amount);
+        }
+        for (chain_token, received) in after {
+            let Some(max_value) = inner.limits.get(&chain_token) else {
+                continue;
+            };
+            let position = inner.positions.get(&chain_token).copied().unwrap_or_default();
+            let value = pricing_client
+                .value(&chain_token.token(), position.long().saturating_add(received))?;
+            if value > *max_value {
+                return Ok(Some(chain_token));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Counts a selected order's inputs towards the cycle's positions.
+    pub(crate) fn accept(&mut self, order: &SignedOrder) {
+        let ru_chain_id = self.risk.inner.lock().unwrap().ru_chain_id;
+        for (chain_token, amount) in receipts(order, ru_chain_id) {
+            let total = self.selected.entry(chain_token).or_default();
+            *total = total.saturating_add(amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use signet_constants::SignetSystemConstants;
+    use tokio::time::Duration;
+
+    /// One whole 18-decimal token, or one normalized USD.
+    const ONE: u128 = 10_u128.pow(18);
+
+    /// An order paying the filler `input` rollup WETH for `output` host USDC (6 decimals).
+    fn weth_for_usdc(constants: &SignetSystemConstants, input: u128, output: u64) -> SignedOrder {
+        OrderBuilder::default()
+            .with_input(constants.rollup().tokens().weth(), U256::from(input))
+            .with_output(
+                constants.host().tokens().usdc(),
+                U256::from(output),
+                Address::ZERO,
+                constants.host_chain_id(),
+            )
+            .with_nonce(input.try_into().unwrap())
+            .build()
+    }
+
+    #[test]
+    fn pending_fills_and_selection_count_towards_limits() {
+        let constants = SignetSystemConstants::parmigiana();
+        let pricing_client = FixedPricingClient::new(&constants, "parmigiana", 10);
+        let weth = ChainTokenPair::new(constants.ru_chain_id(), constants.rollup().tokens().weth());
+        let usdc = ChainTokenPair::new(constants.host_chain_id(), constants.host().tokens().usdc());
+        // WETH is priced at $3000: cap the position at 3 WETH.
+        let limit = PositionLimit::new(weth.chain_id(), weth.token(), U256::from(9_000 * ONE));
+        let risk = RiskEngine::new(constants.ru_chain_id(), &[limit]);
+        risk.set_inventory(weth, U256::from(ONE), 18);
+        risk.set_inventory(usdc, U256::from(10_000_000_000_u64), 6);
+
+        let pending = weth_for_usdc(&constants, ONE, 3_000_000_000);
+        risk.add_pending(&pending, Instant::now() + Duration::from_secs(60));
+        let positions = risk.positions();
+        let position = |chain_token: ChainTokenPair| {
+            positions.iter().find(|position| position.token == chain_token.token()).unwrap()
+        };
+        assert_eq!(position(weth).net, (2 * ONE).to_string());
+        assert_eq!(position(usdc).net, "7000000000");
+
+        // 2 WETH held or pending, so one more fits and a second doesn't.
+        let mut cycle = risk.cycle();
+        let first = weth_for_usdc(&constants, ONE, 3_000_000_000);
+        assert_eq!(cycle.exceeded_limit(&first, &pricing_client).unwrap(), None);
+        cycle.accept(&first);
+        let second = weth_for_usdc(&constants, ONE / 2, 1_500_000_000);
+        assert_eq!(cycle.exceeded_limit(&second, &pricing_client).unwrap(), Some(weth));
+        drop(cycle);
+
+        risk.remove_pending(pending.order_hash());
+        let positions = risk.positions();
+        assert!(positions.iter().all(|position| position.expected_in.is_zero()));
+        assert_eq!(risk.cycle().exceeded_limit(&second, &pricing_client).unwrap(), None);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{ScreeningConfig, metrics};
+use alloy::primitives::Address;
+use eyre::{Result, WrapErr, eyre};
+use futures_util::future::try_join_all;
+use init4_bin_base::deps::tracing::warn;
+use itertools::Itertools;
+use lru::LruCache;
+use serde::{Deserialize, de::IgnoredAny};
+use signet_types::SignedOrder;
+use std::{
+    collections::HashSet,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+use tokio::time::{Duration, Instant};
+
+/// Timeout for a single screening API lookup.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of screening API results remembered.
+const CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(10240).unwrap();
+
+/// Header carrying the screening API key.
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// A screening API's response for one address. Any identification means the address is screened.
+#[derive(Debug, Deserialize)]
+struct ScreeningResponse {
+    identifications: Vec<IgnoredAny>,
+}
+
+#[derive(Debug)]
+struct ScreeningApi {
+    client: reqwest::Client,
+    url: reqwest::Url,
+    api_key: Option<String>,
+}
+
+#[derive(Debug)]
+struct ScreenerInner {
+    list: HashSet<Address>,
+    api: Option<ScreeningApi>,
+    /// Screening API results by address, with when each was looked up.
+    results: Mutex<LruCache<Address, (bool, Instant)>>,
+    cache_ttl: Duration,
+}
+
+/// Screens the parties to an order, its owner and every output recipient, against the
+/// configured address list and screening API, so orders involving a screened address are never
+/// filled. API results are reused for the configured TTL, and an order whose parties can't all be
+/// looked up is treated as unscreened rather than filled.
+#[derive(Debug, Clone)]
+pub(crate) struct Screener {
+    inner: Arc<ScreenerInner>,
+}
+
+impl Screener {
+    pub(crate) fn new(config: &ScreeningConfig) -> Result<Self> {
+        let api = config
+            .api_url()
+            .map(|url| {
+                let client = reqwest::Client::builder()
+                    .timeout(REQUEST_TIMEOUT)
+                    .build()
+                    .wrap_err("failed to build screening API client")?;
+                Ok::<_, eyre::Report>(ScreeningApi {
+                    client,
+                    url: url.clone(),
+                    api_key: config.api_key().map(str::to_owned),
+                })
+            })
+            .transpose()?;
+        let inner = ScreenerInner {
+            list: config.list().clone(),
+            api,
+            results: Mutex::new(LruCache::new(CACHE_SIZE)),
+            cache_ttl: config.cache_ttl(),
+        };
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// The first of the order's parties found to be screened, if any. Errors if a party couldn't
+    /// be looked up.
+    pub(crate) async fn screened_party(&self, order: &SignedOrder) -> Result<Option<Address>> {
+        let parties = parties(order);
+        if let Some(party) = parties.iter().find(|party| self.inner.list.contains(*party)) {
+            return Ok(Some(*party));
+        }
+        if self.inner.api.is_none() {
+            return Ok(None);
+        }
+        let screened = try_join_all(parties.iter().map(|party| self.is_screened(*party))).await?;
+        Ok(parties
+            .into_iter()
+            .zip(screened)
+            .find_map(|(party, screened)| screened.then_some(party)))
+    }
+
+    /// Whether the screening API identifies the address, from the cache while the last result is
+    /// fresh.
+    async fn is_screened(&self, address: Address) -> Result<bool> {
+        let now = Instant::now();
+        let cached = self.inner.results.lock().unwrap().get(&address).copied().filter(
+            |(_screened, looked_up_at)| {
+                now.saturating_duration_since(*looked_up_at) < self.inner.cache_ttl
+            },
+        );
+        if let Some((screened, _looked_up_at)) = cached {
+            return Ok(screened);
+        }
+        let screened = self.look_up(address).await.inspect_err(|error| {
+            warn!(%address, error = format!("{error:#}"), "screening lookup failed");
+            metrics::record_screening_error();
+        })?;
+        self.inner.results.lock().unwrap().put(address, (screened, now));
+        Ok(screened)
+    }
+
+    async fn look_up(&self, address: Address) -> Result<bool> {
+        let api = self.inner.api.as_ref().ok_or_else(|| eyre!("screening API not configured"))?;
+        let mut url = api.url.clone();
+        url.path_segments_mut()
+            .map_err(|()| eyre!("screening API URL can't take a path"))?
+            .pop_if_empty()
+            .push(&address.to_string());
+        let mut request = api.client.get(url);
+        if let Some(api_key) = &api.api_key {
+            request = request.header(API_KEY_HEADER, api_key);
+        }
+        let response: ScreeningResponse = request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .wrap_err("screening API request failed")?
+            .json()
+            .await
+            .wrap_err("failed to parse screening API response")?;
+        Ok(!response.identifications.is_empty())
+    }
+}
+
+/// The order's owner followed by its distinct output recipients.
+fn parties(order: &SignedOrder) -> Vec<Address> {
+    std::iter::once(order.permit().owner)
+        .chain(order.outputs().iter().map(|output| output.recipient))
+        .unique()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use alloy::primitives::U256;
+
+    fn build_order(owner: Address, recipients: &[Address]) -> SignedOrder {
+        let builder =
+            OrderBuilder::default().with_owner(owner).with_input(Address::ZERO, U256::from(1));
+        recipients
+            .iter()
+            .fold(builder, |builder, recipient| {
+                builder.with_output(Address::ZERO, U256::from(1), *recipient, 1)
+            })
+            .build()
+    }
+
+    #[tokio::test]
+    async fn screens_owner_and_recipients_against_list() {
+        let address = Address::repeat_byte;
+        let config = ScreeningConfig::new([address(9)].into(), Duration::from_secs(60));
+        let screener = Screener::new(&config).unwrap();
+        let screened = |order: SignedOrder| {
+            let screener = screener.clone();
+            async move { screener.screened_party(&order).await.unwrap() }
+        };
+
+        assert_eq!(
+            parties(&build_order(address(1), &[address(2), address(1)])),
+            vec![address(1), address(2)]
+        );
+        assert_eq!(screened(build_order(address(1), &[address(2), address(3)])).await, None);
+        assert_eq!(screened(build_order(address(9), &[address(2)])).await, Some(address(9)));
+        assert_eq!(
+            screened(build_order(address(1), &[address(2), address(9)])).await,
+            Some(address(9))
+        );
+    }
+}
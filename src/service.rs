@@ -1,16 +1,27 @@
+use crate::{FillerContext, metrics, probes};
 use axum::{
     Router,
-    http::StatusCode,
+    extract::State,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
     routing::get,
 };
 use eyre::{Report, Result, WrapErr, bail};
-use init4_bin_base::deps::tracing::debug;
+use init4_bin_base::deps::tracing::{debug, warn};
+use metrics_exporter_prometheus::PrometheusHandle;
 use std::net::SocketAddr;
-use tokio::{net::TcpListener, task::JoinHandle};
+use tokio::{
+    net::TcpListener,
+    task::JoinHandle,
+    time::{Duration, interval},
+};
 use tokio_util::sync::CancellationToken;
 
-async fn return_404() -> Response {
+/// How often the Prometheus exporter's histograms are drained, matching the exporter's own
+/// listener.
+const METRICS_UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+pub(crate) async fn return_404() -> Response {
     (StatusCode::NOT_FOUND, "not found").into_response()
 }
 
@@ -18,40 +29,108 @@ async fn return_200() -> Response {
     (StatusCode::OK, "ok").into_response()
 }
 
-/// Serve a `/healthcheck` endpoint on the given port until cancelled or failure.
+/// Serve the `/healthcheck` liveness endpoint and the `/healthcheck/deps` dependency probe
+/// endpoint on the configured healthcheck address until cancelled or failure.
 ///
 /// Returns `Ok(())` on graceful cancellation or an error if the server exits
 /// unexpectedly.
-pub async fn serve_healthcheck(port: u16, cancellation_token: CancellationToken) -> Result<()> {
-    let handle = do_serve_healthcheck(port, cancellation_token.clone());
+pub async fn serve_healthcheck(context: &FillerContext) -> Result<()> {
+    let router = Router::new()
+        .route("/healthcheck", get(return_200))
+        .route("/healthcheck/deps", get(probes::dependencies))
+        .fallback(return_404)
+        .with_state(probes::DependencyProbes::new(context));
+    serve_router(
+        "healthcheck",
+        router,
+        context.healthcheck_address(),
+        context.cancellation_token().clone(),
+    )
+    .await
+}
+
+/// Serve the Prometheus exporter's metrics on `address` for the life of the process, with the
+/// exemplar histograms in OpenMetrics format at `/metrics/exemplars`. Every other path serves the
+/// exporter's metrics, as its own listener did. The address is bound before returning, so a bad
+/// address fails startup.
+pub(crate) fn spawn_metrics_server(handle: PrometheusHandle, address: SocketAddr) -> Result<()> {
+    let listener = std::net::TcpListener::bind(address)
+        .and_then(|listener| {
+            listener.set_nonblocking(true)?;
+            TcpListener::from_std(listener)
+        })
+        .wrap_err_with(|| format!("failed to bind metrics server to {address}"))?;
+    let router = Router::new()
+        .route("/metrics/exemplars", get(exemplar_metrics))
+        .fallback(prometheus_metrics)
+        .with_state(handle.clone());
+    tokio::spawn(async move {
+        if let Err(error) = axum::serve(listener, router).await {
+            warn!(%error, "metrics server exited");
+        }
+    });
+    tokio::spawn(async move {
+        let mut upkeep = interval(METRICS_UPKEEP_INTERVAL);
+        loop {
+            upkeep.tick().await;
+            handle.run_upkeep();
+        }
+    });
+    Ok(())
+}
+
+async fn prometheus_metrics(State(handle): State<PrometheusHandle>) -> String {
+    handle.render()
+}
+
+async fn exemplar_metrics() -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/openmetrics-text; version=1.0.0; charset=utf-8")],
+        metrics::render_exemplar_histograms(),
+    )
+        .into_response()
+}
+
+/// Serve `router` on `address` until cancelled or failure, supervising the spawned server task.
+/// `name` is used in error messages and logs to distinguish the different HTTP surfaces.
+pub(crate) async fn serve_router(
+    name: &'static str,
+    router: Router,
+    address: SocketAddr,
+    cancellation_token: CancellationToken,
+) -> Result<()> {
+    let handle = do_serve(name, router, address, cancellation_token.clone());
     let result = handle.await;
     if cancellation_token.is_cancelled() {
         return Ok(());
     }
     cancellation_token.cancel();
     match result {
-        Ok(Ok(())) => bail!("healthcheck server exited without cancellation"),
+        Ok(Ok(())) => bail!("{name} server exited without cancellation"),
         Ok(error) => error,
         Err(error) if error.is_panic() => {
-            Err(Report::new(error).wrap_err("panic in healthcheck server"))
+            Err(Report::new(error).wrap_err(format!("panic in {name} server")))
         }
-        Err(_) => bail!("healthcheck server task cancelled unexpectedly"),
+        Err(_) => bail!("{name} server task cancelled unexpectedly"),
     }
 }
 
-fn do_serve_healthcheck(port: u16, cancel_token: CancellationToken) -> JoinHandle<Result<()>> {
-    let router = Router::new().route("/healthcheck", get(return_200)).fallback(return_404);
-    let socket_address = SocketAddr::from(([0, 0, 0, 0], port));
+fn do_serve(
+    name: &'static str,
+    router: Router,
+    address: SocketAddr,
+    cancel_token: CancellationToken,
+) -> JoinHandle<Result<()>> {
     tokio::spawn(async move {
-        let listener = TcpListener::bind(socket_address)
+        let listener = TcpListener::bind(address)
             .await
-            .wrap_err_with(|| format!("failed to bind to healthcheck address on port {port}"))?;
+            .wrap_err_with(|| format!("failed to bind {name} server to {address}"))?;
         axum::serve(listener, router)
             .with_graceful_shutdown(async move {
                 cancel_token.cancelled().await;
-                debug!("healthcheck service cancelled");
+                debug!("{name} service cancelled");
             })
             .await
-            .wrap_err("failed serving healthcheck")
+            .wrap_err_with(|| format!("failed serving {name}"))
     })
 }
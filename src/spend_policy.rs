@@ -0,0 +1,329 @@
+use crate::SpendPolicyConfig;
+use alloy::primitives::{Address, B256, U256};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+use tokio::time::{Duration, Instant};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The hot-key limit that an order would exceed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SpendLimit {
+    PerFill,
+    Daily,
+}
+
+impl SpendLimit {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            SpendLimit::PerFill => "per-fill",
+            SpendLimit::Daily => "daily",
+        }
+    }
+}
+
+/// Whether an order may be filled under the spend policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SpendDecision {
+    /// Within the hot-key limits.
+    Allowed,
+    /// Over a limit, but approved by an operator.
+    Approved,
+    /// Over a limit and queued for operator approval.
+    AwaitingApproval(SpendLimit),
+    /// Rejected by an operator.
+    Rejected,
+}
+
+/// An order waiting for operator approval, as served by the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PendingFill {
+    order_hash: B256,
+    owner: Address,
+    /// Normalized 18-decimal USD.
+    notional: U256,
+    limit: SpendLimit,
+    queued_seconds_ago: u64,
+    #[serde(skip)]
+    queued_at: Instant,
+}
+
+#[derive(Debug)]
+struct SpendPolicyInner {
+    config: SpendPolicyConfig,
+    /// Fills made without approval within the last day as `(committed_at, order_hash, notional)`,
+    /// oldest first.
+    spends: VecDeque<(Instant, B256, U256)>,
+    /// Committed orders by order hash, with when they were committed and, if they used up an
+    /// approval, when it was made, so a fill that isn't submitted can be released.
+    committed: HashMap<B256, (Instant, Option<Instant>)>,
+    pending: HashMap<B256, PendingFill>,
+    /// Operator decisions by order hash, with when they were made.
+    approved: HashMap<B256, Instant>,
+    rejected: HashMap<B256, Instant>,
+}
+
+impl SpendPolicyInner {
+    /// Drops spends older than a day, and queue entries and decisions older than a day (by which
+    /// time the order has long expired).
+    fn prune(&mut self, now: Instant) {
+        while self.spends.front().is_some_and(|(at, _, _)| now.duration_since(*at) >= DAY) {
+            self.spends.pop_front();
+        }
+        self.committed.retain(|_, (at, _approved_at)| now.duration_since(*at) < DAY);
+        self.pending.retain(|_, pending| now.duration_since(pending.queued_at) < DAY);
+        self.approved.retain(|_, at| now.duration_since(*at) < DAY);
+        self.rejected.retain(|_, at| now.duration_since(*at) < DAY);
+    }
+
+    fn daily_total(&self) -> U256 {
+        self.spends
+            .iter()
+            .fold(U256::ZERO, |total, (_, _, notional)| total.saturating_add(*notional))
+    }
+}
+
+/// Hot-key spend policy: fills within the per-fill and rolling daily notional limits are made
+/// automatically, and anything larger is queued until an operator approves or rejects it through
+/// the admin API. Shared between the filler loop and the admin API.
+///
+/// Approved fills don't count towards the daily limit, which only tracks what the filler spent on
+/// its own authority. An approval covers the order's next submitted fill only.
+#[derive(Debug, Clone)]
+pub(crate) struct SpendPolicy {
+    inner: Arc<Mutex<SpendPolicyInner>>,
+}
+
+impl SpendPolicy {
+    pub(crate) fn new(config: SpendPolicyConfig) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SpendPolicyInner {
+                config,
+                spends: VecDeque::new(),
+                committed: HashMap::new(),
+                pending: HashMap::new(),
+                approved: HashMap::new(),
+                rejected: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Decide whether an order with the given output value (normalized 18-decimal USD) may be
+    /// filled, queueing it for approval if it exceeds a limit. Nothing is counted until
+    /// [`Self::commit`] is called.
+    pub(crate) fn evaluate(
+        &self,
+        order_hash: B256,
+        owner: Address,
+        notional: U256,
+    ) -> SpendDecision {
+        self.evaluate_at(Instant::now(), order_hash, owner, notional)
+    }
+
+    fn evaluate_at(
+        &self,
+        now: Instant,
+        order_hash: B256,
+        owner: Address,
+        notional: U256,
+    ) -> SpendDecision {
+        let mut inner = self.inner.lock().unwrap();
+        inner.prune(now);
+        if inner.rejected.contains_key(&order_hash) {
+            return SpendDecision::Rejected;
+        }
+        if inner.approved.contains_key(&order_hash) {
+            return SpendDecision::Approved;
+        }
+        let limit = if inner.config.max_fill_notional().is_some_and(|max| notional > max) {
+            SpendLimit::PerFill
+        } else if inner
+            .config
+            .max_daily_notional()
+            .is_some_and(|max| inner.daily_total().saturating_add(notional) > max)
+        {
+            SpendLimit::Daily
+        } else {
+            return SpendDecision::Allowed;
+        };
+        inner.pending.entry(order_hash).or_insert(PendingFill {
+            order_hash,
+            owner,
+            notional,
+            limit,
+            queued_seconds_ago: 0,
+            queued_at: now,
+        });
+        SpendDecision::AwaitingApproval(limit)
+    }
+
+    /// Record that an order allowed by [`Self::evaluate`] was selected for filling: approved
+    /// orders use up their approval, and others count towards the daily limit. Undone by
+    /// [`Self::release`] if the fill isn't submitted after all.
+    pub(crate) fn commit(&self, order_hash: B256, notional: U256) {
+        self.commit_at(Instant::now(), order_hash, notional);
+    }
+
+    fn commit_at(&self, now: Instant, order_hash: B256, notional: U256) {
+        let mut inner = self.inner.lock().unwrap();
+        let approved_at = inner.approved.remove(&order_hash);
+        if approved_at.is_none() {
+            inner.spends.push_back((now, order_hash, notional));
+        }
+        inner.committed.insert(order_hash, (now, approved_at));
+    }
+
+    /// Undo the last [`Self::commit`] of an order whose fill was dropped or failed to submit:
+    /// restores its approval, or stops counting it towards the daily limit.
+    pub(crate) fn release(&self, order_hash: B256) {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.committed.remove(&order_hash) {
+            Some((_committed_at, Some(approved_at))) => {
+                inner.approved.insert(order_hash, approved_at);
+            }
+            Some((_committed_at, None)) => {
+                if let Some(index) =
+                    inner.spends.iter().rposition(|(_, hash, _)| *hash == order_hash)
+                {
+                    inner.spends.remove(index);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Approve a queued order, returning whether it was queued.
+    pub(crate) fn approve(&self, order_hash: B256) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let queued = inner.pending.remove(&order_hash).is_some();
+        if queued {
+            inner.approved.insert(order_hash, Instant::now());
+        }
+        queued
+    }
+
+    /// Reject a queued order so it is skipped rather than re-queued, returning whether it was
+    /// queued.
+    pub(crate) fn reject(&self, order_hash: B256) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let queued = inner.pending.remove(&order_hash).is_some();
+        if queued {
+            inner.rejected.insert(order_hash, Instant::now());
+        }
+        queued
+    }
+
+    /// Orders awaiting approval, oldest first.
+    pub(crate) fn pending(&self) -> Vec<PendingFill> {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        inner.prune(now);
+        let mut pending: Vec<PendingFill> = inner
+            .pending
+            .values()
+            .map(|pending| PendingFill {
+                queued_seconds_ago: now.duration_since(pending.queued_at).as_secs(),
+                ..pending.clone()
+            })
+            .collect();
+        pending.sort_by_key(|pending| pending.queued_at);
+        pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: Address = Address::repeat_byte(0x01);
+
+    fn usd(amount: u64) -> U256 {
+        U256::from(amount) * U256::from(10_u64).pow(U256::from(18))
+    }
+
+    fn hash(byte: u8) -> B256 {
+        B256::repeat_byte(byte)
+    }
+
+    fn policy() -> SpendPolicy {
+        SpendPolicy::new(SpendPolicyConfig::new(Some(usd(100)), Some(usd(150))))
+    }
+
+    #[test]
+    fn large_fill_is_queued_until_approved() {
+        let policy = policy();
+        let now = Instant::now();
+        assert_eq!(
+            policy.evaluate_at(now, hash(1), OWNER, usd(101)),
+            SpendDecision::AwaitingApproval(SpendLimit::PerFill)
+        );
+        // Re-evaluating doesn't duplicate the queue entry.
+        policy.evaluate_at(now, hash(1), OWNER, usd(101));
+        assert_eq!(policy.pending().len(), 1);
+
+        assert!(policy.approve(hash(1)));
+        assert!(policy.pending().is_empty());
+        assert_eq!(policy.evaluate_at(now, hash(1), OWNER, usd(101)), SpendDecision::Approved);
+
+        // The approval is used up on commit and doesn't count towards the daily limit.
+        policy.commit_at(now, hash(1), usd(101));
+        assert_eq!(policy.evaluate_at(now, hash(2), OWNER, usd(100)), SpendDecision::Allowed);
+        assert_eq!(
+            policy.evaluate_at(now, hash(1), OWNER, usd(101)),
+            SpendDecision::AwaitingApproval(SpendLimit::PerFill)
+        );
+    }
+
+    #[test]
+    fn daily_limit_rolls_over() {
+        let policy = policy();
+        let now = Instant::now();
+        assert_eq!(policy.evaluate_at(now, hash(1), OWNER, usd(100)), SpendDecision::Allowed);
+        policy.commit_at(now, hash(1), usd(100));
+        assert_eq!(
+            policy.evaluate_at(now, hash(2), OWNER, usd(51)),
+            SpendDecision::AwaitingApproval(SpendLimit::Daily)
+        );
+        assert_eq!(policy.evaluate_at(now, hash(3), OWNER, usd(50)), SpendDecision::Allowed);
+        assert_eq!(policy.evaluate_at(now + DAY, hash(4), OWNER, usd(100)), SpendDecision::Allowed);
+    }
+
+    #[test]
+    fn released_fills_keep_their_approval_and_daily_allowance() {
+        let policy = policy();
+        let now = Instant::now();
+        policy.evaluate_at(now, hash(1), OWNER, usd(101));
+        policy.approve(hash(1));
+        assert_eq!(policy.evaluate_at(now, hash(1), OWNER, usd(101)), SpendDecision::Approved);
+        policy.commit_at(now, hash(1), usd(101));
+        assert_eq!(policy.evaluate_at(now, hash(2), OWNER, usd(100)), SpendDecision::Allowed);
+        policy.commit_at(now, hash(2), usd(100));
+
+        // Submission failed: the approval survives for the next selection, and the unapproved
+        // fill no longer counts towards the daily limit.
+        policy.release(hash(1));
+        policy.release(hash(2));
+        assert_eq!(policy.evaluate_at(now, hash(1), OWNER, usd(101)), SpendDecision::Approved);
+        assert_eq!(policy.evaluate_at(now, hash(3), OWNER, usd(100)), SpendDecision::Allowed);
+        // Releasing again, or an order never committed, changes nothing.
+        policy.release(hash(2));
+        policy.release(hash(4));
+        assert_eq!(policy.evaluate_at(now, hash(1), OWNER, usd(101)), SpendDecision::Approved);
+    }
+
+    #[test]
+    fn rejected_fill_is_skipped() {
+        let policy = policy();
+        let now = Instant::now();
+        policy.evaluate_at(now, hash(1), OWNER, usd(500));
+        assert!(policy.reject(hash(1)));
+        assert!(!policy.reject(hash(1)));
+        assert!(!policy.approve(hash(1)));
+        assert_eq!(policy.evaluate_at(now, hash(1), OWNER, usd(500)), SpendDecision::Rejected);
+        assert!(policy.pending().is_empty());
+    }
+}
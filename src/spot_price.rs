@@ -0,0 +1,478 @@
+use crate::{
+    DegradationLadder, Dependency, FillerContext, KnownToken, PriceApi, PriceApiConfig,
+    StalePricePolicy, metrics,
+};
+use alloy::primitives::Address;
+use eyre::{Context, Result, bail};
+use init4_bin_base::deps::tracing::{debug, info, instrument, trace, warn};
+use serde::Deserialize;
+use signet_constants::SignetSystemConstants;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tokio::{
+    select,
+    time::{Duration, Instant},
+};
+use tokio_util::sync::CancellationToken;
+
+const COINGECKO_URL: &str = "https://api.coingecko.com/api/v3/simple/price";
+const COINMARKETCAP_URL: &str = "https://pro-api.coinmarketcap.com/v1/cryptocurrency/quotes/latest";
+
+/// Timeout for a single price API request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Prices older than this many refresh intervals are ignored, so a couple of failed requests
+/// don't immediately fall back to the fixed exchange rates.
+const MAX_AGE_INTERVALS: u32 = 3;
+
+/// An asset whose spot price is fetched from the price API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SpotAsset {
+    Eth,
+    Btc,
+    Usdc,
+    Usdt,
+}
+
+impl SpotAsset {
+    const ALL: [Self; 4] = [Self::Eth, Self::Btc, Self::Usdc, Self::Usdt];
+
+    /// CoinGecko's coin ID.
+    const fn coingecko_id(&self) -> &'static str {
+        match self {
+            Self::Eth => "ethereum",
+            Self::Btc => "bitcoin",
+            Self::Usdc => "usd-coin",
+            Self::Usdt => "tether",
+        }
+    }
+
+    /// The ticker symbol, as used by CoinMarketCap.
+    const fn symbol(&self) -> &'static str {
+        match self {
+            Self::Eth => "ETH",
+            Self::Btc => "BTC",
+            Self::Usdc => "USDC",
+            Self::Usdt => "USDT",
+        }
+    }
+
+    /// The asset a known token tracks. Native host ETH and the rollup USD token are excluded: they
+    /// share the native token address, which the pricing client prices as rollup USD.
+    const fn of(token: KnownToken) -> Option<Self> {
+        match token {
+            KnownToken::HostWeth | KnownToken::RollupWeth => Some(Self::Eth),
+            KnownToken::HostWbtc | KnownToken::RollupWbtc => Some(Self::Btc),
+            KnownToken::HostUsdc => Some(Self::Usdc),
+            KnownToken::HostUsdt => Some(Self::Usdt),
+            KnownToken::HostEth | KnownToken::RollupUsd => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SpotPrice {
+    usd: f64,
+    updated_at: Instant,
+}
+
+/// Latest spot USD prices by token address, shared between the background refresh task and the
+/// pricing client.
+#[derive(Debug, Clone)]
+pub(crate) struct SpotPriceCache {
+    inner: Arc<RwLock<HashMap<Address, SpotPrice>>>,
+    max_age: Duration,
+    stale_price_policy: StalePricePolicy,
+}
+
+impl SpotPriceCache {
+    /// Create an empty cache whose prices expire after `max_age`, after which tokens fall back to
+    /// their fixed prices.
+    pub(crate) fn new(max_age: Duration) -> Self {
+        Self { inner: Default::default(), max_age, stale_price_policy: StalePricePolicy::Fixed }
+    }
+
+    /// Create a cache for prices refreshed at the configured interval.
+    pub(crate) fn for_config(config: &PriceApiConfig) -> Self {
+        Self::new(config.refresh_interval() * MAX_AGE_INTERVALS)
+            .with_stale_price_policy(config.stale_price_policy())
+    }
+
+    /// Set how tokens are priced once their price goes stale.
+    pub(crate) const fn with_stale_price_policy(mut self, policy: StalePricePolicy) -> Self {
+        self.stale_price_policy = policy;
+        self
+    }
+
+    /// Whether `token` must not be priced: it had a price that has gone stale, and the policy is
+    /// to reject stale tokens rather than fall back to their fixed prices.
+    pub(crate) fn rejects(&self, token: &Address) -> bool {
+        self.stale_price_policy == StalePricePolicy::Reject
+            && self
+                .inner
+                .read()
+                .unwrap()
+                .get(token)
+                .is_some_and(|price| price.updated_at.elapsed() > self.max_age)
+    }
+
+    /// The number of tokens whose price has gone stale.
+    fn stale_count(&self) -> usize {
+        self.inner
+            .read()
+            .unwrap()
+            .values()
+            .filter(|price| price.updated_at.elapsed() > self.max_age)
+            .count()
+    }
+
+    /// Whether no price has been fetched yet.
+    fn is_empty(&self) -> bool {
+        self.inner.read().unwrap().is_empty()
+    }
+
+    /// The token's USD price per whole token, unless it is missing or stale.
+    pub(crate) fn get(&self, token: &Address) -> Option<f64> {
+        self.inner
+            .read()
+            .unwrap()
+            .get(token)
+            .filter(|price| price.updated_at.elapsed() <= self.max_age)
+            .map(|price| price.usd)
+    }
+
+    /// Set the token's USD price per whole token.
+    pub(crate) fn insert(&self, token: Address, usd: f64) {
+        self.inner.write().unwrap().insert(token, SpotPrice { usd, updated_at: Instant::now() });
+    }
+}
+
+#[derive(Debug)]
+struct SpotPriceState {
+    config: PriceApiConfig,
+    client: reqwest::Client,
+    tokens: HashMap<SpotAsset, Vec<Address>>,
+    cache: SpotPriceCache,
+    degradation: DegradationLadder,
+}
+
+/// Prices from a single price API response.
+enum FetchedPrices {
+    /// Prices of tracked assets, from CoinGecko or CoinMarketCap.
+    Assets(HashMap<SpotAsset, f64>),
+    /// Prices by token address, from a custom endpoint.
+    Tokens(HashMap<Address, f64>),
+}
+
+/// Background task that periodically fetches spot prices and publishes them to the
+/// [`SpotPriceCache`]: ETH, BTC, USDC, and USDT prices from a public price API (CoinGecko or
+/// CoinMarketCap) for every known token tracking those assets, or prices by token address from a
+/// custom endpoint.
+#[derive(Debug)]
+pub struct SpotPriceRefreshTask {
+    state: Option<SpotPriceState>,
+    cancellation_token: CancellationToken,
+}
+
+impl SpotPriceRefreshTask {
+    /// Create the refresh task and fetch prices once so they are available for the first cycle.
+    /// The task does nothing if no price API is configured.
+    #[instrument(skip_all, name = "initialize_spot_price_refresh_task")]
+    pub async fn initialize(context: &FillerContext) -> Self {
+        let cancellation_token = context.cancellation_token().clone();
+        let (Some(config), Some(cache)) = (context.price_api_config(), context.spot_prices())
+        else {
+            return Self { state: None, cancellation_token };
+        };
+        let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(error) => {
+                warn!(%error, "failed to build price API client, using fixed prices only");
+                return Self { state: None, cancellation_token };
+            }
+        };
+        let state = SpotPriceState {
+            config: config.clone(),
+            client,
+            tokens: spot_tokens(context.constants().system()),
+            cache: cache.clone(),
+            degradation: context.degradation().clone(),
+        };
+        info!(api = config.api().as_str(), "spot pricing enabled");
+        select! {
+            biased;
+            _ = cancellation_token.cancelled() => {
+                debug!("spot price refresh task initialization cancelled");
+            }
+            _ = state.refresh() => {}
+        }
+        Self { state: Some(state), cancellation_token }
+    }
+
+    /// Run the periodic refresh loop.
+    pub async fn run(self) {
+        let Some(state) = self.state else {
+            debug!("spot pricing disabled");
+            return;
+        };
+        let mut interval = tokio::time::interval(state.config.refresh_interval());
+        // Consume the immediate first tick; `initialize` already refreshed.
+        interval.tick().await;
+
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("spot price refresh task cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    state.refresh().await;
+                }
+            }
+        }
+    }
+}
+
+impl SpotPriceState {
+    /// Fetches all prices and updates the cache. On failure the previous prices are kept until
+    /// they go stale, at which point the price API is reported degraded.
+    #[instrument(skip_all, fields(api = self.config.api().as_str()))]
+    async fn refresh(&self) {
+        let prices = match fetch(&self.client, &self.config).await {
+            Ok(FetchedPrices::Assets(prices)) => prices,
+            Ok(FetchedPrices::Tokens(prices)) => {
+                for (token, usd) in prices {
+                    trace!(%token, usd, "refreshed spot price");
+                    self.cache.insert(token, usd);
+                }
+                self.degradation.set(Dependency::PriceApi, false);
+                return;
+            }
+            Err(error) => {
+                metrics::record_spot_price_refresh_error();
+                warn!(error = format!("{error:#}"), "failed to fetch spot prices");
+                let stale = self.cache.stale_count();
+                if stale > 0 {
+                    warn!(
+                        stale,
+                        policy = self.config.stale_price_policy().as_str(),
+                        "spot prices are stale"
+                    );
+                }
+                // Until the first successful fetch every token is on its fallback price.
+                self.degradation.set(Dependency::PriceApi, stale > 0 || self.cache.is_empty());
+                return;
+            }
+        };
+        for asset in SpotAsset::ALL {
+            let Some(usd) = prices.get(&asset) else {
+                warn!(asset = asset.symbol(), "price API response missing asset");
+                continue;
+            };
+            trace!(asset = asset.symbol(), usd, "refreshed spot price");
+            for token in self.tokens.get(&asset).into_iter().flatten() {
+                self.cache.insert(*token, *usd);
+            }
+        }
+        self.degradation.set(Dependency::PriceApi, false);
+    }
+}
+
+/// Checks that the configured price API responds with valid prices.
+pub(crate) async fn probe(client: &reqwest::Client, config: &PriceApiConfig) -> Result<()> {
+    fetch(client, config).await.map(drop)
+}
+
+async fn fetch(client: &reqwest::Client, config: &PriceApiConfig) -> Result<FetchedPrices> {
+    let request = match config.api() {
+        PriceApi::CoinGecko => {
+            let ids = SpotAsset::ALL.map(|asset| asset.coingecko_id()).join(",");
+            let request =
+                client.get(COINGECKO_URL).query(&[("ids", ids.as_str()), ("vs_currencies", "usd")]);
+            match config.api_key() {
+                Some(key) => request.header("x-cg-demo-api-key", key),
+                None => request,
+            }
+        }
+        PriceApi::CoinMarketCap => {
+            let symbols = SpotAsset::ALL.map(|asset| asset.symbol()).join(",");
+            let request = client
+                .get(COINMARKETCAP_URL)
+                .query(&[("symbol", symbols.as_str()), ("convert", "USD")]);
+            match config.api_key() {
+                Some(key) => request.header("X-CMC_PRO_API_KEY", key),
+                None => request,
+            }
+        }
+        PriceApi::Custom => {
+            let Some(url) = config.url() else {
+                bail!("no URL configured for the custom price API");
+            };
+            let request = client.get(url.clone());
+            match config.api_key() {
+                Some(key) => request.bearer_auth(key),
+                None => request,
+            }
+        }
+    };
+    let body = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .wrap_err("request failed")?
+        .bytes()
+        .await
+        .wrap_err("failed to read response")?;
+    match config.api() {
+        PriceApi::CoinGecko => parse_coingecko(&body).map(FetchedPrices::Assets),
+        PriceApi::CoinMarketCap => parse_coinmarketcap(&body).map(FetchedPrices::Assets),
+        PriceApi::Custom => parse_custom(&body).map(FetchedPrices::Tokens),
+    }
+}
+
+/// Every known token tracking a spot asset, by asset.
+fn spot_tokens(constants: &SignetSystemConstants) -> HashMap<SpotAsset, Vec<Address>> {
+    let mut tokens: HashMap<SpotAsset, Vec<Address>> = HashMap::new();
+    for known in KnownToken::ALL {
+        if let Some(asset) = SpotAsset::of(known) {
+            tokens.entry(asset).or_default().push(known.resolve(constants).token());
+        }
+    }
+    tokens
+}
+
+/// Parses a CoinGecko `simple/price` response: `{"ethereum": {"usd": 3000.0}, ...}`.
+fn parse_coingecko(body: &[u8]) -> Result<HashMap<SpotAsset, f64>> {
+    let response: HashMap<String, HashMap<String, f64>> =
+        serde_json::from_slice(body).wrap_err("invalid CoinGecko response")?;
+    validated(
+        SpotAsset::ALL
+            .into_iter()
+            .filter_map(|asset| Some((asset, *response.get(asset.coingecko_id())?.get("usd")?))),
+    )
+}
+
+#[derive(Deserialize)]
+struct CoinMarketCapResponse {
+    data: HashMap<String, CoinMarketCapEntry>,
+}
+
+#[derive(Deserialize)]
+struct CoinMarketCapEntry {
+    quote: HashMap<String, CoinMarketCapQuote>,
+}
+
+#[derive(Deserialize)]
+struct CoinMarketCapQuote {
+    price: Option<f64>,
+}
+
+/// Parses a CoinMarketCap v1 `cryptocurrency/quotes/latest` response:
+/// `{"data": {"ETH": {"quote": {"USD": {"price": 3000.0}}}, ...}}`.
+fn parse_coinmarketcap(body: &[u8]) -> Result<HashMap<SpotAsset, f64>> {
+    let response: CoinMarketCapResponse =
+        serde_json::from_slice(body).wrap_err("invalid CoinMarketCap response")?;
+    validated(SpotAsset::ALL.into_iter().filter_map(|asset| {
+        response.data.get(asset.symbol())?.quote.get("USD")?.price.map(|price| (asset, price))
+    }))
+}
+
+/// Parses a custom endpoint's response: `{"0x<token address>": 3000.0, ...}`, in USD per whole
+/// token.
+fn parse_custom(body: &[u8]) -> Result<HashMap<Address, f64>> {
+    let response: HashMap<Address, f64> =
+        serde_json::from_slice(body).wrap_err("invalid price API response")?;
+    for (token, price) in &response {
+        if !price.is_finite() || *price <= 0.0 {
+            bail!("invalid price for {token}: {price}");
+        }
+    }
+    Ok(response)
+}
+
+/// Collects prices, rejecting any that aren't positive and finite.
+fn validated(prices: impl Iterator<Item = (SpotAsset, f64)>) -> Result<HashMap<SpotAsset, f64>> {
+    prices
+        .map(|(asset, price)| {
+            if !price.is_finite() || price <= 0.0 {
+                bail!("invalid {} price: {price}", asset.symbol());
+            }
+            Ok((asset, price))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_coingecko() {
+        let body = br#"{"ethereum":{"usd":3012.5},"bitcoin":{"usd":61000},"tether":{"usd":0.999}}"#;
+        let prices = parse_coingecko(body).unwrap();
+        assert_eq!(prices.get(&SpotAsset::Eth), Some(&3012.5));
+        assert_eq!(prices.get(&SpotAsset::Btc), Some(&61000.0));
+        assert_eq!(prices.get(&SpotAsset::Usdt), Some(&0.999));
+        assert_eq!(prices.get(&SpotAsset::Usdc), None);
+
+        assert!(parse_coingecko(br#"{"ethereum":{"usd":-1}}"#).is_err());
+    }
+
+    #[test]
+    fn parses_coinmarketcap() {
+        let body = br#"{
+            "status": {"error_code": 0},
+            "data": {
+                "ETH": {"symbol": "ETH", "quote": {"USD": {"price": 3012.5}}},
+                "USDC": {"symbol": "USDC", "quote": {"USD": {"price": 1.0001}}},
+                "BTC": {"symbol": "BTC", "quote": {"USD": {"price": null}}}
+            }
+        }"#;
+        let prices = parse_coinmarketcap(body).unwrap();
+        assert_eq!(prices.get(&SpotAsset::Eth), Some(&3012.5));
+        assert_eq!(prices.get(&SpotAsset::Usdc), Some(&1.0001));
+        assert_eq!(prices.get(&SpotAsset::Btc), None);
+    }
+
+    #[test]
+    fn parses_custom() {
+        let weth = Address::repeat_byte(0x11);
+        let body = format!(r#"{{"{weth}": 3012.5, "{}": 1}}"#, Address::repeat_byte(0x22));
+        let prices = parse_custom(body.as_bytes()).unwrap();
+        assert_eq!(prices.get(&weth), Some(&3012.5));
+        assert_eq!(prices.len(), 2);
+        assert!(parse_custom(format!(r#"{{"{weth}": 0}}"#).as_bytes()).is_err());
+        assert!(parse_custom(br#"{"not-an-address": 1}"#).is_err());
+    }
+
+    #[test]
+    fn stale_prices_are_rejected_under_reject_policy() {
+        let cache =
+            SpotPriceCache::new(Duration::ZERO).with_stale_price_policy(StalePricePolicy::Reject);
+        assert!(!cache.rejects(&Address::ZERO), "never-priced tokens use fixed prices");
+        cache.insert(Address::ZERO, 1.0);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(cache.rejects(&Address::ZERO));
+        assert_eq!(cache.stale_count(), 1);
+
+        let cache = SpotPriceCache::new(Duration::ZERO);
+        cache.insert(Address::ZERO, 1.0);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(!cache.rejects(&Address::ZERO));
+    }
+
+    #[test]
+    fn stale_prices_are_ignored() {
+        let cache = SpotPriceCache::new(Duration::ZERO);
+        cache.insert(Address::ZERO, 1.0);
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(cache.get(&Address::ZERO), None);
+
+        let cache = SpotPriceCache::new(Duration::from_secs(60));
+        cache.insert(Address::ZERO, 1.0);
+        assert_eq!(cache.get(&Address::ZERO), Some(1.0));
+    }
+}
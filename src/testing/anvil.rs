@@ -0,0 +1,352 @@
+use crate::{Config, FileOrderSource, FillerContext, FillerTask, FixedPricingClient};
+use alloy::{
+    network::EthereumWallet,
+    primitives::{Address, Bytes, U256},
+    providers::{Provider, ProviderBuilder, ext::AnvilApi},
+    signers::local::PrivateKeySigner,
+    sol,
+};
+use eyre::{OptionExt, Result, WrapErr, bail, eyre};
+use init4_bin_base::utils::signer::LocalOrAws;
+use signet_bundle::SignetEthBundle;
+use signet_constants::SignetConstants;
+use signet_orders::permit2::{PERMIT2, is_order_nonce_consumed};
+use signet_types::{SignedOrder, UnsignedOrder};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    net::TcpListener,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    time::Duration,
+};
+use tokio::time::{Instant, sleep};
+use tokio_util::sync::CancellationToken;
+
+/// How long to wait for a spawned anvil to start serving RPC requests.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Private keys of anvil's default funded accounts after the first, derived from its default
+/// mnemonic, for order owners through [`AnvilHarness::user`]. The first account is left for the
+/// filler's signer.
+const ANVIL_KEYS: [&str; 4] = [
+    "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+    "0x7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6",
+    "0x47e179ec197488593b187f80a00eb0da91f1b9d0b13f8733639f19c30a34926a",
+];
+
+sol! {
+    /// The deposit function of the rollup's wrapped native token.
+    #[sol(rpc)]
+    interface IWrappedNative {
+        function deposit() external payable;
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+}
+
+/// A local anvil node forked from a live chain, killed when dropped. Requires `anvil` on the
+/// `PATH`.
+#[derive(Debug)]
+pub struct AnvilFork {
+    child: Child,
+    port: u16,
+}
+
+impl AnvilFork {
+    /// Spawns anvil forking `fork_url` on a free local port, and waits until it serves requests.
+    pub async fn spawn(fork_url: &str) -> Result<Self> {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .wrap_err("failed to find a free port for anvil")?
+            .port();
+        let child = Command::new("anvil")
+            .args(["--fork-url", fork_url, "--port", &port.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .wrap_err("failed to spawn anvil; is it installed and on the PATH?")?;
+        let fork = Self { child, port };
+
+        let provider = ProviderBuilder::new().connect_http(fork.http_url().parse()?);
+        let started = Instant::now();
+        while provider.get_chain_id().await.is_err() {
+            if started.elapsed() > STARTUP_TIMEOUT {
+                bail!("anvil forking {fork_url} didn't start within {STARTUP_TIMEOUT:?}");
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+        Ok(fork)
+    }
+
+    /// The fork's HTTP RPC URL.
+    pub fn http_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    /// The fork's websocket RPC URL.
+    pub fn ws_url(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.port)
+    }
+
+    /// Sends raw signed transactions to the fork, returning how many were accepted. Transactions
+    /// the fork rejects, such as fills for orders that were already filled, are skipped.
+    async fn send_raw(&self, txs: &[Bytes]) -> Result<usize> {
+        let provider = ProviderBuilder::new().connect_http(self.http_url().parse()?);
+        let mut sent = 0;
+        for tx in txs {
+            if provider.send_raw_transaction(tx).await.is_ok() {
+                sent += 1;
+            }
+        }
+        Ok(sent)
+    }
+}
+
+impl Drop for AnvilFork {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// End-to-end harness running [`FillerTask`] against anvil forks of a Signet chain's host and
+/// rollup. Orders are signed by funded anvil accounts and fed to the filler through a
+/// [`FileOrderSource`] in the harness's directory; the bundles the filler submits are written
+/// there too, and [`Self::land_bundles`] executes them on the forks, so tests can check that
+/// orders were really filled. The filler is configured by the [`Config`] passed in, with its RPC
+/// URLs and order and bundle files pointed at the harness.
+///
+/// Forking needs RPC access to the live chain, and starting the filler needs the chain's
+/// transaction cache to be reachable, as it is still checked at startup.
+#[derive(Debug)]
+pub struct AnvilHarness {
+    chain_name: String,
+    constants: SignetConstants,
+    host: AnvilFork,
+    rollup: AnvilFork,
+    dir: PathBuf,
+}
+
+impl AnvilHarness {
+    /// Forks the host and rollup of the Signet chain `chain_name` from the given RPC URLs,
+    /// keeping the filler's order and bundle files in `dir`, which must exist.
+    pub async fn spawn(
+        chain_name: &str,
+        host_fork_url: &str,
+        rollup_fork_url: &str,
+        dir: impl Into<PathBuf>,
+    ) -> Result<Self> {
+        let constants: SignetConstants =
+            chain_name.parse().wrap_err_with(|| format!("unknown chain {chain_name}"))?;
+        let (host, rollup) =
+            tokio::try_join!(AnvilFork::spawn(host_fork_url), AnvilFork::spawn(rollup_fork_url))?;
+        let harness =
+            Self { chain_name: chain_name.to_string(), constants, host, rollup, dir: dir.into() };
+        fs::write(harness.orders_path(), "").wrap_err("failed to create the orders file")?;
+        Ok(harness)
+    }
+
+    /// The chain's constants.
+    pub const fn constants(&self) -> &SignetConstants {
+        &self.constants
+    }
+
+    /// The host fork.
+    pub const fn host(&self) -> &AnvilFork {
+        &self.host
+    }
+
+    /// The rollup fork.
+    pub const fn rollup(&self) -> &AnvilFork {
+        &self.rollup
+    }
+
+    /// The filler's signer configured in `config`, which must be a local key.
+    pub fn filler(&self, config: &Config) -> Result<PrivateKeySigner> {
+        match config.signer().connect_local() {
+            Ok(LocalOrAws::Local(signer)) => Ok(signer),
+            _ => bail!("the filler's signer must be a local key to run against anvil forks"),
+        }
+    }
+
+    /// A funded anvil account, for signing orders. `index` must be less than 4.
+    pub fn user(&self, index: usize) -> PrivateKeySigner {
+        ANVIL_KEYS[index].parse().expect("anvil keys are valid")
+    }
+
+    /// Sets `address`'s native balance to `amount` on both forks, such as to fund a filler's
+    /// signer that isn't one of anvil's accounts.
+    pub async fn fund(&self, address: Address, amount: U256) -> Result<()> {
+        for fork in [&self.host, &self.rollup] {
+            ProviderBuilder::new()
+                .connect_http(fork.http_url().parse()?)
+                .anvil_set_balance(address, amount)
+                .await
+                .wrap_err("failed to set balance on the fork")?;
+        }
+        Ok(())
+    }
+
+    fn orders_path(&self) -> PathBuf {
+        self.dir.join("orders.jsonl")
+    }
+
+    fn bundles_path(&self) -> PathBuf {
+        self.dir.join("bundles.jsonl")
+    }
+
+    /// Wraps `amount` of `signer`'s native rollup balance into the rollup's wrapped native token
+    /// and approves Permit2 to spend it, so the account can pay order inputs or fill outputs in
+    /// that token.
+    pub async fn wrap_and_approve(&self, signer: &PrivateKeySigner, amount: U256) -> Result<()> {
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(signer.clone()))
+            .connect_http(self.rollup.http_url().parse()?);
+        let wrapped_native = self
+            .wrapped_native()
+            .ok_or_eyre(format!("{} has no known wrapped native token", self.chain_name))?;
+        let wrapped = IWrappedNative::new(wrapped_native, &provider);
+        wrapped.deposit().value(amount).send().await?.get_receipt().await?;
+        wrapped.approve(PERMIT2, U256::MAX).send().await?.get_receipt().await?;
+        Ok(())
+    }
+
+    /// The rollup's wrapped native token, if known for the chain.
+    pub fn wrapped_native(&self) -> Option<Address> {
+        FixedPricingClient::wrapped_token_address(&self.chain_name)
+    }
+
+    /// Signs `order` for this chain with `owner`'s key.
+    pub async fn sign_order(
+        &self,
+        owner: &PrivateKeySigner,
+        order: UnsignedOrder<'_>,
+    ) -> Result<SignedOrder> {
+        order
+            .with_chain(self.constants.system())
+            .sign(owner)
+            .await
+            .map_err(|error| eyre!("failed to sign order: {error}"))
+    }
+
+    /// Adds `order` to the orders the filler fetches.
+    pub fn add_order(&self, order: &SignedOrder) -> Result<()> {
+        let json = serde_json::to_string(order).wrap_err("failed to serialize order")?;
+        OpenOptions::new()
+            .append(true)
+            .open(self.orders_path())
+            .and_then(|mut file| writeln!(file, "{json}"))
+            .wrap_err("failed to write the orders file")
+    }
+
+    /// Initializes the filler's context against the forks, configured by `config` with its RPC
+    /// URLs and order and bundle files overridden. `config` must be for the forked chain.
+    pub async fn filler_context(
+        &self,
+        config: Config,
+        cancellation_token: CancellationToken,
+    ) -> Result<FillerContext> {
+        if config.chain_name() != self.chain_name {
+            bail!(
+                "the filler is configured for {}, but the forks are of {}",
+                config.chain_name(),
+                self.chain_name
+            );
+        }
+        let config = config.with_forks(
+            &self.host.http_url(),
+            &self.rollup.ws_url(),
+            self.orders_path(),
+            self.bundles_path(),
+        )?;
+        FillerContext::initialize(config, cancellation_token).await
+    }
+
+    /// Runs the filler configured by `config` against the forks for `duration`, submitting
+    /// bundles to the bundles file.
+    pub async fn run_filler(&self, config: Config, duration: Duration) -> Result<()> {
+        let cancellation_token = CancellationToken::new();
+        let context = self.filler_context(config, cancellation_token.clone()).await?;
+        let order_source = FileOrderSource::new(self.orders_path(), Some(self.bundles_path()));
+        let filler = FillerTask::with_order_source(&context, order_source);
+        let stop = async {
+            sleep(duration).await;
+            cancellation_token.cancel();
+        };
+        let (result, ()) = tokio::join!(filler.run(), stop);
+        result
+    }
+
+    /// Executes every bundle the filler has submitted on the forks, host transactions first, and
+    /// clears the bundles file. Returns the number of transactions the forks accepted.
+    pub async fn land_bundles(&self) -> Result<usize> {
+        let bundles = match fs::read_to_string(self.bundles_path()) {
+            Ok(bundles) => bundles,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(error) => return Err(error).wrap_err("failed to read the bundles file"),
+        };
+        let mut landed = 0;
+        for line in bundles.lines().filter(|line| !line.trim().is_empty()) {
+            let bundle: SignetEthBundle =
+                serde_json::from_str(line).wrap_err("invalid bundle in the bundles file")?;
+            landed += self.host.send_raw(bundle.host_txs()).await?;
+            landed += self.rollup.send_raw(bundle.txs()).await?;
+        }
+        fs::write(self.bundles_path(), "").wrap_err("failed to clear the bundles file")?;
+        Ok(landed)
+    }
+
+    /// Whether `order` has been filled on the rollup fork: its Permit2 nonce is consumed.
+    pub async fn is_filled(&self, order: &SignedOrder) -> Result<bool> {
+        let provider = ProviderBuilder::new().connect_http(self.rollup.http_url().parse()?);
+        Ok(is_order_nonce_consumed(&provider, order).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config_from_env, testing::OrderBuilder};
+
+    /// Fills an order end to end on forks of the chain the environment configures the filler
+    /// for. Set `HOST_FORK_URL` and `ROLLUP_FORK_URL` to the live chain's RPC URLs, and
+    /// `SIGNER_KEY` to anvil's first account, then run with `--ignored`.
+    #[tokio::test]
+    #[ignore = "needs anvil and RPC access to a live Signet chain"]
+    async fn fills_an_order_on_forks() {
+        let config = config_from_env().unwrap();
+        let dir = std::env::temp_dir().join(format!("signet-filler-e2e-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let harness = AnvilHarness::spawn(
+            config.chain_name(),
+            &std::env::var("HOST_FORK_URL").unwrap(),
+            &std::env::var("ROLLUP_FORK_URL").unwrap(),
+            &dir,
+        )
+        .await
+        .unwrap();
+
+        let amount = U256::from(10_u64).pow(U256::from(18));
+        let filler = harness.filler(&config).unwrap();
+        harness.fund(filler.address(), amount * U256::from(10)).await.unwrap();
+        harness.wrap_and_approve(&filler, amount).await.unwrap();
+        let user = harness.user(0);
+        harness.wrap_and_approve(&user, amount).await.unwrap();
+
+        let wrapped_native = harness.wrapped_native().unwrap();
+        let builder = OrderBuilder::default().with_input(wrapped_native, amount).with_output(
+            wrapped_native,
+            amount * U256::from(9) / U256::from(10),
+            user.address(),
+            harness.constants().system().ru_chain_id(),
+        );
+        let order = harness.sign_order(&user, builder.into_unsigned()).await.unwrap();
+        harness.add_order(&order).unwrap();
+
+        harness.run_filler(config, Duration::from_secs(30)).await.unwrap();
+        assert!(harness.land_bundles().await.unwrap() > 0);
+        assert!(harness.is_filled(&order).await.unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+use crate::OrderPricing;
+use alloy::primitives::B256;
+use futures_util::future::BoxFuture;
+use signet_types::SignedOrder;
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// A scripted pricing error.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct MockPricingError(pub String);
+
+/// One scripted profitability result, returned after an optional latency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockQuote {
+    result: Result<Option<i128>, MockPricingError>,
+    latency: Duration,
+}
+
+impl MockQuote {
+    /// An order within the maximum loss, with `margin` before gas in normalized 18-decimal USD.
+    pub const fn margin(margin: i128) -> Self {
+        Self { result: Ok(Some(margin)), latency: Duration::ZERO }
+    }
+
+    /// An order beyond the maximum loss.
+    pub const fn exceeds_max_loss() -> Self {
+        Self { result: Ok(None), latency: Duration::ZERO }
+    }
+
+    /// An order that fails to price with `message`.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { result: Err(MockPricingError(message.into())), latency: Duration::ZERO }
+    }
+
+    /// Returns the result only after `latency`.
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct MockPricingInner {
+    default: MockQuote,
+    scripted: HashMap<B256, VecDeque<MockQuote>>,
+    calls: HashMap<B256, usize>,
+}
+
+/// A pricing client with scripted results, for testing strategies built on the filler without
+/// pricing real tokens. Pass it to [`FillerTask::with_pricing`](crate::FillerTask::with_pricing)
+/// to drive the filler's pricing stage with it.
+///
+/// Like the filler's own pricing, [`Self::profitability`] gives an order's margin before gas, in
+/// normalized 18-decimal USD, or `None` when the order is beyond the maximum loss. Results
+/// scripted for an order are returned in turn, one per call; once they run out, and for orders
+/// with no script, the default result is returned. Clones share their scripts and call counts.
+#[derive(Debug, Clone)]
+pub struct MockPricingClient {
+    inner: Arc<Mutex<MockPricingInner>>,
+}
+
+impl Default for MockPricingClient {
+    fn default() -> Self {
+        Self::new(MockQuote::exceeds_max_loss())
+    }
+}
+
+impl MockPricingClient {
+    /// A client returning `default` for every order without a script.
+    pub fn new(default: MockQuote) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(MockPricingInner {
+                default,
+                scripted: HashMap::new(),
+                calls: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Scripts the next results for `order`, after any already scripted for it.
+    pub fn with_quotes(
+        self,
+        order: &SignedOrder,
+        quotes: impl IntoIterator<Item = MockQuote>,
+    ) -> Self {
+        self.inner.lock().unwrap().scripted.entry(*order.order_hash()).or_default().extend(quotes);
+        self
+    }
+
+    /// Prices `order`, returning its next scripted result after that result's latency.
+    pub async fn profitability(
+        &self,
+        order: &SignedOrder,
+    ) -> Result<Option<i128>, MockPricingError> {
+        let quote = {
+            let mut inner = self.inner.lock().unwrap();
+            *inner.calls.entry(*order.order_hash()).or_default() += 1;
+            let scripted = inner.scripted.get_mut(order.order_hash()).and_then(VecDeque::pop_front);
+            scripted.unwrap_or_else(|| inner.default.clone())
+        };
+        if !quote.latency.is_zero() {
+            sleep(quote.latency).await;
+        }
+        quote.result
+    }
+
+    /// How many times `order` has been priced.
+    pub fn calls(&self, order: &SignedOrder) -> usize {
+        self.inner.lock().unwrap().calls.get(order.order_hash()).copied().unwrap_or_default()
+    }
+}
+
+impl OrderPricing for MockPricingClient {
+    fn profitability<'a>(
+        &'a self,
+        order: &'a SignedOrder,
+    ) -> BoxFuture<'a, Result<Option<i128>, Box<dyn Error + Send + Sync>>> {
+        Box::pin(
+            async move { MockPricingClient::profitability(self, order).await.map_err(Into::into) },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::OrderBuilder;
+    use alloy::primitives::{Address, U256};
+    use tokio::time::Instant;
+
+    #[tokio::test]
+    async fn scripted_quotes_are_returned_in_turn_then_the_default() {
+        let token = Address::repeat_byte(0xaa);
+        let scripted = OrderBuilder::default().with_input(token, U256::from(1)).build();
+        let unscripted = OrderBuilder::default().with_input(token, U256::from(2)).build();
+        let pricing = MockPricingClient::new(MockQuote::margin(7)).with_quotes(
+            &scripted,
+            [
+                MockQuote::error("price API down").with_latency(Duration::from_millis(20)),
+                MockQuote::exceeds_max_loss(),
+            ],
+        );
+
+        let started = Instant::now();
+        assert_eq!(
+            pricing.profitability(&scripted).await,
+            Err(MockPricingError("price API down".to_string()))
+        );
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(pricing.profitability(&scripted).await, Ok(None));
+        assert_eq!(pricing.profitability(&scripted).await, Ok(Some(7)));
+        assert_eq!(pricing.clone().profitability(&unscripted).await, Ok(Some(7)));
+        assert_eq!((pricing.calls(&scripted), pricing.calls(&unscripted)), (3, 1));
+    }
+
+    #[tokio::test]
+    async fn prices_orders_for_the_filler() {
+        let order =
+            OrderBuilder::default().with_input(Address::repeat_byte(0xaa), U256::from(1)).build();
+        let mock = MockPricingClient::new(MockQuote::margin(7))
+            .with_quotes(&order, [MockQuote::error("price API down")]);
+        let pricing: &dyn OrderPricing = &mock;
+
+        let error = pricing.profitability(&order).await.unwrap_err();
+        assert_eq!(error.downcast_ref(), Some(&MockPricingError("price API down".to_string())));
+        assert_eq!(pricing.profitability(&order).await.unwrap(), Some(7));
+        assert!(!pricing.has_cached_quote(order.order_hash()));
+        assert_eq!(mock.calls(&order), 2);
+    }
+}
@@ -0,0 +1,16 @@
+//! Helpers for testing the filler and strategies built on it, enabled by the `testing` feature.
+//!
+//! - [`OrderBuilder`] builds orders, unsigned for offline checks or signed for a chain.
+//! - [`MockPricingClient`] prices orders with scripted results, latencies, and errors.
+//! - [`AnvilHarness`] runs [`FillerTask`](crate::FillerTask) end to end against local anvil forks
+//!   of a Signet chain's host and rollup, which already have Permit2 and the Orders contracts
+//!   deployed, and lands the fills it submits on the forks.
+
+mod anvil;
+pub use anvil::{AnvilFork, AnvilHarness};
+
+mod mock_pricing;
+pub use mock_pricing::{MockPricingClient, MockPricingError, MockQuote};
+
+mod orders;
+pub use orders::OrderBuilder;
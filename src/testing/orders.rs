@@ -0,0 +1,131 @@
+use alloy::primitives::{Address, Bytes, U256};
+use signet_types::{SignedOrder, UnsignedOrder};
+use signet_zenith::RollupOrders::{
+    Output, Permit2Batch, PermitBatchTransferFrom, TokenPermissions,
+};
+
+/// Builds [`SignedOrder`]s for tests.
+///
+/// [`Self::build`] gives an order with a zeroed signature, for exercising filters and pricing
+/// offline; [`Self::into_unsigned`] gives an order to sign with a real key, for submitting to a
+/// chain. By default the order is owned by `0x0101…01`, has Permit2 nonce 0, and never expires.
+#[derive(Debug, Clone)]
+pub struct OrderBuilder {
+    owner: Address,
+    inputs: Vec<TokenPermissions>,
+    outputs: Vec<Output>,
+    nonce: u64,
+    deadline: u64,
+}
+
+impl Default for OrderBuilder {
+    fn default() -> Self {
+        Self {
+            owner: Address::repeat_byte(0x01),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            nonce: 0,
+            deadline: u64::MAX,
+        }
+    }
+}
+
+impl OrderBuilder {
+    /// Sets the order's owner. Ignored when signing, where the signer is the owner.
+    pub const fn with_owner(mut self, owner: Address) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Adds an input of `amount` base units of the rollup token `token`.
+    pub fn with_input(mut self, token: Address, amount: U256) -> Self {
+        self.inputs.push(TokenPermissions { token, amount });
+        self
+    }
+
+    /// Adds an output of `amount` base units of `token` on `chain_id`, paid to `recipient`.
+    pub fn with_output(
+        mut self,
+        token: Address,
+        amount: U256,
+        recipient: Address,
+        chain_id: u64,
+    ) -> Self {
+        self.outputs.push(Output {
+            token,
+            amount,
+            recipient,
+            chainId: chain_id.try_into().expect("chain ID fits in a u32"),
+        });
+        self
+    }
+
+    /// Sets the order's Permit2 nonce.
+    pub const fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Sets the order's deadline, in seconds since the Unix epoch.
+    pub const fn with_deadline(mut self, deadline: u64) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// The order with a zeroed signature. It can't be filled on chain.
+    pub fn build(self) -> SignedOrder {
+        SignedOrder::new(
+            Permit2Batch {
+                permit: PermitBatchTransferFrom {
+                    permitted: self.inputs,
+                    nonce: U256::from(self.nonce),
+                    deadline: U256::from(self.deadline),
+                },
+                owner: self.owner,
+                signature: Bytes::from([0; 65]),
+            },
+            self.outputs,
+        )
+    }
+
+    /// The order, to be signed once its chain is set with [`UnsignedOrder::with_chain`].
+    pub fn into_unsigned(self) -> UnsignedOrder<'static> {
+        let order = UnsignedOrder::new().with_deadline(self.deadline).with_nonce(self.nonce);
+        let order = self
+            .inputs
+            .into_iter()
+            .fold(order, |order, input| order.with_input(input.token, input.amount));
+        self.outputs.into_iter().fold(order, |order, output| order.with_raw_output(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use signet_constants::SignetSystemConstants;
+
+    #[tokio::test]
+    async fn built_and_signed_orders_match() {
+        let constants = SignetSystemConstants::parmigiana();
+        let signer = PrivateKeySigner::random();
+        let builder = OrderBuilder::default()
+            .with_owner(signer.address())
+            .with_input(constants.rollup().tokens().weth(), U256::from(10))
+            .with_output(
+                constants.host().tokens().usdc(),
+                U256::from(20),
+                signer.address(),
+                constants.host_chain_id(),
+            )
+            .with_nonce(3)
+            .with_deadline(1_000);
+
+        let built = builder.clone().build();
+        let signed = builder.into_unsigned().with_chain(&constants).sign(&signer).await.unwrap();
+        assert_eq!(built.permit().permit, signed.permit().permit);
+        assert_eq!(built.permit().owner, signed.permit().owner);
+        assert_eq!(built.outputs(), signed.outputs());
+        assert!(built.is_expired_at(1_001));
+    }
+}
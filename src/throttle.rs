@@ -0,0 +1,348 @@
+use crate::FillThrottleConfig;
+use alloy::primitives::{Address, B256, U256};
+use serde::Serialize;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+use tokio::time::{Duration, Instant};
+
+const MINUTE: Duration = Duration::from_secs(60);
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+/// The rolling-window limit that caused a fill to be throttled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ThrottleLimit {
+    FillsPerMinute,
+    FillsPerHour,
+    NotionalPerMinute,
+    NotionalPerHour,
+    OwnerFillsPerHour,
+    OwnerNotionalPerHour,
+}
+
+impl ThrottleLimit {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            ThrottleLimit::FillsPerMinute => "fills-per-minute",
+            ThrottleLimit::FillsPerHour => "fills-per-hour",
+            ThrottleLimit::NotionalPerMinute => "notional-per-minute",
+            ThrottleLimit::NotionalPerHour => "notional-per-hour",
+            ThrottleLimit::OwnerFillsPerHour => "owner-fills-per-hour",
+            ThrottleLimit::OwnerNotionalPerHour => "owner-notional-per-hour",
+        }
+    }
+}
+
+/// Point-in-time view of the throttle, served by the admin API.
+#[derive(Debug, Serialize)]
+pub(crate) struct ThrottleSnapshot {
+    override_active: bool,
+    fills_last_minute: usize,
+    fills_last_hour: usize,
+    /// Normalized 18-decimal USD.
+    notional_last_minute: U256,
+    /// Normalized 18-decimal USD.
+    notional_last_hour: U256,
+    max_fills_per_minute: Option<u32>,
+    max_fills_per_hour: Option<u32>,
+    /// Normalized 18-decimal USD.
+    max_notional_per_minute: Option<U256>,
+    /// Normalized 18-decimal USD.
+    max_notional_per_hour: Option<U256>,
+    max_fills_per_owner_per_hour: Option<u32>,
+    /// Normalized 18-decimal USD.
+    max_notional_per_owner_per_hour: Option<U256>,
+}
+
+#[derive(Debug)]
+struct ThrottleInner {
+    config: FillThrottleConfig,
+    /// Fills accepted within the last hour as `(accepted_at, order_hash, owner, notional)`,
+    /// oldest first.
+    fills: VecDeque<(Instant, B256, Address, U256)>,
+    override_active: bool,
+}
+
+impl ThrottleInner {
+    fn prune(&mut self, now: Instant) {
+        while self.fills.front().is_some_and(|(at, _, _, _)| now.duration_since(*at) >= HOUR) {
+            self.fills.pop_front();
+        }
+    }
+
+    /// Fill count and total notional within `window` of `now`, from `owner` only if given.
+    /// Assumes `prune` has been called.
+    fn usage(&self, now: Instant, window: Duration, owner: Option<&Address>) -> (usize, U256) {
+        self.fills
+            .iter()
+            .rev()
+            .take_while(|(at, _, _, _)| now.duration_since(*at) < window)
+            .filter(|(_, _, fill_owner, _)| owner.is_none_or(|owner| owner == fill_owner))
+            .fold((0, U256::ZERO), |(count, total), (_, _, _, notional)| {
+                (count + 1, total.saturating_add(*notional))
+            })
+    }
+}
+
+/// Rolling-window cap on the number and notional value of fills, overall and per order owner,
+/// enforced across cycles. Shared between the filler loop (which acquires capacity for each
+/// selected order, and releases it if the fill isn't submitted) and the admin API (which can
+/// inspect it and override it).
+#[derive(Debug, Clone)]
+pub(crate) struct FillThrottle {
+    inner: Arc<Mutex<ThrottleInner>>,
+}
+
+impl FillThrottle {
+    pub(crate) fn new(config: FillThrottleConfig) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ThrottleInner {
+                config,
+                fills: VecDeque::new(),
+                override_active: false,
+            })),
+        }
+    }
+
+    /// Reserve capacity for a fill of an order from `owner` with the given notional value
+    /// (normalized 18-decimal USD), returning the first limit it would exceed instead. While the
+    /// admin override is active the fill is always allowed, but still counted. Undone by
+    /// [`Self::release`] if the fill isn't submitted after all.
+    pub(crate) fn try_acquire(
+        &self,
+        order_hash: B256,
+        owner: Address,
+        notional: U256,
+    ) -> Result<(), ThrottleLimit> {
+        self.try_acquire_at(Instant::now(), order_hash, owner, notional)
+    }
+
+    fn try_acquire_at(
+        &self,
+        now: Instant,
+        order_hash: B256,
+        owner: Address,
+        notional: U256,
+    ) -> Result<(), ThrottleLimit> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.prune(now);
+        if !inner.override_active {
+            let config = &inner.config;
+            for (window, owner, max_fills, max_notional, fills_limit, notional_limit) in [
+                (
+                    MINUTE,
+                    None,
+                    config.max_fills_per_minute(),
+                    config.max_notional_per_minute(),
+                    ThrottleLimit::FillsPerMinute,
+                    ThrottleLimit::NotionalPerMinute,
+                ),
+                (
+                    HOUR,
+                    None,
+                    config.max_fills_per_hour(),
+                    config.max_notional_per_hour(),
+                    ThrottleLimit::FillsPerHour,
+                    ThrottleLimit::NotionalPerHour,
+                ),
+                (
+                    HOUR,
+                    Some(&owner),
+                    config.max_fills_per_owner_per_hour(),
+                    config.max_notional_per_owner_per_hour(),
+                    ThrottleLimit::OwnerFillsPerHour,
+                    ThrottleLimit::OwnerNotionalPerHour,
+                ),
+            ] {
+                let (count, total) = inner.usage(now, window, owner);
+                if max_fills.is_some_and(|max| count >= max.get() as usize) {
+                    return Err(fills_limit);
+                }
+                if max_notional.is_some_and(|max| total.saturating_add(notional) > max) {
+                    return Err(notional_limit);
+                }
+            }
+        }
+        inner.fills.push_back((now, order_hash, owner, notional));
+        Ok(())
+    }
+
+    /// Release the capacity last reserved for an order whose fill was dropped or failed to
+    /// submit, so it no longer counts towards the limits.
+    pub(crate) fn release(&self, order_hash: B256) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(index) = inner.fills.iter().rposition(|(_, hash, _, _)| *hash == order_hash) {
+            inner.fills.remove(index);
+        }
+    }
+
+    /// Enable or disable the admin override, which lets every fill through until disabled.
+    pub(crate) fn set_override(&self, active: bool) {
+        self.inner.lock().unwrap().override_active = active;
+    }
+
+    pub(crate) fn snapshot(&self) -> ThrottleSnapshot {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        inner.prune(now);
+        let (fills_last_minute, notional_last_minute) = inner.usage(now, MINUTE, None);
+        let (fills_last_hour, notional_last_hour) = inner.usage(now, HOUR, None);
+        let config = &inner.config;
+        ThrottleSnapshot {
+            override_active: inner.override_active,
+            fills_last_minute,
+            fills_last_hour,
+            notional_last_minute,
+            notional_last_hour,
+            max_fills_per_minute: config.max_fills_per_minute().map(|max| max.get()),
+            max_fills_per_hour: config.max_fills_per_hour().map(|max| max.get()),
+            max_notional_per_minute: config.max_notional_per_minute(),
+            max_notional_per_hour: config.max_notional_per_hour(),
+            max_fills_per_owner_per_hour: config
+                .max_fills_per_owner_per_hour()
+                .map(|max| max.get()),
+            max_notional_per_owner_per_hour: config.max_notional_per_owner_per_hour(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroU32;
+
+    const ONE_USD: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+    const OWNER: Address = Address::repeat_byte(0x01);
+    const HASH: B256 = B256::repeat_byte(0xaa);
+
+    #[test]
+    fn fill_count_limits_apply_per_window() {
+        let throttle = FillThrottle::new(FillThrottleConfig::new(
+            NonZeroU32::new(2),
+            NonZeroU32::new(3),
+            None,
+            None,
+            None,
+            None,
+        ));
+        let start = Instant::now();
+        assert!(throttle.try_acquire_at(start, HASH, OWNER, ONE_USD).is_ok());
+        assert!(throttle.try_acquire_at(start, HASH, OWNER, ONE_USD).is_ok());
+        assert_eq!(
+            throttle.try_acquire_at(start, HASH, OWNER, ONE_USD),
+            Err(ThrottleLimit::FillsPerMinute)
+        );
+
+        // The minute window has rolled over, but the hourly cap still applies.
+        let later = start + MINUTE;
+        assert!(throttle.try_acquire_at(later, HASH, OWNER, ONE_USD).is_ok());
+        assert_eq!(
+            throttle.try_acquire_at(later, HASH, OWNER, ONE_USD),
+            Err(ThrottleLimit::FillsPerHour)
+        );
+
+        // Once the hour has passed the earliest fills fall out of the window.
+        assert!(throttle.try_acquire_at(start + HOUR, HASH, OWNER, ONE_USD).is_ok());
+    }
+
+    #[test]
+    fn notional_limit_includes_the_new_fill() {
+        let throttle = FillThrottle::new(FillThrottleConfig::new(
+            None,
+            None,
+            Some(ONE_USD * U256::from(10)),
+            None,
+            None,
+            None,
+        ));
+        let now = Instant::now();
+        assert!(throttle.try_acquire_at(now, HASH, OWNER, ONE_USD * U256::from(6)).is_ok());
+        assert_eq!(
+            throttle.try_acquire_at(now, HASH, OWNER, ONE_USD * U256::from(5)),
+            Err(ThrottleLimit::NotionalPerMinute)
+        );
+        // A smaller fill that still fits is allowed.
+        assert!(throttle.try_acquire_at(now, HASH, OWNER, ONE_USD * U256::from(4)).is_ok());
+    }
+
+    #[test]
+    fn owner_limits_apply_per_owner() {
+        let throttle = FillThrottle::new(FillThrottleConfig::new(
+            None,
+            None,
+            None,
+            None,
+            NonZeroU32::new(2),
+            Some(ONE_USD * U256::from(10)),
+        ));
+        let other = Address::repeat_byte(0x02);
+        let now = Instant::now();
+        assert!(throttle.try_acquire_at(now, HASH, OWNER, ONE_USD).is_ok());
+        assert!(throttle.try_acquire_at(now, HASH, OWNER, ONE_USD).is_ok());
+        assert_eq!(
+            throttle.try_acquire_at(now, HASH, OWNER, ONE_USD),
+            Err(ThrottleLimit::OwnerFillsPerHour)
+        );
+        // Another owner's fills are counted separately.
+        assert!(throttle.try_acquire_at(now, HASH, other, ONE_USD * U256::from(9)).is_ok());
+        assert_eq!(
+            throttle.try_acquire_at(now, HASH, other, ONE_USD * U256::from(2)),
+            Err(ThrottleLimit::OwnerNotionalPerHour)
+        );
+        assert!(throttle.try_acquire_at(now + HOUR, HASH, OWNER, ONE_USD).is_ok());
+    }
+
+    #[test]
+    fn released_fills_no_longer_count() {
+        let throttle = FillThrottle::new(FillThrottleConfig::new(
+            None,
+            NonZeroU32::new(1),
+            None,
+            None,
+            None,
+            None,
+        ));
+        let now = Instant::now();
+        let failed = B256::repeat_byte(0xbb);
+        assert!(throttle.try_acquire_at(now, failed, OWNER, ONE_USD).is_ok());
+        assert_eq!(
+            throttle.try_acquire_at(now, HASH, OWNER, ONE_USD),
+            Err(ThrottleLimit::FillsPerHour)
+        );
+
+        // The first fill failed to submit, so the hourly cap is free for another.
+        throttle.release(failed);
+        assert!(throttle.try_acquire_at(now, HASH, OWNER, ONE_USD).is_ok());
+        // Releasing an order with nothing reserved changes nothing.
+        throttle.release(failed);
+        assert_eq!(
+            throttle.try_acquire_at(now, failed, OWNER, ONE_USD),
+            Err(ThrottleLimit::FillsPerHour)
+        );
+    }
+
+    #[test]
+    fn override_bypasses_limits() {
+        let throttle = FillThrottle::new(FillThrottleConfig::new(
+            NonZeroU32::new(1),
+            None,
+            None,
+            None,
+            None,
+            None,
+        ));
+        let now = Instant::now();
+        assert!(throttle.try_acquire_at(now, HASH, OWNER, ONE_USD).is_ok());
+        assert!(throttle.try_acquire_at(now, HASH, OWNER, ONE_USD).is_err());
+
+        throttle.set_override(true);
+        assert!(throttle.try_acquire_at(now, HASH, OWNER, ONE_USD).is_ok());
+
+        throttle.set_override(false);
+        assert_eq!(
+            throttle.try_acquire_at(now, HASH, OWNER, ONE_USD),
+            Err(ThrottleLimit::FillsPerMinute)
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{OwnerStanding, ToxicFlowConfig};
+use alloy::primitives::{Address, U256};
+use serde::Serialize;
+use signet_types::SignedOrder;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+use tokio::time::{Duration, Instant};
+
+/// Maximum number of landed fills awaiting their markout. Older ones are dropped.
+const MAX_PENDING_MARKOUTS: usize = 1024;
+
+/// Markouts whose decayed magnitude falls below this, in USD, are dropped from the table.
+const FORGOTTEN_MARKOUT: f64 = 0.01;
+
+/// One USD in normalized 18-decimal USD.
+const USD: f64 = 1e18;
+
+/// A landed fill awaiting its markout.
+#[derive(Debug)]
+pub(crate) struct PendingMarkout {
+    pub(crate) order: SignedOrder,
+    /// The value of the outputs the filler paid, in normalized 18-decimal USD, when the fill was
+    /// confirmed.
+    pub(crate) paid: U256,
+    due_at: Instant,
+}
+
+impl PendingMarkout {
+    /// The markout in USD given the current value of the inputs received, in normalized
+    /// 18-decimal USD: positive if the fill made money.
+    pub(crate) fn markout(&self, received: U256) -> f64 {
+        (f64::from(received) - f64::from(self.paid)) / USD
+    }
+}
+
+/// An owner's current decayed markout, as served by the admin API.
+#[derive(Debug, Serialize)]
+pub(crate) struct OwnerMarkout {
+    owner: Address,
+    markout_usd: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Markout {
+    value: f64,
+    updated_at: Instant,
+}
+
+impl Markout {
+    /// The markout decayed exponentially from `updated_at` to `now`.
+    fn decayed(&self, now: Instant, half_life: Duration) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.value * 0.5_f64.powf(elapsed / half_life.as_secs_f64())
+    }
+}
+
+#[derive(Debug)]
+struct ToxicFlowInner {
+    config: ToxicFlowConfig,
+    markouts: HashMap<Address, Markout>,
+    pending: VecDeque<PendingMarkout>,
+}
+
+/// Per-owner realized PnL of landed fills, to catch adverse selection: counterparties whose
+/// orders are only profitable at the quoted price and systematically lose money once the price
+/// moves after inclusion. Each landed fill is marked out a configured delay after it was
+/// confirmed, as the value of the inputs received then less the value of the outputs paid, and
+/// markouts accumulate per owner with exponential decay. Owners whose decayed loss reaches the
+/// configured thresholds are deprioritized or paused.
+///
+/// Shared between the filler loop (which records markouts and consults standings) and the admin
+/// API (which can list and reset them). Markouts are held in memory and start fresh on restart.
+#[derive(Debug, Clone)]
+pub(crate) struct ToxicFlow {
+    inner: Arc<Mutex<ToxicFlowInner>>,
+}
+
+impl ToxicFlow {
+    pub(crate) fn new(config: ToxicFlowConfig) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ToxicFlowInner {
+                config,
+                markouts: HashMap::new(),
+                pending: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Tracks a landed fill of `order`, for which the filler paid outputs worth `paid`, until its
+    /// markout is due.
+    pub(crate) fn track(&self, order: SignedOrder, paid: U256) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.pending.len() == MAX_PENDING_MARKOUTS {
+            inner.pending.pop_front();
+        }
+        let due_at = Instant::now() + inner.config.markout_delay();
+        inner.pending.push_back(PendingMarkout { order, paid, due_at });
+    }
+
+    /// Takes the tracked fills whose markout is due.
+    pub(crate) fn take_due(&self) -> Vec<PendingMarkout> {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        let due = inner.pending.iter().take_while(|pending| pending.due_at <= now).count();
+        inner.pending.drain(..due).collect()
+    }
+
+    /// Adds a fill's markout, in USD, to its owner's.
+    pub(crate) fn record(&self, owner: Address, markout: f64) {
+        self.record_at(owner, markout, Instant::now());
+    }
+
+    fn record_at(&self, owner: Address, markout: f64, now: Instant) {
+        let mut inner = self.inner.lock().unwrap();
+        let half_life = inner.config.half_life();
+        let entry = inner.markouts.entry(owner).or_insert(Markout { value: 0.0, updated_at: now });
+        *entry = Markout { value: entry.decayed(now, half_life) + markout, updated_at: now };
+    }
+
+    /// How the given owner's orders should currently be treated.
+    pub(crate) fn standing(&self, owner: Address) -> OwnerStanding {
+        self.standing_at(owner, Instant::now())
+    }
+
+    fn standing_at(&self, owner: Address, now: Instant) -> OwnerStanding {
+        let inner = self.inner.lock().unwrap();
+        let Some(markout) = inner.markouts.get(&owner) else {
+            return OwnerStanding::Good;
+        };
+        let loss = -markout.decayed(now, inner.config.half_life());
+        if inner.config.pause_loss().is_some_and(|threshold| loss >= threshold) {
+            OwnerStanding::Skipped
+        } else if inner.config.deprioritize_loss().is_some_and(|threshold| loss >= threshold) {
+            OwnerStanding::Deprioritized
+        } else {
+            OwnerStanding::Good
+        }
+    }
+
+    /// Clear an owner's markout, returning whether they had one.
+    pub(crate) fn reset(&self, owner: Address) -> bool {
+        self.inner.lock().unwrap().markouts.remove(&owner).is_some()
+    }
+
+    /// Current decayed markouts for every owner that still has one, biggest loss first. Fully
+    /// decayed entries are pruned.
+    pub(crate) fn markouts(&self) -> Vec<OwnerMarkout> {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        let half_life = inner.config.half_life();
+        inner
+            .markouts
+            .retain(|_, markout| markout.decayed(now, half_life).abs() >= FORGOTTEN_MARKOUT);
+        let mut markouts: Vec<OwnerMarkout> = inner
+            .markouts
+            .iter()
+            .map(|(owner, markout)| OwnerMarkout {
+                owner: *owner,
+                markout_usd: markout.decayed(now, half_life),
+            })
+            .collect();
+        markouts.sort_by(|a, b| a.markout_usd.total_cmp(&b.markout_usd));
+        markouts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: Address = Address::repeat_byte(0x01);
+    const HALF_LIFE: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn losses_deprioritize_then_pause_and_decay() {
+        let toxic_flow = ToxicFlow::new(ToxicFlowConfig::new(
+            Duration::from_secs(60),
+            HALF_LIFE,
+            Some(10.0),
+            Some(40.0),
+        ));
+        let now = Instant::now();
+        toxic_flow.record_at(OWNER, -8.0, now);
+        assert_eq!(toxic_flow.standing_at(OWNER, now), OwnerStanding::Good);
+        toxic_flow.record_at(OWNER, -4.0, now);
+        assert_eq!(toxic_flow.standing_at(OWNER, now), OwnerStanding::Deprioritized);
+        toxic_flow.record_at(OWNER, -30.0, now);
+        assert_eq!(toxic_flow.standing_at(OWNER, now), OwnerStanding::Skipped);
+        assert_eq!(toxic_flow.standing_at(Address::ZERO, now), OwnerStanding::Good);
+
+        // After one half-life the loss has halved to 21.
+        assert_eq!(toxic_flow.standing_at(OWNER, now + HALF_LIFE), OwnerStanding::Deprioritized);
+        // Profitable fills offset losses.
+        toxic_flow.record_at(OWNER, 15.0, now + HALF_LIFE);
+        assert_eq!(toxic_flow.standing_at(OWNER, now + HALF_LIFE), OwnerStanding::Good);
+
+        toxic_flow.record_at(OWNER, -100.0, now + HALF_LIFE);
+        assert!(toxic_flow.reset(OWNER));
+        assert_eq!(toxic_flow.standing_at(OWNER, now + HALF_LIFE), OwnerStanding::Good);
+        assert!(!toxic_flow.reset(OWNER));
+    }
+}
@@ -0,0 +1,376 @@
+use crate::{
+    FillProviderType, FillerContext, FixedPricingClient, IERC20, KnownToken, TokenDiscovery,
+    TwapPool, metrics,
+};
+use alloy::{
+    primitives::{Address, U256},
+    sol,
+};
+use futures_util::future::join_all;
+use init4_bin_base::deps::tracing::{debug, info, instrument, trace, warn};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tokio::{
+    select,
+    time::{Duration, Instant},
+};
+use tokio_util::sync::CancellationToken;
+
+/// How often the background task refreshes pool TWAPs.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// TWAP prices older than this are ignored, falling back to the fixed exchange rates.
+const MAX_PRICE_AGE: Duration = Duration::from_secs(300);
+
+/// Base of the Uniswap V3 tick price formula: the price at tick `i` is `1.0001^i`.
+const TICK_BASE: f64 = 1.0001;
+
+sol! {
+    /// The parts of the Uniswap V3 pool interface needed to read its TWAP oracle.
+    #[sol(rpc)]
+    interface IUniswapV3Pool {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function observe(uint32[] calldata secondsAgos)
+            external
+            view
+            returns (int56[] memory tickCumulatives, uint160[] memory secondsPerLiquidityCumulativeX128s);
+    }
+}
+
+/// A token's TWAP price, expressed relative to a quote token that has a fixed USD price.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TwapPrice {
+    quote_token: Address,
+    quote_per_base: f64,
+    base_decimals: u8,
+    updated_at: Instant,
+}
+
+impl TwapPrice {
+    /// The token the price is quoted in.
+    pub(crate) const fn quote_token(&self) -> Address {
+        self.quote_token
+    }
+
+    /// Whole quote tokens per whole base token.
+    pub(crate) const fn quote_per_base(&self) -> f64 {
+        self.quote_per_base
+    }
+
+    /// Decimals of the priced token.
+    pub(crate) const fn base_decimals(&self) -> u8 {
+        self.base_decimals
+    }
+}
+
+/// Latest TWAP prices by token address, shared between the background refresh task and the
+/// pricing client.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TwapPriceCache {
+    inner: Arc<RwLock<HashMap<Address, TwapPrice>>>,
+}
+
+impl TwapPriceCache {
+    /// The token's TWAP price, unless it is missing or stale.
+    pub(crate) fn get(&self, token: &Address) -> Option<TwapPrice> {
+        self.inner
+            .read()
+            .unwrap()
+            .get(token)
+            .filter(|price| price.updated_at.elapsed() <= MAX_PRICE_AGE)
+            .copied()
+    }
+
+    fn insert(&self, token: Address, price: TwapPrice) {
+        self.inner.write().unwrap().insert(token, price);
+    }
+
+    /// Insert a fresh price directly, bypassing the pools.
+    #[cfg(test)]
+    pub(crate) fn insert_fresh(
+        &self,
+        token: Address,
+        quote_token: Address,
+        quote_per_base: f64,
+        base_decimals: u8,
+    ) {
+        let price =
+            TwapPrice { quote_token, quote_per_base, base_decimals, updated_at: Instant::now() };
+        self.insert(token, price);
+    }
+}
+
+/// A pool's tokens, resolved once from the chain.
+#[derive(Debug, Clone, Copy)]
+struct PoolTokens {
+    base: Address,
+    base_decimals: u8,
+    base_is_token0: bool,
+    quote: Address,
+    quote_decimals: u8,
+}
+
+#[derive(Debug)]
+struct TwapState {
+    pools: Vec<TwapPool>,
+    pool_tokens: HashMap<Address, PoolTokens>,
+    /// Rollup counterparts of host tokens, which share their host token's TWAP.
+    aliases: HashMap<Address, Address>,
+    fixed_prices: FixedPricingClient,
+    cache: TwapPriceCache,
+    host_provider: FillProviderType,
+    /// Source of pools for tokens enabled by discovery, if it is enabled.
+    discovery: Option<TokenDiscovery>,
+}
+
+/// Background task that periodically reads the configured Uniswap V3 pools' TWAP oracles on the
+/// host chain and publishes the resulting prices to the [`TwapPriceCache`].
+///
+/// Each pool must pair the token to be priced with a quote token the fixed pricing client already
+/// prices (preferring a $1 stablecoin when both are). Prices for host WETH and WBTC also apply to
+/// their rollup counterparts. Pools of tokens enabled by token discovery are added as they are
+/// enabled.
+#[derive(Debug)]
+pub struct TwapRefreshTask {
+    state: Option<TwapState>,
+    cancellation_token: CancellationToken,
+}
+
+impl TwapRefreshTask {
+    /// Create the refresh task and perform an initial refresh so prices are available for the
+    /// first cycle. The task does nothing unless pools are configured or token discovery is
+    /// enabled.
+    #[instrument(skip_all, name = "initialize_twap_refresh_task")]
+    pub async fn initialize(context: &FillerContext) -> Self {
+        let cancellation_token = context.cancellation_token().clone();
+        let Some(cache) = context.twap_prices() else {
+            return Self { state: None, cancellation_token };
+        };
+        let pools = context.twap_config().map(|config| config.pools().to_vec()).unwrap_or_default();
+        let constants = context.constants().system();
+        let aliases = [
+            (KnownToken::RollupWeth, KnownToken::HostWeth),
+            (KnownToken::RollupWbtc, KnownToken::HostWbtc),
+        ]
+        .into_iter()
+        .map(|(rollup, host)| (rollup.resolve(constants).token(), host.resolve(constants).token()))
+        .collect();
+        let mut state = TwapState {
+            pools,
+            pool_tokens: HashMap::new(),
+            aliases,
+            fixed_prices: FixedPricingClient::new(constants, context.chain_name(), 0)
+                .with_token_table(context.token_table()),
+            cache: cache.clone(),
+            host_provider: context.host_provider().clone(),
+            discovery: context.token_discovery().cloned(),
+        };
+        info!(pools = state.pools.len(), "TWAP pricing enabled");
+        select! {
+            biased;
+            _ = cancellation_token.cancelled() => {
+                debug!("TWAP refresh task initialization cancelled");
+            }
+            _ = state.refresh() => {}
+        }
+        Self { state: Some(state), cancellation_token }
+    }
+
+    /// Run the periodic refresh loop.
+    pub async fn run(self) {
+        let Some(mut state) = self.state else {
+            debug!("TWAP pricing disabled");
+            return;
+        };
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        // Consume the immediate first tick; `initialize` already refreshed.
+        interval.tick().await;
+
+        loop {
+            select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    debug!("TWAP refresh task cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    state.refresh().await;
+                }
+            }
+        }
+    }
+}
+
+impl TwapState {
+    /// Picks up pools of newly discovered tokens, resolves any pools whose tokens aren't known yet,
+    /// then reads every resolved pool's TWAP. Pools that fail keep their previous price until it
+    /// goes stale.
+    #[instrument(skip_all, fields(pool_count = self.pools.len()))]
+    async fn refresh(&mut self) {
+        if let Some(discovery) = &self.discovery {
+            self.pools.extend(discovery.take_new_pools());
+        }
+        let unresolved: Vec<Address> = self
+            .pools
+            .iter()
+            .map(|pool| pool.address())
+            .filter(|address| !self.pool_tokens.contains_key(address))
+            .collect();
+        let this = &*self;
+        let resolved = join_all(
+            unresolved
+                .into_iter()
+                .map(|address| async move { (address, this.resolve_pool(address).await) }),
+        )
+        .await;
+        for (address, result) in resolved {
+            match result {
+                Ok(tokens) => {
+                    info!(pool = %address, base = %tokens.base, quote = %tokens.quote, "resolved TWAP pool");
+                    self.pool_tokens.insert(address, tokens);
+                }
+                Err(error) => {
+                    metrics::record_twap_refresh_error();
+                    warn!(pool = %address, error = format!("{error:#}"), "failed to resolve TWAP pool");
+                }
+            }
+        }
+
+        let this = &*self;
+        let results = join_all(this.pools.iter().filter_map(|pool| {
+            let tokens = *this.pool_tokens.get(&pool.address())?;
+            Some(async move { (pool, tokens, this.read_price(pool, tokens).await) })
+        }))
+        .await;
+        for (pool, tokens, result) in results {
+            match result {
+                Ok(price) => {
+                    trace!(
+                        pool = %pool.address(),
+                        base = %tokens.base,
+                        quote_per_base = price.quote_per_base,
+                        "refreshed TWAP"
+                    );
+                    self.cache.insert(tokens.base, price);
+                    self.aliases
+                        .iter()
+                        .filter(|(_, host)| **host == tokens.base)
+                        .for_each(|(rollup, _)| self.cache.insert(*rollup, price));
+                }
+                Err(error) => {
+                    metrics::record_twap_refresh_error();
+                    warn!(
+                        pool = %pool.address(),
+                        error = format!("{error:#}"),
+                        "failed to refresh TWAP"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Reads the pool's tokens and decimals and decides which token is priced.
+    async fn resolve_pool(&self, address: Address) -> eyre::Result<PoolTokens> {
+        let pool = IUniswapV3Pool::new(address, &self.host_provider);
+        let (token0_call, token1_call) = (pool.token0(), pool.token1());
+        let (token0, token1) = tokio::try_join!(token0_call.call(), token1_call.call())?;
+        let (erc20_0, erc20_1) =
+            (IERC20::new(token0, &self.host_provider), IERC20::new(token1, &self.host_provider));
+        let (decimals0_call, decimals1_call) = (erc20_0.decimals(), erc20_1.decimals());
+        let (decimals0, decimals1) =
+            tokio::try_join!(decimals0_call.call(), decimals1_call.call())?;
+        let price0 = self.fixed_prices.fixed_price_usd(&token0);
+        let price1 = self.fixed_prices.fixed_price_usd(&token1);
+        let base_is_token0 = match (price0, price1) {
+            (None, None) => eyre::bail!("neither {token0} nor {token1} has a fixed USD price"),
+            (None, Some(_)) => true,
+            (Some(_), None) => false,
+            // Both are priced: quote in the stablecoin, if there is one.
+            (Some(price0), Some(_)) => price0 != U256::from(1),
+        };
+        Ok(if base_is_token0 {
+            PoolTokens {
+                base: token0,
+                base_decimals: decimals0,
+                base_is_token0,
+                quote: token1,
+                quote_decimals: decimals1,
+            }
+        } else {
+            PoolTokens {
+                base: token1,
+                base_decimals: decimals1,
+                base_is_token0,
+                quote: token0,
+                quote_decimals: decimals0,
+            }
+        })
+    }
+
+    /// Reads the pool's time-weighted average tick over its observation window.
+    async fn read_price(&self, pool: &TwapPool, tokens: PoolTokens) -> eyre::Result<TwapPrice> {
+        let window = u32::try_from(pool.window().as_secs()).unwrap_or(u32::MAX);
+        let observation = IUniswapV3Pool::new(pool.address(), &self.host_provider)
+            .observe(vec![window, 0])
+            .call()
+            .await?;
+        let [start, end] = observation.tickCumulatives[..] else {
+            eyre::bail!("expected 2 tick cumulatives, got {}", observation.tickCumulatives.len());
+        };
+        let tick = average_tick(start.as_i64(), end.as_i64(), window);
+        Ok(TwapPrice {
+            quote_token: tokens.quote,
+            quote_per_base: quote_per_base(
+                tick,
+                tokens.base_is_token0,
+                tokens.base_decimals,
+                tokens.quote_decimals,
+            ),
+            base_decimals: tokens.base_decimals,
+            updated_at: Instant::now(),
+        })
+    }
+}
+
+/// The arithmetic mean tick over `window` seconds, rounded towards negative infinity as in
+/// Uniswap's `OracleLibrary.consult`.
+fn average_tick(start_cumulative: i64, end_cumulative: i64, window: u32) -> i64 {
+    (end_cumulative - start_cumulative).div_euclid(i64::from(window))
+}
+
+/// Whole quote tokens per whole base token at `tick`. The tick price `1.0001^tick` is the raw
+/// amount of token1 per raw unit of token0.
+fn quote_per_base(tick: i64, base_is_token0: bool, base_decimals: u8, quote_decimals: u8) -> f64 {
+    let token1_per_token0 = TICK_BASE.powf(tick as f64);
+    let raw_quote_per_raw_base =
+        if base_is_token0 { token1_per_token0 } else { token1_per_token0.recip() };
+    raw_quote_per_raw_base * 10_f64.powi(i32::from(base_decimals) - i32::from(quote_decimals))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn average_tick_rounds_down() {
+        assert_eq!(average_tick(0, 600, 60), 10);
+        assert_eq!(average_tick(0, -610, 60), -11);
+        assert_eq!(average_tick(100, 100, 60), 0);
+    }
+
+    #[test]
+    fn quote_per_base_accounts_for_order_and_decimals() {
+        // USDC (6 decimals) as token0, WETH (18 decimals) as token1, at $3000/ETH: token1 per
+        // token0 in raw units is 1e12 / 3000.
+        let tick = (1e12_f64 / 3000.0).ln() / TICK_BASE.ln();
+        let price = quote_per_base(tick.round() as i64, false, 18, 6);
+        assert!((price - 3000.0).abs() / 3000.0 < 1e-3, "price {price}");
+
+        // Same pool with tokens swapped.
+        let price = quote_per_base(-tick.round() as i64, true, 18, 6);
+        assert!((price - 3000.0).abs() / 3000.0 < 1e-3, "price {price}");
+    }
+}